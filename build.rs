@@ -0,0 +1,16 @@
+//! Captures the git commit tspec was built from, exposed to the crate as
+//! `env!("TSPEC_GIT_SHA")` for `tspec version --verbose`.
+
+fn main() {
+    let sha = std::process::Command::new("git")
+        .args(["rev-parse", "--short=8", "HEAD"])
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        .filter(|sha| !sha.is_empty())
+        .unwrap_or_else(|| "unknown".to_string());
+
+    println!("cargo:rustc-env=TSPEC_GIT_SHA={sha}");
+    println!("cargo:rerun-if-changed=.git/HEAD");
+}
@@ -2,19 +2,24 @@
 //!
 //! Provides build_all, run_all, test_all for operating on all workspace members.
 
+use clap::ValueEnum;
 use std::process::ExitCode;
 
 use std::path::{Path, PathBuf};
 
-use crate::binary::{binary_size, strip_binary};
+use crate::binary::{binary_size, strip_binary, strip_binary_with_report};
 use crate::cargo_build::{build_package, test_package};
 use crate::cmd::{TestResult, parse_test_results};
-use crate::compare::{SpecResult, compare_specs, print_comparison};
-use crate::find_paths::find_tspecs;
-use crate::run::run_binary;
-use crate::tspec::{hash_spec, load_spec, spec_name_from_path};
-use crate::types::CargoFlags;
-use crate::workspace::WorkspaceInfo;
+use crate::compare::{SpecResult, compare_specs, print_comparison, tests_failed};
+use crate::compat::is_incompatible;
+use crate::find_paths::{find_tspec, find_tspecs};
+use crate::run::{RunOutcome, run_binary};
+use crate::term_width::{elide_middle, terminal_width};
+use crate::tspec::{expand_run_cwd, hash_spec, load_spec, spec_name_from_path};
+use crate::types::{CargoFlags, Verbosity, resolve_profile};
+use crate::units::format_size;
+use crate::warnings::{Warning, Warnings};
+use crate::workspace::{MemberFilter, MemberScope, WorkspaceInfo};
 use crate::{print_header, print_hline};
 
 /// Normalize tspec patterns for per-package matching in all-packages mode.
@@ -53,14 +58,46 @@ fn normalize_tspec_patterns(patterns: &[String]) -> Option<Vec<String>> {
     }
 }
 
-/// Warn that shell glob expansion likely ate the tspec pattern.
-pub fn warn_shell_glob_expansion(patterns: &[String]) {
-    eprintln!(
-        "Warning: -t arguments ({}) don't look like tspec files.",
-        patterns.join(", ")
-    );
-    eprintln!("  The shell likely expanded your glob before tspec could see it.");
-    eprintln!("  Quote the pattern to prevent shell expansion: -t 'pattern*'");
+/// Explain why `tspec run -w` found no runnable app packages.
+///
+/// Lists every workspace member and why it was excluded from
+/// `runnable_members()` (currently the only reason is "no binary target").
+pub fn no_runnable_members_message(workspace: &WorkspaceInfo) -> String {
+    let mut msg = String::from("no runnable app packages found in this workspace\n");
+    for member in &workspace.members {
+        let reason = if member.has_binary {
+            "runnable"
+        } else {
+            "no binary target"
+        };
+        msg.push_str(&format!("  {} — {reason}\n", member.name));
+    }
+    msg
+}
+
+/// Print the "(N build tools excluded — use --include-build-tools)" note
+/// when `filtered_members` dropped any `BuildTool`-kind members, so the
+/// exclusion is visible instead of silent. No-op when `excluded == 0`.
+fn print_build_tool_exclusion_note(excluded: usize) {
+    if excluded == 0 {
+        return;
+    }
+    let plural = if excluded == 1 { "" } else { "s" };
+    println!("({excluded} build tool{plural} excluded — use --include-build-tools)");
+}
+
+/// Warn that shell glob expansion likely ate the tspec pattern. With no
+/// collector, print immediately (single-shot early return, nothing to
+/// interleave with); with a collector, accumulate like every other
+/// batch-run warning.
+fn warn_shell_glob_expansion(patterns: &[String], warnings: Option<&mut Warnings>) {
+    let warning = Warning::ShellGlobExpansion {
+        patterns: patterns.to_vec(),
+    };
+    match warnings {
+        Some(w) => w.push(warning),
+        None => eprintln!("{warning}"),
+    }
 }
 
 /// Resolve tspec patterns for a workspace member.
@@ -76,18 +113,122 @@ fn resolve_specs_for_member(member_path: &Path, patterns: &[String]) -> Vec<Path
 
 /// Extract a short spec label from an optional tspec path.
 ///
-/// Includes the spec hash when the file can be loaded, e.g. `"tspec [c5f653a9]"`.
-fn spec_label(tspec: &Option<String>) -> String {
+/// Includes the spec hash when the file can be loaded, e.g. `"tspec [c5f653a9]"`,
+/// and the effective profile when one is resolved, e.g. `"tspec [c5f653a9] (release)"`.
+fn spec_label(tspec: &Option<String>, cli_profile: Option<&str>, force_profile: bool) -> String {
+    let loaded = tspec.as_ref().and_then(|p| load_spec(Path::new(p)).ok());
+    let spec_profile = loaded.as_ref().and_then(|s| s.cargo.profile.as_deref());
+    let profile_part = resolve_profile(spec_profile, cli_profile, force_profile)
+        .profile
+        .map(|p| format!(" ({p})"))
+        .unwrap_or_default();
+
     match tspec {
         Some(path) => {
             let name = spec_name_from_path(Path::new(path));
-            match load_spec(Path::new(path)).and_then(|s| hash_spec(&s)) {
-                Ok(hash) => format!("{name} [{hash}]"),
-                Err(_) => name,
-            }
+            let base = match loaded.as_ref().and_then(|s| hash_spec(s).ok()) {
+                Some(hash) => format!("{name} [{hash}]"),
+                None => name,
+            };
+            format!("{base}{profile_part}")
+        }
+        None => profile_part.trim_start().to_string(),
+    }
+}
+
+/// How to order a batch summary's rows (`--sort-by`). Name is always the
+/// secondary sort key, so ties (including `Time` until durations are
+/// tracked) come out in a stable, deterministic order.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SortBy {
+    #[default]
+    Name,
+    /// Largest binary first.
+    Size,
+    /// Not tracked yet — sorts the same as `Name` until per-result build
+    /// durations exist to sort by.
+    Time,
+}
+
+/// How to lay out a batch build summary (`--group-by`). `Flat` (the
+/// default) prints one row per (package, spec) build, same as always;
+/// `Package` groups those rows under a subheading per package, so a
+/// package built under several specs reads as one group instead of several
+/// same-named rows scattered through the table.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum GroupBy {
+    #[default]
+    Flat,
+    Package,
+}
+
+/// Totals for a batch of `OpResult` rows, distinguishing the number of rows
+/// (one per (package, spec) build) from the number of distinct packages
+/// those rows span — a package built under N specs contributes N rows but
+/// only 1 to `packages`. Pure aggregation so footer wording can be tested
+/// without printing anything.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct BuildTotals {
+    pub ok: usize,
+    pub failed: usize,
+    pub skipped: usize,
+    pub packages: usize,
+}
+
+impl BuildTotals {
+    /// Total rows (builds) counted, i.e. `ok + failed + skipped`.
+    pub fn rows(&self) -> usize {
+        self.ok + self.failed + self.skipped
+    }
+}
+
+fn build_totals(results: &[OpResult]) -> BuildTotals {
+    let mut totals = BuildTotals::default();
+    let mut seen = std::collections::BTreeSet::new();
+    for r in results {
+        seen.insert(r.name.as_str());
+        if r.skipped {
+            totals.skipped += 1;
+        } else if r.success {
+            totals.ok += 1;
+        } else {
+            totals.failed += 1;
         }
-        None => String::new(),
     }
+    totals.packages = seen.len();
+    totals
+}
+
+/// The "Build: N ok, M failed" footer, extended with a "(R builds across P
+/// packages)" clause whenever the row count and package count diverge (i.e.
+/// at least one package was built under more than one spec) — otherwise the
+/// clause would just repeat the same number twice.
+fn build_footer(totals: BuildTotals) -> String {
+    let mut footer = format!("Build: {} ok, {} failed", totals.ok, totals.failed);
+    if totals.skipped > 0 {
+        footer.push_str(&format!(", {} skipped (incompatible)", totals.skipped));
+    }
+    if totals.rows() != totals.packages {
+        footer.push_str(&format!(
+            " ({} builds across {} packages)",
+            totals.rows(),
+            totals.packages
+        ));
+    }
+    footer
+}
+
+/// Order `results` for summary rendering without mutating or cloning them.
+fn sorted_results(results: &[OpResult], sort_by: SortBy) -> Vec<&OpResult> {
+    let mut sorted: Vec<&OpResult> = results.iter().collect();
+    sorted.sort_by(|a, b| {
+        let primary = match sort_by {
+            SortBy::Size => b.size.unwrap_or(0).cmp(&a.size.unwrap_or(0)),
+            SortBy::Name | SortBy::Time => std::cmp::Ordering::Equal,
+        };
+        primary.then_with(|| a.name.cmp(&b.name))
+    });
+    sorted
 }
 
 /// Result of a batch operation on a single package
@@ -99,30 +240,109 @@ pub struct OpResult {
     pub message: String,
     pub size: Option<u64>,
     pub test_counts: Option<TestResult>,
+    /// True when this (package, spec) pair was skipped rather than run, e.g.
+    /// by `--only-compatible` finding the spec's hash on the package's
+    /// incompatible list. Counted separately from ok/failed in summaries.
+    pub skipped: bool,
+    /// Bytes removed by `--strip`, when stripping ran and succeeded.
+    pub stripped_saved: Option<u64>,
 }
 
 /// Build all workspace packages (excluding build tools)
 ///
 /// When `tspec_patterns` is empty, each package uses its default spec.
 /// When non-empty, patterns are resolved per-package; packages with no matches are skipped.
+/// Options for [`build_all`] beyond the workspace and spec patterns, grouped
+/// to keep the function under clippy's argument-count limit.
+pub struct BuildAllOptions {
+    pub force_profile: bool,
+    pub strip: bool,
+    pub fail_fast: bool,
+    /// True for an explicit `-w`/`--workspace` request, which (like `cargo
+    /// build --workspace`) builds every member regardless of `[workspace]
+    /// default-members`. False for the implicit all-packages fallback, which
+    /// honors `default-members` like a bare `cargo build` would.
+    pub explicit_workspace: bool,
+    /// Skip a (package, spec) pair whose hash is on the package's
+    /// `compat.toml` incompatible list instead of building it.
+    pub only_compatible: bool,
+    /// Force a synthetic per-spec target_dir for specs that don't set their
+    /// own cargo.target_dir (see `resolve_isolated_target_dir`).
+    pub isolate: bool,
+    /// Suppress cargo's own "Compiling xyz" progress spam while still
+    /// re-rendering warnings/errors from cargo's JSON message stream.
+    pub quiet_cargo: bool,
+    /// Scrub inherited environment variables before invoking cargo for
+    /// every member built (see `--hermetic-env`).
+    pub hermetic_env: bool,
+    /// Include `BuildTool`-kind members instead of excluding them (see
+    /// `--include-build-tools`).
+    pub include_build_tools: bool,
+    /// Skip generating the temporary linker-args build.rs, routing
+    /// `linker.args` through RUSTFLAGS instead (see `--no-buildrs`).
+    pub no_buildrs: bool,
+    /// Leave a generated linker-args build.rs in place after each build
+    /// (see `--keep-buildrs`).
+    pub keep_buildrs: bool,
+    /// Fail instead of warning when an ambient RUSTFLAGS/
+    /// CARGO_ENCODED_RUSTFLAGS would override the spec's own (see
+    /// `--strict-flags`).
+    pub strict_flags: bool,
+    /// Rebuild every member even if its spec and sources are unchanged
+    /// since the last successful build (see `--force`).
+    pub force: bool,
+    /// Skip cargo for a member whose spec changed but only in `[run]`/
+    /// `[test]` fields, which don't affect the build (see `--smart-rebuild`).
+    pub smart_rebuild: bool,
+}
+
 pub fn build_all(
     workspace: &WorkspaceInfo,
     tspec_patterns: &[String],
     cli_profile: Option<&str>,
-    strip: bool,
-    fail_fast: bool,
     flags: &CargoFlags,
+    options: BuildAllOptions,
 ) -> Vec<OpResult> {
+    let BuildAllOptions {
+        force_profile,
+        strip,
+        fail_fast,
+        explicit_workspace,
+        only_compatible,
+        isolate,
+        quiet_cargo,
+        hermetic_env,
+        include_build_tools,
+        no_buildrs,
+        keep_buildrs,
+        strict_flags,
+        force,
+        smart_rebuild,
+    } = options;
+    let mut warnings = Warnings::new();
     let normalized = match normalize_tspec_patterns(tspec_patterns) {
         Some(n) => n,
         None => {
-            warn_shell_glob_expansion(tspec_patterns);
+            warn_shell_glob_expansion(tspec_patterns, Some(&mut warnings));
+            warnings.print_grouped();
             return Vec::new();
         }
     };
     let mut results = Vec::new();
 
-    for member in workspace.buildable_members() {
+    let scope = if explicit_workspace {
+        MemberScope::All
+    } else {
+        MemberScope::Default
+    };
+    let filtered = workspace.filtered_members(
+        scope,
+        MemberFilter {
+            include_build_tools,
+        },
+    );
+    print_build_tool_exclusion_note(filtered.excluded_build_tools);
+    for member in filtered.members {
         let specs = resolve_specs_for_member(&member.path, &normalized);
         if specs.is_empty() && !normalized.is_empty() {
             continue;
@@ -140,20 +360,59 @@ pub fn build_all(
         };
 
         for tspec in &tspec_list {
-            let spec = spec_label(tspec);
+            let spec = spec_label(tspec, cli_profile, force_profile);
+
+            if only_compatible
+                && let Some(hash) = spec_hash_for(&member.path, tspec.as_deref())
+                && is_incompatible(&member.path, &hash).unwrap_or(false)
+            {
+                println!("  skipping {spec} [{hash}]: on incompatible list");
+                results.push(OpResult {
+                    name: member.name.clone(),
+                    version: member.version.clone(),
+                    spec,
+                    success: true,
+                    message: format!("skipped (incompatible: {hash})"),
+                    size: None,
+                    test_counts: None,
+                    skipped: true,
+                    stripped_saved: None,
+                });
+                continue;
+            }
+
             let result = match build_package(
                 &member.name,
                 tspec.as_deref(),
+                false,
+                false,
+                force,
                 cli_profile,
+                force_profile,
                 &workspace.root,
                 flags,
+                isolate,
+                quiet_cargo,
+                hermetic_env,
+                no_buildrs,
+                keep_buildrs,
+                strict_flags,
+                smart_rebuild,
+                Some(&mut warnings),
             ) {
                 Ok(build_result) => {
-                    if strip
-                        && member.has_binary
-                        && let Err(e) = strip_binary(&build_result.binary_path)
-                    {
-                        eprintln!("  warning: strip failed: {}", e);
+                    let mut stripped_saved = None;
+                    if strip && member.has_binary {
+                        match strip_binary_with_report(&build_result.binary_path) {
+                            Ok(crate::binary::StripOutcome::Stripped(savings)) => {
+                                stripped_saved = Some(savings.saved())
+                            }
+                            Ok(crate::binary::StripOutcome::Skipped(_)) => {}
+                            Err(e) => warnings.push(Warning::StripFailed {
+                                package: member.name.clone(),
+                                error: e.to_string(),
+                            }),
+                        }
                     }
                     let size = binary_size(&build_result.binary_path).ok();
                     OpResult {
@@ -164,6 +423,8 @@ pub fn build_all(
                         message: format!("{}", build_result.binary_path.display()),
                         size,
                         test_counts: None,
+                        skipped: false,
+                        stripped_saved,
                     }
                 }
                 Err(e) => OpResult {
@@ -174,6 +435,8 @@ pub fn build_all(
                     message: e.to_string(),
                     size: None,
                     test_counts: None,
+                    skipped: false,
+                    stripped_saved: None,
                 },
             };
 
@@ -181,35 +444,96 @@ pub fn build_all(
             results.push(result);
 
             if failed && fail_fast {
+                warnings.print_grouped();
                 return results;
             }
         }
     }
 
+    warnings.print_grouped();
     results
 }
 
+/// Resolve a spec's content hash for `--only-compatible` checks. Returns
+/// `None` if the spec can't be found or fails to load (build_package will
+/// surface the real error; this path only gates the skip check).
+fn spec_hash_for(package_dir: &Path, tspec: Option<&str>) -> Option<String> {
+    let path = find_tspec(package_dir, tspec).ok().flatten()?;
+    let spec = load_spec(&path).ok()?;
+    hash_spec(&spec).ok()
+}
+
+/// Resolve a member's effective `[run] cwd`/`[run] args`/`[run] expect_exit`
+/// for `run_all`. Falls back to "no cwd override, no default args, expect 0"
+/// if the spec can't be found or fails to load (build_package will surface
+/// the real error).
+fn run_settings_for(
+    package_dir: &Path,
+    tspec: Option<&str>,
+) -> (Option<PathBuf>, Vec<String>, i32) {
+    let Some(path) = find_tspec(package_dir, tspec).ok().flatten() else {
+        return (None, Vec::new(), 0);
+    };
+    let Ok(spec) = load_spec(&path) else {
+        return (None, Vec::new(), 0);
+    };
+    (
+        expand_run_cwd(&spec, package_dir),
+        spec.run.args,
+        spec.run.expect_exit,
+    )
+}
+
+/// Format a run's result for the RUN SUMMARY: a bare exit code on a match,
+/// or "got X, expected Y" on a mismatch so the table shows exactly what
+/// diverged.
+fn run_result_message(outcome: RunOutcome, expected_exit: i32) -> (bool, String) {
+    if outcome.matches_expectation(expected_exit) {
+        (true, format!("exit code: {}", outcome.code()))
+    } else {
+        (
+            false,
+            format!("got {}, expected {}", outcome.code(), expected_exit),
+        )
+    }
+}
+
 /// Run all app packages sequentially
 ///
 /// When `tspec_patterns` is empty, each package uses its default spec.
 /// When non-empty, patterns are resolved per-package; packages with no matches are skipped.
+/// `expect_exit_override`, from `--expect-exit`, wins over each member's own
+/// `[run] expect_exit`.
+#[allow(clippy::too_many_arguments)]
 pub fn run_all(
     workspace: &WorkspaceInfo,
     tspec_patterns: &[String],
     cli_profile: Option<&str>,
+    force_profile: bool,
     strip: bool,
     flags: &CargoFlags,
+    include_build_tools: bool,
+    expect_exit_override: Option<i32>,
 ) -> Vec<OpResult> {
+    let mut warnings = Warnings::new();
     let normalized = match normalize_tspec_patterns(tspec_patterns) {
         Some(n) => n,
         None => {
-            warn_shell_glob_expansion(tspec_patterns);
+            warn_shell_glob_expansion(tspec_patterns, Some(&mut warnings));
+            warnings.print_grouped();
             return Vec::new();
         }
     };
     let mut results = Vec::new();
 
-    for member in workspace.runnable_members() {
+    let filtered = workspace.filtered_members(
+        MemberScope::Runnable,
+        MemberFilter {
+            include_build_tools,
+        },
+    );
+    print_build_tool_exclusion_note(filtered.excluded_build_tools);
+    for member in filtered.members {
         let specs = resolve_specs_for_member(&member.path, &normalized);
         if specs.is_empty() && !normalized.is_empty() {
             continue;
@@ -227,28 +551,64 @@ pub fn run_all(
         };
 
         for tspec in &tspec_list {
-            let spec = spec_label(tspec);
+            let spec = spec_label(tspec, cli_profile, force_profile);
+            let (run_cwd, run_args, spec_expect_exit) =
+                run_settings_for(&member.path, tspec.as_deref());
+            let expected_exit = expect_exit_override.unwrap_or(spec_expect_exit);
+            if flags.verbosity >= Verbosity::Debug {
+                let cwd_str = run_cwd
+                    .as_ref()
+                    .map(|p| p.display().to_string())
+                    .unwrap_or_else(|| "(default)".to_string());
+                let args_str = if run_args.is_empty() {
+                    "(none)".to_string()
+                } else {
+                    run_args.join(" ")
+                };
+                println!("[debug] run cwd: {cwd_str}");
+                println!("[debug] run args: {args_str}");
+            }
             let result = match build_package(
                 &member.name,
                 tspec.as_deref(),
+                false,
+                false,
+                false,
                 cli_profile,
+                force_profile,
                 &workspace.root,
                 flags,
+                false,
+                false,
+                false,
+                false,
+                false,
+                false,
+                false,
+                Some(&mut warnings),
             ) {
                 Ok(build_result) => {
                     if strip && let Err(e) = strip_binary(&build_result.binary_path) {
-                        eprintln!("  warning: strip failed: {}", e);
+                        warnings.push(Warning::StripFailed {
+                            package: member.name.clone(),
+                            error: e.to_string(),
+                        });
                     }
-                    match run_binary(&build_result.binary_path, &[]) {
-                        Ok(exit_code) => OpResult {
-                            name: member.name.clone(),
-                            version: member.version.clone(),
-                            spec: spec.clone(),
-                            success: true,
-                            message: format!("exit code: {}", exit_code),
-                            size: None,
-                            test_counts: None,
-                        },
+                    match run_binary(&build_result.binary_path, &run_args, run_cwd.as_deref()) {
+                        Ok(outcome) => {
+                            let (success, message) = run_result_message(outcome, expected_exit);
+                            OpResult {
+                                name: member.name.clone(),
+                                version: member.version.clone(),
+                                spec: spec.clone(),
+                                success,
+                                message,
+                                size: None,
+                                test_counts: None,
+                                skipped: false,
+                                stripped_saved: None,
+                            }
+                        }
                         Err(e) => OpResult {
                             name: member.name.clone(),
                             version: member.version.clone(),
@@ -257,6 +617,8 @@ pub fn run_all(
                             message: format!("run failed: {}", e),
                             size: None,
                             test_counts: None,
+                            skipped: false,
+                            stripped_saved: None,
                         },
                     }
                 }
@@ -268,6 +630,8 @@ pub fn run_all(
                     message: format!("build failed: {}", e),
                     size: None,
                     test_counts: None,
+                    skipped: false,
+                    stripped_saved: None,
                 },
             };
 
@@ -275,6 +639,7 @@ pub fn run_all(
         }
     }
 
+    warnings.print_grouped();
     results
 }
 
@@ -282,29 +647,53 @@ pub fn run_all(
 ///
 /// When `tspec_patterns` is empty, each package uses its default spec.
 /// When non-empty, patterns are resolved per-package; packages with no matches are skipped.
+#[allow(clippy::too_many_arguments)]
 pub fn test_all(
     workspace: &WorkspaceInfo,
     tspec_patterns: &[String],
     cli_profile: Option<&str>,
+    force_profile: bool,
     fail_fast: bool,
     flags: &CargoFlags,
+    explicit_workspace: bool,
+    isolate: bool,
+    include_build_tools: bool,
+    no_buildrs: bool,
+    keep_buildrs: bool,
 ) -> Vec<OpResult> {
+    let mut warnings = Warnings::new();
     let normalized = match normalize_tspec_patterns(tspec_patterns) {
         Some(n) => n,
         None => {
-            warn_shell_glob_expansion(tspec_patterns);
+            warn_shell_glob_expansion(tspec_patterns, Some(&mut warnings));
+            warnings.print_grouped();
             return Vec::new();
         }
     };
     let mut results = Vec::new();
 
-    for member in workspace.buildable_members() {
+    let scope = if explicit_workspace {
+        MemberScope::All
+    } else {
+        MemberScope::Default
+    };
+    let filtered = workspace.filtered_members(
+        scope,
+        MemberFilter {
+            include_build_tools,
+        },
+    );
+    print_build_tool_exclusion_note(filtered.excluded_build_tools);
+    for member in filtered.members {
         let specs = resolve_specs_for_member(&member.path, &normalized);
         if specs.is_empty() && !normalized.is_empty() {
             continue;
         }
 
-        println!("=== {} ===", member.name);
+        match cli_profile {
+            Some(p) => println!("=== {} (profile: {p}) ===", member.name),
+            None => println!("=== {} ===", member.name),
+        }
 
         let tspec_list: Vec<Option<String>> = if specs.is_empty() {
             vec![None]
@@ -316,13 +705,18 @@ pub fn test_all(
         };
 
         for tspec in &tspec_list {
-            let spec = spec_label(tspec);
+            let spec = spec_label(tspec, cli_profile, force_profile);
             let result = match test_package(
                 &member.name,
                 tspec.as_deref(),
                 cli_profile,
+                force_profile,
                 &workspace.root,
                 flags,
+                isolate,
+                no_buildrs,
+                keep_buildrs,
+                Some(&mut warnings),
             ) {
                 Ok(result_lines) => {
                     let counts = parse_test_results(&result_lines);
@@ -334,6 +728,8 @@ pub fn test_all(
                         message: "ok".to_string(),
                         size: None,
                         test_counts: Some(counts),
+                        skipped: false,
+                        stripped_saved: None,
                     }
                 }
                 Err(e) => OpResult {
@@ -344,6 +740,8 @@ pub fn test_all(
                     message: e.to_string(),
                     size: None,
                     test_counts: None,
+                    skipped: false,
+                    stripped_saved: None,
                 },
             };
 
@@ -351,11 +749,13 @@ pub fn test_all(
             results.push(result);
 
             if failed && fail_fast {
+                warnings.print_grouped();
                 return results;
             }
         }
     }
 
+    warnings.print_grouped();
     results
 }
 
@@ -406,6 +806,16 @@ fn print_summary_table(
     } else {
         0
     };
+    // Package/Spec columns are always shown in full (that's the identifying
+    // part of the row); on a narrow terminal it's the detail column —
+    // build output, error messages, paths — that gets elided in the middle.
+    let width = terminal_width(None);
+    let fixed_prefix_len = if has_spec {
+        2 + max_name_len + 2 + max_spec_len + 2
+    } else {
+        2 + max_name_len + 2
+    };
+    let detail_budget = width.saturating_sub(fixed_prefix_len);
 
     println!();
     print_header!(format!("{ws_name} {cmd} SUMMARY"));
@@ -426,17 +836,22 @@ fn print_summary_table(
     }
 
     for (row, vname) in rows.iter().zip(versioned_names.iter()) {
+        let detail = if detail_budget > 0 {
+            elide_middle(&row.detail, detail_budget)
+        } else {
+            row.detail.clone()
+        };
         if has_spec {
             println!(
                 "  {:nw$}  {:sw$}  {}",
                 vname,
                 row.spec,
-                row.detail,
+                detail,
                 nw = max_name_len,
                 sw = max_spec_len
             );
         } else {
-            println!("  {:width$}  {}", vname, row.detail, width = max_name_len);
+            println!("  {:width$}  {}", vname, detail, width = max_name_len);
         }
     }
 
@@ -448,8 +863,29 @@ fn print_summary_table(
     println!();
 }
 
-/// Print a summary of operation results (for tests)
-pub fn print_test_summary(name: &str, results: &[OpResult]) -> ExitCode {
+/// Build the "(N ignored, M doctests)" suffix for a per-package test summary row.
+fn counts_suffix(counts: &TestResult) -> String {
+    let mut parts = Vec::new();
+    if counts.ignored > 0 {
+        parts.push(format!("{} ignored", counts.ignored));
+    }
+    let doc_total = counts.doc_passed + counts.doc_failed;
+    if doc_total > 0 {
+        parts.push(format!("{doc_total} doctests"));
+    }
+    if parts.is_empty() {
+        String::new()
+    } else {
+        format!(" ({})", parts.join(", "))
+    }
+}
+
+/// Print a summary of operation results (for tests).
+///
+/// `cli_profile`, when set, is echoed in the footer so mixed-profile CI logs
+/// (e.g. a debug run followed by a `--profile release-small` run) aren't
+/// ambiguous about which totals belong to which run.
+pub fn print_test_summary(name: &str, results: &[OpResult], cli_profile: Option<&str>) -> ExitCode {
     let mut pkg_passed = 0;
     let mut pkg_failed = 0;
     let mut total = TestResult::default();
@@ -461,14 +897,7 @@ pub fn print_test_summary(name: &str, results: &[OpResult]) -> ExitCode {
                 pkg_passed += 1;
                 if let Some(counts) = &r.test_counts {
                     total.merge(counts);
-                    if counts.ignored > 0 {
-                        format!(
-                            "[PASS]  {} passed ({} ignored)",
-                            counts.passed, counts.ignored
-                        )
-                    } else {
-                        format!("[PASS]  {} passed", counts.passed)
-                    }
+                    format!("[PASS]  {} passed{}", counts.passed, counts_suffix(counts))
                 } else {
                     "[PASS]".to_string()
                 }
@@ -476,14 +905,7 @@ pub fn print_test_summary(name: &str, results: &[OpResult]) -> ExitCode {
                 pkg_failed += 1;
                 if let Some(counts) = &r.test_counts {
                     total.merge(counts);
-                    if counts.ignored > 0 {
-                        format!(
-                            "[FAIL]  {} failed ({} ignored)",
-                            counts.failed, counts.ignored
-                        )
-                    } else {
-                        format!("[FAIL]  {} failed", counts.failed)
-                    }
+                    format!("[FAIL]  {} failed{}", counts.failed, counts_suffix(counts))
                 } else {
                     "[FAIL]".to_string()
                 }
@@ -498,17 +920,23 @@ pub fn print_test_summary(name: &str, results: &[OpResult]) -> ExitCode {
         .collect();
 
     let pkg_count = pkg_passed + pkg_failed;
-    let footer = if total.ignored > 0 {
-        format!(
-            "Test: {} packages, {} passed, {} failed ({} ignored)",
-            pkg_count, total.passed, total.failed, total.ignored
-        )
-    } else {
-        format!(
-            "Test: {} packages, {} passed, {} failed",
-            pkg_count, total.passed, total.failed
-        )
-    };
+    let mut footer = format!(
+        "Test: {} packages, {} passed, {} failed",
+        pkg_count, total.passed, total.failed
+    );
+    if total.ignored > 0 {
+        footer.push_str(&format!(" ({} ignored)", total.ignored));
+    }
+    let doc_total = total.doc_passed + total.doc_failed;
+    if doc_total > 0 {
+        footer.push_str(&format!(
+            ", {doc_total} doctests ({} passed, {} failed)",
+            total.doc_passed, total.doc_failed
+        ));
+    }
+    if let Some(p) = cli_profile {
+        footer.push_str(&format!(" [profile: {p}]"));
+    }
 
     print_summary_table(name, "TEST", "Status", &rows, &footer);
 
@@ -519,54 +947,125 @@ pub fn print_test_summary(name: &str, results: &[OpResult]) -> ExitCode {
     }
 }
 
-/// Print a summary for build operations (OK/FAILED)
-pub fn print_summary(name: &str, results: &[OpResult]) -> ExitCode {
-    let mut ok_count = 0;
-    let mut failed_count = 0;
+/// Render a single result's "[ OK ]  1.2 MB"-style detail string.
+fn build_detail(r: &OpResult) -> &'static str {
+    if r.skipped {
+        "[SKIP]"
+    } else if r.success {
+        "[ OK ]"
+    } else {
+        "[FAIL]"
+    }
+}
 
-    let rows: Vec<SummaryRow> = results
-        .iter()
-        .map(|r| {
-            let status = if r.success {
-                ok_count += 1;
-                "[ OK ]"
-            } else {
-                failed_count += 1;
-                "[FAIL]"
-            };
-            let size_str = r.size.map(format_size).unwrap_or_else(|| "--".to_string());
-            SummaryRow {
-                name: r.name.clone(),
-                version: r.version.clone(),
-                spec: r.spec.clone(),
-                detail: format!("{status}  {size_str:>6}"),
-            }
-        })
-        .collect();
+/// Print a summary for build operations (OK/FAILED/SKIPPED)
+pub fn print_summary(name: &str, results: &[OpResult], sort_by: SortBy) -> ExitCode {
+    print_summary_grouped(name, results, sort_by, GroupBy::Flat)
+}
 
-    print_summary_table(
-        name,
-        "BUILD",
-        "Status    Size",
-        &rows,
-        &format!("Build: {ok_count} ok, {failed_count} failed"),
-    );
+/// Print a summary for build operations, optionally grouping rows by
+/// package (`--group-by package`) instead of the default flat, one-row-
+/// per-build layout. The footer and exit code are identical either way —
+/// grouping only changes how the rows above it are laid out.
+pub fn print_summary_grouped(
+    name: &str,
+    results: &[OpResult],
+    sort_by: SortBy,
+    group_by: GroupBy,
+) -> ExitCode {
+    let totals = build_totals(results);
+    let total_saved: u64 = results.iter().filter_map(|r| r.stripped_saved).sum();
+    let mut footer = build_footer(totals);
+    if total_saved > 0 {
+        footer.push_str(&format!(", saved {}", format_size(total_saved)));
+    }
 
-    if failed_count > 0 {
+    match group_by {
+        GroupBy::Flat => {
+            let rows: Vec<SummaryRow> = sorted_results(results, sort_by)
+                .into_iter()
+                .map(|r| {
+                    let status = build_detail(r);
+                    let size_str = r.size.map(format_size).unwrap_or_else(|| "--".to_string());
+                    SummaryRow {
+                        name: r.name.clone(),
+                        version: r.version.clone(),
+                        spec: r.spec.clone(),
+                        detail: format!("{status}  {size_str:>6}"),
+                    }
+                })
+                .collect();
+            print_summary_table(name, "BUILD", "Status    Size", &rows, &footer);
+        }
+        GroupBy::Package => {
+            print_grouped_summary_table(name, "BUILD", results, sort_by, &footer);
+        }
+    }
+
+    if totals.failed > 0 {
         ExitCode::from(1)
     } else {
         ExitCode::SUCCESS
     }
 }
 
-fn format_size(bytes: u64) -> String {
-    if bytes >= 1_000_000 {
-        format!("{:.1}M", bytes as f64 / 1_000_000.0)
-    } else if bytes >= 1_000 {
-        format!("{:.1}K", bytes as f64 / 1_000.0)
-    } else {
-        format!("{}", bytes)
+/// Like `print_summary_table`, but rows are grouped under a subheading per
+/// distinct package name instead of repeating the name on every row —
+/// `--group-by package`'s rendering. Package groups keep `sort_by`'s
+/// ordering within themselves; groups are ordered by package name.
+fn print_grouped_summary_table(
+    ws_name: &str,
+    cmd: &str,
+    results: &[OpResult],
+    sort_by: SortBy,
+    footer: &str,
+) {
+    let ordered = sorted_results(results, sort_by);
+    let mut by_package: std::collections::BTreeMap<&str, Vec<&OpResult>> =
+        std::collections::BTreeMap::new();
+    for r in &ordered {
+        by_package.entry(r.name.as_str()).or_default().push(r);
+    }
+
+    println!();
+    print_header!(format!("{ws_name} {cmd} SUMMARY (by package)"));
+
+    let max_spec_len = results
+        .iter()
+        .map(|r| r.spec.len())
+        .max()
+        .unwrap_or(4)
+        .max(4);
+
+    for (name, rows) in &by_package {
+        let version = rows.first().map(|r| r.version.as_str()).unwrap_or("");
+        let heading = if version.is_empty() {
+            (*name).to_string()
+        } else {
+            format!("{name} v{version}")
+        };
+        println!(
+            "  {heading} ({} spec{}):",
+            rows.len(),
+            if rows.len() == 1 { "" } else { "s" }
+        );
+        for r in rows {
+            let status = build_detail(r);
+            let size_str = r.size.map(format_size).unwrap_or_else(|| "--".to_string());
+            println!(
+                "    {:sw$}  {status}  {size_str:>6}",
+                r.spec,
+                sw = max_spec_len
+            );
+        }
+    }
+
+    println!();
+    if !footer.is_empty() {
+        println!("  {footer}");
     }
+    print_hline!();
+    println!();
 }
 
 /// Result of a compare operation on a single package
@@ -575,26 +1074,86 @@ pub struct CompareResult {
     pub specs: Vec<SpecResult>,
 }
 
+/// Filter out spec paths whose filename matches any of the exclude globs.
+fn apply_exclude_specs(spec_paths: Vec<PathBuf>, exclude_patterns: &[String]) -> Vec<PathBuf> {
+    if exclude_patterns.is_empty() {
+        return spec_paths;
+    }
+    let globs: Vec<glob::Pattern> = exclude_patterns
+        .iter()
+        .filter_map(|p| glob::Pattern::new(p).ok())
+        .collect();
+    spec_paths
+        .into_iter()
+        .filter(|path| {
+            let name = path
+                .file_name()
+                .map(|f| f.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            !globs.iter().any(|g| g.matches(&name))
+        })
+        .collect()
+}
+
+/// Boolean options for `compare_all`, bundled to stay under clippy's
+/// argument-count limit (mirrors `BuildAllOptions`).
+pub struct CompareAllOptions {
+    pub fail_fast: bool,
+    pub segments: bool,
+    /// Also run each spec's tests after building (see `compare_specs`).
+    pub with_tests: bool,
+    /// With `with_tests`, exclude failing specs from "smallest" sorting and
+    /// make the overall compare fail.
+    pub require_pass: bool,
+    /// Force a synthetic per-spec target_dir for specs that don't set their
+    /// own cargo.target_dir (see `resolve_isolated_target_dir`).
+    pub isolate: bool,
+    /// Include `BuildTool`-kind members instead of excluding them (see
+    /// `--include-build-tools`).
+    pub include_build_tools: bool,
+    /// Build every spec even when duplicates are detected (see
+    /// `--allow-duplicate-builds`).
+    pub allow_duplicate_builds: bool,
+}
+
 /// Compare all workspace packages that have binaries
 ///
 /// When `tspec_patterns` is empty, each package discovers its own specs via default glob.
 /// When non-empty, patterns are resolved per-package; packages with no matches are skipped.
+/// `exclude_patterns` filters discovered spec paths by filename after resolution.
 pub fn compare_all(
     workspace: &WorkspaceInfo,
     tspec_patterns: &[String],
-    fail_fast: bool,
+    exclude_patterns: &[String],
     flags: &CargoFlags,
+    options: CompareAllOptions,
 ) -> Vec<CompareResult> {
+    let CompareAllOptions {
+        fail_fast,
+        segments,
+        with_tests,
+        require_pass,
+        isolate,
+        include_build_tools,
+        allow_duplicate_builds,
+    } = options;
     let normalized = match normalize_tspec_patterns(tspec_patterns) {
         Some(n) => n,
         None => {
-            warn_shell_glob_expansion(tspec_patterns);
+            warn_shell_glob_expansion(tspec_patterns, None);
             return Vec::new();
         }
     };
     let mut results = Vec::new();
 
-    for member in workspace.buildable_members() {
+    let filtered = workspace.filtered_members(
+        MemberScope::All,
+        MemberFilter {
+            include_build_tools,
+        },
+    );
+    print_build_tool_exclusion_note(filtered.excluded_build_tools);
+    for member in filtered.members {
         if !member.has_binary {
             continue;
         }
@@ -608,22 +1167,46 @@ pub fn compare_all(
             }
             resolved
         };
+        let spec_paths = apply_exclude_specs(spec_paths, exclude_patterns);
+        if spec_paths.is_empty() {
+            continue;
+        }
 
         println!("=== {} ===", member.name);
 
-        let (op, specs) = match compare_specs(&member.name, &spec_paths, &workspace.root, flags) {
-            Ok(spec_results) => (
-                OpResult {
-                    name: member.name.clone(),
-                    version: member.version.clone(),
-                    spec: String::new(),
-                    success: true,
-                    message: "ok".to_string(),
-                    size: None,
-                    test_counts: None,
-                },
-                spec_results,
-            ),
+        let (op, specs) = match compare_specs(
+            &member.name,
+            &spec_paths,
+            &workspace.root,
+            flags,
+            segments,
+            with_tests,
+            require_pass,
+            isolate,
+            allow_duplicate_builds,
+        ) {
+            Ok(spec_results) => {
+                let any_test_failure =
+                    require_pass && spec_results.iter().any(|r| tests_failed(r.tests.as_ref()));
+                (
+                    OpResult {
+                        name: member.name.clone(),
+                        version: member.version.clone(),
+                        spec: String::new(),
+                        success: !any_test_failure,
+                        message: if any_test_failure {
+                            "one or more specs failed tests".to_string()
+                        } else {
+                            "ok".to_string()
+                        },
+                        size: None,
+                        test_counts: None,
+                        skipped: false,
+                        stripped_saved: None,
+                    },
+                    spec_results,
+                )
+            }
             Err(e) => (
                 OpResult {
                     name: member.name.clone(),
@@ -633,6 +1216,8 @@ pub fn compare_all(
                     message: e.to_string(),
                     size: None,
                     test_counts: None,
+                    skipped: false,
+                    stripped_saved: None,
                 },
                 Vec::new(),
             ),
@@ -653,7 +1238,11 @@ pub fn compare_all(
 ///
 /// With a single package, just prints its comparison table.
 /// With multiple packages, reprints all per-package tables then an overall OK/FAIL summary.
-pub fn print_compare_summary(name: &str, results: &[CompareResult]) -> ExitCode {
+pub fn print_compare_summary(
+    name: &str,
+    results: &[CompareResult],
+    baseline_spec: Option<&str>,
+) -> ExitCode {
     let has_failure = results.iter().any(|r| !r.op.success);
 
     // Reprint per-package comparison tables together
@@ -664,7 +1253,7 @@ pub fn print_compare_summary(name: &str, results: &[CompareResult]) -> ExitCode
             } else {
                 format!("{} v{}", result.op.name, result.op.version)
             };
-            print_comparison(&versioned, &result.specs);
+            print_comparison(&versioned, &result.specs, baseline_spec);
         }
     }
 
@@ -749,8 +1338,132 @@ pub fn print_run_summary(name: &str, results: &[OpResult]) -> ExitCode {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::workspace::{PackageKind, PackageMember};
     use std::process::ExitCode;
 
+    fn make_member(name: &str, has_binary: bool) -> PackageMember {
+        PackageMember {
+            name: name.to_string(),
+            version: "0.1.0".to_string(),
+            path: PathBuf::from(format!("/tmp/{name}")),
+            has_binary,
+            kind: if has_binary {
+                PackageKind::App
+            } else {
+                PackageKind::Lib
+            },
+        }
+    }
+
+    #[test]
+    fn run_result_message_matching_exit_is_success() {
+        let (success, message) = run_result_message(RunOutcome::Exited(3), 3);
+        assert!(success);
+        assert_eq!(message, "exit code: 3");
+    }
+
+    #[test]
+    fn run_result_message_mismatched_exit_is_failure() {
+        let (success, message) = run_result_message(RunOutcome::Exited(1), 3);
+        assert!(!success);
+        assert_eq!(message, "got 1, expected 3");
+    }
+
+    #[test]
+    fn run_result_message_signal_never_matches() {
+        // Even an expectation of 0 (the default) must not be satisfied by a
+        // signal kill, despite RunOutcome::code()'s conventional 1.
+        let (success, _) = run_result_message(RunOutcome::Signaled(Some(9)), 0);
+        assert!(!success);
+    }
+
+    #[test]
+    fn build_all_only_compatible_skips_incompatible_spec_with_clear_message() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("tspec.ts.toml"), "panic = \"abort\"\n").unwrap();
+        let spec = load_spec(&dir.path().join("tspec.ts.toml")).unwrap();
+        let hash = hash_spec(&spec).unwrap();
+        std::fs::write(
+            dir.path().join(crate::compat::COMPAT_FILE),
+            format!("incompatible = [\"{hash}\"]\n"),
+        )
+        .unwrap();
+
+        let workspace = WorkspaceInfo {
+            root: dir.path().to_path_buf(),
+            members: vec![PackageMember {
+                name: "pkg".to_string(),
+                version: "0.1.0".to_string(),
+                path: dir.path().to_path_buf(),
+                has_binary: true,
+                kind: PackageKind::App,
+            }],
+            version: None,
+            default_members: Vec::new(),
+        };
+
+        let results = build_all(
+            &workspace,
+            &[],
+            None,
+            &CargoFlags::default(),
+            BuildAllOptions {
+                force_profile: false,
+                strip: false,
+                fail_fast: false,
+                explicit_workspace: true,
+                only_compatible: true,
+                isolate: false,
+                quiet_cargo: false,
+                hermetic_env: false,
+                include_build_tools: false,
+                no_buildrs: false,
+                keep_buildrs: false,
+                strict_flags: false,
+                force: false,
+                smart_rebuild: false,
+            },
+        );
+
+        assert_eq!(results.len(), 1);
+        assert!(results[0].skipped);
+        assert!(results[0].success);
+        assert!(
+            results[0].message.contains("skipped") && results[0].message.contains(&hash),
+            "message should clearly note the skip and hash: {}",
+            results[0].message
+        );
+    }
+
+    #[test]
+    fn no_runnable_members_message_lib_only_workspace() {
+        let workspace = WorkspaceInfo {
+            root: PathBuf::from("/tmp/ws"),
+            members: vec![make_member("mylib", false), make_member("othertool", false)],
+            version: None,
+            default_members: Vec::new(),
+        };
+
+        let msg = no_runnable_members_message(&workspace);
+        assert!(msg.contains("no runnable app packages found"));
+        assert!(msg.contains("mylib — no binary target"));
+        assert!(msg.contains("othertool — no binary target"));
+    }
+
+    #[test]
+    fn no_runnable_members_message_marks_runnable_members_too() {
+        let workspace = WorkspaceInfo {
+            root: PathBuf::from("/tmp/ws"),
+            members: vec![make_member("mylib", false), make_member("myapp", true)],
+            version: None,
+            default_members: Vec::new(),
+        };
+
+        let msg = no_runnable_members_message(&workspace);
+        assert!(msg.contains("mylib — no binary target"));
+        assert!(msg.contains("myapp — runnable"));
+    }
+
     fn make_op(name: &str, success: bool, counts: Option<TestResult>) -> OpResult {
         OpResult {
             name: name.to_string(),
@@ -764,6 +1477,8 @@ mod tests {
             },
             size: None,
             test_counts: counts,
+            skipped: false,
+            stripped_saved: None,
         }
     }
 
@@ -778,6 +1493,7 @@ mod tests {
                     failed: 0,
                     ignored: 0,
                     filtered: 0,
+                    ..Default::default()
                 }),
             ),
             make_op(
@@ -788,10 +1504,11 @@ mod tests {
                     failed: 0,
                     ignored: 0,
                     filtered: 0,
+                    ..Default::default()
                 }),
             ),
         ];
-        let code = print_test_summary("test", &results);
+        let code = print_test_summary("test", &results, None);
         assert_eq!(code, ExitCode::SUCCESS);
     }
 
@@ -806,6 +1523,7 @@ mod tests {
                     failed: 0,
                     ignored: 0,
                     filtered: 0,
+                    ..Default::default()
                 }),
             ),
             make_op(
@@ -816,18 +1534,47 @@ mod tests {
                     failed: 2,
                     ignored: 0,
                     filtered: 0,
+                    ..Default::default()
                 }),
             ),
         ];
-        let code = print_test_summary("test", &results);
+        let code = print_test_summary("test", &results, None);
         assert_eq!(code, ExitCode::from(1));
     }
 
+    #[test]
+    fn sorted_results_by_size_descending_with_name_tiebreak() {
+        let mut a = make_op("a-small", true, None);
+        a.size = Some(100);
+        let mut b = make_op("b-big", true, None);
+        b.size = Some(300);
+        let mut c = make_op("c-tied", true, None);
+        c.size = Some(100);
+        let results = vec![a, b, c];
+
+        let sorted = sorted_results(&results, SortBy::Size);
+        let names: Vec<&str> = sorted.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["b-big", "a-small", "c-tied"]);
+    }
+
+    #[test]
+    fn sorted_results_by_name() {
+        let results = vec![
+            make_op("zeta", true, None),
+            make_op("alpha", true, None),
+            make_op("mid", true, None),
+        ];
+
+        let sorted = sorted_results(&results, SortBy::Name);
+        let names: Vec<&str> = sorted.iter().map(|r| r.name.as_str()).collect();
+        assert_eq!(names, vec!["alpha", "mid", "zeta"]);
+    }
+
     #[test]
     fn test_summary_no_counts() {
         // Packages without test_counts (e.g., build failure before tests ran)
         let results = vec![make_op("pkg-a", false, None)];
-        let code = print_test_summary("test", &results);
+        let code = print_test_summary("test", &results, None);
         assert_eq!(code, ExitCode::from(1));
     }
 
@@ -842,6 +1589,7 @@ mod tests {
                     failed: 0,
                     ignored: 3,
                     filtered: 0,
+                    ..Default::default()
                 }),
             ),
             make_op(
@@ -852,12 +1600,117 @@ mod tests {
                     failed: 0,
                     ignored: 0,
                     filtered: 0,
+                    ..Default::default()
                 }),
             ),
         ];
-        let code = print_test_summary("test", &results);
+        let code = print_test_summary("test", &results, None);
         assert_eq!(code, ExitCode::SUCCESS);
         // pkg-a row shows "(3 ignored)", pkg-b does not;
         // footer shows "(3 ignored)" in totals
     }
+
+    #[test]
+    fn exclude_specs_no_patterns_returns_all() {
+        let paths = vec![PathBuf::from("tspec.ts.toml"), PathBuf::from("a.ts.toml")];
+        let filtered = apply_exclude_specs(paths.clone(), &[]);
+        assert_eq!(filtered, paths);
+    }
+
+    #[test]
+    fn exclude_specs_filters_matching_filename() {
+        let paths = vec![
+            PathBuf::from("pkg/tspec.ts.toml"),
+            PathBuf::from("pkg/tspec.experimental.ts.toml"),
+        ];
+        let filtered = apply_exclude_specs(paths, &["tspec.experimental*".to_string()]);
+        assert_eq!(filtered, vec![PathBuf::from("pkg/tspec.ts.toml")]);
+    }
+
+    #[test]
+    fn exclude_specs_leaves_non_matching_untouched() {
+        let paths = vec![PathBuf::from("pkg/tspec.release.ts.toml")];
+        let filtered = apply_exclude_specs(paths.clone(), &["tspec.experimental*".to_string()]);
+        assert_eq!(filtered, paths);
+    }
+
+    fn make_op_with_spec(name: &str, spec: &str, success: bool, skipped: bool) -> OpResult {
+        OpResult {
+            spec: spec.to_string(),
+            skipped,
+            ..make_op(name, success, None)
+        }
+    }
+
+    #[test]
+    fn build_totals_one_spec_per_package_matches_row_count() {
+        let results = vec![
+            make_op_with_spec("pkg-a", "tspec.ts.toml", true, false),
+            make_op_with_spec("pkg-b", "tspec.ts.toml", false, false),
+        ];
+        let totals = build_totals(&results);
+        assert_eq!(totals.ok, 1);
+        assert_eq!(totals.failed, 1);
+        assert_eq!(totals.skipped, 0);
+        assert_eq!(totals.packages, 2);
+        assert_eq!(totals.rows(), totals.packages);
+    }
+
+    #[test]
+    fn build_totals_distinguishes_rows_from_packages_when_multi_spec() {
+        let results = vec![
+            make_op_with_spec("pkg-a", "static.ts.toml", true, false),
+            make_op_with_spec("pkg-a", "musl.ts.toml", true, false),
+            make_op_with_spec("pkg-b", "tspec.ts.toml", false, false),
+        ];
+        let totals = build_totals(&results);
+        assert_eq!(totals.ok, 2);
+        assert_eq!(totals.failed, 1);
+        assert_eq!(totals.packages, 2);
+        assert_eq!(totals.rows(), 3);
+    }
+
+    #[test]
+    fn build_totals_counts_skipped_rows_separately() {
+        let results = vec![
+            make_op_with_spec("pkg-a", "tspec.ts.toml", true, false),
+            make_op_with_spec("pkg-b", "tspec.ts.toml", true, true),
+        ];
+        let totals = build_totals(&results);
+        assert_eq!(totals.ok, 1);
+        assert_eq!(totals.skipped, 1);
+        assert_eq!(totals.packages, 2);
+    }
+
+    #[test]
+    fn build_footer_omits_builds_clause_when_rows_equal_packages() {
+        let results = vec![
+            make_op_with_spec("pkg-a", "tspec.ts.toml", true, false),
+            make_op_with_spec("pkg-b", "tspec.ts.toml", true, false),
+        ];
+        let footer = build_footer(build_totals(&results));
+        assert_eq!(footer, "Build: 2 ok, 0 failed");
+    }
+
+    #[test]
+    fn build_footer_adds_builds_clause_when_a_package_has_multiple_specs() {
+        let results = vec![
+            make_op_with_spec("pkg-a", "static.ts.toml", true, false),
+            make_op_with_spec("pkg-a", "musl.ts.toml", false, false),
+        ];
+        let footer = build_footer(build_totals(&results));
+        assert_eq!(footer, "Build: 1 ok, 1 failed (2 builds across 1 packages)");
+    }
+
+    #[test]
+    fn print_summary_flat_exit_code_unaffected_by_group_by() {
+        let results = vec![
+            make_op_with_spec("pkg-a", "static.ts.toml", true, false),
+            make_op_with_spec("pkg-a", "musl.ts.toml", false, false),
+        ];
+        let flat = print_summary_grouped("ws", &results, SortBy::Name, GroupBy::Flat);
+        let grouped = print_summary_grouped("ws", &results, SortBy::Name, GroupBy::Package);
+        assert_eq!(flat, ExitCode::from(1));
+        assert_eq!(grouped, ExitCode::from(1));
+    }
 }
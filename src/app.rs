@@ -0,0 +1,151 @@
+//! Shared CLI entry point for both the standalone `tspec` binary and the
+//! `cargo-tspec` binary (see `src/bin/cargo-tspec.rs`), which lets `cargo
+//! tspec ...` invoke tspec as a cargo subcommand.
+
+use anyhow::Context;
+use clap::{CommandFactory, FromArgMatches};
+use std::ffi::OsString;
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::Instant;
+
+use crate::cli::{Cli, Commands, augment_with_examples};
+use crate::cmd::Execute;
+use crate::find_paths::{
+    RootMode, TSPEC_SPEC_DIR_ENV, check_root_manifest, find_project_root_with_mode,
+    resolve_manifest_path,
+};
+use crate::metadata_cache::REFRESH_METADATA_ENV;
+use crate::types::{CargoFlags, Verbosity};
+use crate::usage;
+
+/// Parse `args` (argv, including argv[0]) and run the resolved command.
+/// `bin_name` overrides what usage/help text shows as the program name —
+/// `"tspec"` for the standalone binary, `"cargo tspec"` for the
+/// `cargo-tspec` subcommand binary — so `--help` reads right either way.
+pub fn run(args: Vec<OsString>, bin_name: &str) -> Result<ExitCode, anyhow::Error> {
+    let command = augment_with_examples(Cli::command()).bin_name(bin_name.to_string());
+    let matches = command.get_matches_from(args);
+    let cli = Cli::from_arg_matches(&matches).unwrap_or_else(|e| e.exit());
+    let flags = CargoFlags {
+        verbosity: Verbosity::from_count(cli.verbose),
+        jobs: cli.jobs,
+        locked: cli.locked,
+        offline: cli.offline,
+        extra_args: Vec::new(),
+    };
+
+    if cli.refresh_metadata {
+        // SAFETY: single-threaded at this point, before any command runs.
+        unsafe {
+            std::env::set_var(REFRESH_METADATA_ENV, "1");
+        }
+    }
+
+    let root_mode = match cli.root_mode {
+        Some(mode) => mode,
+        None => RootMode::from_env()?,
+    };
+    let project_root = match cli.manifest_path {
+        Some(ref path) => resolve_manifest_path(path)?,
+        None => find_project_root_with_mode(root_mode)?,
+    };
+    check_root_manifest(&project_root)?;
+
+    if !cli.spec_dir.is_empty() {
+        let resolved: Vec<_> = cli
+            .spec_dir
+            .iter()
+            .map(|dir| {
+                if dir.is_absolute() {
+                    dir.clone()
+                } else {
+                    project_root.join(dir)
+                }
+            })
+            .collect();
+        let joined =
+            std::env::join_paths(&resolved).context("--spec-dir values contain an invalid path")?;
+        // SAFETY: single-threaded at this point, before any command runs.
+        unsafe {
+            std::env::set_var(TSPEC_SPEC_DIR_ENV, joined);
+        }
+    }
+
+    let command_name = command_name(&cli.command);
+    let start = Instant::now();
+    let result = dispatch(cli.command, &project_root, &flags);
+    let success = matches!(&result, Ok(code) if *code == ExitCode::SUCCESS);
+    usage::record(&project_root, command_name, start.elapsed(), success);
+    result
+}
+
+fn command_name(command: &Commands) -> &'static str {
+    match command {
+        Commands::Build(_) => "build",
+        Commands::Run(_) => "run",
+        Commands::Test(_) => "test",
+        Commands::Bench(_) => "bench",
+        Commands::Clean(_) => "clean",
+        Commands::Clippy(_) => "clippy",
+        Commands::Fmt(_) => "fmt",
+        Commands::Compare(_) => "compare",
+        Commands::Baselines(_) => "baselines",
+        Commands::Ci(_) => "ci",
+        Commands::Ts(_) => "ts",
+        Commands::Version(_) => "version",
+        Commands::Install(_) => "install",
+        Commands::Completions(_) => "completions",
+        Commands::Generate(_) => "generate",
+        Commands::CompleteCandidates(_) => "complete-candidates",
+        Commands::Usage(_) => "usage",
+        Commands::ExplainPath(_) => "explain-path",
+        Commands::Print(_) => "print",
+        Commands::Experiment(_) => "experiment",
+        Commands::Report(_) => "report",
+        Commands::List(_) => "list",
+        Commands::Deps(_) => "deps",
+        Commands::Repro(_) => "repro",
+        Commands::Doctor(_) => "doctor",
+        Commands::Targets(_) => "targets",
+        Commands::Schema(_) => "schema",
+        Commands::Examples(_) => "examples",
+    }
+}
+
+fn dispatch(
+    command: Commands,
+    project_root: &Path,
+    flags: &CargoFlags,
+) -> Result<ExitCode, anyhow::Error> {
+    match command {
+        Commands::Build(cmd) => cmd.execute(project_root, flags),
+        Commands::Run(cmd) => cmd.execute(project_root, flags),
+        Commands::Test(cmd) => cmd.execute(project_root, flags),
+        Commands::Bench(cmd) => cmd.execute(project_root, flags),
+        Commands::Clean(cmd) => cmd.execute(project_root, flags),
+        Commands::Clippy(cmd) => cmd.execute(project_root, flags),
+        Commands::Fmt(cmd) => cmd.execute(project_root, flags),
+        Commands::Compare(cmd) => cmd.execute(project_root, flags),
+        Commands::Baselines(cmd) => cmd.execute(project_root, flags),
+        Commands::Ci(cmd) => cmd.execute(project_root, flags),
+        Commands::Ts(cmd) => cmd.execute(project_root, flags),
+        Commands::Version(cmd) => cmd.execute(project_root, flags),
+        Commands::Install(cmd) => cmd.execute(project_root, flags),
+        Commands::Completions(cmd) => cmd.execute(project_root, flags),
+        Commands::Generate(cmd) => cmd.execute(project_root, flags),
+        Commands::CompleteCandidates(cmd) => cmd.execute(project_root, flags),
+        Commands::Usage(cmd) => cmd.execute(project_root, flags),
+        Commands::ExplainPath(cmd) => cmd.execute(project_root, flags),
+        Commands::Print(cmd) => cmd.execute(project_root, flags),
+        Commands::Experiment(cmd) => cmd.execute(project_root, flags),
+        Commands::Report(cmd) => cmd.execute(project_root, flags),
+        Commands::List(cmd) => cmd.execute(project_root, flags),
+        Commands::Deps(cmd) => cmd.execute(project_root, flags),
+        Commands::Repro(cmd) => cmd.execute(project_root, flags),
+        Commands::Doctor(cmd) => cmd.execute(project_root, flags),
+        Commands::Targets(cmd) => cmd.execute(project_root, flags),
+        Commands::Schema(cmd) => cmd.execute(project_root, flags),
+        Commands::Examples(cmd) => cmd.execute(project_root, flags),
+    }
+}
@@ -0,0 +1,120 @@
+//! Optional audit log for `ts` field edits.
+//!
+//! Set `TSPEC_AUDIT_LOG=<path>` and every `ts set`/`unset`/`add`/`remove`
+//! appends one line: `timestamp user command key value file`. Unlike
+//! `usage.rs`'s opt-in telemetry (which deliberately never records who ran
+//! a command), this exists for traceability in a shared repo, so it does
+//! record the user. Append-only and best-effort: a logging failure (bad
+//! path, permissions) never fails the edit itself.
+
+use std::fs::OpenOptions;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+
+/// Env var naming the audit log file. Unset (the default) disables logging.
+pub const TSPEC_AUDIT_LOG_ENV: &str = "TSPEC_AUDIT_LOG";
+
+fn audit_log_path() -> Option<PathBuf> {
+    std::env::var_os(TSPEC_AUDIT_LOG_ENV).map(PathBuf::from)
+}
+
+fn current_user() -> String {
+    std::env::var("USER")
+        .or_else(|_| std::env::var("USERNAME"))
+        .unwrap_or_else(|_| "unknown".to_string())
+}
+
+/// Append one audit line for `command key value file` if `TSPEC_AUDIT_LOG`
+/// is set. Best-effort: any failure (unwritable path, etc.) is swallowed so
+/// the underlying edit always succeeds regardless of logging.
+pub fn record(command: &str, key: &str, value: &str, file: &Path) {
+    let Some(log_path) = audit_log_path() else {
+        return;
+    };
+    let _ = append_line(&log_path, command, key, value, file);
+}
+
+fn append_line(
+    log_path: &Path,
+    command: &str,
+    key: &str,
+    value: &str,
+    file: &Path,
+) -> std::io::Result<()> {
+    if let Some(parent) = log_path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        std::fs::create_dir_all(parent)?;
+    }
+    let line = format!(
+        "{} {} {} {} {} {}",
+        crate::usage::now_utc_iso(),
+        current_user(),
+        command,
+        key,
+        value,
+        file.display(),
+    );
+    // A single write_all() under PIPE_BUF with O_APPEND is atomic on
+    // POSIX, so concurrent tspec processes can't interleave partial lines
+    // (same reasoning as usage.rs's append_record).
+    let mut f = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(log_path)?;
+    writeln!(f, "{line}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// Tests that touch `TSPEC_AUDIT_LOG` mutate process-global state, so
+    /// they must not run concurrently with each other.
+    fn with_audit_log<R>(path: &Path, f: impl FnOnce() -> R) -> R {
+        // SAFETY: serialized by this function's own lock-free convention —
+        // no other test in this module reads/writes TSPEC_AUDIT_LOG.
+        unsafe { std::env::set_var(TSPEC_AUDIT_LOG_ENV, path) };
+        let result = f();
+        unsafe { std::env::remove_var(TSPEC_AUDIT_LOG_ENV) };
+        result
+    }
+
+    #[test]
+    fn record_is_a_noop_without_the_env_var() {
+        let dir = TempDir::new().unwrap();
+        let log_path = dir.path().join("audit.log");
+        record("set", "panic", "abort", Path::new("tspec.ts.toml"));
+        assert!(!log_path.exists());
+    }
+
+    #[test]
+    fn record_appends_a_line_with_the_expected_fields() {
+        let dir = TempDir::new().unwrap();
+        let log_path = dir.path().join("audit.log");
+        let spec_path = dir.path().join("tspec.ts.toml");
+
+        with_audit_log(&log_path, || {
+            record("set", "cargo.profile", "release", &spec_path);
+        });
+
+        let content = std::fs::read_to_string(&log_path).unwrap();
+        let line = content.lines().next().unwrap();
+        let fields: Vec<&str> = line.split(' ').collect();
+        assert_eq!(fields[2], "set");
+        assert_eq!(fields[3], "cargo.profile");
+        assert_eq!(fields[4], "release");
+        assert_eq!(fields[5], spec_path.display().to_string());
+    }
+
+    #[test]
+    fn record_creates_the_log_directory_if_missing() {
+        let dir = TempDir::new().unwrap();
+        let log_path = dir.path().join("nested").join("audit.log");
+
+        with_audit_log(&log_path, || {
+            record("unset", "panic", "", Path::new("tspec.ts.toml"));
+        });
+
+        assert!(log_path.exists());
+    }
+}
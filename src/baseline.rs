@@ -0,0 +1,340 @@
+//! Named compare baselines (`tspec compare --save-as`/`--against`,
+//! `tspec baselines list/show/delete`).
+//!
+//! A baseline is a snapshot of one `tspec compare` run's per-spec sizes and
+//! content hashes, stored under `.tspec/baselines/<label>.json` so later runs
+//! can diff against it by label instead of only the in-memory first-row
+//! baseline `print_comparison` already supports.
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::compare::SpecResult;
+
+/// Bump when the on-disk shape changes, so a future loader can migrate old files.
+const BASELINE_FORMAT_VERSION: u32 = 1;
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct BaselineEntry {
+    pub spec: String,
+    pub size: u64,
+    /// Content hash of the spec that produced this row, when known (the
+    /// `cargo --release`/`cargo --release-strip` rows have none).
+    pub hash: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Baseline {
+    pub version: u32,
+    pub entries: Vec<BaselineEntry>,
+}
+
+/// Directory baselines are stored under, relative to the project root.
+fn baselines_dir(project_root: &Path) -> PathBuf {
+    project_root.join(".tspec").join("baselines")
+}
+
+/// Path to a single baseline's JSON file.
+pub fn baseline_path(project_root: &Path, label: &str) -> PathBuf {
+    baselines_dir(project_root).join(format!("{label}.json"))
+}
+
+/// Split a `SpecResult::name` like `"tspec.min.toml [abcd1234]"` into its
+/// spec name and content hash. Rows with no `[hash]` suffix (the
+/// `cargo --release*` baseline rows) return `(name, None)` unchanged.
+pub(crate) fn split_name_hash(name: &str) -> (String, Option<String>) {
+    if let Some(open) = name.rfind(" [")
+        && name.ends_with(']')
+    {
+        let spec = name[..open].to_string();
+        let hash = name[open + 2..name.len() - 1].to_string();
+        return (spec, Some(hash));
+    }
+    (name.to_string(), None)
+}
+
+/// Build a [`Baseline`] from a compare run's results, sorted by spec name so
+/// the on-disk file diffs cleanly across saves.
+pub fn baseline_from_results(results: &[SpecResult]) -> Baseline {
+    let mut entries: Vec<BaselineEntry> = results
+        .iter()
+        .map(|r| {
+            let (spec, hash) = split_name_hash(&r.name);
+            BaselineEntry {
+                spec,
+                size: r.size,
+                hash,
+            }
+        })
+        .collect();
+    entries.sort_by(|a, b| a.spec.cmp(&b.spec));
+    Baseline {
+        version: BASELINE_FORMAT_VERSION,
+        entries,
+    }
+}
+
+/// Save a compare run as a named baseline, creating `.tspec/baselines/` if needed.
+pub fn save_baseline(project_root: &Path, label: &str, results: &[SpecResult]) -> Result<PathBuf> {
+    let dir = baselines_dir(project_root);
+    std::fs::create_dir_all(&dir)
+        .with_context(|| format!("failed to create directory: {}", dir.display()))?;
+    let baseline = baseline_from_results(results);
+    let path = baseline_path(project_root, label);
+    let json = serde_json::to_string_pretty(&baseline).context("failed to serialize baseline")?;
+    std::fs::write(&path, json)
+        .with_context(|| format!("failed to write baseline: {}", path.display()))?;
+    Ok(path)
+}
+
+/// Load a named baseline.
+pub fn load_baseline(project_root: &Path, label: &str) -> Result<Baseline> {
+    let path = baseline_path(project_root, label);
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("no baseline named '{label}' ({})", path.display()))?;
+    serde_json::from_str(&content)
+        .with_context(|| format!("failed to parse baseline: {}", path.display()))
+}
+
+/// Delete a named baseline. Errors if it doesn't exist.
+pub fn delete_baseline(project_root: &Path, label: &str) -> Result<()> {
+    let path = baseline_path(project_root, label);
+    std::fs::remove_file(&path)
+        .with_context(|| format!("no baseline named '{label}' ({})", path.display()))
+}
+
+/// List every saved baseline's label, sorted.
+pub fn list_baselines(project_root: &Path) -> Result<Vec<String>> {
+    let dir = baselines_dir(project_root);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+    let mut labels: Vec<String> = std::fs::read_dir(&dir)
+        .with_context(|| format!("failed to read directory: {}", dir.display()))?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension().and_then(|e| e.to_str()) == Some("json") {
+                path.file_stem().map(|s| s.to_string_lossy().into_owned())
+            } else {
+                None
+            }
+        })
+        .collect();
+    labels.sort();
+    Ok(labels)
+}
+
+/// One row of a baseline-vs-current diff: a spec aligned by name, present in
+/// the baseline, the current run, or (rarely) both but with a different hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BaselineDiffRow {
+    pub spec: String,
+    pub baseline_size: Option<u64>,
+    pub current_size: Option<u64>,
+    pub hash_changed: bool,
+}
+
+impl BaselineDiffRow {
+    /// Signed byte delta (current minus baseline), when both sides are present.
+    pub fn delta(&self) -> Option<i64> {
+        match (self.baseline_size, self.current_size) {
+            (Some(b), Some(c)) => Some(c as i64 - b as i64),
+            _ => None,
+        }
+    }
+
+    /// Percent delta relative to the baseline size, when both sides are
+    /// present and the baseline size is nonzero.
+    pub fn percent(&self) -> Option<f64> {
+        let delta = self.delta()?;
+        if self.baseline_size == Some(0) || self.baseline_size.is_none() {
+            return None;
+        }
+        Some(delta as f64 / self.baseline_size.unwrap() as f64 * 100.0)
+    }
+}
+
+/// Align a baseline's entries against a fresh compare run's results by spec
+/// name, marking specs missing from either side explicitly. Pure so the
+/// row-alignment logic is unit-testable without a real build.
+pub fn diff_against(baseline: &Baseline, results: &[SpecResult]) -> Vec<BaselineDiffRow> {
+    let mut rows = Vec::new();
+    let mut seen = std::collections::BTreeSet::new();
+
+    for entry in &baseline.entries {
+        seen.insert(entry.spec.clone());
+        let current = results
+            .iter()
+            .map(|r| split_name_hash(&r.name))
+            .find(|(spec, _)| spec == &entry.spec);
+        let (current_size, hash_changed) = match current {
+            Some((_, current_hash)) => (
+                results
+                    .iter()
+                    .find(|r| split_name_hash(&r.name).0 == entry.spec)
+                    .map(|r| r.size),
+                current_hash != entry.hash,
+            ),
+            None => (None, false),
+        };
+        rows.push(BaselineDiffRow {
+            spec: entry.spec.clone(),
+            baseline_size: Some(entry.size),
+            current_size,
+            hash_changed,
+        });
+    }
+
+    for r in results {
+        let (spec, _) = split_name_hash(&r.name);
+        if seen.insert(spec.clone()) {
+            rows.push(BaselineDiffRow {
+                spec,
+                baseline_size: None,
+                current_size: Some(r.size),
+                hash_changed: false,
+            });
+        }
+    }
+
+    rows
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(name: &str, size: u64) -> SpecResult {
+        SpecResult {
+            name: name.to_string(),
+            size,
+            stripped_size: None,
+            triple: "host".to_string(),
+            segments: None,
+            tests: None,
+        }
+    }
+
+    #[test]
+    fn split_name_hash_splits_hashed_name() {
+        assert_eq!(
+            split_name_hash("tspec.min.toml [abcd1234]"),
+            ("tspec.min.toml".to_string(), Some("abcd1234".to_string()))
+        );
+    }
+
+    #[test]
+    fn split_name_hash_passes_through_unhashed_name() {
+        assert_eq!(
+            split_name_hash("cargo --release"),
+            ("cargo --release".to_string(), None)
+        );
+    }
+
+    #[test]
+    fn baseline_from_results_sorts_by_spec_name() {
+        let results = vec![result("b.toml [1]", 20), result("a.toml [2]", 10)];
+        let baseline = baseline_from_results(&results);
+        assert_eq!(baseline.entries[0].spec, "a.toml");
+        assert_eq!(baseline.entries[1].spec, "b.toml");
+    }
+
+    #[test]
+    fn diff_against_matches_by_spec_name() {
+        let baseline = Baseline {
+            version: 1,
+            entries: vec![BaselineEntry {
+                spec: "a.toml".to_string(),
+                size: 100,
+                hash: Some("aaaa".to_string()),
+            }],
+        };
+        let results = vec![result("a.toml [aaaa]", 90)];
+        let rows = diff_against(&baseline, &results);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].delta(), Some(-10));
+        assert!(!rows[0].hash_changed);
+    }
+
+    #[test]
+    fn diff_against_flags_hash_change() {
+        let baseline = Baseline {
+            version: 1,
+            entries: vec![BaselineEntry {
+                spec: "a.toml".to_string(),
+                size: 100,
+                hash: Some("aaaa".to_string()),
+            }],
+        };
+        let results = vec![result("a.toml [bbbb]", 100)];
+        let rows = diff_against(&baseline, &results);
+        assert!(rows[0].hash_changed);
+    }
+
+    #[test]
+    fn diff_against_marks_spec_missing_from_current_run() {
+        let baseline = Baseline {
+            version: 1,
+            entries: vec![BaselineEntry {
+                spec: "gone.toml".to_string(),
+                size: 100,
+                hash: None,
+            }],
+        };
+        let rows = diff_against(&baseline, &[]);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].current_size, None);
+        assert_eq!(rows[0].delta(), None);
+    }
+
+    #[test]
+    fn diff_against_marks_spec_new_in_current_run() {
+        let baseline = Baseline {
+            version: 1,
+            entries: vec![],
+        };
+        let results = vec![result("new.toml [cccc]", 50)];
+        let rows = diff_against(&baseline, &results);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].baseline_size, None);
+        assert_eq!(rows[0].current_size, Some(50));
+    }
+
+    #[test]
+    fn save_and_load_baseline_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        let results = vec![result("a.toml [aaaa]", 10)];
+        save_baseline(dir.path(), "v1", &results).unwrap();
+        let loaded = load_baseline(dir.path(), "v1").unwrap();
+        assert_eq!(loaded.entries[0].spec, "a.toml");
+        assert_eq!(loaded.entries[0].size, 10);
+    }
+
+    #[test]
+    fn list_baselines_sorted_and_empty_when_none_saved() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(list_baselines(dir.path()).unwrap().is_empty());
+        save_baseline(dir.path(), "zeta", &[result("a.toml [x]", 1)]).unwrap();
+        save_baseline(dir.path(), "alpha", &[result("a.toml [x]", 1)]).unwrap();
+        assert_eq!(
+            list_baselines(dir.path()).unwrap(),
+            vec!["alpha".to_string(), "zeta".to_string()]
+        );
+    }
+
+    #[test]
+    fn delete_baseline_removes_file() {
+        let dir = tempfile::tempdir().unwrap();
+        save_baseline(dir.path(), "v1", &[result("a.toml [x]", 1)]).unwrap();
+        delete_baseline(dir.path(), "v1").unwrap();
+        assert!(load_baseline(dir.path(), "v1").is_err());
+    }
+
+    #[test]
+    fn delete_baseline_missing_label_is_an_error() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(delete_baseline(dir.path(), "nope").is_err());
+    }
+}
@@ -0,0 +1,17 @@
+//! Lets `cargo tspec ...` invoke tspec as a cargo subcommand. Cargo finds
+//! this binary on PATH by its `cargo-<name>` filename and, like it does for
+//! `cargo clippy`/`cargo fmt`, re-inserts the subcommand name as the first
+//! argument — so `cargo tspec build -w` spawns this as
+//! `cargo-tspec tspec build -w`. Strip that inserted "tspec" before handing
+//! argv to the shared CLI so parsing sees the same args as the plain
+//! `tspec` binary would.
+use std::ffi::OsString;
+use std::process::ExitCode;
+
+fn main() -> Result<ExitCode, anyhow::Error> {
+    let mut args: Vec<OsString> = std::env::args_os().collect();
+    if args.get(1).map(|a| a.as_os_str()) == Some(std::ffi::OsStr::new("tspec")) {
+        args.remove(1);
+    }
+    tspec::app::run(args, "cargo tspec")
+}
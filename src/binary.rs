@@ -3,12 +3,195 @@ use std::fs;
 use std::path::Path;
 use std::process::Command;
 
-/// Strip symbols from a binary
+const ELF_MAGIC: &[u8; 4] = b"\x7fELF";
+const ELFCLASS64: u8 = 2;
+const ELFDATA2LSB: u8 = 1;
+const PT_LOAD: u32 = 1;
+const PF_W: u32 = 0x2;
+
+/// Loadable-segment stats parsed from an ELF64 LE binary's program headers.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ElfSegments {
+    /// Total `PT_LOAD` `p_filesz` — bytes actually stored on disk/flash.
+    pub flash: u64,
+    /// Total `PT_LOAD` `p_memsz` — bytes occupied in RAM at load time.
+    pub ram: u64,
+    /// Sum of `p_memsz - p_filesz` for writable (`PF_W`) `PT_LOAD` segments.
+    pub bss: u64,
+}
+
+/// Parse ELF program headers and summarize loadable-segment sizes.
+///
+/// Returns `Ok(None)` for non-ELF files or ELF variants this minimal reader
+/// doesn't understand (only ELF64 little-endian is supported), so callers
+/// can degrade to file-size-only reporting.
+pub fn read_elf_segments(path: &Path) -> Result<Option<ElfSegments>> {
+    let data = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+
+    if data.len() < 64 || &data[0..4] != ELF_MAGIC {
+        return Ok(None);
+    }
+    if data[4] != ELFCLASS64 || data[5] != ELFDATA2LSB {
+        return Ok(None); // 32-bit or big-endian ELF: unsupported by this reader
+    }
+
+    let phoff = u64::from_le_bytes(data[32..40].try_into().unwrap()) as usize;
+    let phentsize = u16::from_le_bytes(data[54..56].try_into().unwrap()) as usize;
+    let phnum = u16::from_le_bytes(data[56..58].try_into().unwrap()) as usize;
+
+    let mut segments = ElfSegments::default();
+    for i in 0..phnum {
+        let start = phoff + i * phentsize;
+        let Some(phdr) = data.get(start..start + 56) else {
+            bail!("truncated ELF program header in {}", path.display());
+        };
+
+        let p_type = u32::from_le_bytes(phdr[0..4].try_into().unwrap());
+        if p_type != PT_LOAD {
+            continue;
+        }
+        let p_flags = u32::from_le_bytes(phdr[4..8].try_into().unwrap());
+        let p_filesz = u64::from_le_bytes(phdr[32..40].try_into().unwrap());
+        let p_memsz = u64::from_le_bytes(phdr[40..48].try_into().unwrap());
+
+        segments.flash += p_filesz;
+        segments.ram += p_memsz;
+        if p_flags & PF_W != 0 {
+            segments.bss += p_memsz.saturating_sub(p_filesz);
+        }
+    }
+
+    Ok(Some(segments))
+}
+
+/// One ELF64 section header, with its name resolved via `.shstrtab`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ElfSection {
+    pub name: String,
+    /// Byte offset of the section's content within the file.
+    pub offset: u64,
+    /// Size of the section's content in bytes.
+    pub size: u64,
+}
+
+/// Parse ELF64 LE section headers and resolve their names.
+///
+/// Returns `Ok(None)` for the same cases as [`read_elf_segments`]: non-ELF
+/// files, or ELF variants this minimal reader doesn't understand.
+pub fn read_elf_sections(path: &Path) -> Result<Option<Vec<ElfSection>>> {
+    let data = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+
+    if data.len() < 64 || &data[0..4] != ELF_MAGIC {
+        return Ok(None);
+    }
+    if data[4] != ELFCLASS64 || data[5] != ELFDATA2LSB {
+        return Ok(None);
+    }
+
+    let shoff = u64::from_le_bytes(data[40..48].try_into().unwrap()) as usize;
+    let shentsize = u16::from_le_bytes(data[58..60].try_into().unwrap()) as usize;
+    let shnum = u16::from_le_bytes(data[60..62].try_into().unwrap()) as usize;
+    let shstrndx = u16::from_le_bytes(data[62..64].try_into().unwrap()) as usize;
+
+    if shnum == 0 {
+        return Ok(Some(Vec::new()));
+    }
+
+    let read_shdr = |idx: usize| -> Result<(u32, u64, u64)> {
+        let start = shoff + idx * shentsize;
+        let Some(shdr) = data.get(start..start + 64) else {
+            bail!("truncated ELF section header in {}", path.display());
+        };
+        let sh_name = u32::from_le_bytes(shdr[0..4].try_into().unwrap());
+        let sh_offset = u64::from_le_bytes(shdr[24..32].try_into().unwrap());
+        let sh_size = u64::from_le_bytes(shdr[32..40].try_into().unwrap());
+        Ok((sh_name, sh_offset, sh_size))
+    };
+
+    let (_, shstrtab_offset, shstrtab_size) = read_shdr(shstrndx)?;
+    let shstrtab = data
+        .get(shstrtab_offset as usize..(shstrtab_offset + shstrtab_size) as usize)
+        .with_context(|| format!("truncated .shstrtab in {}", path.display()))?;
+
+    let name_at = |name_off: u32| -> String {
+        let start = name_off as usize;
+        let end = shstrtab[start..]
+            .iter()
+            .position(|&b| b == 0)
+            .map(|i| start + i)
+            .unwrap_or(shstrtab.len());
+        String::from_utf8_lossy(&shstrtab[start..end]).into_owned()
+    };
+
+    let mut sections = Vec::with_capacity(shnum);
+    for i in 0..shnum {
+        let (sh_name, offset, size) = read_shdr(i)?;
+        sections.push(ElfSection {
+            name: name_at(sh_name),
+            offset,
+            size,
+        });
+    }
+
+    Ok(Some(sections))
+}
+
+/// Native executable/object format, identified by magic bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BinaryFormat {
+    Elf,
+    MachO,
+    Pe,
+    /// Not a recognized native binary — e.g. a shell script wrapper some
+    /// build hooks produce in place of the real artifact.
+    Unknown,
+}
+
+/// Identify a file's format from its leading magic bytes, without assuming
+/// it's long enough to also be a *valid* binary of that format — a short
+/// or truncated file can still report `Elf`/`MachO`/`Pe` here; parsers like
+/// [`read_elf_segments`] do their own length checks before trusting it.
+pub fn detect_format(path: &Path) -> Result<BinaryFormat> {
+    let mut buf = [0u8; 4];
+    let data = fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let len = data.len().min(4);
+    buf[..len].copy_from_slice(&data[..len]);
+
+    Ok(if &buf == ELF_MAGIC {
+        BinaryFormat::Elf
+    } else if matches!(
+        buf,
+        [0xfe, 0xed, 0xfa, 0xce]
+            | [0xfe, 0xed, 0xfa, 0xcf]
+            | [0xce, 0xfa, 0xed, 0xfe]
+            | [0xcf, 0xfa, 0xed, 0xfe]
+            | [0xca, 0xfe, 0xba, 0xbe]
+            | [0xbe, 0xba, 0xfe, 0xca]
+    ) {
+        BinaryFormat::MachO
+    } else if buf[0..2] == *b"MZ" {
+        BinaryFormat::Pe
+    } else {
+        BinaryFormat::Unknown
+    })
+}
+
+/// Strip symbols from a binary.
+///
+/// Skips files that don't identify as a native binary (per [`detect_format`])
+/// instead of handing them to the `strip` tool, which can silently corrupt a
+/// non-ELF/Mach-O/PE artifact such as a wrapper script some build hooks
+/// produce in place of the real binary.
 pub fn strip_binary(path: &Path) -> Result<()> {
     if !path.exists() {
         bail!("binary not found: {}", path.display());
     }
 
+    if detect_format(path)? == BinaryFormat::Unknown {
+        println!("  skipped strip (not a native binary): {}", path.display());
+        return Ok(());
+    }
+
     let status = Command::new("strip")
         .arg(path)
         .status()
@@ -33,10 +216,164 @@ pub fn binary_size(path: &Path) -> Result<u64> {
     Ok(meta.len())
 }
 
+/// Before/after sizes from a strip, in bytes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct StripSavings {
+    pub before: u64,
+    pub after: u64,
+}
+
+impl StripSavings {
+    /// Bytes removed by the strip. Saturating: a strip can't make a binary
+    /// bigger, but this avoids a panic if it somehow did.
+    pub fn saved(&self) -> u64 {
+        self.before.saturating_sub(self.after)
+    }
+}
+
+/// Format a `StripSavings` as the one-line report printed after `--strip`,
+/// e.g. "before 120.0K -> after 64.0K, saved 56.0K".
+pub fn format_strip_savings(savings: StripSavings) -> String {
+    format!(
+        "before {} -> after {}, saved {}",
+        crate::units::format_size(savings.before),
+        crate::units::format_size(savings.after),
+        crate::units::format_size(savings.saved())
+    )
+}
+
+/// Outcome of [`strip_binary_with_report`]: either the before/after sizes
+/// from a real strip, or a note that the artifact wasn't a native binary
+/// and stripping was skipped — kept distinct from a strip failure, which
+/// is still an `Err`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum StripOutcome {
+    Stripped(StripSavings),
+    Skipped(BinaryFormat),
+}
+
+/// Strip a binary and report the before/after size saving, or report that
+/// stripping was skipped because the artifact isn't a native binary.
+pub fn strip_binary_with_report(path: &Path) -> Result<StripOutcome> {
+    let format = detect_format(path)?;
+    if format == BinaryFormat::Unknown {
+        strip_binary(path)?; // prints the "skipped" notice
+        return Ok(StripOutcome::Skipped(format));
+    }
+
+    let before = binary_size(path)?;
+    strip_binary(path)?;
+    let after = binary_size(path)?;
+    Ok(StripOutcome::Stripped(StripSavings { before, after }))
+}
+
+/// Build a minimal valid ELF64 LE file with a null section, one section per
+/// `(name, content)` pair, and a trailing `.shstrtab`. Shared by this
+/// module's and `repro`'s tests, which both need synthetic ELF input
+/// without shipping real binaries as fixtures.
+#[cfg(test)]
+pub(crate) fn build_synthetic_elf(sections: &[(&str, &[u8])]) -> Vec<u8> {
+    let mut shstrtab_content = vec![0u8];
+    let mut name_offsets = vec![0u32];
+    for (name, _) in sections {
+        name_offsets.push(shstrtab_content.len() as u32);
+        shstrtab_content.extend_from_slice(name.as_bytes());
+        shstrtab_content.push(0);
+    }
+    let shstrtab_name_off = shstrtab_content.len() as u32;
+    shstrtab_content.extend_from_slice(b".shstrtab\0");
+
+    const EHDR_SIZE: usize = 64;
+    let mut section_offsets = Vec::with_capacity(sections.len());
+    let mut body = Vec::new();
+    let mut cursor = EHDR_SIZE;
+    for (_, content) in sections {
+        section_offsets.push(cursor as u64);
+        body.extend_from_slice(content);
+        cursor += content.len();
+    }
+    let shstrtab_offset = cursor as u64;
+    body.extend_from_slice(&shstrtab_content);
+    cursor += shstrtab_content.len();
+
+    let shoff = cursor as u64;
+    let shnum = sections.len() + 2; // null + sections + shstrtab
+    let shstrndx = shnum - 1;
+
+    let mut file = vec![0u8; EHDR_SIZE];
+    file[0..4].copy_from_slice(ELF_MAGIC);
+    file[4] = ELFCLASS64;
+    file[5] = ELFDATA2LSB;
+    file[40..48].copy_from_slice(&shoff.to_le_bytes());
+    file[58..60].copy_from_slice(&64u16.to_le_bytes());
+    file[60..62].copy_from_slice(&(shnum as u16).to_le_bytes());
+    file[62..64].copy_from_slice(&(shstrndx as u16).to_le_bytes());
+    file.extend_from_slice(&body);
+
+    file.extend_from_slice(&[0u8; 64]); // null section header
+    for (i, (_, content)) in sections.iter().enumerate() {
+        let mut shdr = [0u8; 64];
+        shdr[0..4].copy_from_slice(&name_offsets[i + 1].to_le_bytes());
+        shdr[24..32].copy_from_slice(&section_offsets[i].to_le_bytes());
+        shdr[32..40].copy_from_slice(&(content.len() as u64).to_le_bytes());
+        file.extend_from_slice(&shdr);
+    }
+    let mut shstrtab_shdr = [0u8; 64];
+    shstrtab_shdr[0..4].copy_from_slice(&shstrtab_name_off.to_le_bytes());
+    shstrtab_shdr[24..32].copy_from_slice(&shstrtab_offset.to_le_bytes());
+    shstrtab_shdr[32..40].copy_from_slice(&(shstrtab_content.len() as u64).to_le_bytes());
+    file.extend_from_slice(&shstrtab_shdr);
+
+    file
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::io::Write;
+    use std::path::PathBuf;
+
+    fn fixture_path(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures/elf")
+            .join(name)
+    }
+
+    #[test]
+    fn read_elf_segments_parses_load_and_bss() {
+        let segments = read_elf_segments(&fixture_path("tiny-load-bss.elf"))
+            .unwrap()
+            .unwrap();
+        assert_eq!(segments.flash, 0x10);
+        assert_eq!(segments.ram, 0x30);
+        assert_eq!(segments.bss, 0x20); // memsz - filesz on the writable PT_LOAD
+    }
+
+    #[test]
+    fn read_elf_segments_non_elf_returns_none() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("not_elf");
+        std::fs::write(&path, b"just some plain bytes, not an ELF file").unwrap();
+
+        let result = read_elf_segments(&path).unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn read_elf_segments_truncated_file_returns_none() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("short");
+        std::fs::write(&path, b"\x7fELF").unwrap(); // magic only, too short for a header
+
+        let result = read_elf_segments(&path).unwrap();
+        assert!(result.is_none());
+    }
+
+    #[test]
+    fn read_elf_segments_missing_file_errors() {
+        let result = read_elf_segments(Path::new("/nonexistent/path/to/binary"));
+        assert!(result.is_err());
+    }
 
     #[test]
     fn binary_size_returns_correct_size() {
@@ -85,4 +422,132 @@ mod tests {
         let size = binary_size(&path).unwrap();
         assert_eq!(size, 1000);
     }
+
+    #[test]
+    fn strip_savings_saved_computes_difference() {
+        let savings = StripSavings {
+            before: 120_000,
+            after: 64_000,
+        };
+        assert_eq!(savings.saved(), 56_000);
+    }
+
+    #[test]
+    fn strip_savings_saved_saturates_if_after_exceeds_before() {
+        let savings = StripSavings {
+            before: 10,
+            after: 20,
+        };
+        assert_eq!(savings.saved(), 0);
+    }
+
+    #[test]
+    fn format_strip_savings_renders_before_after_saved() {
+        let savings = StripSavings {
+            before: 120_000,
+            after: 64_000,
+        };
+        let text = format_strip_savings(savings);
+        assert!(text.contains("before"));
+        assert!(text.contains("after"));
+        assert!(text.contains("saved"));
+    }
+
+    #[test]
+    fn strip_binary_with_report_errors_on_missing_file() {
+        let path = Path::new("/nonexistent/path/to/binary");
+        let result = strip_binary_with_report(path);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn detect_format_recognizes_elf() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bin");
+        std::fs::write(&path, b"\x7fELF\x02\x01\x01\x00").unwrap();
+        assert_eq!(detect_format(&path).unwrap(), BinaryFormat::Elf);
+    }
+
+    #[test]
+    fn detect_format_recognizes_mach_o() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bin");
+        std::fs::write(&path, [0xfe, 0xed, 0xfa, 0xcf, 0, 0, 0, 0]).unwrap();
+        assert_eq!(detect_format(&path).unwrap(), BinaryFormat::MachO);
+    }
+
+    #[test]
+    fn detect_format_recognizes_pe() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bin.exe");
+        std::fs::write(&path, b"MZ\x90\x00").unwrap();
+        assert_eq!(detect_format(&path).unwrap(), BinaryFormat::Pe);
+    }
+
+    #[test]
+    fn detect_format_unknown_for_script() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("wrapper.sh");
+        std::fs::write(&path, b"#!/bin/sh\necho hi\n").unwrap();
+        assert_eq!(detect_format(&path).unwrap(), BinaryFormat::Unknown);
+    }
+
+    #[test]
+    fn detect_format_unknown_for_short_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("tiny");
+        std::fs::write(&path, b"Hi").unwrap();
+        assert_eq!(detect_format(&path).unwrap(), BinaryFormat::Unknown);
+    }
+
+    #[test]
+    fn strip_binary_skips_non_native_artifact_instead_of_corrupting_it() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("wrapper.sh");
+        let content = b"#!/bin/sh\necho hi\n".to_vec();
+        std::fs::write(&path, &content).unwrap();
+
+        strip_binary(&path).unwrap();
+
+        assert_eq!(std::fs::read(&path).unwrap(), content);
+    }
+
+    #[test]
+    fn strip_binary_with_report_skips_non_native_artifact() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("wrapper.sh");
+        std::fs::write(&path, b"#!/bin/sh\necho hi\n").unwrap();
+
+        let outcome = strip_binary_with_report(&path).unwrap();
+        assert_eq!(outcome, StripOutcome::Skipped(BinaryFormat::Unknown));
+    }
+
+    #[test]
+    fn read_elf_sections_resolves_names_and_offsets() {
+        let data = build_synthetic_elf(&[(".text", b"\x90\x90\x90"), (".data", b"hello")]);
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("synthetic.elf");
+        std::fs::write(&path, &data).unwrap();
+
+        let sections = read_elf_sections(&path).unwrap().unwrap();
+        let names: Vec<&str> = sections.iter().map(|s| s.name.as_str()).collect();
+        assert_eq!(names, vec!["", ".text", ".data", ".shstrtab"]);
+
+        let text = sections.iter().find(|s| s.name == ".text").unwrap();
+        assert_eq!(text.size, 3);
+        assert_eq!(
+            &data[text.offset as usize..(text.offset + text.size) as usize],
+            b"\x90\x90\x90"
+        );
+    }
+
+    #[test]
+    fn read_elf_sections_non_elf_returns_none() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("not_elf");
+        std::fs::write(&path, b"just some plain bytes, not an ELF file").unwrap();
+
+        let result = read_elf_sections(&path).unwrap();
+        assert!(result.is_none());
+    }
 }
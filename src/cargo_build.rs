@@ -6,46 +6,110 @@ use std::process::Command;
 
 use crate::tee::tee_stdout;
 
+use crate::cargo_json::run_quiet;
+use crate::conflicts::{
+    CodegenUnitsInfo, detect_conflicts, effective_codegen_units, format_conflict,
+};
 use crate::find_paths::{
     find_package_dir, find_tspec, get_binary_path, get_binary_path_simple, get_package_name,
+    resolve_bin_name,
+};
+use crate::fingerprint::{
+    compute_fingerprint, compute_source_fingerprint, fingerprint_path, read_fingerprint,
+    write_fingerprint,
+};
+use crate::metadata::{read_tspec_metadata, verify_spec_hash};
+use crate::smart_rebuild::{
+    RebuildKind, classify_rebuild, last_spec_path, read_last_build, write_last_build,
+};
+use crate::target_check::check_target_triple;
+use crate::tspec::{
+    apply_dev_overlay, apply_workspace_linker_defaults, expand_target_dir, load_spec,
+    resolve_isolated_target_dir, spec_name_from_path, verify_target_json_hash,
 };
-use crate::tspec::{expand_target_dir, load_spec, spec_name_from_path};
-use crate::types::{CargoFlags, Spec, Verbosity, flatten_config};
+use crate::types::{
+    CargoFlags, ProfileSource, Spec, Verbosity, flatten_config, profile_conflict_notice,
+    profile_override_config_args, resolve_profile,
+};
+use crate::warnings::Warnings;
 
 const TSPEC_BUILD_RS_MARKER: &str = "// Generated by tspec - do not edit";
 
+/// The cargo binary to spawn for every nested cargo invocation. Honors
+/// `CARGO` (cargo sets this for any process it spawns, including external
+/// subcommands like `cargo-tspec`) so `cargo tspec build` re-invokes the
+/// same cargo it was itself invoked with, instead of whatever `cargo` comes
+/// first on PATH.
+pub fn cargo_program() -> String {
+    std::env::var("CARGO").unwrap_or_else(|_| "cargo".to_string())
+}
+
 /// Built-in cargo profiles that don't require a `[profile.<name>]` definition.
 const BUILTIN_PROFILES: &[&str] = &["dev", "debug", "release", "test", "bench"];
 
+/// `true` if `profile` is a built-in, or a custom profile with a
+/// `[profile.<name>]` section in `workspace`'s Cargo.toml. Unreadable or
+/// unparsable Cargo.toml is treated as "not found" rather than an error,
+/// since callers use this for advisory warnings as well as hard validation.
+fn profile_is_defined(profile: &str, workspace: &Path) -> bool {
+    if BUILTIN_PROFILES.contains(&profile) {
+        return true;
+    }
+    let Ok(content) = fs::read_to_string(workspace.join("Cargo.toml")) else {
+        return false;
+    };
+    let Ok(doc) = toml::from_str::<toml::Value>(&content) else {
+        return false;
+    };
+    doc.get("profile")
+        .and_then(|p| p.as_table())
+        .is_some_and(|profiles| profiles.contains_key(profile))
+}
+
 /// Validate that a custom profile is defined in the workspace Cargo.toml.
 /// Built-in profiles (dev, release, test, bench) are always valid.
 /// Custom profiles must have a `[profile.<name>]` section with `inherits`.
 pub fn validate_profile(profile: &str, workspace: &Path) -> Result<()> {
-    if BUILTIN_PROFILES.contains(&profile) {
-        return Ok(());
-    }
-    let cargo_toml_path = workspace.join("Cargo.toml");
-    let content = fs::read_to_string(&cargo_toml_path)
-        .with_context(|| format!("failed to read {}", cargo_toml_path.display()))?;
-    let doc: toml::Value =
-        toml::from_str(&content).context("failed to parse workspace Cargo.toml")?;
-    if let Some(profiles) = doc.get("profile").and_then(|p| p.as_table())
-        && profiles.contains_key(profile)
-    {
+    if profile_is_defined(profile, workspace) {
         return Ok(());
     }
     bail!(
         "profile `{}` is not defined in {}. Custom profiles must be defined with \
          `[profile.{}]` and an `inherits` field (e.g., `inherits = \"release\"`).",
         profile,
-        cargo_toml_path.display(),
+        workspace.join("Cargo.toml").display(),
         profile,
     )
 }
 /// Check for spec settings that are likely misconfigurations.
+/// `no_buildrs` is the `--no-buildrs` flag for this invocation, needed here
+/// because it changes whether `linker.args` on a lib-only package is actually
+/// ignored (see below). `workspace_root` is where a custom `cargo.profile`'s
+/// `[profile.<name>]` section would live (cargo only reads `[profile]` from
+/// the workspace root, never a member's own Cargo.toml).
 /// Returns a list of warning messages (printed at top and bottom of output).
-pub fn check_spec_misconfigurations(pkg_name: &str, spec: &Spec, pkg_dir: &Path) -> Vec<String> {
+pub fn check_spec_misconfigurations(
+    pkg_name: &str,
+    spec: &Spec,
+    pkg_dir: &Path,
+    workspace_root: &Path,
+    no_buildrs: bool,
+) -> Vec<String> {
     let mut warnings = Vec::new();
+
+    // A custom cargo.profile with no matching [profile.<name>] will fail at
+    // build time (see validate_profile); warn here too so `ts set` surfaces
+    // it immediately instead of only at the next build.
+    if let Some(profile) = &spec.cargo.profile
+        && !profile_is_defined(profile, workspace_root)
+    {
+        warnings.push(format!(
+            "Warning: cargo.profile `{profile}` for {pkg_name} is not a built-in \
+             profile and no matching [profile.{profile}] was found in {}; \
+             the build will fail until one is added",
+            workspace_root.join("Cargo.toml").display(),
+        ));
+    }
     let has_linker_args = !spec.linker.args.is_empty();
     let has_bin_target = pkg_dir.join("src/main.rs").exists();
 
@@ -66,14 +130,46 @@ pub fn check_spec_misconfigurations(pkg_name: &str, spec: &Spec, pkg_dir: &Path)
         }
     }
 
-    // linker.args on lib-only package
-    if has_linker_args && !has_bin_target {
+    // linker.args on lib-only package (--no-buildrs routes them through
+    // RUSTFLAGS instead, which does apply to a lib target, so this warning
+    // doesn't hold in that mode).
+    if has_linker_args && !has_bin_target && !no_buildrs {
         warnings.push(format!(
             "Warning: linker.args ignored for {} (no binary target)",
             pkg_name
         ));
     }
 
+    // --no-buildrs widens linker.args from "just the bin" to every target.
+    if has_linker_args && no_buildrs {
+        warnings.push(format!(
+            "Warning: --no-buildrs routes linker.args for {} through RUSTFLAGS \
+             (-C link-arg=), applying to every target in the package, not just the bin",
+            pkg_name
+        ));
+    }
+
+    // target_triple without the std component installed (and not compiling
+    // it from source via build_std) fails partway through the build.
+    if let Some(triple) = &spec.cargo.target_triple
+        && let Some(warning) = check_target_triple(triple, !spec.cargo.build_std.is_empty())
+    {
+        warnings.push(format!("Warning: {warning} ({pkg_name})"));
+    }
+
+    // Same knob (opt-level, panic, lto, codegen-units, strip, link args) set
+    // through more than one spec channel with differing values.
+    for conflict in detect_conflicts(spec) {
+        warnings.push(format!("{} ({pkg_name})", format_conflict(&conflict)));
+    }
+
+    // An ambient RUSTFLAGS/CARGO_ENCODED_RUSTFLAGS would silently override
+    // what this spec resolves to (see `rustflags_conflict`).
+    let ambient: Vec<(String, String)> = std::env::vars().collect();
+    if let Some(warning) = rustflags_conflict(spec, &ambient) {
+        warnings.push(format!("Warning: {warning} ({pkg_name})"));
+    }
+
     warnings
 }
 
@@ -131,22 +227,200 @@ pub struct BuildResult {
     pub target_base: PathBuf,
 }
 
+/// Full decision trace behind a computed binary path, for `tspec explain-path`.
+///
+/// Every field here is read off the same values `run_cargo` computes before
+/// invoking cargo, and `binary_path` comes from the same `get_binary_path`/
+/// `get_binary_path_simple` calls `run_cargo` uses — this can't drift from
+/// what a real build would produce.
+#[derive(Debug, serde::Serialize)]
+pub struct PathExplanation {
+    pub project_root: PathBuf,
+    pub package_dir: PathBuf,
+    pub package_name: String,
+    pub spec_path: Option<PathBuf>,
+    pub spec_profile: Option<String>,
+    pub target_triple: Option<String>,
+    pub target_json_stem: Option<String>,
+    pub target_dir_template: Option<String>,
+    pub expanded_target_dir: Option<String>,
+    pub cli_profile: Option<String>,
+    pub force_profile: bool,
+    pub resolved_profile: Option<String>,
+    pub profile_source: ProfileSource,
+    /// Set when the spec and CLI profiles disagreed and the spec won (see
+    /// `ProfileResolution::conflict`).
+    pub profile_conflict: Option<(String, String)>,
+    pub binary_path: PathBuf,
+    pub exists: bool,
+    pub size: Option<u64>,
+    /// Modification time as seconds since the Unix epoch (no date/time dependency).
+    pub mtime_unix: Option<u64>,
+    /// Configured `codegen-units`/`lto` and whether lto overrides codegen-units
+    /// to effectively 1, resolved the same way `detect_conflicts` resolves
+    /// precedence between spec channels.
+    pub codegen_units: CodegenUnitsInfo,
+}
+
+/// Compute a `PathExplanation` for a package, following the exact same
+/// package/spec/profile resolution steps as `run_cargo`.
+pub fn explain_binary_path(
+    pkg_name: &str,
+    tspec: Option<&str>,
+    cli_profile: Option<&str>,
+    force_profile: bool,
+    project_root: &Path,
+) -> Result<PathExplanation> {
+    let pkg_dir = find_package_dir(project_root, pkg_name)?;
+    let tspec_path = find_tspec(&pkg_dir, tspec)?;
+    let pkg_name = get_package_name(&pkg_dir)?;
+
+    let (spec, target_dir_template, expanded_td) = if let Some(path) = &tspec_path {
+        let mut s = load_spec(path)?;
+        if pkg_dir.join("src/main.rs").exists() {
+            apply_workspace_linker_defaults(&mut s, project_root)?;
+        }
+        let name = spec_name_from_path(path);
+        let td = expand_target_dir(&s, &name, cli_profile, force_profile)?;
+        let template = s.cargo.target_dir.clone();
+        (Some(s), template, td)
+    } else {
+        (None, None, None)
+    };
+
+    let bin_name = resolve_bin_name(&pkg_dir, &pkg_name);
+    let binary_path = if let Some(spec) = &spec {
+        get_binary_path(
+            project_root,
+            &bin_name,
+            spec,
+            cli_profile,
+            expanded_td.as_deref(),
+            force_profile,
+        )
+    } else {
+        get_binary_path_simple(project_root, &bin_name, cli_profile)
+    };
+
+    let profile_resolution = resolve_profile(
+        spec.as_ref().and_then(|s| s.cargo.profile.as_deref()),
+        cli_profile,
+        force_profile,
+    );
+
+    let target = spec.as_ref().and_then(|s| s.cargo.target_triple.clone());
+    let target_json_stem = spec.as_ref().and_then(|s| {
+        s.cargo
+            .target_json
+            .as_ref()
+            .and_then(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+    });
+
+    let metadata = fs::metadata(&binary_path).ok();
+    let exists = metadata.is_some();
+    let size = metadata.as_ref().map(|m| m.len());
+    let mtime_unix = metadata.as_ref().and_then(|m| {
+        m.modified()
+            .ok()
+            .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|d| d.as_secs())
+    });
+
+    let codegen_units = spec
+        .as_ref()
+        .map(effective_codegen_units)
+        .unwrap_or_default();
+
+    Ok(PathExplanation {
+        project_root: project_root.to_path_buf(),
+        package_dir: pkg_dir,
+        package_name: pkg_name,
+        spec_path: tspec_path,
+        spec_profile: spec.as_ref().and_then(|s| s.cargo.profile.clone()),
+        target_triple: target,
+        target_json_stem,
+        target_dir_template,
+        expanded_target_dir: expanded_td,
+        cli_profile: cli_profile.map(str::to_string),
+        force_profile,
+        resolved_profile: profile_resolution.profile,
+        profile_source: profile_resolution.source,
+        profile_conflict: profile_resolution.conflict,
+        binary_path,
+        exists,
+        size,
+        mtime_unix,
+        codegen_units,
+    })
+}
+
+/// Apply global cargo flags, dropping `-v`/`-vv` when `--quiet-cargo` is
+/// about to run this as a `cargo build -q`: cargo itself errors out on
+/// `--verbose` combined with `--quiet`, so quiet-cargo silently wins instead
+/// of letting that surface as a confusing build failure.
+fn apply_flags_respecting_quiet_cargo(
+    flags: &CargoFlags,
+    mode: CargoMode,
+    quiet_cargo: bool,
+    cmd: &mut Command,
+) {
+    if mode == CargoMode::Build && quiet_cargo {
+        CargoFlags {
+            verbosity: Verbosity::Normal,
+            ..flags.clone()
+        }
+        .apply_to_command(cmd);
+    } else {
+        flags.apply_to_command(cmd);
+    }
+}
+
 /// Unified cargo runner for build and test operations.
 ///
 /// Handles spec loading, build.rs generation, command construction, and cleanup.
 /// `cli_profile` is the profile from the CLI (e.g., "release", "release-small").
 /// `None` means debug (default). `Some("release")` is equivalent to `--release`.
+/// `force_profile` makes `cli_profile` win over a conflicting `spec.cargo.profile`
+/// instead of the spec silently taking precedence.
+/// `no_buildrs` skips generating the temporary build.rs for linker args entirely,
+/// routing them through `RUSTFLAGS -C link-arg=` instead (applies to every
+/// target in the package, not just the bin — a warning is printed).
+/// `keep_buildrs` leaves a build.rs tspec generated in place after the build
+/// instead of deleting it, for inspection; has no effect with `no_buildrs` or
+/// when the package already had its own build.rs.
+/// `no_spec` forces the plain-cargo path even when a default spec would
+/// otherwise be found, without needing `tspec` to point elsewhere.
+/// `smart_rebuild` (Build mode only) additionally skips invoking cargo when
+/// the spec changed but only in fields `classify_rebuild` finds don't affect
+/// the build (see `crate::smart_rebuild`).
+#[allow(clippy::too_many_arguments)]
 pub fn run_cargo(
     mode: CargoMode,
     pkg_name: &str,
     tspec: Option<&str>,
+    no_spec: bool,
+    dev_overlay: bool,
+    force: bool,
     cli_profile: Option<&str>,
+    force_profile: bool,
     project_root: &Path,
     flags: &CargoFlags,
+    isolate: bool,
+    quiet_cargo: bool,
+    hermetic_env: bool,
+    no_buildrs: bool,
+    keep_buildrs: bool,
+    strict_flags: bool,
+    smart_rebuild: bool,
+    mut warnings: Option<&mut Warnings>,
 ) -> Result<(BuildResult, Vec<String>)> {
     let verbosity = flags.verbosity;
     let pkg_dir = find_package_dir(project_root, pkg_name)?;
-    let tspec_path = find_tspec(&pkg_dir, tspec)?;
+    let tspec_path = if no_spec {
+        None
+    } else {
+        find_tspec(&pkg_dir, tspec)?
+    };
 
     // Resolve actual package name from Cargo.toml (needed when pkg_name is a path)
     let pkg_name = get_package_name(&pkg_dir)?;
@@ -160,9 +434,24 @@ pub fn run_cargo(
 
     // Load spec once (if present) and compute target_dir
     let (spec, expanded_td) = if let Some(path) = &tspec_path {
-        let s = load_spec(path)?;
+        let mut s = load_spec(path)?;
+        let metadata = read_tspec_metadata(&pkg_dir)?;
+        verify_spec_hash(&metadata, &s, &pkg_name)?;
+        verify_target_json_hash(&s, project_root)?;
+        if pkg_dir.join("src/main.rs").exists() {
+            apply_workspace_linker_defaults(&mut s, project_root)?;
+        }
+        if dev_overlay {
+            let overlay_profile = cli_profile.or(s.cargo.profile.as_deref()).unwrap_or("dev");
+            let (overlaid, changes) = apply_dev_overlay(&s, overlay_profile);
+            println!("Applying --dev-overlay relaxations:");
+            for change in &changes {
+                println!("  {}", change.0);
+            }
+            s = overlaid;
+        }
         let name = spec_name_from_path(path);
-        let td = expand_target_dir(&s, &name)?;
+        let td = resolve_isolated_target_dir(&s, &name, isolate, cli_profile, force_profile)?;
         (Some(s), td)
     } else {
         (None, None)
@@ -173,37 +462,116 @@ pub fn run_cargo(
         Some(td) => project_root.join("target").join(td),
         None => project_root.join("target"),
     };
+    let bin_name = resolve_bin_name(&pkg_dir, &pkg_name);
     let binary_path = if let Some(spec) = &spec {
         get_binary_path(
             project_root,
-            &pkg_name,
+            &bin_name,
             spec,
             cli_profile,
             expanded_td.as_deref(),
+            force_profile,
         )
     } else {
-        get_binary_path_simple(project_root, &pkg_name, cli_profile)
+        get_binary_path_simple(project_root, &bin_name, cli_profile)
     };
 
-    // Validate the effective profile exists before invoking cargo
-    let effective_profile = spec
-        .as_ref()
-        .and_then(|s| s.cargo.profile.as_deref())
-        .or(cli_profile);
+    // Skip invoking cargo entirely when the spec and every source file are
+    // unchanged since the last successful build and the binary is still
+    // there — `--force` bypasses this. Test/bench never pass `force: true`
+    // in, but the fingerprint is only ever recorded/checked for Build mode.
+    let build_fingerprint =
+        (mode == CargoMode::Build).then(|| compute_fingerprint(&pkg_dir, spec.as_ref()));
+    let fp_path = fingerprint_path(&target_base, &pkg_name);
+    if let Some(fp) = &build_fingerprint
+        && !force
+        && binary_path.exists()
+        && read_fingerprint(&fp_path).as_deref() == Some(fp.as_str())
+    {
+        println!("{pkg_name}: up to date (spec and sources unchanged; use --force to rebuild)");
+        return Ok((
+            BuildResult {
+                binary_path,
+                target_base,
+            },
+            Vec::new(),
+        ));
+    }
+
+    // Narrower than the fingerprint skip above: the spec itself changed,
+    // but only in fields that don't affect what cargo needs to do (e.g.
+    // `[run]`/`[test]` defaults). Opt-in via --smart-rebuild since the
+    // classification, unlike the fingerprint hash, can't see source changes
+    // on its own — it only ever runs after the fingerprint check already
+    // found *something* different.
+    let smart_rebuild_path = last_spec_path(&target_base, &pkg_name);
+    if smart_rebuild
+        && mode == CargoMode::Build
+        && !force
+        && binary_path.exists()
+        && let Some(current_spec) = &spec
+        && let Some(last_build) = read_last_build(&smart_rebuild_path)
+        && last_build.source_fingerprint == compute_source_fingerprint(&pkg_dir)
+        && classify_rebuild(&last_build.spec, current_spec) == RebuildKind::NoRebuildNeeded
+    {
+        println!(
+            "{pkg_name}: up to date (spec change only affects [run]/[test], not the build; \
+             cargo build skipped)"
+        );
+        return Ok((
+            BuildResult {
+                binary_path,
+                target_base,
+            },
+            Vec::new(),
+        ));
+    }
+
+    // Resolve the effective profile (spec vs CLI) and warn on an unforced conflict.
+    let profile_resolution = resolve_profile(
+        spec.as_ref().and_then(|s| s.cargo.profile.as_deref()),
+        cli_profile,
+        force_profile,
+    );
+    if let Some((spec_profile, ignored_cli_profile)) = &profile_resolution.conflict {
+        println!(
+            "{}",
+            profile_conflict_notice(spec_profile, ignored_cli_profile)
+        );
+    }
+    let effective_profile = profile_resolution.profile.as_deref();
     if let Some(profile) = effective_profile {
         validate_profile(profile, project_root)?;
     }
 
-    // Check for misconfigurations before running cargo
+    // Check for misconfigurations before running cargo. With no collector,
+    // print them now so they're visible even if the build itself fails; with
+    // a collector (batch runs), accumulate instead so they're reported once,
+    // grouped and deduplicated, at the end of the run.
     let spec_warnings = if let Some(spec) = &spec {
-        check_spec_misconfigurations(&pkg_name, spec, &pkg_dir)
+        check_spec_misconfigurations(&pkg_name, spec, &pkg_dir, project_root, no_buildrs)
     } else {
         Vec::new()
     };
+    match warnings.as_deref_mut() {
+        Some(w) => w.extend_misconfigurations(spec_warnings.clone()),
+        None => reprint_warnings(&spec_warnings),
+    }
+
+    // `--strict-flags`: turn the RUSTFLAGS-override footgun into a hard
+    // error instead of just a warning, before spending time on a build
+    // whose result may be silently wrong.
+    if strict_flags && let Some(spec) = &spec {
+        let ambient: Vec<(String, String)> = std::env::vars().collect();
+        if let Some(conflict) = rustflags_conflict(spec, &ambient) {
+            bail!("{conflict} (--strict-flags)");
+        }
+    }
 
     let verb = match mode {
         CargoMode::Build => "Building",
         CargoMode::Test => "Testing",
+        CargoMode::Bench => "Benching",
     };
 
     // Apply spec if present, otherwise plain cargo subcommand
@@ -227,12 +595,10 @@ pub fn run_cargo(
                 println!("[debug] target_dir: {} (expanded from spec)", td);
             }
             let eff = effective_profile.unwrap_or("debug");
-            let source = if spec.cargo.profile.is_some() {
-                "from spec"
-            } else if cli_profile.is_some() {
-                "from CLI"
-            } else {
-                "default"
+            let source = match profile_resolution.source {
+                ProfileSource::Spec => "from spec",
+                ProfileSource::Cli => "from CLI",
+                ProfileSource::Default => "default",
             };
             println!("[debug] effective profile: {} ({})", eff, source);
         }
@@ -240,11 +606,17 @@ pub fn run_cargo(
         // Generate temporary build.rs for linker flags if needed
         let has_linker_args = !spec.linker.args.is_empty();
         let has_bin_target = pkg_dir.join("src/main.rs").exists();
-        if has_linker_args && has_bin_target && !had_build_rs {
+        if has_linker_args && has_bin_target && !had_build_rs && !no_buildrs {
             generate_build_rs(&build_rs_path, &pkg_name, spec)?;
         }
 
         let mut cmd = build_cargo_command(spec, mode)?;
+        if hermetic_env || spec.cargo.hermetic_env {
+            apply_hermetic_env(&mut cmd, &spec.cargo.env_allowlist, verbosity);
+        }
+        if dev_overlay {
+            cmd.env("CARGO_INCREMENTAL", "1");
+        }
         cmd.arg(mode.subcommand());
         cmd.arg("-p").arg(&pkg_name);
         cmd.current_dir(project_root);
@@ -258,6 +630,8 @@ pub fn run_cargo(
             project_root,
             cli_profile,
             expanded_td.as_deref(),
+            force_profile,
+            no_buildrs,
         )?;
 
         // For test mode, append -Zpanic_abort_tests to RUSTFLAGS if needed
@@ -276,8 +650,17 @@ pub fn run_cargo(
             cmd.env("RUSTFLAGS", new_flags);
         }
 
+        // Spec-level default test-binary args (`[test] args`), ahead of any
+        // CLI trailing test args appended below via `flags.extra_args`.
+        if mode == CargoMode::Test && !spec.test.args.is_empty() {
+            cmd.arg("--");
+            cmd.args(&spec.test.args);
+        }
+
         // Pass global flags through to cargo (-v/-vv, -j N, extra args, etc.)
-        flags.apply_to_command(&mut cmd);
+        // --quiet-cargo wins over -v/-vv: cargo rejects --verbose and --quiet
+        // together, and silencing progress output is the whole point of the flag.
+        apply_flags_respecting_quiet_cargo(flags, mode, quiet_cargo, &mut cmd);
 
         // -v: print command line and env vars
         print_verbose_command(&cmd, verbosity);
@@ -285,7 +668,10 @@ pub fn run_cargo(
         cmd
     } else {
         println!("{} {} (no tspec)", verb, pkg_name);
-        let mut cmd = Command::new("cargo");
+        let mut cmd = Command::new(cargo_program());
+        if hermetic_env {
+            apply_hermetic_env(&mut cmd, &[], verbosity);
+        }
         cmd.arg(mode.subcommand());
         cmd.arg("-p").arg(&pkg_name);
         cmd.current_dir(project_root);
@@ -299,7 +685,7 @@ pub fn run_cargo(
         }
 
         // Pass global flags through to cargo (-v/-vv, -j N, extra args, etc.)
-        flags.apply_to_command(&mut cmd);
+        apply_flags_respecting_quiet_cargo(flags, mode, quiet_cargo, &mut cmd);
 
         // -v: print command line even without spec
         print_verbose_command(&cmd, verbosity);
@@ -314,7 +700,7 @@ pub fn run_cargo(
         let mut suppressing_zero_block = false;
         let tee = tee_stdout(
             &mut cmd,
-            |line| line.starts_with("test result:"),
+            |line| line.starts_with("test result:") || line.trim_start().starts_with("Doc-tests "),
             |line| {
                 if line.trim() == "running 0 tests" {
                     suppressing_zero_block = true;
@@ -337,6 +723,10 @@ pub fn run_cargo(
         )
         .with_context(|| format!("failed to run cargo {}", mode.subcommand()))?;
         (tee.status, tee.matched_lines)
+    } else if mode == CargoMode::Build && quiet_cargo {
+        let s = run_quiet(&mut cmd)
+            .with_context(|| format!("failed to run cargo {}", mode.subcommand()))?;
+        (s, Vec::new())
     } else {
         let s = cmd
             .status()
@@ -344,9 +734,14 @@ pub fn run_cargo(
         (s, Vec::new())
     };
 
-    // Clean up generated build.rs (only if we created it)
+    // Clean up generated build.rs (only if we created it), unless the caller
+    // asked to keep it around for inspection.
     if !had_build_rs && build_rs_path.exists() {
-        let _ = fs::remove_file(&build_rs_path);
+        if keep_buildrs {
+            println!("  build.rs kept at {}", build_rs_path.display());
+        } else {
+            let _ = fs::remove_file(&build_rs_path);
+        }
     }
 
     if !status.success() {
@@ -366,9 +761,22 @@ pub fn run_cargo(
 
     if mode == CargoMode::Build {
         println!("  {}", binary_path.display());
+        if build_fingerprint.is_some() {
+            // Recompute post-build: cargo may have touched files in the
+            // package dir (e.g. Cargo.lock), so the pre-build snapshot
+            // above would never match on the very next invocation.
+            let fp = compute_fingerprint(&pkg_dir, spec.as_ref());
+            let _ = write_fingerprint(&fp_path, &fp);
+        }
+        if let Some(current_spec) = &spec {
+            let source_fingerprint = compute_source_fingerprint(&pkg_dir);
+            let _ = write_last_build(&smart_rebuild_path, current_spec, &source_fingerprint);
+        }
     }
     warn_stale_build_rs(had_stale_build_rs);
-    reprint_warnings(&spec_warnings);
+    if warnings.is_none() {
+        reprint_warnings(&spec_warnings);
+    }
     Ok((
         BuildResult {
             binary_path,
@@ -379,43 +787,148 @@ pub fn run_cargo(
 }
 
 /// Build a package with a spec, returns the binary path on success.
+/// `isolate` forces a synthetic `{name}-{hash}` target_dir when the spec
+/// doesn't define one, so it doesn't share artifacts with other specs.
+/// `quiet_cargo` suppresses cargo's own "Compiling xyz" progress spam while
+/// still re-rendering warnings/errors from cargo's JSON message stream.
+/// `no_buildrs`/`keep_buildrs` are the `--no-buildrs`/`--keep-buildrs`
+/// overrides for the generated linker-args build.rs (see `run_cargo`).
+/// `strict_flags` turns an ambient RUSTFLAGS/CARGO_ENCODED_RUSTFLAGS that
+/// would override the spec's own into a hard error (see `rustflags_conflict`).
+/// `warnings` collects spec warnings instead of printing them immediately;
+/// pass `None` to print them the moment they're found (the single-package
+/// commands' behavior).
+/// `no_spec` forces the plain-cargo path even when a default spec exists
+/// (see `run_cargo`).
+/// `dev_overlay` applies `apply_dev_overlay()` to the resolved spec before
+/// building — see `run_cargo`.
+/// `force` skips the up-to-date fingerprint check and always invokes cargo
+/// (see `run_cargo`).
+/// `smart_rebuild` skips cargo for a spec change limited to `[run]`/`[test]`
+/// fields (see `run_cargo`).
+#[allow(clippy::too_many_arguments)]
 pub fn build_package(
     pkg_name: &str,
     tspec: Option<&str>,
+    no_spec: bool,
+    dev_overlay: bool,
+    force: bool,
     cli_profile: Option<&str>,
+    force_profile: bool,
     project_root: &Path,
     flags: &CargoFlags,
+    isolate: bool,
+    quiet_cargo: bool,
+    hermetic_env: bool,
+    no_buildrs: bool,
+    keep_buildrs: bool,
+    strict_flags: bool,
+    smart_rebuild: bool,
+    warnings: Option<&mut Warnings>,
 ) -> Result<BuildResult> {
     let (build_result, _) = run_cargo(
         CargoMode::Build,
         pkg_name,
         tspec,
+        no_spec,
+        dev_overlay,
+        force,
         cli_profile,
+        force_profile,
         project_root,
         flags,
+        isolate,
+        quiet_cargo,
+        hermetic_env,
+        no_buildrs,
+        keep_buildrs,
+        strict_flags,
+        smart_rebuild,
+        warnings,
     )?;
     Ok(build_result)
 }
 
-/// Test a package with a spec, returns raw `test result:` lines.
+/// Test a package with a spec, returns raw `test result:`/`Doc-tests ` lines
+/// (in original order, for `parse_test_results` to aggregate).
+/// `isolate` forces a synthetic `{name}-{hash}` target_dir when the spec
+/// doesn't define one, so it doesn't share artifacts with other specs.
+/// `no_buildrs`/`keep_buildrs` are the `--no-buildrs`/`--keep-buildrs`
+/// overrides for the generated linker-args build.rs (see `run_cargo`).
+/// `warnings` collects spec warnings instead of printing them immediately;
+/// pass `None` to print them the moment they're found.
+#[allow(clippy::too_many_arguments)]
 pub fn test_package(
     pkg_name: &str,
     tspec: Option<&str>,
     cli_profile: Option<&str>,
+    force_profile: bool,
     project_root: &Path,
     flags: &CargoFlags,
+    isolate: bool,
+    no_buildrs: bool,
+    keep_buildrs: bool,
+    warnings: Option<&mut Warnings>,
 ) -> Result<Vec<String>> {
     let (_, matched_lines) = run_cargo(
         CargoMode::Test,
         pkg_name,
         tspec,
+        false,
+        false,
+        false,
         cli_profile,
+        force_profile,
         project_root,
         flags,
+        isolate,
+        false,
+        false,
+        no_buildrs,
+        keep_buildrs,
+        false,
+        false,
+        warnings,
     )?;
     Ok(matched_lines)
 }
 
+/// Benchmark a package with a spec, running `cargo bench`.
+///
+/// Output is passed straight through rather than parsed like `test_package`
+/// does — bench harnesses (the nightly `#[bench]` attribute, criterion, etc.)
+/// don't share a single result-line format worth relying on.
+pub fn bench_package(
+    pkg_name: &str,
+    tspec: Option<&str>,
+    cli_profile: Option<&str>,
+    force_profile: bool,
+    project_root: &Path,
+    flags: &CargoFlags,
+) -> Result<()> {
+    run_cargo(
+        CargoMode::Bench,
+        pkg_name,
+        tspec,
+        false,
+        false,
+        false,
+        cli_profile,
+        force_profile,
+        project_root,
+        flags,
+        false,
+        false,
+        false,
+        false,
+        false,
+        false,
+        false,
+        None,
+    )?;
+    Ok(())
+}
+
 /// Plain `cargo build --release` with no spec lookup.
 /// Used by compare to produce a baseline build.
 pub fn plain_cargo_build_release(
@@ -423,7 +936,25 @@ pub fn plain_cargo_build_release(
     project_root: &Path,
     flags: &CargoFlags,
 ) -> Result<BuildResult> {
-    build_package(pkg_name, None, Some("release"), project_root, flags)
+    build_package(
+        pkg_name,
+        None,
+        false,
+        false,
+        false,
+        Some("release"),
+        false,
+        project_root,
+        flags,
+        false,
+        false,
+        false,
+        false,
+        false,
+        false,
+        false,
+        None,
+    )
 }
 
 /// Generate a temporary build.rs with scoped linker flags from tspec.toml
@@ -431,10 +962,14 @@ pub fn generate_build_rs(path: &Path, crate_name: &str, spec: &Spec) -> Result<(
     let mut lines = vec![TSPEC_BUILD_RS_MARKER.to_string(), "fn main() {".to_string()];
 
     for arg in &spec.linker.args {
-        lines.push(format!(
-            "    println!(\"cargo:rustc-link-arg-bin={}={}\");",
-            crate_name, arg
-        ));
+        // `crate_name`/`arg` are embedded as Rust source, not just shell text —
+        // a linker arg containing a `"` or `\` would otherwise produce a
+        // build.rs that fails to even compile. `{:?}` on the fully-assembled
+        // payload emits a valid, escaped Rust string literal, so
+        // `is_tspec_generated_build_rs`'s substring check still matches the
+        // common (plain-ASCII) case unchanged.
+        let payload = format!("cargo:rustc-link-arg-bin={crate_name}={arg}");
+        lines.push(format!("    println!({payload:?});"));
     }
 
     lines.push("}".to_string());
@@ -447,6 +982,7 @@ pub fn generate_build_rs(path: &Path, crate_name: &str, spec: &Spec) -> Result<(
 pub enum CargoMode {
     Build,
     Test,
+    Bench,
 }
 
 impl CargoMode {
@@ -454,19 +990,24 @@ impl CargoMode {
         match self {
             CargoMode::Build => "build",
             CargoMode::Test => "test",
+            CargoMode::Bench => "bench",
         }
     }
 }
 
 /// Check if spec requires nightly toolchain.
 /// For Test mode, panic=abort also needs nightly because `-Zpanic_abort_tests` is nightly-only.
+/// Bench follows Build's rule — `cargo bench` itself is stable, nightly is only pulled in by
+/// the same spec settings (build_std, unstable -Z flags, panic=immediate-abort) as Build.
 fn requires_nightly(spec: &Spec, mode: CargoMode) -> bool {
     let panic_needs_nightly = match mode {
         CargoMode::Test => spec
             .panic
             .map(|p| p.rustc_panic_value().is_some())
             .unwrap_or(false),
-        CargoMode::Build => spec.panic.map(|p| p.requires_nightly()).unwrap_or(false),
+        CargoMode::Build | CargoMode::Bench => {
+            spec.panic.map(|p| p.requires_nightly()).unwrap_or(false)
+        }
     };
 
     let has_build_std = !spec.cargo.build_std.is_empty();
@@ -503,9 +1044,69 @@ fn print_verbose_command(cmd: &Command, verbosity: Verbosity) {
     }
 }
 
+/// Environment variable names always kept under `--hermetic-env`, regardless
+/// of spec `cargo.env_allowlist`: rustup needs these to pick a toolchain, and
+/// without PATH/HOME the cargo/rustc subprocess can't even start.
+const HERMETIC_ENV_BASE_ALLOWLIST: &[&str] = &[
+    "PATH",
+    "HOME",
+    "CARGO_HOME",
+    "RUSTUP_HOME",
+    "RUSTUP_TOOLCHAIN",
+    "TERM",
+];
+
+/// Compute the env scrubbing for `--hermetic-env`: which ambient vars survive
+/// and which get dropped. Pure function of the ambient environment and the
+/// spec's extra allowlist, so two calls with the same inputs (independent of
+/// what's actually running in the process) produce the same plan — the
+/// reproducibility property the flag exists for.
+pub fn hermetic_env_plan(
+    ambient: &[(String, String)],
+    extra_allowlist: &[String],
+) -> (Vec<(String, String)>, Vec<String>) {
+    let mut kept = Vec::new();
+    let mut dropped = Vec::new();
+    for (key, value) in ambient {
+        if HERMETIC_ENV_BASE_ALLOWLIST.contains(&key.as_str())
+            || extra_allowlist.iter().any(|a| a == key)
+        {
+            kept.push((key.clone(), value.clone()));
+        } else {
+            dropped.push(key.clone());
+        }
+    }
+    kept.sort();
+    dropped.sort();
+    (kept, dropped)
+}
+
+/// Apply `--hermetic-env` to `cmd`: clear its inherited environment, then
+/// restore only the vars `hermetic_env_plan` keeps. Prints which vars were
+/// scrubbed at `-v` and above, matching `print_verbose_command`'s style.
+fn apply_hermetic_env(cmd: &mut Command, env_allowlist: &[String], verbosity: Verbosity) {
+    let ambient: Vec<(String, String)> = std::env::vars().collect();
+    let (kept, dropped) = hermetic_env_plan(&ambient, env_allowlist);
+    cmd.env_clear();
+    for (key, value) in &kept {
+        cmd.env(key, value);
+    }
+    if verbosity >= Verbosity::Verbose && !dropped.is_empty() {
+        println!("[verbose] hermetic-env scrubbed: {}", dropped.join(", "));
+    }
+}
+
 /// Build the base cargo command (with toolchain if needed)
 fn build_cargo_command(spec: &Spec, mode: CargoMode) -> Result<Command> {
-    let mut cmd = Command::new("cargo");
+    let needs_toolchain_override = spec.toolchain.is_some() || requires_nightly(spec, mode);
+    // `+toolchain` is a rustup-proxy feature: it only works when the binary
+    // actually invoked is the rustup `cargo` shim on PATH, not the concrete
+    // per-toolchain cargo that $CARGO points at, so skip cargo_program() here.
+    let mut cmd = if needs_toolchain_override {
+        Command::new("cargo")
+    } else {
+        Command::new(cargo_program())
+    };
 
     if let Some(tc) = &spec.toolchain {
         cmd.arg(format!("+{}", tc));
@@ -516,14 +1117,124 @@ fn build_cargo_command(spec: &Spec, mode: CargoMode) -> Result<Command> {
     Ok(cmd)
 }
 
+/// Resolve the rustc flags a spec contributes on their own — panic mode,
+/// strip mode, and the explicit `rustflags` list — in the same order
+/// `apply_spec_to_command` applies them. Excludes the version-script flag,
+/// which needs filesystem access to generate its script file and isn't
+/// knowable from the spec alone. Pure and side-effect-free, so it's also
+/// what `build --print-rustflags` uses to preview the resolved value.
+pub fn resolve_base_rustflags(spec: &Spec) -> Vec<String> {
+    let mut rustc_flags = Vec::new();
+
+    if let Some(panic_mode) = spec.panic
+        && let Some(panic_value) = panic_mode.rustc_panic_value()
+    {
+        rustc_flags.push(format!("-C panic={}", panic_value));
+    }
+
+    if let Some(strip_mode) = spec.strip
+        && let Some(strip_value) = strip_mode.rustc_strip_value()
+    {
+        rustc_flags.push(format!("-C strip={}", strip_value));
+    }
+
+    for flag in &spec.rustflags {
+        rustc_flags.push(flag.clone());
+    }
+
+    rustc_flags
+}
+
+/// Args (excluding the `rustc` program name) for `rustc --print cfg` under
+/// the target and flags a build of `spec` would use, so `tspec print cfg`
+/// shows the cfg attributes actually active for this spec rather than the
+/// host's default set. `resolve_base_rustflags`'s flags are multi-word
+/// strings built for joining into a `RUSTFLAGS` value, so each is split on
+/// whitespace into individual rustc arguments here.
+pub fn resolve_cfg_args(spec: &Spec, workspace: &Path) -> Vec<String> {
+    let mut args = vec!["--print".to_string(), "cfg".to_string()];
+
+    if let Some(path) = resolve_target_json_path(spec, workspace) {
+        args.push("--target".to_string());
+        args.push(path.display().to_string());
+    } else if let Some(ref triple) = spec.cargo.target_triple {
+        args.push("--target".to_string());
+        args.push(triple.clone());
+    }
+
+    for flag in resolve_base_rustflags(spec) {
+        args.extend(flag.split_whitespace().map(str::to_string));
+    }
+
+    args
+}
+
+/// Resolve a spec's custom target JSON path relative to `workspace` — the
+/// directory the real cargo invocation runs from (`apply_spec_to_command`'s
+/// caller uses `cmd.current_dir(project_root)`, not the package directory),
+/// which is what a spec's `cargo.target_json` path is relative to.
+pub fn resolve_target_json_path(spec: &Spec, workspace: &Path) -> Option<PathBuf> {
+    spec.cargo.target_json.as_ref().map(|p| workspace.join(p))
+}
+
+/// Env var names cargo consults for rustc flags, in the priority order cargo
+/// itself uses (highest first) — `CARGO_ENCODED_RUSTFLAGS` beats `RUSTFLAGS`.
+const RUSTFLAGS_ENV_VARS: &[&str] = &["CARGO_ENCODED_RUSTFLAGS", "RUSTFLAGS"];
+
+/// Whether an ambient `RUSTFLAGS`/`CARGO_ENCODED_RUSTFLAGS` would silently
+/// win over the `RUSTFLAGS` tspec sets on the cargo child process for this
+/// spec. `cmd.env("RUSTFLAGS", ...)` only overrides that one var — an
+/// inherited `CARGO_ENCODED_RUSTFLAGS` passes straight through and cargo
+/// prefers it over `RUSTFLAGS` regardless of who set the latter. Pure
+/// function of `ambient` (normally `std::env::vars()`) so it's testable
+/// without mutating the process environment, matching `hermetic_env_plan`.
+pub fn rustflags_conflict(spec: &Spec, ambient: &[(String, String)]) -> Option<String> {
+    if resolve_base_rustflags(spec).is_empty() {
+        return None;
+    }
+    let culprit = RUSTFLAGS_ENV_VARS
+        .iter()
+        .find(|key| ambient.iter().any(|(k, _)| k == *key))?;
+    Some(format!(
+        "{culprit} is already set in the environment; cargo prioritizes it over the \
+         RUSTFLAGS tspec resolves from this spec, so rustflags/panic/strip settings may \
+         be silently ignored. Unset it, or build with --hermetic-env to scrub it."
+    ))
+}
+
+/// Resolve every environment variable a spec build would set, as
+/// `(KEY, VALUE)` pairs in the order the real build applies them: the
+/// `TSPEC_SPEC_FILE` path tspec-build reads in `build.rs`, then `RUSTFLAGS`
+/// if `resolve_base_rustflags` contributes any. Pure and side-effect-free,
+/// so it's also what `build --print-env` uses to preview the resolved
+/// overrides without building.
+pub fn resolve_env_overrides(spec: &Spec, tspec_path: &Path) -> Vec<(String, String)> {
+    let mut overrides = vec![(
+        "TSPEC_SPEC_FILE".to_string(),
+        tspec_path.display().to_string(),
+    )];
+
+    let rustflags = resolve_base_rustflags(spec);
+    if !rustflags.is_empty() {
+        overrides.push(("RUSTFLAGS".to_string(), rustflags.join(" ")));
+    }
+
+    overrides
+}
+
 /// Apply spec parameters to a cargo command.
 /// `cli_profile` is the CLI-specified profile (None = debug default).
+/// `force_profile` makes `cli_profile` win over a conflicting `spec.cargo.profile`.
+/// `no_buildrs` routes `linker.args` through `-C link-arg=` rustc flags
+/// instead of relying on the caller having generated a temporary build.rs.
 pub fn apply_spec_to_command(
     cmd: &mut Command,
     spec: &Spec,
     workspace: &Path,
     cli_profile: Option<&str>,
     expanded_target_dir: Option<&str>,
+    force_profile: bool,
+    no_buildrs: bool,
 ) -> Result<()> {
     // Set custom target directory if specified
     if let Some(td) = expanded_target_dir {
@@ -538,9 +1249,10 @@ pub fn apply_spec_to_command(
         cmd.arg("-Z").arg(z_flag);
     }
 
-    // Handle cargo config
-    let has_profile = spec.cargo.profile.is_some();
-    if let Some(ref profile) = spec.cargo.profile {
+    // Resolve effective profile (spec vs CLI, with optional forced CLI override)
+    let resolved_profile =
+        resolve_profile(spec.cargo.profile.as_deref(), cli_profile, force_profile);
+    if let Some(ref profile) = resolved_profile.profile {
         match profile.as_str() {
             "debug" | "dev" => {
                 // Debug/dev is default, no flag needed
@@ -569,31 +1281,38 @@ pub fn apply_spec_to_command(
         cmd.arg("--config").arg(format!("{}={}", key, value));
     }
 
-    // If no profile in spec, fall back to CLI profile
-    if !has_profile && let Some(p) = cli_profile {
-        match p {
-            "debug" | "dev" => {} // default, no flag needed
-            _ => {
-                cmd.arg("--profile").arg(p);
-            }
-        }
+    // Per-package profile overrides (e.g. deps at opt-level 2, final crate at z),
+    // translated into the same --config mechanism cargo uses for
+    // `[profile.<p>.package.<name>]`.
+    for (key, value) in profile_override_config_args(&spec.profile_overrides)? {
+        cmd.arg("--config").arg(format!("{}={}", key, value));
     }
 
-    // Collect rustc flags
-    let mut rustc_flags: Vec<String> = Vec::new();
-
-    // Handle high-level panic mode (rustc -C flag)
-    if let Some(panic_mode) = spec.panic
-        && let Some(panic_value) = panic_mode.rustc_panic_value()
-    {
-        rustc_flags.push(format!("-C panic={}", panic_value));
+    // `cargo.opt_level_deps` convenience: shorthand for
+    // `profile_overrides.<effective profile>.deps.opt-level`. Skipped when the
+    // profile already sets that key explicitly, so the explicit form wins.
+    if let Some(opt_level) = &spec.cargo.opt_level_deps {
+        let profile_name = resolved_profile.profile.as_deref().unwrap_or("dev");
+        let explicit_key = format!("{profile_name}.deps.opt-level");
+        if !spec.profile_overrides.contains_key(&explicit_key) {
+            cmd.arg("--config").arg(format!(
+                "profile.{profile_name}.package.\"*\".opt-level={opt_level}"
+            ));
+        }
     }
 
-    // Handle high-level strip mode
-    if let Some(strip_mode) = spec.strip
-        && let Some(strip_value) = strip_mode.rustc_strip_value()
-    {
-        rustc_flags.push(format!("-C strip={}", strip_value));
+    // Collect rustc flags, starting with the ones that don't need filesystem
+    // access (see `resolve_base_rustflags`).
+    let mut rustc_flags: Vec<String> = resolve_base_rustflags(spec);
+
+    // `--no-buildrs`: route linker.args through RUSTFLAGS instead of a
+    // generated build.rs. Unlike `cargo:rustc-link-arg-bin`, `-C link-arg=`
+    // applies to every target cargo builds for this invocation, not just
+    // the bin (see `check_spec_misconfigurations`'s warning for this).
+    if no_buildrs {
+        for arg in &spec.linker.args {
+            rustc_flags.push(format!("-C link-arg={}", arg));
+        }
     }
 
     if !spec.cargo.build_std.is_empty() {
@@ -602,31 +1321,9 @@ pub fn apply_spec_to_command(
         cmd.arg("-Z").arg(format!("build-std={}", crates_str));
     }
 
-    for flag in &spec.rustflags {
-        rustc_flags.push(flag.clone());
-    }
-
     // Handle version script (generates file and adds linker arg)
-    if let Some(vs) = &spec.linker.version_script {
-        let vs_dir = match expanded_target_dir {
-            Some(td) => workspace.join("target").join(td),
-            None => workspace.join("target"),
-        };
-        let _ = fs::create_dir_all(&vs_dir);
-        let version_script_path = vs_dir.join("tspec-version.script");
-
-        // Generate version script: { global: sym1; sym2; local: *; };
-        let globals = vs.global.join("; ");
-        let content = format!("{{ global: {}; local: {}; }};", globals, vs.local);
-
-        let mut f =
-            fs::File::create(&version_script_path).context("failed to create version script")?;
-        writeln!(f, "{}", content)?;
-
-        rustc_flags.push(format!(
-            "-C link-arg=-Wl,--version-script={}",
-            version_script_path.display()
-        ));
+    if let Some(vs_arg) = write_version_script(spec, workspace, expanded_target_dir)? {
+        rustc_flags.push(format!("-C link-arg={}", vs_arg));
     }
 
     // Apply rustc flags (linker args from Args handled by generated build.rs)
@@ -637,11 +1334,130 @@ pub fn apply_spec_to_command(
     Ok(())
 }
 
+/// Write the version-script file for `spec.linker.version_script` (if set)
+/// into `workspace`/target/`expanded_target_dir`, and return the raw
+/// `-Wl,--version-script=...` linker argument pointing at it. Shared by
+/// `apply_spec_to_command` (which always delivers this via `-C link-arg=`
+/// RUSTFLAGS) and `resolve_link_args` (which reports it as part of the final
+/// linker argument list regardless of how `spec.linker.args` itself is
+/// delivered).
+fn write_version_script(
+    spec: &Spec,
+    workspace: &Path,
+    expanded_target_dir: Option<&str>,
+) -> Result<Option<String>> {
+    let Some(vs) = &spec.linker.version_script else {
+        return Ok(None);
+    };
+    let vs_dir = match expanded_target_dir {
+        Some(td) => workspace.join("target").join(td),
+        None => workspace.join("target"),
+    };
+    let _ = fs::create_dir_all(&vs_dir);
+    let version_script_path = vs_dir.join("tspec-version.script");
+
+    // Generate version script: { global: sym1; sym2; local: *; };
+    let globals = vs.global.join("; ");
+    let content = format!("{{ global: {}; local: {}; }};", globals, vs.local);
+
+    let mut f =
+        fs::File::create(&version_script_path).context("failed to create version script")?;
+    writeln!(f, "{}", content)?;
+
+    Ok(Some(format!(
+        "-Wl,--version-script={}",
+        version_script_path.display()
+    )))
+}
+
+/// Resolve the final ordered linker argument list a build of `spec` injects,
+/// independent of *how* each arg is delivered to the linker (generated
+/// `build.rs` `cargo:rustc-link-arg-bin` directives vs `-C link-arg=`
+/// RUSTFLAGS under `--no-buildrs` — see `apply_spec_to_command`).
+/// `spec.linker.args` come first, followed by the version-script argument if
+/// configured, matching the order `apply_spec_to_command` applies them.
+/// Writes the version-script file as a side effect, same as a real build, so
+/// this is what `tspec print link-args` uses to preview the resolved list.
+pub fn resolve_link_args(
+    spec: &Spec,
+    workspace: &Path,
+    expanded_target_dir: Option<&str>,
+) -> Result<Vec<String>> {
+    let mut args = spec.linker.args.clone();
+    if let Some(vs_arg) = write_version_script(spec, workspace, expanded_target_dir)? {
+        args.push(vs_arg);
+    }
+    Ok(args)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use tempfile::TempDir;
 
+    #[test]
+    fn quiet_cargo_build_drops_verbose_flag() {
+        let flags = CargoFlags {
+            verbosity: Verbosity::Verbose,
+            ..Default::default()
+        };
+        let mut cmd = Command::new("cargo");
+        apply_flags_respecting_quiet_cargo(&flags, CargoMode::Build, true, &mut cmd);
+        let args: Vec<_> = cmd
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        assert!(!args.contains(&"-v".to_string()));
+    }
+
+    #[test]
+    fn non_quiet_build_keeps_verbose_flag() {
+        let flags = CargoFlags {
+            verbosity: Verbosity::Verbose,
+            ..Default::default()
+        };
+        let mut cmd = Command::new("cargo");
+        apply_flags_respecting_quiet_cargo(&flags, CargoMode::Build, false, &mut cmd);
+        let args: Vec<_> = cmd
+            .get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect();
+        assert!(args.contains(&"-v".to_string()));
+    }
+
+    #[test]
+    fn hermetic_env_plan_keeps_base_allowlist() {
+        let ambient = vec![
+            ("PATH".to_string(), "/usr/bin".to_string()),
+            ("HOME".to_string(), "/root".to_string()),
+            ("RUSTFLAGS".to_string(), "-Cfoo".to_string()),
+        ];
+        let (kept, dropped) = hermetic_env_plan(&ambient, &[]);
+        let kept_keys: Vec<&str> = kept.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(kept_keys, vec!["HOME", "PATH"]);
+        assert_eq!(dropped, vec!["RUSTFLAGS".to_string()]);
+    }
+
+    #[test]
+    fn hermetic_env_plan_honors_extra_allowlist() {
+        let ambient = vec![("CC".to_string(), "clang".to_string())];
+        let (kept, dropped) = hermetic_env_plan(&ambient, &["CC".to_string()]);
+        assert_eq!(kept, vec![("CC".to_string(), "clang".to_string())]);
+        assert!(dropped.is_empty());
+    }
+
+    #[test]
+    fn hermetic_env_plan_ignores_ambient_value_changes() {
+        // Two builds with a different ambient RUSTFLAGS must scrub to the
+        // same plan — the reproducibility property --hermetic-env exists for.
+        let a = vec![("RUSTFLAGS".to_string(), "-Cfoo".to_string())];
+        let b = vec![("RUSTFLAGS".to_string(), "-Cbar".to_string())];
+        let (kept_a, dropped_a) = hermetic_env_plan(&a, &[]);
+        let (kept_b, dropped_b) = hermetic_env_plan(&b, &[]);
+        assert_eq!(kept_a, kept_b);
+        assert_eq!(dropped_a, dropped_b);
+    }
+
     #[test]
     fn remove_stale_tspec_build_rs_removes_marker_file() {
         let tmp = TempDir::new().unwrap();
@@ -723,7 +1539,7 @@ mod tests {
         let mut cmd = Command::new("cargo");
         cmd.arg("build");
         let workspace = PathBuf::from("/tmp/fake");
-        apply_spec_to_command(&mut cmd, &spec, &workspace, None, None).unwrap();
+        apply_spec_to_command(&mut cmd, &spec, &workspace, None, None, false, false).unwrap();
 
         let args: Vec<String> = cmd.get_args().map(|a| a.to_string_lossy().into()).collect();
         // Should have: -Z json-target-spec --target x86_64-custom.json
@@ -745,7 +1561,7 @@ mod tests {
         let mut cmd = Command::new("cargo");
         cmd.arg("build");
         let workspace = PathBuf::from("/tmp/fake");
-        apply_spec_to_command(&mut cmd, &spec, &workspace, None, None).unwrap();
+        apply_spec_to_command(&mut cmd, &spec, &workspace, None, None, false, false).unwrap();
 
         let args: Vec<String> = cmd.get_args().map(|a| a.to_string_lossy().into()).collect();
         assert!(!args.contains(&"json-target-spec".to_string()));
@@ -774,7 +1590,7 @@ mod tests {
         let mut cmd = Command::new("cargo");
         cmd.arg("build");
         let workspace = PathBuf::from("/tmp/fake");
-        apply_spec_to_command(&mut cmd, &spec, &workspace, None, None).unwrap();
+        apply_spec_to_command(&mut cmd, &spec, &workspace, None, None, false, false).unwrap();
 
         let args: Vec<String> = cmd.get_args().map(|a| a.to_string_lossy().into()).collect();
 
@@ -823,7 +1639,7 @@ mod tests {
         let mut cmd = Command::new("cargo");
         cmd.arg("build");
         let workspace = PathBuf::from("/tmp/fake");
-        apply_spec_to_command(&mut cmd, &spec, &workspace, None, None).unwrap();
+        apply_spec_to_command(&mut cmd, &spec, &workspace, None, None, false, false).unwrap();
 
         let args: Vec<String> = cmd.get_args().map(|a| a.to_string_lossy().into()).collect();
 
@@ -846,18 +1662,203 @@ mod tests {
     }
 
     #[test]
-    fn config_empty_emits_nothing() {
-        let spec = Spec::default();
+    fn profile_overrides_emit_config_args() {
+        use crate::types::ConfigValue;
+        use std::collections::BTreeMap;
+
+        let spec = Spec {
+            profile_overrides: BTreeMap::from([
+                (
+                    "release.deps.opt-level".to_string(),
+                    ConfigValue::Integer(2),
+                ),
+                (
+                    "release.package.mycrate.opt-level".to_string(),
+                    ConfigValue::String("z".to_string()),
+                ),
+            ]),
+            ..Default::default()
+        };
 
         let mut cmd = Command::new("cargo");
         cmd.arg("build");
         let workspace = PathBuf::from("/tmp/fake");
-        apply_spec_to_command(&mut cmd, &spec, &workspace, None, None).unwrap();
+        apply_spec_to_command(
+            &mut cmd,
+            &spec,
+            &workspace,
+            Some("release"),
+            None,
+            false,
+            false,
+        )
+        .unwrap();
+
+        let args: Vec<String> = cmd.get_args().map(|a| a.to_string_lossy().into()).collect();
+        let config_positions: Vec<usize> = args
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| *a == "--config")
+            .map(|(i, _)| i)
+            .collect();
+        assert_eq!(config_positions.len(), 2);
+        assert_eq!(
+            args[config_positions[0] + 1],
+            "profile.release.package.\"*\".opt-level=2"
+        );
+        assert_eq!(
+            args[config_positions[1] + 1],
+            "profile.release.package.mycrate.opt-level=\"z\""
+        );
+    }
+
+    #[test]
+    fn profile_overrides_reject_disallowed_key() {
+        use crate::types::ConfigValue;
+        use std::collections::BTreeMap;
+
+        let spec = Spec {
+            profile_overrides: BTreeMap::from([(
+                "release.deps.lto".to_string(),
+                ConfigValue::Bool(true),
+            )]),
+            ..Default::default()
+        };
+
+        let mut cmd = Command::new("cargo");
+        cmd.arg("build");
+        let workspace = PathBuf::from("/tmp/fake");
+        let err = apply_spec_to_command(
+            &mut cmd,
+            &spec,
+            &workspace,
+            Some("release"),
+            None,
+            false,
+            false,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("not allowed per-package"));
+    }
+
+    #[test]
+    fn opt_level_deps_convenience_emits_wildcard_override() {
+        use crate::types::ConfigValue;
+
+        let mut spec = Spec::default();
+        spec.cargo.opt_level_deps = Some(ConfigValue::Integer(2));
+
+        let mut cmd = Command::new("cargo");
+        cmd.arg("build");
+        let workspace = PathBuf::from("/tmp/fake");
+        apply_spec_to_command(
+            &mut cmd,
+            &spec,
+            &workspace,
+            Some("release"),
+            None,
+            false,
+            false,
+        )
+        .unwrap();
+
+        let args: Vec<String> = cmd.get_args().map(|a| a.to_string_lossy().into()).collect();
+        let config_pos = args.iter().position(|a| a == "--config").unwrap();
+        assert_eq!(
+            args[config_pos + 1],
+            "profile.release.package.\"*\".opt-level=2"
+        );
+    }
+
+    #[test]
+    fn opt_level_deps_convenience_yields_to_explicit_override() {
+        use crate::types::ConfigValue;
+        use std::collections::BTreeMap;
+
+        let mut spec = Spec {
+            profile_overrides: BTreeMap::from([(
+                "release.deps.opt-level".to_string(),
+                ConfigValue::String("z".to_string()),
+            )]),
+            ..Default::default()
+        };
+        spec.cargo.opt_level_deps = Some(ConfigValue::Integer(2));
+
+        let mut cmd = Command::new("cargo");
+        cmd.arg("build");
+        let workspace = PathBuf::from("/tmp/fake");
+        apply_spec_to_command(
+            &mut cmd,
+            &spec,
+            &workspace,
+            Some("release"),
+            None,
+            false,
+            false,
+        )
+        .unwrap();
+
+        let args: Vec<String> = cmd.get_args().map(|a| a.to_string_lossy().into()).collect();
+        let config_positions: Vec<usize> = args
+            .iter()
+            .enumerate()
+            .filter(|(_, a)| *a == "--config")
+            .map(|(i, _)| i)
+            .collect();
+        // Only the explicit override should be emitted, not a duplicate from the shorthand.
+        assert_eq!(config_positions.len(), 1);
+        assert_eq!(
+            args[config_positions[0] + 1],
+            "profile.release.package.\"*\".opt-level=\"z\""
+        );
+    }
+
+    #[test]
+    fn config_empty_emits_nothing() {
+        let spec = Spec::default();
+
+        let mut cmd = Command::new("cargo");
+        cmd.arg("build");
+        let workspace = PathBuf::from("/tmp/fake");
+        apply_spec_to_command(&mut cmd, &spec, &workspace, None, None, false, false).unwrap();
 
         let args: Vec<String> = cmd.get_args().map(|a| a.to_string_lossy().into()).collect();
         assert!(!args.contains(&"--config".to_string()));
     }
 
+    #[test]
+    fn no_buildrs_routes_linker_args_through_rustflags() {
+        let mut spec = Spec::default();
+        spec.linker.args = vec!["-static".to_string(), "-nostdlib".to_string()];
+
+        let mut cmd = Command::new("cargo");
+        cmd.arg("build");
+        let workspace = PathBuf::from("/tmp/fake");
+        apply_spec_to_command(&mut cmd, &spec, &workspace, None, None, false, true).unwrap();
+
+        let rustflags = cmd
+            .get_envs()
+            .find(|(k, _)| k == &"RUSTFLAGS")
+            .and_then(|(_, v)| v)
+            .map(|v| v.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        assert!(rustflags.contains("-C link-arg=-static"));
+        assert!(rustflags.contains("-C link-arg=-nostdlib"));
+    }
+
+    #[test]
+    fn without_no_buildrs_linker_args_are_not_added_to_rustflags() {
+        let mut spec = Spec::default();
+        spec.linker.args = vec!["-static".to_string()];
+
+        let mut cmd = Command::new("cargo");
+        cmd.arg("build");
+        let workspace = PathBuf::from("/tmp/fake");
+        apply_spec_to_command(&mut cmd, &spec, &workspace, None, None, false, false).unwrap();
+
+        assert!(cmd.get_envs().find(|(k, _)| k == &"RUSTFLAGS").is_none());
+    }
+
     #[test]
     fn validate_profile_accepts_builtins() {
         let tmp = TempDir::new().unwrap();
@@ -901,6 +1902,51 @@ mod tests {
         validate_profile("release-small", tmp.path()).unwrap();
     }
 
+    #[test]
+    fn check_spec_misconfigurations_accepts_defined_custom_profile() {
+        let tmp = TempDir::new().unwrap();
+        write_lib_only_pkg(&tmp, "mylib");
+        fs::write(
+            tmp.path().join("Cargo.toml"),
+            concat!(
+                "[package]\nname = \"mylib\"\nversion = \"0.1.0\"\n",
+                "[profile.release-small]\ninherits = \"release\"\nopt-level = \"z\"\n",
+            ),
+        )
+        .unwrap();
+        let mut spec = Spec::default();
+        spec.cargo.profile = Some("release-small".to_string());
+
+        let warnings = check_spec_misconfigurations("mylib", &spec, tmp.path(), tmp.path(), false);
+        assert!(!warnings.iter().any(|w| w.contains("cargo.profile")));
+    }
+
+    #[test]
+    fn check_spec_misconfigurations_warns_on_undefined_custom_profile() {
+        let tmp = TempDir::new().unwrap();
+        write_lib_only_pkg(&tmp, "mylib");
+        let mut spec = Spec::default();
+        spec.cargo.profile = Some("release-small".to_string());
+
+        let warnings = check_spec_misconfigurations("mylib", &spec, tmp.path(), tmp.path(), false);
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.contains("cargo.profile `release-small`") && w.contains("mylib"))
+        );
+    }
+
+    #[test]
+    fn check_spec_misconfigurations_does_not_warn_on_builtin_profile() {
+        let tmp = TempDir::new().unwrap();
+        write_lib_only_pkg(&tmp, "mylib");
+        let mut spec = Spec::default();
+        spec.cargo.profile = Some("release".to_string());
+
+        let warnings = check_spec_misconfigurations("mylib", &spec, tmp.path(), tmp.path(), false);
+        assert!(!warnings.iter().any(|w| w.contains("cargo.profile")));
+    }
+
     #[test]
     fn is_tspec_generated_rejects_mixed_content() {
         let content = concat!(
@@ -911,4 +1957,405 @@ mod tests {
         );
         assert!(!is_tspec_generated_build_rs(content));
     }
+
+    #[test]
+    fn bench_subcommand_string() {
+        assert_eq!(CargoMode::Bench.subcommand(), "bench");
+    }
+
+    #[test]
+    fn bench_command_carries_spec_flags() {
+        use crate::types::ConfigValue;
+
+        let mut spec = Spec::default();
+        spec.cargo.target_triple = Some("x86_64-unknown-linux-gnu".to_string());
+        spec.cargo.opt_level_deps = Some(ConfigValue::Integer(2));
+
+        let mut cmd = build_cargo_command(&spec, CargoMode::Bench).unwrap();
+        cmd.arg(CargoMode::Bench.subcommand());
+        let workspace = PathBuf::from("/tmp/fake");
+        apply_spec_to_command(&mut cmd, &spec, &workspace, None, None, false, false).unwrap();
+
+        let args: Vec<String> = cmd.get_args().map(|a| a.to_string_lossy().into()).collect();
+        assert_eq!(args[0], "bench");
+        let target_pos = args.iter().position(|a| a == "--target").unwrap();
+        assert_eq!(args[target_pos + 1], "x86_64-unknown-linux-gnu");
+        assert!(args.contains(&"profile.dev.package.\"*\".opt-level=2".to_string()));
+    }
+
+    #[test]
+    fn bench_requires_nightly_follows_build_rule() {
+        let spec = Spec {
+            panic: Some(crate::options::PanicMode::ImmediateAbort),
+            ..Default::default()
+        };
+        assert_eq!(
+            requires_nightly(&spec, CargoMode::Bench),
+            requires_nightly(&spec, CargoMode::Build)
+        );
+    }
+
+    fn write_pkg(tmp: &TempDir, name: &str) {
+        fs::write(
+            tmp.path().join("Cargo.toml"),
+            format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n"),
+        )
+        .unwrap();
+        fs::create_dir_all(tmp.path().join("src")).unwrap();
+        fs::write(tmp.path().join("src/main.rs"), "fn main() {}\n").unwrap();
+    }
+
+    fn write_lib_only_pkg(tmp: &TempDir, name: &str) {
+        fs::write(
+            tmp.path().join("Cargo.toml"),
+            format!("[package]\nname = \"{name}\"\nversion = \"0.1.0\"\n"),
+        )
+        .unwrap();
+        fs::create_dir_all(tmp.path().join("src")).unwrap();
+        fs::write(tmp.path().join("src/lib.rs"), "").unwrap();
+    }
+
+    #[test]
+    fn linker_args_on_lib_only_warn_ignored_without_no_buildrs() {
+        let tmp = TempDir::new().unwrap();
+        write_lib_only_pkg(&tmp, "mylib");
+        let mut spec = Spec::default();
+        spec.linker.args = vec!["-static".to_string()];
+
+        let warnings = check_spec_misconfigurations("mylib", &spec, tmp.path(), tmp.path(), false);
+        assert!(warnings.iter().any(|w| w.contains("ignored for mylib")));
+    }
+
+    #[test]
+    fn linker_args_with_no_buildrs_warn_widened_scope_instead() {
+        let tmp = TempDir::new().unwrap();
+        write_lib_only_pkg(&tmp, "mylib");
+        let mut spec = Spec::default();
+        spec.linker.args = vec!["-static".to_string()];
+
+        let warnings = check_spec_misconfigurations("mylib", &spec, tmp.path(), tmp.path(), true);
+        assert!(!warnings.iter().any(|w| w.contains("ignored for mylib")));
+        assert!(
+            warnings
+                .iter()
+                .any(|w| w.contains("--no-buildrs routes linker.args for mylib"))
+        );
+    }
+
+    #[test]
+    fn explain_binary_path_no_spec() {
+        let tmp = TempDir::new().unwrap();
+        write_pkg(&tmp, "myapp");
+
+        let e = explain_binary_path("myapp", None, None, false, tmp.path()).unwrap();
+        assert_eq!(e.package_name, "myapp");
+        assert!(e.spec_path.is_none());
+        assert_eq!(e.profile_source, ProfileSource::Default);
+        assert_eq!(e.binary_path, tmp.path().join("target/debug/myapp"));
+        assert!(!e.exists);
+    }
+
+    #[test]
+    fn explain_binary_path_with_spec_profile_and_triple() {
+        let tmp = TempDir::new().unwrap();
+        write_pkg(&tmp, "myapp");
+        fs::write(
+            tmp.path().join("tspec.ts.toml"),
+            "[cargo]\nprofile = \"release\"\ntarget_triple = \"x86_64-unknown-linux-gnu\"\n",
+        )
+        .unwrap();
+
+        let e = explain_binary_path("myapp", None, None, false, tmp.path()).unwrap();
+        assert_eq!(e.spec_profile.as_deref(), Some("release"));
+        assert_eq!(e.target_triple.as_deref(), Some("x86_64-unknown-linux-gnu"));
+        assert_eq!(e.resolved_profile.as_deref(), Some("release"));
+        assert_eq!(e.profile_source, ProfileSource::Spec);
+        assert_eq!(
+            e.binary_path,
+            tmp.path()
+                .join("target/x86_64-unknown-linux-gnu/release/myapp")
+        );
+    }
+
+    #[test]
+    fn explain_binary_path_reports_cli_spec_conflict() {
+        let tmp = TempDir::new().unwrap();
+        write_pkg(&tmp, "myapp");
+        fs::write(
+            tmp.path().join("tspec.ts.toml"),
+            "[cargo]\nprofile = \"release\"\n",
+        )
+        .unwrap();
+
+        let e =
+            explain_binary_path("myapp", None, Some("release-small"), false, tmp.path()).unwrap();
+        assert_eq!(
+            e.profile_conflict,
+            Some(("release".to_string(), "release-small".to_string()))
+        );
+        // Spec wins without --force-profile.
+        assert_eq!(e.resolved_profile.as_deref(), Some("release"));
+    }
+
+    #[test]
+    fn explain_binary_path_surfaces_codegen_units_and_lto_override() {
+        let tmp = TempDir::new().unwrap();
+        write_pkg(&tmp, "myapp");
+        fs::write(
+            tmp.path().join("tspec.ts.toml"),
+            "[cargo.config.profile.release]\n\"codegen-units\" = 16\nlto = true\n",
+        )
+        .unwrap();
+
+        let e = explain_binary_path("myapp", None, None, false, tmp.path()).unwrap();
+        assert_eq!(e.codegen_units.codegen_units.as_deref(), Some("16"));
+        assert_eq!(e.codegen_units.lto.as_deref(), Some("true"));
+        assert!(e.codegen_units.lto_forces_single_unit);
+    }
+
+    #[test]
+    fn resolve_base_rustflags_combines_panic_and_explicit_flags() {
+        let spec = Spec {
+            panic: Some(crate::options::PanicMode::Abort),
+            rustflags: vec!["-C".to_string(), "opt-level=2".to_string()],
+            ..Default::default()
+        };
+
+        let flags = resolve_base_rustflags(&spec);
+        assert_eq!(flags, vec!["-C panic=abort", "-C", "opt-level=2"]);
+    }
+
+    #[test]
+    fn resolve_base_rustflags_empty_for_default_spec() {
+        let spec = Spec::default();
+        assert!(resolve_base_rustflags(&spec).is_empty());
+    }
+
+    #[test]
+    fn rustflags_conflict_detects_ambient_rustflags() {
+        let spec = Spec {
+            panic: Some(crate::options::PanicMode::Abort),
+            ..Default::default()
+        };
+        let ambient = vec![("RUSTFLAGS".to_string(), "-C target-cpu=native".to_string())];
+
+        let warning = rustflags_conflict(&spec, &ambient).unwrap();
+        assert!(warning.contains("RUSTFLAGS is already set"));
+    }
+
+    #[test]
+    fn rustflags_conflict_prefers_cargo_encoded_rustflags_as_culprit() {
+        let spec = Spec {
+            panic: Some(crate::options::PanicMode::Abort),
+            ..Default::default()
+        };
+        let ambient = vec![
+            ("RUSTFLAGS".to_string(), "-C target-cpu=native".to_string()),
+            (
+                "CARGO_ENCODED_RUSTFLAGS".to_string(),
+                "-Ctarget-cpu=native".to_string(),
+            ),
+        ];
+
+        let warning = rustflags_conflict(&spec, &ambient).unwrap();
+        assert!(warning.contains("CARGO_ENCODED_RUSTFLAGS is already set"));
+    }
+
+    #[test]
+    fn rustflags_conflict_none_when_spec_contributes_no_rustflags() {
+        let spec = Spec::default();
+        let ambient = vec![("RUSTFLAGS".to_string(), "-C target-cpu=native".to_string())];
+
+        assert!(rustflags_conflict(&spec, &ambient).is_none());
+    }
+
+    #[test]
+    fn rustflags_conflict_none_when_ambient_is_clean() {
+        let spec = Spec {
+            panic: Some(crate::options::PanicMode::Abort),
+            ..Default::default()
+        };
+        let ambient = vec![("PATH".to_string(), "/usr/bin".to_string())];
+
+        assert!(rustflags_conflict(&spec, &ambient).is_none());
+    }
+
+    #[test]
+    fn check_spec_misconfigurations_does_not_warn_without_rustflags_conflict() {
+        // `check_spec_misconfigurations` reads the real process env for this
+        // check, so the conflict-detection itself is covered directly
+        // against `rustflags_conflict` above (mutating shared process env
+        // here would be flaky under parallel tests); this just confirms a
+        // default spec never trips the wiring.
+        let tmp = TempDir::new().unwrap();
+        write_lib_only_pkg(&tmp, "mylib");
+        let spec = Spec::default();
+
+        let warnings = check_spec_misconfigurations("mylib", &spec, tmp.path(), tmp.path(), false);
+        assert!(
+            !warnings
+                .iter()
+                .any(|w| w.contains("is already set in the environment"))
+        );
+    }
+
+    #[test]
+    fn check_spec_misconfigurations_warns_on_opt_level_conflict() {
+        let tmp = TempDir::new().unwrap();
+        write_lib_only_pkg(&tmp, "mylib");
+        let mut spec = Spec::default();
+        spec.cargo.config.insert(
+            "profile.release.opt-level".to_string(),
+            crate::types::ConfigValue::String("z".to_string()),
+        );
+        spec.rustflags.push("-C opt-level=3".to_string());
+
+        let warnings = check_spec_misconfigurations("mylib", &spec, tmp.path(), tmp.path(), false);
+        assert!(
+            warnings.iter().any(|w| {
+                w.contains("'opt-level' is set through multiple spec channels")
+                    && w.contains("mylib")
+                    && w.contains("rustflags will take effect")
+            }),
+            "missing opt-level conflict warning in: {warnings:?}"
+        );
+    }
+
+    #[test]
+    fn resolve_env_overrides_includes_spec_file_and_rustflags() {
+        let spec = Spec {
+            panic: Some(crate::options::PanicMode::Abort),
+            rustflags: vec!["-C".to_string(), "opt-level=2".to_string()],
+            ..Default::default()
+        };
+
+        let overrides = resolve_env_overrides(&spec, Path::new("/proj/tspec.ts.toml"));
+        assert_eq!(
+            overrides,
+            vec![
+                (
+                    "TSPEC_SPEC_FILE".to_string(),
+                    "/proj/tspec.ts.toml".to_string()
+                ),
+                (
+                    "RUSTFLAGS".to_string(),
+                    "-C panic=abort -C opt-level=2".to_string()
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn resolve_env_overrides_omits_rustflags_for_default_spec() {
+        let spec = Spec::default();
+        let overrides = resolve_env_overrides(&spec, Path::new("/proj/tspec.ts.toml"));
+        assert_eq!(
+            overrides,
+            vec![(
+                "TSPEC_SPEC_FILE".to_string(),
+                "/proj/tspec.ts.toml".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn resolve_cfg_args_includes_target_triple_and_split_rustflags() {
+        let spec = Spec {
+            panic: Some(crate::options::PanicMode::Abort),
+            cargo: crate::types::CargoConfig {
+                target_triple: Some("x86_64-unknown-linux-musl".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        assert_eq!(
+            resolve_cfg_args(&spec, Path::new("/proj")),
+            vec![
+                "--print",
+                "cfg",
+                "--target",
+                "x86_64-unknown-linux-musl",
+                "-C",
+                "panic=abort",
+            ]
+        );
+    }
+
+    #[test]
+    fn resolve_cfg_args_no_target_for_default_spec() {
+        let spec = Spec::default();
+        assert_eq!(
+            resolve_cfg_args(&spec, Path::new("/proj")),
+            vec!["--print", "cfg"]
+        );
+    }
+
+    #[test]
+    fn resolve_target_json_path_joins_relative_path_onto_workspace() {
+        let mut spec = Spec::default();
+        spec.cargo.target_json = Some(PathBuf::from("targets/custom.json"));
+        assert_eq!(
+            resolve_target_json_path(&spec, Path::new("/proj")),
+            Some(PathBuf::from("/proj/targets/custom.json"))
+        );
+    }
+
+    #[test]
+    fn resolve_target_json_path_none_without_target_json() {
+        let spec = Spec::default();
+        assert_eq!(resolve_target_json_path(&spec, Path::new("/proj")), None);
+    }
+
+    #[test]
+    fn resolve_link_args_matches_no_buildrs_rustc_flags() {
+        // Same spec run through both the real build path (`--no-buildrs`
+        // routes `linker.args` into `-C link-arg=` RUSTFLAGS) and the print
+        // plan should agree on the underlying linker argument list.
+        let tmp = TempDir::new().unwrap();
+        let mut spec = Spec::default();
+        spec.linker.args = vec!["-static".to_string()];
+
+        let mut cmd = Command::new("true");
+        apply_spec_to_command(&mut cmd, &spec, tmp.path(), None, None, false, true).unwrap();
+        let rustflags = cmd
+            .get_envs()
+            .find(|(k, _)| *k == "RUSTFLAGS")
+            .and_then(|(_, v)| v)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .to_string();
+
+        let link_args = resolve_link_args(&spec, tmp.path(), None).unwrap();
+        assert_eq!(link_args, vec!["-static".to_string()]);
+        assert_eq!(rustflags, "-C link-arg=-static");
+    }
+
+    #[test]
+    fn resolve_link_args_appends_version_script() {
+        let tmp = TempDir::new().unwrap();
+        let mut spec = Spec::default();
+        spec.linker.version_script = Some(crate::types::VersionScript {
+            global: vec!["_start".to_string()],
+            local: "*".to_string(),
+        });
+
+        let link_args = resolve_link_args(&spec, tmp.path(), None).unwrap();
+        assert_eq!(link_args.len(), 1);
+        assert!(link_args[0].starts_with("-Wl,--version-script="));
+        assert!(link_args[0].ends_with("tspec-version.script"));
+        assert!(tmp.path().join("target/tspec-version.script").exists());
+    }
+
+    #[test]
+    fn resolve_link_args_empty_for_default_spec() {
+        let tmp = TempDir::new().unwrap();
+        let spec = Spec::default();
+        assert!(
+            resolve_link_args(&spec, tmp.path(), None)
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
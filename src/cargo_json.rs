@@ -0,0 +1,137 @@
+//! Incremental line-by-line handling of cargo's
+//! `--message-format=json-render-diagnostics` stream, for `build --quiet-cargo`.
+//!
+//! Plain `cargo build -q` suppresses the "Compiling xyz v1.2.3" progress
+//! spam, but it also swallows compiler warnings entirely — not what
+//! `--quiet-cargo` wants. Instead we run cargo with `-q` *and*
+//! `--message-format=json-render-diagnostics`, which emits one JSON object
+//! per line: `compiler-message` records carry the exact text cargo would
+//! otherwise have printed for a warning/error, while `compiler-artifact`/
+//! `build-finished` records are progress bookkeeping we can discard. Lines
+//! are processed one at a time (never buffered as a whole) since a build can
+//! run for a long time and we want diagnostics to appear as they occur.
+
+use std::io::{BufRead, Write};
+use std::process::{Command, ExitStatus, Stdio};
+
+use anyhow::{Context, Result};
+
+/// Render one line of the JSON stream to `out` if it's a `compiler-message`
+/// diagnostic. Returns `false` if the line isn't valid JSON, signalling the
+/// caller to fall back to printing it as plain text instead.
+fn render_line(line: &str, out: &mut dyn Write) -> bool {
+    if line.trim().is_empty() {
+        return true;
+    }
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(line) else {
+        return false;
+    };
+    if value.get("reason").and_then(|r| r.as_str()) == Some("compiler-message")
+        && let Some(rendered) = value
+            .get("message")
+            .and_then(|m| m.get("rendered"))
+            .and_then(|r| r.as_str())
+    {
+        let _ = out.write_all(rendered.as_bytes());
+    }
+    true
+}
+
+/// Consume `reader` line by line, re-rendering diagnostics to `out` and
+/// discarding everything else. A line that fails to parse as JSON (cargo
+/// didn't actually speak the message-format protocol we expected) is printed
+/// as-is instead of dropped, so real output is never silently lost.
+pub fn render_quiet_stream<R: BufRead>(reader: R, out: &mut dyn Write) -> Result<()> {
+    for line in reader.lines() {
+        let line = line.context("failed to read cargo's json message stream")?;
+        if !render_line(&line, out) {
+            let _ = writeln!(out, "{line}");
+        }
+    }
+    Ok(())
+}
+
+/// Run `cmd` with `-q --message-format=json-render-diagnostics` appended,
+/// streaming only the re-rendered diagnostics to stderr and discarding
+/// artifact/progress records.
+pub fn run_quiet(cmd: &mut Command) -> Result<ExitStatus> {
+    cmd.arg("-q")
+        .arg("--message-format=json-render-diagnostics");
+
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::inherit())
+        .spawn()
+        .context("failed to spawn command")?;
+
+    let stdout = child.stdout.take().expect("stdout was piped");
+    let reader = std::io::BufReader::new(stdout);
+    render_quiet_stream(reader, &mut std::io::stderr())?;
+
+    child.wait().context("failed to wait for command")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn render_to_string(lines: &[&str]) -> String {
+        let input = lines.join("\n");
+        let mut out = Vec::new();
+        render_quiet_stream(input.as_bytes(), &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn compiler_message_is_rendered() {
+        let line =
+            r#"{"reason":"compiler-message","message":{"rendered":"warning: unused variable\n"}}"#;
+        let out = render_to_string(&[line]);
+        assert_eq!(out, "warning: unused variable\n");
+    }
+
+    #[test]
+    fn compiler_artifact_is_discarded() {
+        let line = r#"{"reason":"compiler-artifact","package_id":"foo 0.1.0"}"#;
+        let out = render_to_string(&[line]);
+        assert_eq!(out, "");
+    }
+
+    #[test]
+    fn build_finished_is_discarded() {
+        let line = r#"{"reason":"build-finished","success":true}"#;
+        let out = render_to_string(&[line]);
+        assert_eq!(out, "");
+    }
+
+    #[test]
+    fn blank_lines_are_skipped() {
+        let out = render_to_string(&["", "   ", ""]);
+        assert_eq!(out, "");
+    }
+
+    #[test]
+    fn multiple_diagnostics_are_all_rendered_in_order() {
+        let lines = [
+            r#"{"reason":"compiler-artifact","package_id":"foo 0.1.0"}"#,
+            r#"{"reason":"compiler-message","message":{"rendered":"warning: first\n"}}"#,
+            r#"{"reason":"compiler-message","message":{"rendered":"error: second\n"}}"#,
+            r#"{"reason":"build-finished","success":false}"#,
+        ];
+        let out = render_to_string(&lines);
+        assert_eq!(out, "warning: first\nerror: second\n");
+    }
+
+    #[test]
+    fn non_json_line_falls_back_to_plain_passthrough() {
+        let out = render_to_string(&["not actually json"]);
+        assert_eq!(out, "not actually json\n");
+    }
+
+    #[test]
+    fn compiler_message_without_rendered_field_produces_no_output() {
+        let line = r#"{"reason":"compiler-message","message":{"spans":[]}}"#;
+        let out = render_to_string(&[line]);
+        assert_eq!(out, "");
+    }
+}
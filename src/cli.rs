@@ -1,7 +1,10 @@
 use crate::cmd::{
-    BuildCmd, CleanCmd, ClippyCmd, CompareCmd, FmtCmd, InstallCmd, RunCmd, TestCmd, TsCmd,
-    VersionCmd,
+    BaselinesCmd, BenchCmd, BuildCmd, CiCmd, CleanCmd, ClippyCmd, CompareCmd,
+    CompleteCandidatesCmd, CompletionsCmd, DepsCmd, DoctorCmd, ExamplesCmd, ExperimentCmd,
+    ExplainPathCmd, FmtCmd, GenerateCmd, InstallCmd, ListCmd, PrintCmd, ReportCmd, ReproCmd,
+    RunCmd, SchemaCmd, TargetsCmd, TestCmd, TsCmd, UsageCmd, VersionCmd,
 };
+use crate::find_paths::RootMode;
 use clap::{ArgAction, Parser, Subcommand};
 use std::path::PathBuf;
 
@@ -15,6 +18,12 @@ pub struct Cli {
     /// Number of parallel jobs to pass to cargo
     #[arg(short = 'j', long = "jobs", global = true)]
     pub jobs: Option<u16>,
+    /// Require Cargo.lock to stay unchanged, passed through to cargo
+    #[arg(long = "locked", global = true)]
+    pub locked: bool,
+    /// Run without accessing the network, passed through to cargo
+    #[arg(long = "offline", global = true)]
+    pub offline: bool,
     /// Path to Cargo.toml or directory containing one
     #[arg(
         long = "manifest-path",
@@ -23,6 +32,20 @@ pub struct Cli {
         value_name = "PATH"
     )]
     pub manifest_path: Option<PathBuf>,
+    /// How to resolve a package nested inside a workspace it isn't a member
+    /// of: "workspace" (default) prefers the enclosing workspace root,
+    /// "nearest" stops at the package itself. Overrides TSPEC_ROOT_MODE.
+    #[arg(long = "root-mode", global = true, value_name = "MODE")]
+    pub root_mode: Option<RootMode>,
+    /// Bypass the cargo metadata cache and force a fresh `cargo metadata`
+    /// run (also settable via TSPEC_REFRESH_METADATA)
+    #[arg(long = "refresh-metadata", global = true)]
+    pub refresh_metadata: bool,
+    /// Extra directory to search for a named spec before falling back to
+    /// the package directory (repeatable). Resolved relative to the
+    /// project root. Also settable via TSPEC_SPEC_DIR.
+    #[arg(long = "spec-dir", global = true, value_name = "DIR")]
+    pub spec_dir: Vec<PathBuf>,
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -35,6 +58,8 @@ pub enum Commands {
     Run(RunCmd),
     /// Test package(s) with a translation spec
     Test(TestCmd),
+    /// Benchmark package(s) with a translation spec
+    Bench(BenchCmd),
     /// Clean build artifacts
     Clean(CleanCmd),
     /// Run clippy lints
@@ -43,10 +68,60 @@ pub enum Commands {
     Fmt(FmtCmd),
     /// Compare specs for a package (size only)
     Compare(CompareCmd),
+    /// Manage named compare baselines saved with `compare --save-as`
+    Baselines(BaselinesCmd),
+    /// Run fmt-check, clippy, build, and test as one pipeline
+    Ci(CiCmd),
     /// Manage translation specs
     Ts(TsCmd),
     /// Print version information
     Version(VersionCmd),
     /// Install a package from a local path
     Install(InstallCmd),
+    /// Generate a shell completion script
+    Completions(CompletionsCmd),
+    /// Generate scaffolding for a workspace adopting tspec (e.g. CI smoke tests)
+    Generate(GenerateCmd),
+    /// List package/spec completion candidates for a partial word
+    #[command(hide = true, name = "complete-candidates")]
+    CompleteCandidates(CompleteCandidatesCmd),
+    /// Inspect the opt-in local usage log
+    Usage(UsageCmd),
+    /// Explain how tspec computes a package's expected binary path
+    ExplainPath(ExplainPathCmd),
+    /// Print a resolved build input (cfg, target-spec-json, link-args, env)
+    Print(PrintCmd),
+    /// Manage temporary spec experiments, selectable elsewhere as `-t @NAME`
+    Experiment(ExperimentCmd),
+    /// Generate workspace-wide reports (e.g. a spec usage inventory)
+    Report(ReportCmd),
+    /// List workspace members with kind and default-spec annotations
+    List(ListCmd),
+    /// Show a spec's resolved dependency set, or diff two specs' dependency sets
+    Deps(DepsCmd),
+    /// Build a package twice and diff the binaries for reproducibility
+    Repro(ReproCmd),
+    /// Run workspace-wide health checks (e.g. dangling spec references)
+    Doctor(DoctorCmd),
+    /// List target triples, for picking a `cargo.target_triple` value
+    Targets(TargetsCmd),
+    /// Emit a schema for `*.ts.toml` spec files
+    Schema(SchemaCmd),
+    /// (hidden) Print the example registry, or check it stays valid
+    #[command(hide = true)]
+    Examples(ExamplesCmd),
+}
+
+/// Attach each subcommand's registered examples (see [`crate::examples`])
+/// as `after_help`, so e.g. `tspec build --help` shows real invocations
+/// without every command hand-writing its own `after_help` string.
+pub fn augment_with_examples(mut command: clap::Command) -> clap::Command {
+    for sub in command.get_subcommands_mut() {
+        if let Some(examples) = crate::examples::for_command(sub.get_name()) {
+            *sub = sub
+                .clone()
+                .after_help(crate::examples::render_after_help(examples));
+        }
+    }
+    command
 }
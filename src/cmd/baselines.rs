@@ -0,0 +1,160 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use std::path::Path;
+use std::process::ExitCode;
+
+use super::{Execute, current_package_name, resolve_package_arg};
+use crate::baseline::{delete_baseline, list_baselines, load_baseline};
+use crate::find_paths::resolve_package_dir;
+use crate::types::CargoFlags;
+
+/// Manage named `tspec compare` baselines saved with `--save-as`
+#[derive(Args)]
+pub struct BaselinesCmd {
+    #[command(subcommand)]
+    command: BaselinesCommands,
+}
+
+#[derive(Subcommand)]
+pub enum BaselinesCommands {
+    /// List every baseline saved for a package
+    List {
+        /// Package to list baselines for (name or path, e.g. "." for current dir)
+        #[arg(value_name = "PACKAGE")]
+        positional: Option<String>,
+        /// Package to list baselines for (defaults to current directory)
+        #[arg(short = 'p', long = "package")]
+        package: Option<String>,
+    },
+    /// Print a saved baseline's entries
+    Show {
+        /// Baseline label
+        label: String,
+        /// Package the baseline was saved under (name or path)
+        #[arg(value_name = "PACKAGE")]
+        positional: Option<String>,
+        /// Package the baseline was saved under (defaults to current directory)
+        #[arg(short = 'p', long = "package")]
+        package: Option<String>,
+    },
+    /// Delete a saved baseline
+    Delete {
+        /// Baseline label
+        label: String,
+        /// Package the baseline was saved under (name or path)
+        #[arg(value_name = "PACKAGE")]
+        positional: Option<String>,
+        /// Package the baseline was saved under (defaults to current directory)
+        #[arg(short = 'p', long = "package")]
+        package: Option<String>,
+    },
+}
+
+/// Resolve the package directory a `baselines` subcommand should read from:
+/// positional/`-p` PACKAGE, falling back to the current directory's package.
+fn resolve_target_dir(
+    project_root: &Path,
+    positional: &Option<String>,
+    package: &Option<String>,
+) -> Result<std::path::PathBuf> {
+    let resolved = match positional.as_deref().or(package.as_deref()) {
+        Some(pkg) => resolve_package_arg(pkg, project_root)?,
+        None => current_package_name(project_root),
+    };
+    resolve_package_dir(project_root, resolved.as_deref())
+}
+
+impl Execute for BaselinesCmd {
+    fn execute(&self, project_root: &Path, flags: &CargoFlags) -> Result<ExitCode> {
+        let _ = flags;
+        match &self.command {
+            BaselinesCommands::List {
+                positional,
+                package,
+            } => {
+                let package_dir = resolve_target_dir(project_root, positional, package)?;
+                let labels = list_baselines(&package_dir)?;
+                if labels.is_empty() {
+                    println!("no baselines saved");
+                } else {
+                    for label in &labels {
+                        println!("{label}");
+                    }
+                }
+                Ok(ExitCode::SUCCESS)
+            }
+            BaselinesCommands::Show {
+                label,
+                positional,
+                package,
+            } => {
+                let package_dir = resolve_target_dir(project_root, positional, package)?;
+                let baseline = load_baseline(&package_dir, label)?;
+                println!("{label} (version {})", baseline.version);
+                for entry in &baseline.entries {
+                    match &entry.hash {
+                        Some(hash) => println!("  {} [{hash}]  {} bytes", entry.spec, entry.size),
+                        None => println!("  {}  {} bytes", entry.spec, entry.size),
+                    }
+                }
+                Ok(ExitCode::SUCCESS)
+            }
+            BaselinesCommands::Delete {
+                label,
+                positional,
+                package,
+            } => {
+                let package_dir = resolve_target_dir(project_root, positional, package)?;
+                delete_baseline(&package_dir, label)?;
+                println!("Deleted baseline '{label}'");
+                Ok(ExitCode::SUCCESS)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+
+    fn parse(args: &[&str]) -> BaselinesCmd {
+        let mut full = vec!["tspec", "baselines"];
+        full.extend_from_slice(args);
+        let cli = Cli::try_parse_from(full).unwrap();
+        match cli.command {
+            crate::cli::Commands::Baselines(cmd) => cmd,
+            _ => panic!("expected Baselines command"),
+        }
+    }
+
+    #[test]
+    fn parses_list_with_package() {
+        let cmd = parse(&["list", "-p", "mypkg"]);
+        match cmd.command {
+            BaselinesCommands::List { package, .. } => {
+                assert_eq!(package.as_deref(), Some("mypkg"))
+            }
+            _ => panic!("expected List"),
+        }
+    }
+
+    #[test]
+    fn parses_show_with_label() {
+        let cmd = parse(&["show", "v1"]);
+        match cmd.command {
+            BaselinesCommands::Show { label, .. } => assert_eq!(label, "v1"),
+            _ => panic!("expected Show"),
+        }
+    }
+
+    #[test]
+    fn parses_delete_with_label() {
+        let cmd = parse(&["delete", "v1"]);
+        match cmd.command {
+            BaselinesCommands::Delete { label, .. } => assert_eq!(label, "v1"),
+            _ => panic!("expected Delete"),
+        }
+    }
+}
@@ -0,0 +1,165 @@
+use anyhow::Result;
+use clap::Args;
+use std::path::Path;
+use std::process::ExitCode;
+
+use super::{Execute, current_package_name, resolve_package_arg};
+use crate::cargo_build::bench_package;
+use crate::find_paths::{find_tspecs, get_package_name, resolve_package_dir};
+use crate::types::CargoFlags;
+use crate::workspace::WorkspaceInfo;
+
+/// Benchmark package(s) with a translation spec, running `cargo bench`.
+///
+/// Unlike `tspec test`, output is passed straight through rather than parsed —
+/// bench harnesses (libtest's nightly `#[bench]`, criterion, etc.) don't share
+/// a result-line format worth relying on, so there's no `--list`/`--names`/
+/// `--test` filtering here, only package and spec selection.
+#[derive(Args)]
+pub struct BenchCmd {
+    /// Package to benchmark (name or path, e.g. "." for current dir)
+    #[arg(value_name = "PACKAGE")]
+    pub positional: Option<String>,
+    /// Package to benchmark (defaults to current directory or all packages)
+    #[arg(short = 'p', long = "package")]
+    pub package: Option<String>,
+    /// Benchmark all workspace packages (even when in a package directory)
+    #[arg(short = 'w', long = "workspace")]
+    pub workspace: bool,
+    /// Spec file(s) or glob pattern(s) to bench with (defaults to package's tspec file)
+    #[arg(short = 't', long = "tspec", num_args = 1..)]
+    pub tspec: Vec<String>,
+    /// Build profile (e.g., release, release-small, or any custom profile)
+    #[arg(long)]
+    pub profile: Option<String>,
+    /// Let --profile win when it conflicts with the spec's cargo.profile
+    #[arg(long = "force-profile")]
+    pub force_profile: bool,
+}
+
+impl Execute for BenchCmd {
+    fn execute(&self, project_root: &Path, flags: &CargoFlags) -> Result<ExitCode> {
+        let cli_profile = self.profile.as_deref();
+
+        // Resolve package: --workspace > -p/positional PKG > cwd > all
+        let resolved = if self.workspace {
+            None
+        } else {
+            match self.positional.as_deref().or(self.package.as_deref()) {
+                Some(pkg) => resolve_package_arg(pkg, project_root)?,
+                None => current_package_name(project_root),
+            }
+        };
+
+        match resolved {
+            None => {
+                let workspace = WorkspaceInfo::discover(project_root)?;
+                for member in workspace.buildable_members() {
+                    println!("=== {} ===", member.name);
+                    bench_package(
+                        &member.name,
+                        None,
+                        cli_profile,
+                        self.force_profile,
+                        project_root,
+                        flags,
+                    )?;
+                }
+                Ok(ExitCode::SUCCESS)
+            }
+            Some(name) => {
+                if self.tspec.is_empty() {
+                    bench_package(
+                        &name,
+                        None,
+                        cli_profile,
+                        self.force_profile,
+                        project_root,
+                        flags,
+                    )?;
+                } else {
+                    let package_dir = resolve_package_dir(project_root, Some(&name))?;
+                    let pkg_name = get_package_name(&package_dir)?;
+                    let spec_paths = find_tspecs(&package_dir, &self.tspec)?;
+                    for spec_path in &spec_paths {
+                        let spec_str = spec_path.to_string_lossy();
+                        bench_package(
+                            &pkg_name,
+                            Some(&spec_str),
+                            cli_profile,
+                            self.force_profile,
+                            project_root,
+                            flags,
+                        )?;
+                    }
+                }
+                Ok(ExitCode::SUCCESS)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+
+    fn parse(args: &[&str]) -> BenchCmd {
+        let mut full = vec!["tspec", "bench"];
+        full.extend_from_slice(args);
+        let cli = Cli::try_parse_from(full).unwrap();
+        match cli.command {
+            crate::cli::Commands::Bench(cmd) => cmd,
+            _ => panic!("expected Bench command"),
+        }
+    }
+
+    #[test]
+    fn package_optional() {
+        let cmd = parse(&[]);
+        assert!(cmd.package.is_none());
+    }
+
+    #[test]
+    fn package_explicit() {
+        let cmd = parse(&["-p", "myapp"]);
+        assert_eq!(cmd.package.as_deref(), Some("myapp"));
+    }
+
+    #[test]
+    fn tspec_single_file() {
+        let cmd = parse(&["-t", "foo.ts.toml"]);
+        assert_eq!(cmd.tspec, vec!["foo.ts.toml"]);
+    }
+
+    #[test]
+    fn workspace_flag_short() {
+        let cmd = parse(&["-w"]);
+        assert!(cmd.workspace);
+    }
+
+    #[test]
+    fn workspace_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.workspace);
+    }
+
+    #[test]
+    fn profile_flag() {
+        let cmd = parse(&["--profile", "release-small"]);
+        assert_eq!(cmd.profile.as_deref(), Some("release-small"));
+    }
+
+    #[test]
+    fn force_profile_flag() {
+        let cmd = parse(&["--force-profile", "--profile", "release-small"]);
+        assert!(cmd.force_profile);
+    }
+
+    #[test]
+    fn force_profile_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.force_profile);
+    }
+}
@@ -3,12 +3,14 @@ use clap::Args;
 use std::path::Path;
 use std::process::ExitCode;
 
-use super::{Execute, current_package_name, resolve_package_arg};
-use crate::all::{build_all, print_summary};
-use crate::binary::strip_binary;
-use crate::cargo_build::build_package;
+use super::{Execute, current_package_name, execute_cargo_subcommand, resolve_package_arg};
+use crate::all::{GroupBy, SortBy, build_all, print_summary_grouped};
+use crate::binary::{StripOutcome, format_strip_savings, strip_binary_with_report};
+use crate::cargo_build::{build_package, resolve_base_rustflags, resolve_env_overrides};
 use crate::find_paths::{find_tspecs, get_package_name, resolve_package_dir};
+use crate::tspec::{hash_spec, load_spec, resolve_spec, verify_expected_hash};
 use crate::types::CargoFlags;
+use crate::usage;
 use crate::workspace::WorkspaceInfo;
 
 /// Build package(s) with a translation spec
@@ -24,20 +26,101 @@ pub struct BuildCmd {
     #[arg(short = 'w', long = "workspace")]
     pub workspace: bool,
     /// Spec file(s) or glob pattern(s) to build with (defaults to package's tspec file)
-    #[arg(short = 't', long = "tspec", num_args = 1..)]
+    #[arg(short = 't', long = "tspec", num_args = 1.., conflicts_with = "no_spec")]
     pub tspec: Vec<String>,
+    /// Force plain cargo build, skipping spec application even if a default
+    /// tspec file is present. Distinct from -t with a different spec.
+    #[arg(long = "no-spec")]
+    pub no_spec: bool,
+    /// Build with the selected spec's expensive codegen knobs (lto,
+    /// codegen-units, opt-level) relaxed to the profile default and
+    /// CARGO_INCREMENTAL=1 set, for a fast edit-compile loop. Target triple,
+    /// panic/strip modes, and linker args are kept, and the build goes into
+    /// a separate `{name}-dev-overlay` target_dir so it never pollutes the
+    /// real spec's artifacts. See `tspec::apply_dev_overlay`.
+    #[arg(long = "dev-overlay", conflicts_with = "no_spec")]
+    pub dev_overlay: bool,
     /// Release build
     #[arg(short, long, conflicts_with = "profile")]
     pub release: bool,
     /// Build profile (e.g., release, release-small, or any custom profile)
     #[arg(long)]
     pub profile: Option<String>,
+    /// Let --profile win when it conflicts with the spec's cargo.profile
+    #[arg(long = "force-profile")]
+    pub force_profile: bool,
     /// Strip symbols from binary after build
     #[arg(short, long)]
     pub strip: bool,
     /// Stop on first failure (for all-packages mode)
     #[arg(short, long)]
     pub fail_fast: bool,
+    /// Print the RUSTFLAGS the spec resolves to and exit without building
+    #[arg(long = "print-rustflags")]
+    pub print_rustflags: bool,
+    /// Print every environment variable the spec would set (TSPEC_SPEC_FILE,
+    /// RUSTFLAGS) as KEY=VALUE lines and exit without building
+    #[arg(long = "print-env")]
+    pub print_env: bool,
+    /// In all-packages mode, skip a (package, spec) pair whose hash is on
+    /// the package's compat.toml incompatible list
+    #[arg(long = "only-compatible")]
+    pub only_compatible: bool,
+    /// Force a synthetic per-spec target_dir, avoiding shared artifacts
+    /// between specs that don't set their own cargo.target_dir
+    #[arg(long = "isolate")]
+    pub isolate: bool,
+    /// Suppress cargo's own "Compiling xyz" progress output, but still show
+    /// full compiler warnings/errors as they occur
+    #[arg(long = "quiet-cargo")]
+    pub quiet_cargo: bool,
+    /// Scrub inherited environment variables before invoking cargo, keeping
+    /// only a fixed allowlist plus the spec's `cargo.env_allowlist`. Use -v
+    /// to see which vars were dropped. Also settable per-spec via
+    /// `cargo.hermetic_env = true`.
+    #[arg(long = "hermetic-env")]
+    pub hermetic_env: bool,
+    /// Include BuildTool-kind members (e.g. xtask) in all-packages mode
+    /// instead of excluding them
+    #[arg(long = "include-build-tools")]
+    pub include_build_tools: bool,
+    /// Skip generating a temporary build.rs for linker.args and route them
+    /// through RUSTFLAGS `-C link-arg=` instead (applies to every target in
+    /// the package, not just the bin)
+    #[arg(long = "no-buildrs")]
+    pub no_buildrs: bool,
+    /// Leave a generated linker-args build.rs in place after the build for
+    /// inspection instead of deleting it
+    #[arg(long = "keep-buildrs")]
+    pub keep_buildrs: bool,
+    /// Order the -w summary's rows by name (default), size (largest first),
+    /// or time (not tracked yet, sorts like name)
+    #[arg(long = "sort-by", value_enum, default_value_t = SortBy::Name)]
+    pub sort_by: SortBy,
+    /// Lay out the -w summary flat (default, one row per build) or grouped
+    /// under a subheading per package, so a package built under several
+    /// specs reads as one group instead of several same-named rows
+    #[arg(long = "group-by", value_enum, default_value_t = GroupBy::Flat)]
+    pub group_by: GroupBy,
+    /// Fail instead of warning when an ambient RUSTFLAGS/
+    /// CARGO_ENCODED_RUSTFLAGS would silently override the spec's own
+    #[arg(long = "strict-flags")]
+    pub strict_flags: bool,
+    /// Rebuild even if the spec and every source file are unchanged since
+    /// the last successful build (see the "up to date" skip in `run_cargo`)
+    #[arg(long = "force")]
+    pub force: bool,
+    /// Skip invoking cargo when the spec changed since the last successful
+    /// build but only in fields that don't affect the build, e.g. `[run]`/
+    /// `[test]` defaults (see `crate::smart_rebuild`)
+    #[arg(long = "smart-rebuild")]
+    pub smart_rebuild: bool,
+    /// Fail before building unless the resolved spec's `hash_spec` value
+    /// matches exactly, guarding a release pipeline against an accidental
+    /// local edit to the spec. Prints the expected and actual hash on
+    /// mismatch. See `tspec ts hash` to read a spec's current hash.
+    #[arg(long = "expect-hash")]
+    pub expect_hash: Option<String>,
 }
 
 impl BuildCmd {
@@ -51,6 +134,91 @@ impl BuildCmd {
             None
         }
     }
+
+    /// Whether this build can skip tspec's own spec-aware pipeline
+    /// (`run_cargo`'s progress reporting, up-to-date check, build.rs
+    /// generation, etc.) and delegate straight to `cargo build` instead.
+    /// True only when `spec_will_apply` is false and none of the flags
+    /// below — which only mean something with a spec, or change what
+    /// tspec itself prints/does around the build — were requested.
+    fn should_delegate_to_cargo(&self, spec_will_apply: bool) -> bool {
+        should_delegate_to_cargo(
+            spec_will_apply,
+            self.dev_overlay,
+            self.isolate,
+            self.hermetic_env,
+            self.no_buildrs,
+            self.keep_buildrs,
+            self.strict_flags,
+            self.smart_rebuild,
+            self.force,
+            self.quiet_cargo,
+            self.strip,
+        )
+    }
+}
+
+/// Pure decision behind [`BuildCmd::should_delegate_to_cargo`], split out so
+/// it's unit-testable without constructing a full `BuildCmd` via clap.
+#[allow(clippy::too_many_arguments)]
+fn should_delegate_to_cargo(
+    spec_will_apply: bool,
+    dev_overlay: bool,
+    isolate: bool,
+    hermetic_env: bool,
+    no_buildrs: bool,
+    keep_buildrs: bool,
+    strict_flags: bool,
+    smart_rebuild: bool,
+    force: bool,
+    quiet_cargo: bool,
+    strip: bool,
+) -> bool {
+    !spec_will_apply
+        && !dev_overlay
+        && !isolate
+        && !hermetic_env
+        && !no_buildrs
+        && !keep_buildrs
+        && !strict_flags
+        && !smart_rebuild
+        && !force
+        && !quiet_cargo
+        && !strip
+}
+
+/// `-p <name>` plus `--release`/`--profile <p>`, the only bits of a
+/// spec-free `tspec build` invocation that still need forwarding to a
+/// delegated `cargo build`.
+fn cargo_passthrough_args(pkg_name: &str, cli_profile: Option<&str>) -> Vec<std::ffi::OsString> {
+    let mut args: Vec<std::ffi::OsString> = vec!["-p".into(), pkg_name.into()];
+    match cli_profile {
+        Some("release") => args.push("--release".into()),
+        Some(profile) => {
+            args.push("--profile".into());
+            args.push(profile.into());
+        }
+        None => {}
+    }
+    args
+}
+
+/// Record a spec touch for the usage log, with its content hash when it loads cleanly.
+fn note_spec_from_path(spec_path: &Path) {
+    let name = spec_path
+        .file_name()
+        .map(|s| s.to_string_lossy().to_string())
+        .unwrap_or_else(|| spec_path.display().to_string());
+    let hash = load_spec(spec_path).and_then(|s| hash_spec(&s)).ok();
+    usage::note_spec(name, hash);
+}
+
+/// Print the result of a `--strip` pass: a savings line, or nothing extra
+/// beyond the "skipped strip" notice `strip_binary` already printed.
+fn print_strip_outcome(outcome: StripOutcome) {
+    if let StripOutcome::Stripped(savings) = outcome {
+        println!("  {}", format_strip_savings(savings));
+    }
 }
 
 impl Execute for BuildCmd {
@@ -67,40 +235,178 @@ impl Execute for BuildCmd {
             }
         };
 
+        if let Some(profile) = cli_profile {
+            usage::note_profile(profile);
+        }
+
         match resolved {
             None => {
+                if self.print_rustflags {
+                    anyhow::bail!("--print-rustflags requires a single package, e.g. -p <name>");
+                }
+                if self.print_env {
+                    anyhow::bail!("--print-env requires a single package, e.g. -p <name>");
+                }
+                if self.dev_overlay {
+                    anyhow::bail!("--dev-overlay requires a single package, e.g. -p <name>");
+                }
+                if self.expect_hash.is_some() {
+                    anyhow::bail!("--expect-hash requires a single package, e.g. -p <name>");
+                }
                 let workspace = WorkspaceInfo::discover(project_root)?;
                 let results = build_all(
                     &workspace,
                     &self.tspec,
                     cli_profile,
-                    self.strip,
-                    self.fail_fast,
                     flags,
+                    crate::all::BuildAllOptions {
+                        force_profile: self.force_profile,
+                        strip: self.strip,
+                        fail_fast: self.fail_fast,
+                        explicit_workspace: self.workspace,
+                        only_compatible: self.only_compatible,
+                        isolate: self.isolate,
+                        quiet_cargo: self.quiet_cargo,
+                        hermetic_env: self.hermetic_env,
+                        include_build_tools: self.include_build_tools,
+                        no_buildrs: self.no_buildrs,
+                        keep_buildrs: self.keep_buildrs,
+                        strict_flags: self.strict_flags,
+                        force: self.force,
+                        smart_rebuild: self.smart_rebuild,
+                    },
                 );
-                Ok(print_summary(&workspace.name_versioned(), &results))
+                Ok(print_summary_grouped(
+                    &workspace.name_versioned(),
+                    &results,
+                    self.sort_by,
+                    self.group_by,
+                ))
             }
             Some(name) => {
+                usage::note_package(&name);
+                if self.print_rustflags {
+                    let spec = if self.tspec.is_empty() {
+                        resolve_spec(Some(&name), None, project_root)?.1
+                    } else {
+                        let pkg_dir = resolve_package_dir(project_root, Some(&name))?;
+                        find_tspecs(&pkg_dir, &self.tspec)?
+                            .into_iter()
+                            .next()
+                            .map(|path| load_spec(&path))
+                            .transpose()?
+                    };
+                    let flags = match spec {
+                        Some(spec) => resolve_base_rustflags(&spec),
+                        None => Vec::new(),
+                    };
+                    println!("{}", flags.join(" "));
+                    return Ok(ExitCode::SUCCESS);
+                }
+                if self.print_env {
+                    let (spec_path, spec) = if self.tspec.is_empty() {
+                        resolve_spec(Some(&name), None, project_root)?
+                    } else {
+                        let pkg_dir = resolve_package_dir(project_root, Some(&name))?;
+                        let path = find_tspecs(&pkg_dir, &self.tspec)?.into_iter().next();
+                        match path {
+                            Some(path) => {
+                                let spec = load_spec(&path)?;
+                                (path, Some(spec))
+                            }
+                            None => (pkg_dir, None),
+                        }
+                    };
+                    let overrides = match &spec {
+                        Some(spec) => resolve_env_overrides(spec, &spec_path),
+                        None => Vec::new(),
+                    };
+                    for (key, value) in &overrides {
+                        println!("{key}={value}");
+                    }
+                    return Ok(ExitCode::SUCCESS);
+                }
                 if self.tspec.is_empty() {
-                    let result = build_package(&name, None, cli_profile, project_root, flags)?;
+                    let resolved_spec = if self.no_spec {
+                        None
+                    } else {
+                        resolve_spec(Some(&name), None, project_root).ok()
+                    };
+                    if let Some((spec_path, Some(_))) = &resolved_spec {
+                        note_spec_from_path(spec_path);
+                    }
+                    if let Some(expected) = &self.expect_hash {
+                        match &resolved_spec {
+                            Some((_, Some(spec))) => verify_expected_hash(spec, expected)?,
+                            _ => anyhow::bail!(
+                                "--expect-hash given but no spec resolved for '{name}' to verify"
+                            ),
+                        }
+                    }
+                    let spec_will_apply =
+                        matches!(&resolved_spec, Some((_, Some(_))) if !self.no_spec);
+                    if self.should_delegate_to_cargo(spec_will_apply) {
+                        println!("Building {name} (no tspec)");
+                        return execute_cargo_subcommand(
+                            "build",
+                            &cargo_passthrough_args(&name, cli_profile),
+                            project_root,
+                            flags,
+                        );
+                    }
+                    let result = build_package(
+                        &name,
+                        None,
+                        self.no_spec,
+                        self.dev_overlay,
+                        self.force,
+                        cli_profile,
+                        self.force_profile,
+                        project_root,
+                        flags,
+                        self.isolate,
+                        self.quiet_cargo,
+                        self.hermetic_env,
+                        self.no_buildrs,
+                        self.keep_buildrs,
+                        self.strict_flags,
+                        self.smart_rebuild,
+                        None,
+                    )?;
                     if self.strip {
-                        strip_binary(&result.binary_path)?;
+                        print_strip_outcome(strip_binary_with_report(&result.binary_path)?);
                     }
                 } else {
                     let package_dir = resolve_package_dir(project_root, Some(&name))?;
                     let pkg_name = get_package_name(&package_dir)?;
                     let spec_paths = find_tspecs(&package_dir, &self.tspec)?;
                     for spec_path in &spec_paths {
+                        note_spec_from_path(spec_path);
+                        if let Some(expected) = &self.expect_hash {
+                            verify_expected_hash(&load_spec(spec_path)?, expected)?;
+                        }
                         let spec_str = spec_path.to_string_lossy();
                         let result = build_package(
                             &pkg_name,
                             Some(&spec_str),
+                            false,
+                            self.dev_overlay,
+                            self.force,
                             cli_profile,
+                            self.force_profile,
                             project_root,
                             flags,
+                            self.isolate,
+                            self.quiet_cargo,
+                            self.hermetic_env,
+                            self.no_buildrs,
+                            self.keep_buildrs,
+                            self.strict_flags,
+                            self.smart_rebuild,
+                            None,
                         )?;
                         if self.strip {
-                            strip_binary(&result.binary_path)?;
+                            print_strip_outcome(strip_binary_with_report(&result.binary_path)?);
                         }
                     }
                 }
@@ -144,6 +450,42 @@ mod tests {
         assert!(cmd.tspec.is_empty());
     }
 
+    #[test]
+    fn print_rustflags_off_by_default() {
+        let cmd = parse(&[]);
+        assert!(!cmd.print_rustflags);
+    }
+
+    #[test]
+    fn print_rustflags_flag() {
+        let cmd = parse(&["-p", "myapp", "--print-rustflags"]);
+        assert!(cmd.print_rustflags);
+    }
+
+    #[test]
+    fn print_env_off_by_default() {
+        let cmd = parse(&[]);
+        assert!(!cmd.print_env);
+    }
+
+    #[test]
+    fn print_env_flag() {
+        let cmd = parse(&["-p", "myapp", "--print-env"]);
+        assert!(cmd.print_env);
+    }
+
+    #[test]
+    fn strict_flags_off_by_default() {
+        let cmd = parse(&[]);
+        assert!(!cmd.strict_flags);
+    }
+
+    #[test]
+    fn strict_flags_flag() {
+        let cmd = parse(&["-p", "myapp", "--strict-flags"]);
+        assert!(cmd.strict_flags);
+    }
+
     #[test]
     fn tspec_single_file() {
         let cmd = parse(&["-t", "foo.ts.toml"]);
@@ -237,4 +579,184 @@ mod tests {
         let result = Cli::try_parse_from(["tspec", "build", "-r", "--profile", "custom"]);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn force_profile_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.force_profile);
+    }
+
+    #[test]
+    fn force_profile_flag() {
+        let cmd = parse(&["--force-profile", "--profile", "release-small"]);
+        assert!(cmd.force_profile);
+    }
+
+    #[test]
+    fn isolate_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.isolate);
+    }
+
+    #[test]
+    fn isolate_flag() {
+        let cmd = parse(&["--isolate"]);
+        assert!(cmd.isolate);
+    }
+
+    #[test]
+    fn quiet_cargo_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.quiet_cargo);
+    }
+
+    #[test]
+    fn quiet_cargo_flag() {
+        let cmd = parse(&["--quiet-cargo"]);
+        assert!(cmd.quiet_cargo);
+    }
+
+    #[test]
+    fn hermetic_env_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.hermetic_env);
+    }
+
+    #[test]
+    fn hermetic_env_flag() {
+        let cmd = parse(&["--hermetic-env"]);
+        assert!(cmd.hermetic_env);
+    }
+
+    #[test]
+    fn no_buildrs_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.no_buildrs);
+    }
+
+    #[test]
+    fn no_buildrs_flag() {
+        let cmd = parse(&["--no-buildrs"]);
+        assert!(cmd.no_buildrs);
+    }
+
+    #[test]
+    fn keep_buildrs_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.keep_buildrs);
+    }
+
+    #[test]
+    fn keep_buildrs_flag() {
+        let cmd = parse(&["--keep-buildrs"]);
+        assert!(cmd.keep_buildrs);
+    }
+
+    #[test]
+    fn no_spec_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.no_spec);
+    }
+
+    #[test]
+    fn no_spec_flag() {
+        let cmd = parse(&["--no-spec"]);
+        assert!(cmd.no_spec);
+    }
+
+    #[test]
+    fn no_spec_conflicts_with_tspec() {
+        let result = Cli::try_parse_from(["tspec", "build", "--no-spec", "-t", "foo.ts.toml"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn dev_overlay_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.dev_overlay);
+    }
+
+    #[test]
+    fn dev_overlay_flag() {
+        let cmd = parse(&["--dev-overlay"]);
+        assert!(cmd.dev_overlay);
+    }
+
+    #[test]
+    fn dev_overlay_conflicts_with_no_spec() {
+        let result = Cli::try_parse_from(["tspec", "build", "--dev-overlay", "--no-spec"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn smart_rebuild_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.smart_rebuild);
+    }
+
+    #[test]
+    fn smart_rebuild_flag() {
+        let cmd = parse(&["--smart-rebuild"]);
+        assert!(cmd.smart_rebuild);
+    }
+
+    #[test]
+    fn delegates_when_no_spec_and_no_flags() {
+        let cmd = parse(&[]);
+        assert!(cmd.should_delegate_to_cargo(false));
+    }
+
+    #[test]
+    fn does_not_delegate_when_spec_applies() {
+        let cmd = parse(&[]);
+        assert!(!cmd.should_delegate_to_cargo(true));
+    }
+
+    #[test]
+    fn does_not_delegate_with_strip() {
+        let cmd = parse(&["-s"]);
+        assert!(!cmd.should_delegate_to_cargo(false));
+    }
+
+    #[test]
+    fn does_not_delegate_with_isolate() {
+        let cmd = parse(&["--isolate"]);
+        assert!(!cmd.should_delegate_to_cargo(false));
+    }
+
+    #[test]
+    fn does_not_delegate_with_smart_rebuild() {
+        let cmd = parse(&["--smart-rebuild"]);
+        assert!(!cmd.should_delegate_to_cargo(false));
+    }
+
+    #[test]
+    fn passthrough_args_plain() {
+        let args = cargo_passthrough_args("myapp", None);
+        assert_eq!(args, vec!["-p", "myapp"]);
+    }
+
+    #[test]
+    fn passthrough_args_release() {
+        let args = cargo_passthrough_args("myapp", Some("release"));
+        assert_eq!(args, vec!["-p", "myapp", "--release"]);
+    }
+
+    #[test]
+    fn passthrough_args_named_profile() {
+        let args = cargo_passthrough_args("myapp", Some("release-small"));
+        assert_eq!(args, vec!["-p", "myapp", "--profile", "release-small"]);
+    }
+
+    #[test]
+    fn expect_hash_default_none() {
+        let cmd = parse(&[]);
+        assert!(cmd.expect_hash.is_none());
+    }
+
+    #[test]
+    fn expect_hash_flag() {
+        let cmd = parse(&["--expect-hash", "a1b2c3d4"]);
+        assert_eq!(cmd.expect_hash.as_deref(), Some("a1b2c3d4"));
+    }
 }
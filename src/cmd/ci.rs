@@ -0,0 +1,509 @@
+use anyhow::{Result, bail};
+use clap::{Args, ValueEnum};
+use serde::Deserialize;
+use std::path::Path;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+use super::{BuildCmd, ClippyCmd, Execute, FmtCmd, TestCmd};
+use crate::hooks::{SummaryPayload, report_failed_hooks, run_hooks};
+use crate::types::CargoFlags;
+use crate::workspace::WorkspaceInfo;
+use crate::{print_header, print_hline};
+
+/// One stage of the `ci` pipeline. The pipeline itself is just a `Vec<CiStage>`
+/// (see [`default_stages`]) selected via `--stage` or `ci.stages` in
+/// `[workspace.metadata.tspec]` (see [`configured_stages`]); adding a new
+/// kind of stage is a matter of adding a variant and a case in
+/// [`run_stage`] — no changes needed to the driving loop.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CiStage {
+    #[value(name = "fmt-check")]
+    FmtCheck,
+    Clippy,
+    Build,
+    Test,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CiWorkspaceConfig {
+    #[serde(default)]
+    stages: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TspecWorkspaceConfig {
+    ci: Option<CiWorkspaceConfig>,
+    hooks: Option<HooksConfig>,
+}
+
+/// `[workspace.metadata.tspec.hooks]` — commands run at the end of `ci`, see
+/// [`crate::hooks`]. `on_summary` always runs; `on_failure` also runs, but
+/// only when the pipeline had a failing stage.
+#[derive(Debug, Default, Deserialize)]
+struct HooksConfig {
+    #[serde(default)]
+    on_summary: Vec<String>,
+    #[serde(default)]
+    on_failure: Vec<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct WorkspaceMetadata {
+    #[serde(default)]
+    tspec: TspecWorkspaceConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct WorkspaceSection {
+    #[serde(default)]
+    metadata: WorkspaceMetadata,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CargoToml {
+    #[serde(default)]
+    workspace: WorkspaceSection,
+}
+
+/// Read `ci.stages = [...]` from `[workspace.metadata.tspec]` in
+/// `project_root/Cargo.toml`, if configured. `None` means unset — not the
+/// same as an empty pipeline, which `--stage` can't express anyway since
+/// clap requires at least one value.
+///
+/// Mirrors `usage::usage_log_path`'s error handling: a missing Cargo.toml or
+/// parse error is treated as "unset" rather than failing the command, since
+/// a config-reading hiccup shouldn't block `ci` from running its default
+/// pipeline.
+fn configured_stages(project_root: &Path) -> Option<Vec<String>> {
+    let content = std::fs::read_to_string(project_root.join("Cargo.toml")).ok()?;
+    let parsed: CargoToml = toml::from_str(&content).ok()?;
+    let stages = parsed.workspace.metadata.tspec.ci?.stages;
+    if stages.is_empty() {
+        None
+    } else {
+        Some(stages)
+    }
+}
+
+/// Read `hooks.on_summary`/`hooks.on_failure` from `[workspace.metadata.tspec]`,
+/// if configured. Empty when unset or the manifest can't be read/parsed —
+/// same non-fatal treatment as [`configured_stages`].
+fn configured_hooks(project_root: &Path) -> HooksConfig {
+    std::fs::read_to_string(project_root.join("Cargo.toml"))
+        .ok()
+        .and_then(|content| toml::from_str::<CargoToml>(&content).ok())
+        .and_then(|parsed| parsed.workspace.metadata.tspec.hooks)
+        .unwrap_or_default()
+}
+
+/// Parse workspace-config stage names into `CiStage`s, using the same names
+/// `--stage` accepts (e.g. "fmt-check").
+fn parse_configured_stages(names: &[String]) -> Result<Vec<CiStage>> {
+    let mut stages = Vec::with_capacity(names.len());
+    for name in names {
+        let Ok(stage) = CiStage::from_str(name, false) else {
+            let valid = CiStage::value_variants()
+                .iter()
+                .filter_map(|s| s.to_possible_value())
+                .map(|v| v.get_name().to_string())
+                .collect::<Vec<_>>()
+                .join(", ");
+            bail!(
+                "unknown ci stage '{name}' in [workspace.metadata.tspec] ci.stages \
+                 (valid stages: {valid})"
+            );
+        };
+        stages.push(stage);
+    }
+    Ok(stages)
+}
+
+impl CiStage {
+    fn label(self) -> &'static str {
+        match self {
+            CiStage::FmtCheck => "fmt --check",
+            CiStage::Clippy => "clippy",
+            CiStage::Build => "build -w",
+            CiStage::Test => "test -w",
+        }
+    }
+}
+
+/// The default pipeline run by `tspec ci` with no `--stage` flags.
+pub fn default_stages() -> Vec<CiStage> {
+    vec![
+        CiStage::FmtCheck,
+        CiStage::Clippy,
+        CiStage::Build,
+        CiStage::Test,
+    ]
+}
+
+/// Run fmt-check, clippy, build, and test as one pipeline with a combined summary
+#[derive(Args)]
+pub struct CiCmd {
+    /// Stages to run, in order (defaults to fmt-check, clippy, build, test)
+    #[arg(long = "stage", value_enum, num_args = 1..)]
+    pub stages: Vec<CiStage>,
+    /// Keep running later stages even after one fails
+    #[arg(long = "keep-going")]
+    pub keep_going: bool,
+    /// Spec file(s) or glob pattern(s) for the build/test stages
+    #[arg(short = 't', long = "tspec", num_args = 1..)]
+    pub tspec: Vec<String>,
+    /// Release build for the build/test stages
+    #[arg(short, long, conflicts_with = "profile")]
+    pub release: bool,
+    /// Build profile for the build/test stages
+    #[arg(long)]
+    pub profile: Option<String>,
+    /// Let --profile win when it conflicts with the spec's cargo.profile
+    #[arg(long = "force-profile")]
+    pub force_profile: bool,
+    /// Skip the `[workspace.metadata.tspec] hooks.on_summary`/`on_failure`
+    /// commands, if any are configured
+    #[arg(long = "no-hooks")]
+    pub no_hooks: bool,
+}
+
+impl CiCmd {
+    /// Run one stage through the programmatic cmd facade (no re-spawning `tspec`).
+    fn run_stage(
+        &self,
+        stage: CiStage,
+        project_root: &Path,
+        flags: &CargoFlags,
+    ) -> Result<ExitCode> {
+        match stage {
+            CiStage::FmtCheck => FmtCmd {
+                positional: None,
+                package: None,
+                workspace: true,
+                check: true,
+            }
+            .execute(project_root, flags),
+            CiStage::Clippy => ClippyCmd {
+                positional: None,
+                package: None,
+                workspace: true,
+            }
+            .execute(project_root, flags),
+            CiStage::Build => BuildCmd {
+                positional: None,
+                package: None,
+                workspace: true,
+                tspec: self.tspec.clone(),
+                no_spec: false,
+                dev_overlay: false,
+                release: self.release,
+                profile: self.profile.clone(),
+                force_profile: self.force_profile,
+                strip: false,
+                fail_fast: !self.keep_going,
+                print_rustflags: false,
+                print_env: false,
+                only_compatible: false,
+                isolate: false,
+                quiet_cargo: false,
+                hermetic_env: false,
+                no_buildrs: false,
+                keep_buildrs: false,
+                include_build_tools: false,
+                sort_by: crate::all::SortBy::Name,
+                group_by: crate::all::GroupBy::Flat,
+                strict_flags: false,
+                force: false,
+                smart_rebuild: false,
+                expect_hash: None,
+            }
+            .execute(project_root, flags),
+            CiStage::Test => TestCmd {
+                positional: None,
+                package: None,
+                workspace: true,
+                tspec: self.tspec.clone(),
+                release: self.release,
+                profile: self.profile.clone(),
+                force_profile: self.force_profile,
+                fail_fast: !self.keep_going,
+                list: false,
+                target_names: false,
+                name_filter: Vec::new(),
+                test_target: Vec::new(),
+                all_tests: false,
+                test_args: Vec::new(),
+                isolate: false,
+                no_buildrs: false,
+                keep_buildrs: false,
+                include_build_tools: false,
+            }
+            .execute(project_root, flags),
+        }
+    }
+}
+
+struct StageOutcome {
+    stage: CiStage,
+    success: bool,
+    duration: Duration,
+}
+
+impl Execute for CiCmd {
+    fn execute(&self, project_root: &Path, flags: &CargoFlags) -> Result<ExitCode> {
+        let workspace = WorkspaceInfo::discover(project_root)?;
+        // Precedence: explicit --stage flags > workspace config
+        // (ci.stages under [workspace.metadata.tspec]) > built-in default.
+        let stages = if !self.stages.is_empty() {
+            self.stages.clone()
+        } else if let Some(configured) = configured_stages(project_root) {
+            parse_configured_stages(&configured)?
+        } else {
+            default_stages()
+        };
+
+        let pipeline_start = Instant::now();
+        let mut outcomes = Vec::with_capacity(stages.len());
+        let mut first_failure: Option<(CiStage, String)> = None;
+
+        for stage in stages {
+            let stage_start = Instant::now();
+            let result = self.run_stage(stage, project_root, flags);
+            let duration = stage_start.elapsed();
+            let success = result.is_ok();
+
+            if let Err(e) = result
+                && first_failure.is_none()
+            {
+                first_failure = Some((stage, format!("{e:#}")));
+            }
+
+            outcomes.push(StageOutcome {
+                stage,
+                success,
+                duration,
+            });
+
+            if !success && !self.keep_going {
+                break;
+            }
+        }
+
+        print_ci_summary(
+            &workspace.name_versioned(),
+            &outcomes,
+            pipeline_start.elapsed(),
+        );
+
+        if !self.no_hooks {
+            run_ci_hooks(project_root, &outcomes);
+        }
+
+        match first_failure {
+            Some((stage, msg)) => {
+                eprintln!("ci: stage '{}' failed: {msg}", stage.label());
+                Ok(ExitCode::FAILURE)
+            }
+            None => Ok(ExitCode::SUCCESS),
+        }
+    }
+}
+
+/// Run `hooks.on_summary` (always) and `hooks.on_failure` (only when a stage
+/// failed) from `[workspace.metadata.tspec]`, reporting any hook failures.
+///
+/// `ci` doesn't track individual packages the way `build -w`/`test -w` do
+/// (a stage either passes or fails as a whole), so `SummaryPayload::
+/// failed_packages` carries the labels of the failed stages (e.g.
+/// `"build -w"`) rather than package names — the closest analogue available
+/// here.
+fn run_ci_hooks(project_root: &Path, outcomes: &[StageOutcome]) {
+    let hooks = configured_hooks(project_root);
+    if hooks.on_summary.is_empty() && hooks.on_failure.is_empty() {
+        return;
+    }
+
+    let failed_stages: Vec<String> = outcomes
+        .iter()
+        .filter(|o| !o.success)
+        .map(|o| o.stage.label().to_string())
+        .collect();
+    let payload = SummaryPayload::new("ci", failed_stages.clone());
+
+    report_failed_hooks(&run_hooks(&hooks.on_summary, &payload));
+    if !failed_stages.is_empty() {
+        report_failed_hooks(&run_hooks(&hooks.on_failure, &payload));
+    }
+}
+
+fn print_ci_summary(ws_name: &str, outcomes: &[StageOutcome], total: Duration) {
+    let max_label_len = outcomes
+        .iter()
+        .map(|o| o.stage.label().len())
+        .max()
+        .unwrap_or(5)
+        .max(5);
+
+    println!();
+    print_header!(format!("{ws_name} CI SUMMARY"));
+    println!(
+        "  {:width$}  {:7}  Time",
+        "Stage",
+        "Result",
+        width = max_label_len
+    );
+    for outcome in outcomes {
+        let status = if outcome.success { "ok" } else { "FAILED" };
+        println!(
+            "  {:width$}  {:7}  {:.2}s",
+            outcome.stage.label(),
+            status,
+            outcome.duration.as_secs_f64(),
+            width = max_label_len
+        );
+    }
+
+    println!();
+    let verdict = if outcomes.iter().all(|o| o.success) {
+        "ci: all stages passed"
+    } else {
+        "ci: one or more stages failed"
+    };
+    println!("  {verdict} ({:.2}s total)", total.as_secs_f64());
+    print_hline!();
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+    use tempfile::TempDir;
+
+    fn write_cargo_toml(dir: &Path, content: &str) {
+        std::fs::write(dir.join("Cargo.toml"), content).unwrap();
+    }
+
+    #[test]
+    fn configured_stages_unset_by_default() {
+        let dir = TempDir::new().unwrap();
+        write_cargo_toml(dir.path(), "[workspace]\nmembers = []\n");
+        assert_eq!(configured_stages(dir.path()), None);
+    }
+
+    #[test]
+    fn configured_stages_reads_workspace_metadata() {
+        let dir = TempDir::new().unwrap();
+        write_cargo_toml(
+            dir.path(),
+            "[workspace]\nmembers = []\n\n\
+             [workspace.metadata.tspec.ci]\nstages = [\"fmt-check\", \"build\"]\n",
+        );
+        assert_eq!(
+            configured_stages(dir.path()),
+            Some(vec!["fmt-check".to_string(), "build".to_string()])
+        );
+    }
+
+    #[test]
+    fn parse_configured_stages_accepts_known_names() {
+        let stages = parse_configured_stages(&["clippy".to_string(), "test".to_string()]).unwrap();
+        assert_eq!(stages, vec![CiStage::Clippy, CiStage::Test]);
+    }
+
+    #[test]
+    fn parse_configured_stages_rejects_unknown_name() {
+        let err = parse_configured_stages(&["lint".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("unknown ci stage 'lint'"));
+    }
+
+    fn parse(args: &[&str]) -> CiCmd {
+        let mut full = vec!["tspec", "ci"];
+        full.extend_from_slice(args);
+        let cli = Cli::try_parse_from(full).unwrap();
+        match cli.command {
+            crate::cli::Commands::Ci(cmd) => cmd,
+            _ => panic!("expected Ci command"),
+        }
+    }
+
+    #[test]
+    fn default_stages_is_fmt_clippy_build_test() {
+        assert_eq!(
+            default_stages(),
+            vec![
+                CiStage::FmtCheck,
+                CiStage::Clippy,
+                CiStage::Build,
+                CiStage::Test
+            ]
+        );
+    }
+
+    #[test]
+    fn stages_empty_by_default() {
+        let cmd = parse(&[]);
+        assert!(cmd.stages.is_empty());
+    }
+
+    #[test]
+    fn stages_explicit() {
+        let cmd = parse(&["--stage", "clippy", "--stage", "build"]);
+        assert_eq!(cmd.stages, vec![CiStage::Clippy, CiStage::Build]);
+    }
+
+    #[test]
+    fn keep_going_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.keep_going);
+    }
+
+    #[test]
+    fn keep_going_flag() {
+        let cmd = parse(&["--keep-going"]);
+        assert!(cmd.keep_going);
+    }
+
+    #[test]
+    fn no_hooks_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.no_hooks);
+    }
+
+    #[test]
+    fn no_hooks_flag() {
+        let cmd = parse(&["--no-hooks"]);
+        assert!(cmd.no_hooks);
+    }
+
+    #[test]
+    fn configured_hooks_unset_by_default() {
+        let dir = TempDir::new().unwrap();
+        write_cargo_toml(dir.path(), "[workspace]\nmembers = []\n");
+        let hooks = configured_hooks(dir.path());
+        assert!(hooks.on_summary.is_empty());
+        assert!(hooks.on_failure.is_empty());
+    }
+
+    #[test]
+    fn configured_hooks_reads_workspace_metadata() {
+        let dir = TempDir::new().unwrap();
+        write_cargo_toml(
+            dir.path(),
+            "[workspace]\nmembers = []\n\n\
+             [workspace.metadata.tspec.hooks]\n\
+             on_summary = [\"./notify.sh\"]\n\
+             on_failure = [\"./page.sh\"]\n",
+        );
+        let hooks = configured_hooks(dir.path());
+        assert_eq!(hooks.on_summary, vec!["./notify.sh".to_string()]);
+        assert_eq!(hooks.on_failure, vec!["./page.sh".to_string()]);
+    }
+
+    #[test]
+    fn profile_and_release_conflict() {
+        let result = Cli::try_parse_from(["tspec", "ci", "-r", "--profile", "custom"]);
+        assert!(result.is_err());
+    }
+}
@@ -1,13 +1,19 @@
-use anyhow::Result;
+use anyhow::{Context, Result};
 use clap::Args;
 use std::path::Path;
 use std::process::ExitCode;
 
 use super::{Execute, current_package_name, resolve_package_arg};
 use crate::all::{compare_all, print_compare_summary};
-use crate::compare::{compare_specs, print_comparison};
+use crate::baseline::{diff_against, load_baseline, save_baseline};
+use crate::compare::{
+    compare_specs, filter_changed_specs, print_baseline_diff, print_comparison, render_csv,
+    tests_failed,
+};
 use crate::find_paths::{find_tspecs, get_package_name, get_package_version, resolve_package_dir};
+use crate::tspec::{hash_spec, load_spec};
 use crate::types::CargoFlags;
+use crate::usage;
 use crate::workspace::WorkspaceInfo;
 
 /// Compare specs for a package (size only)
@@ -25,13 +31,84 @@ pub struct CompareCmd {
     /// Spec file(s) or glob pattern(s) (defaults to tspec* pattern)
     #[arg(short = 't', long = "tspec", num_args = 1..)]
     pub tspec: Vec<String>,
+    /// Exclude spec(s) by filename glob (repeatable), e.g. --exclude-spec 'tspec.experimental*'
+    #[arg(long = "exclude-spec")]
+    pub exclude_spec: Vec<String>,
     /// Stop on first failure (for all-packages mode)
     #[arg(short, long)]
     pub fail_fast: bool,
+    /// Report ELF loadable-segment sizes (flash/RAM/BSS) alongside file size
+    #[arg(long)]
+    pub segments: bool,
+    /// Use the spec whose name contains this substring as the delta/percent
+    /// baseline instead of the first (smallest) row, e.g. --baseline-spec tspec.min
+    #[arg(long = "baseline-spec")]
+    pub baseline_spec: Option<String>,
+    /// Also run each spec's tests after building and show a Tests column
+    #[arg(long = "with-tests")]
+    pub with_tests: bool,
+    /// With --with-tests, exclude specs whose tests failed (or didn't run)
+    /// from "smallest" sorting and exit non-zero if any failed
+    #[arg(long = "require-pass", requires = "with_tests")]
+    pub require_pass: bool,
+    /// Force a synthetic per-spec target_dir, avoiding shared artifacts
+    /// between specs that don't set their own cargo.target_dir
+    #[arg(long = "isolate")]
+    pub isolate: bool,
+    /// Only compare specs whose file changed since REF (via `git diff --name-only`),
+    /// skipping unchanged ones. Answers "did this PR's spec change affect size?"
+    #[arg(long = "changed-specs", value_name = "REF")]
+    pub changed_specs: Option<String>,
+    /// Build every spec even when two or more resolve to an identical hash
+    /// (default: build each unique hash once and reuse the result)
+    #[arg(long = "allow-duplicate-builds")]
+    pub allow_duplicate_builds: bool,
+    /// Include BuildTool-kind members (e.g. xtask) in all-packages mode
+    /// instead of excluding them
+    #[arg(long = "include-build-tools")]
+    pub include_build_tools: bool,
+    /// Save this run's per-spec sizes/hashes as a named baseline (single-package only)
+    #[arg(long = "save-as", value_name = "LABEL", conflicts_with = "against")]
+    pub save_as: Option<String>,
+    /// Show size/hash deltas against a baseline saved with --save-as (single-package only)
+    #[arg(long = "against", value_name = "LABEL")]
+    pub against: Option<String>,
+    /// Write per-spec size (and, with --segments, section sizes) to a CSV
+    /// file, in addition to the normal table output (single-package only)
+    #[arg(long = "csv", value_name = "PATH")]
+    pub csv: Option<std::path::PathBuf>,
+}
+
+/// Run `git diff --name-only <ref>` in `project_root` and return the paths
+/// it reports, repo-root-relative. Used by `--changed-specs`.
+fn git_changed_files(project_root: &Path, git_ref: &str) -> Result<Vec<String>> {
+    let output = std::process::Command::new("git")
+        .arg("diff")
+        .arg("--name-only")
+        .arg(git_ref)
+        .current_dir(project_root)
+        .output()?;
+    if !output.status.success() {
+        anyhow::bail!(
+            "git diff --name-only {git_ref} failed: {}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(str::to_string)
+        .collect())
 }
 
 impl Execute for CompareCmd {
     fn execute(&self, project_root: &Path, flags: &CargoFlags) -> Result<ExitCode> {
+        if self.workspace && (self.save_as.is_some() || self.against.is_some()) {
+            anyhow::bail!("--save-as/--against require a single package, e.g. -p <name>");
+        }
+        if self.workspace && self.csv.is_some() {
+            anyhow::bail!("--csv requires a single package, e.g. -p <name>");
+        }
+
         // Resolve package: --workspace > -p/positional PKG > cwd > all
         let resolved = if self.workspace {
             None
@@ -45,23 +122,81 @@ impl Execute for CompareCmd {
         match resolved {
             None => {
                 let workspace = WorkspaceInfo::discover(project_root)?;
-                let results = compare_all(&workspace, &self.tspec, self.fail_fast, flags);
-                Ok(print_compare_summary(&workspace.name_versioned(), &results))
+                let results = compare_all(
+                    &workspace,
+                    &self.tspec,
+                    &self.exclude_spec,
+                    flags,
+                    crate::all::CompareAllOptions {
+                        fail_fast: self.fail_fast,
+                        segments: self.segments,
+                        with_tests: self.with_tests,
+                        require_pass: self.require_pass,
+                        isolate: self.isolate,
+                        include_build_tools: self.include_build_tools,
+                        allow_duplicate_builds: self.allow_duplicate_builds,
+                    },
+                );
+                Ok(print_compare_summary(
+                    &workspace.name_versioned(),
+                    &results,
+                    self.baseline_spec.as_deref(),
+                ))
             }
             Some(pkg_name) => {
                 let package_dir = resolve_package_dir(project_root, Some(&pkg_name))?;
                 let pkg_name = get_package_name(&package_dir)?;
-                let spec_paths = if self.tspec.is_empty() {
+                usage::note_package(&pkg_name);
+                let mut spec_paths = if self.tspec.is_empty() {
                     find_tspecs(&package_dir, &self.tspec).unwrap_or_default()
                 } else {
                     find_tspecs(&package_dir, &self.tspec)?
                 };
-                let results = compare_specs(&pkg_name, &spec_paths, project_root, flags)?;
+                if let Some(git_ref) = &self.changed_specs {
+                    let changed = git_changed_files(project_root, git_ref)?;
+                    spec_paths = filter_changed_specs(&spec_paths, &changed);
+                }
+                for spec_path in &spec_paths {
+                    let name = spec_path
+                        .file_name()
+                        .map(|s| s.to_string_lossy().to_string())
+                        .unwrap_or_else(|| spec_path.display().to_string());
+                    let hash = load_spec(spec_path).and_then(|s| hash_spec(&s)).ok();
+                    usage::note_spec(name, hash);
+                }
+                let results = compare_specs(
+                    &pkg_name,
+                    &spec_paths,
+                    project_root,
+                    flags,
+                    self.segments,
+                    self.with_tests,
+                    self.require_pass,
+                    self.isolate,
+                    self.allow_duplicate_builds,
+                )?;
                 let versioned = match get_package_version(&package_dir) {
                     Ok(ver) => format!("{pkg_name} v{ver}"),
                     Err(_) => pkg_name.clone(),
                 };
-                print_comparison(&versioned, &results);
+                print_comparison(&versioned, &results, self.baseline_spec.as_deref());
+                if let Some(path) = &self.csv {
+                    std::fs::write(path, render_csv(&results))
+                        .with_context(|| format!("failed to write CSV: {}", path.display()))?;
+                    println!("Wrote CSV to {}", path.display());
+                }
+                if let Some(label) = &self.save_as {
+                    let path = save_baseline(&package_dir, label, &results)?;
+                    println!("Saved baseline '{label}' to {}", path.display());
+                }
+                if let Some(label) = &self.against {
+                    let baseline = load_baseline(&package_dir, label)?;
+                    let rows = diff_against(&baseline, &results);
+                    print_baseline_diff(label, &rows);
+                }
+                if self.require_pass && results.iter().any(|r| tests_failed(r.tests.as_ref())) {
+                    return Ok(ExitCode::from(1));
+                }
                 Ok(ExitCode::SUCCESS)
             }
         }
@@ -159,4 +294,120 @@ mod tests {
         assert!(cmd.workspace);
         assert!(cmd.fail_fast);
     }
+
+    #[test]
+    fn exclude_spec_empty_by_default() {
+        let cmd = parse(&[]);
+        assert!(cmd.exclude_spec.is_empty());
+    }
+
+    #[test]
+    fn exclude_spec_single() {
+        let cmd = parse(&["--exclude-spec", "tspec.experimental*"]);
+        assert_eq!(cmd.exclude_spec, vec!["tspec.experimental*"]);
+    }
+
+    #[test]
+    fn segments_flag_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.segments);
+    }
+
+    #[test]
+    fn segments_flag_set() {
+        let cmd = parse(&["--segments"]);
+        assert!(cmd.segments);
+    }
+
+    #[test]
+    fn baseline_spec_default_none() {
+        let cmd = parse(&[]);
+        assert!(cmd.baseline_spec.is_none());
+    }
+
+    #[test]
+    fn baseline_spec_flag() {
+        let cmd = parse(&["--baseline-spec", "tspec.min"]);
+        assert_eq!(cmd.baseline_spec.as_deref(), Some("tspec.min"));
+    }
+
+    #[test]
+    fn with_tests_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.with_tests);
+    }
+
+    #[test]
+    fn with_tests_flag() {
+        let cmd = parse(&["--with-tests"]);
+        assert!(cmd.with_tests);
+    }
+
+    #[test]
+    fn require_pass_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.require_pass);
+    }
+
+    #[test]
+    fn require_pass_flag() {
+        let cmd = parse(&["--with-tests", "--require-pass"]);
+        assert!(cmd.require_pass);
+    }
+
+    #[test]
+    fn require_pass_without_with_tests_is_an_error() {
+        let result = Cli::try_parse_from(["tspec", "compare", "--require-pass"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn isolate_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.isolate);
+    }
+
+    #[test]
+    fn isolate_flag() {
+        let cmd = parse(&["--isolate"]);
+        assert!(cmd.isolate);
+    }
+
+    #[test]
+    fn allow_duplicate_builds_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.allow_duplicate_builds);
+    }
+
+    #[test]
+    fn allow_duplicate_builds_flag() {
+        let cmd = parse(&["--allow-duplicate-builds"]);
+        assert!(cmd.allow_duplicate_builds);
+    }
+
+    #[test]
+    fn csv_default_none() {
+        let cmd = parse(&[]);
+        assert!(cmd.csv.is_none());
+    }
+
+    #[test]
+    fn csv_flag() {
+        let cmd = parse(&["--csv", "out.csv"]);
+        assert_eq!(cmd.csv, Some(std::path::PathBuf::from("out.csv")));
+    }
+
+    #[test]
+    fn exclude_spec_repeatable() {
+        let cmd = parse(&[
+            "--exclude-spec",
+            "tspec.experimental*",
+            "--exclude-spec",
+            "tspec.broken*",
+        ]);
+        assert_eq!(
+            cmd.exclude_spec,
+            vec!["tspec.experimental*", "tspec.broken*"]
+        );
+    }
 }
@@ -0,0 +1,136 @@
+use anyhow::Result;
+use clap::{Args, CommandFactory};
+use clap_complete::{Shell, generate};
+use std::io;
+use std::path::Path;
+use std::process::ExitCode;
+
+use super::Execute;
+use crate::cli::Cli;
+use crate::find_paths::find_tspecs;
+use crate::types::CargoFlags;
+use crate::workspace::WorkspaceInfo;
+
+/// Generate a shell completion script
+#[derive(Args)]
+pub struct CompletionsCmd {
+    /// Shell to generate completions for
+    #[arg(value_enum)]
+    pub shell: Shell,
+}
+
+impl Execute for CompletionsCmd {
+    fn execute(&self, _project_root: &Path, _flags: &CargoFlags) -> Result<ExitCode> {
+        let mut cmd = Cli::command();
+        let name = cmd.get_name().to_string();
+        generate(self.shell, &mut cmd, name, &mut io::stdout());
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+/// (hidden) List package/spec completion candidates for a partial word
+///
+/// Shelled out to by the completion scripts from `tspec completions`, so
+/// package names and spec files stay current without regenerating the
+/// static script.
+#[derive(Args)]
+pub struct CompleteCandidatesCmd {
+    /// Word being completed (defaults to listing everything)
+    pub partial: Option<String>,
+}
+
+impl Execute for CompleteCandidatesCmd {
+    fn execute(&self, project_root: &Path, _flags: &CargoFlags) -> Result<ExitCode> {
+        let partial = self.partial.as_deref().unwrap_or("");
+        for candidate in complete_candidates(project_root, partial) {
+            println!("{candidate}");
+        }
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+/// List completion candidates for a `--package`/`--tspec` style argument.
+///
+/// `partial` is matched as a prefix against workspace package names and
+/// against spec filenames discovered for `project_root`. This is the shared
+/// logic behind `tspec complete-candidates`, the hidden command the
+/// generated shell scripts call out to.
+pub fn complete_candidates(project_root: &Path, partial: &str) -> Vec<String> {
+    let mut candidates = Vec::new();
+
+    if let Ok(workspace) = WorkspaceInfo::discover(project_root) {
+        for member in &workspace.members {
+            if member.name.starts_with(partial) {
+                candidates.push(member.name.clone());
+            }
+        }
+    }
+
+    if let Ok(specs) = find_tspecs(project_root, &[]) {
+        for spec in specs {
+            if let Some(name) = spec.file_name().map(|s| s.to_string_lossy().to_string())
+                && name.starts_with(partial)
+            {
+                candidates.push(name);
+            }
+        }
+    }
+
+    candidates
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::Write as _;
+
+    #[test]
+    fn generate_produces_non_empty_output_for_each_shell() {
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let mut cmd = Cli::command();
+            let name = cmd.get_name().to_string();
+            let mut buf = Vec::new();
+            generate(shell, &mut cmd, name, &mut buf);
+            assert!(
+                !buf.is_empty(),
+                "expected non-empty completion script for {shell:?}"
+            );
+        }
+    }
+
+    fn write_fixture_tspec(dir: &Path, name: &str) {
+        let mut file = fs::File::create(dir.join(name)).unwrap();
+        writeln!(file, "panic = \"abort\"").unwrap();
+    }
+
+    #[test]
+    fn complete_candidates_matches_spec_filenames_by_prefix() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(
+            tmp.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        write_fixture_tspec(tmp.path(), "tspec.ts.toml");
+        write_fixture_tspec(tmp.path(), "tspec.release.ts.toml");
+
+        let mut candidates = complete_candidates(tmp.path(), "tspec.r");
+        candidates.sort();
+        assert_eq!(candidates, vec!["tspec.release.ts.toml"]);
+    }
+
+    #[test]
+    fn complete_candidates_empty_for_unmatched_prefix() {
+        let tmp = tempfile::tempdir().unwrap();
+        fs::write(
+            tmp.path().join("Cargo.toml"),
+            "[package]\nname = \"demo\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        write_fixture_tspec(tmp.path(), "tspec.ts.toml");
+
+        let candidates = complete_candidates(tmp.path(), "nonexistent-prefix");
+        assert!(candidates.is_empty());
+    }
+}
@@ -0,0 +1,302 @@
+use anyhow::{Result, bail};
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use std::path::Path;
+use std::process::ExitCode;
+
+use super::{Execute, current_package_name, resolve_package_arg};
+use crate::deps::{DepInfo, DepsDiff, diff_dependencies, resolve_dependencies};
+use crate::find_paths::{find_package_dir, find_tspec};
+use crate::tspec::load_spec;
+use crate::types::CargoFlags;
+use crate::{print_header, print_hline};
+
+/// Output format for `tspec deps`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum DepsFormat {
+    Table,
+    Json,
+}
+
+/// Show the dependency set a spec's configuration resolves to, or diff two
+/// specs' dependency sets against each other.
+///
+/// Resolves dependencies via `cargo metadata --filter-platform <triple>`
+/// using the spec's `cargo.target_triple`, so target-gated dependencies are
+/// included/excluded the same way a real build under that spec would see
+/// them. `build_std` crates are rebuilt from source outside cargo's normal
+/// dependency graph, so they're listed separately as a note rather than
+/// folded into the diff.
+#[derive(Args)]
+pub struct DepsCmd {
+    /// Package to inspect (name or path, e.g. "." for current dir)
+    #[arg(value_name = "PACKAGE")]
+    pub positional: Option<String>,
+    /// Package to inspect (defaults to current directory)
+    #[arg(short = 'p', long = "package")]
+    pub package: Option<String>,
+    /// Spec to resolve dependencies under; pass twice (-t a -t b) to diff two specs
+    #[arg(short = 't', long = "tspec", required = true)]
+    pub tspec: Vec<String>,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = DepsFormat::Table)]
+    pub format: DepsFormat,
+}
+
+#[derive(Serialize)]
+struct DepsListOutput {
+    package: String,
+    spec: String,
+    target: Option<String>,
+    build_std: Vec<String>,
+    dependencies: Vec<DepInfo>,
+}
+
+#[derive(Serialize)]
+struct DepsDiffOutput {
+    package: String,
+    spec_a: String,
+    spec_b: String,
+    target_a: Option<String>,
+    target_b: Option<String>,
+    build_std_a: Vec<String>,
+    build_std_b: Vec<String>,
+    diff: DepsDiff,
+}
+
+/// One named spec's resolved target triple, build_std list, and dependency set.
+struct ResolvedSpec {
+    name: String,
+    target: Option<String>,
+    build_std: Vec<String>,
+    deps: Vec<DepInfo>,
+}
+
+fn resolve_spec_deps(pkg_dir: &Path, manifest_path: &Path, name: &str) -> Result<ResolvedSpec> {
+    let spec_path = find_tspec(pkg_dir, Some(name))?.ok_or_else(|| {
+        anyhow::anyhow!(
+            "no tspec file found matching '{}' in {}",
+            name,
+            pkg_dir.display()
+        )
+    })?;
+    let spec = load_spec(&spec_path)?;
+    let target = spec.cargo.target_triple.clone();
+    let deps = resolve_dependencies(manifest_path, target.as_deref())?;
+    Ok(ResolvedSpec {
+        name: name.to_string(),
+        target,
+        build_std: spec.cargo.build_std.clone(),
+        deps,
+    })
+}
+
+fn print_deps_list(pkg: &str, resolved: &ResolvedSpec) {
+    println!();
+    print_header!(format!("{pkg} DEPS ({})", resolved.name));
+    if let Some(target) = &resolved.target {
+        println!("  target: {target}");
+    }
+    println!("  {} resolved crates", resolved.deps.len());
+    println!();
+    for dep in &resolved.deps {
+        println!("  {:<32} {}", dep.name, dep.version);
+    }
+    if !resolved.build_std.is_empty() {
+        println!();
+        println!(
+            "  Note: build_std rebuilds {} from source; cargo's dependency \
+             graph above doesn't include them.",
+            resolved.build_std.join(", ")
+        );
+    }
+    print_hline!();
+    println!();
+}
+
+fn print_deps_diff(pkg: &str, a: &ResolvedSpec, b: &ResolvedSpec, diff: &DepsDiff) {
+    println!();
+    print_header!(format!("{pkg} DEPS DIFF: {} vs {}", a.name, b.name));
+    println!(
+        "  {} common, {} only in {}, {} only in {}, {} version difference(s)",
+        diff.common_count,
+        diff.only_a.len(),
+        a.name,
+        diff.only_b.len(),
+        b.name,
+        diff.version_diffs.len(),
+    );
+    if !diff.only_a.is_empty() {
+        println!();
+        println!("  Only in {}:", a.name);
+        for dep in &diff.only_a {
+            println!("    {} {}", dep.name, dep.version);
+        }
+    }
+    if !diff.only_b.is_empty() {
+        println!();
+        println!("  Only in {}:", b.name);
+        for dep in &diff.only_b {
+            println!("    {} {}", dep.name, dep.version);
+        }
+    }
+    if !diff.version_diffs.is_empty() {
+        println!();
+        println!("  Version differences:");
+        for vd in &diff.version_diffs {
+            println!(
+                "    {}: {}={} {}={}",
+                vd.name,
+                a.name,
+                vd.versions_a.join(","),
+                b.name,
+                vd.versions_b.join(","),
+            );
+        }
+    }
+    if !a.build_std.is_empty() || !b.build_std.is_empty() {
+        println!();
+        println!("  Note: build_std crates rebuilt from source aren't shown above:");
+        println!(
+            "    {}: {}",
+            a.name,
+            if a.build_std.is_empty() {
+                "-".to_string()
+            } else {
+                a.build_std.join(", ")
+            }
+        );
+        println!(
+            "    {}: {}",
+            b.name,
+            if b.build_std.is_empty() {
+                "-".to_string()
+            } else {
+                b.build_std.join(", ")
+            }
+        );
+    }
+    print_hline!();
+    println!();
+}
+
+impl Execute for DepsCmd {
+    fn execute(&self, project_root: &Path, flags: &CargoFlags) -> Result<ExitCode> {
+        let _ = flags;
+        if self.tspec.len() > 2 {
+            bail!("tspec deps accepts at most two -t specs (pass one to list, two to diff)");
+        }
+
+        let pkg = match self.positional.as_deref().or(self.package.as_deref()) {
+            Some(pkg) => pkg.to_string(),
+            None => match current_package_name(project_root) {
+                Some(name) => name,
+                None => {
+                    eprintln!(
+                        "Error: no package specified and cwd does not resolve to a single package"
+                    );
+                    return Ok(ExitCode::from(1));
+                }
+            },
+        };
+        let pkg_name = resolve_package_arg(&pkg, project_root)?.unwrap_or(pkg);
+        let pkg_dir = find_package_dir(project_root, &pkg_name)?;
+        let manifest_path = pkg_dir.join("Cargo.toml");
+
+        let resolved: Vec<ResolvedSpec> = self
+            .tspec
+            .iter()
+            .map(|name| resolve_spec_deps(&pkg_dir, &manifest_path, name))
+            .collect::<Result<_>>()?;
+
+        match resolved.as_slice() {
+            [single] => match self.format {
+                DepsFormat::Table => print_deps_list(&pkg_name, single),
+                DepsFormat::Json => {
+                    let output = DepsListOutput {
+                        package: pkg_name.clone(),
+                        spec: single.name.clone(),
+                        target: single.target.clone(),
+                        build_std: single.build_std.clone(),
+                        dependencies: single.deps.clone(),
+                    };
+                    println!("{}", serde_json::to_string_pretty(&output)?);
+                }
+            },
+            [a, b] => {
+                let diff = diff_dependencies(&a.deps, &b.deps);
+                match self.format {
+                    DepsFormat::Table => print_deps_diff(&pkg_name, a, b, &diff),
+                    DepsFormat::Json => {
+                        let output = DepsDiffOutput {
+                            package: pkg_name.clone(),
+                            spec_a: a.name.clone(),
+                            spec_b: b.name.clone(),
+                            target_a: a.target.clone(),
+                            target_b: b.target.clone(),
+                            build_std_a: a.build_std.clone(),
+                            build_std_b: b.build_std.clone(),
+                            diff,
+                        };
+                        println!("{}", serde_json::to_string_pretty(&output)?);
+                    }
+                }
+            }
+            _ => unreachable!(
+                "clap's required = true guarantees at least one, checked above for at most two"
+            ),
+        }
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+
+    fn parse(args: &[&str]) -> DepsCmd {
+        let mut full = vec!["tspec", "deps"];
+        full.extend_from_slice(args);
+        let cli = Cli::try_parse_from(full).unwrap();
+        match cli.command {
+            crate::cli::Commands::Deps(cmd) => cmd,
+            _ => panic!("expected Deps command"),
+        }
+    }
+
+    #[test]
+    fn single_tspec() {
+        let cmd = parse(&["-t", "tspec.ts.toml"]);
+        assert_eq!(cmd.tspec, vec!["tspec.ts.toml".to_string()]);
+    }
+
+    #[test]
+    fn two_tspecs_for_diff() {
+        let cmd = parse(&["-t", "a.ts.toml", "-t", "b.ts.toml"]);
+        assert_eq!(
+            cmd.tspec,
+            vec!["a.ts.toml".to_string(), "b.ts.toml".to_string()]
+        );
+    }
+
+    #[test]
+    fn requires_at_least_one_tspec() {
+        let result = Cli::try_parse_from(["tspec", "deps"]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn format_defaults_to_table() {
+        let cmd = parse(&["-t", "tspec.ts.toml"]);
+        assert_eq!(cmd.format, DepsFormat::Table);
+    }
+
+    #[test]
+    fn more_than_two_tspecs_rejected_at_execute_time() {
+        let cmd = parse(&["-t", "a.ts.toml", "-t", "b.ts.toml", "-t", "c.ts.toml"]);
+        assert_eq!(cmd.tspec.len(), 3);
+    }
+}
@@ -0,0 +1,30 @@
+use anyhow::Result;
+use clap::Args;
+use std::path::Path;
+use std::process::ExitCode;
+
+use super::Execute;
+use crate::refcheck::{check_refs, print_dangling};
+use crate::types::CargoFlags;
+use crate::workspace::WorkspaceInfo;
+
+/// Run workspace-wide health checks (currently: dangling spec references).
+/// Home for future checks as they're added — each one should also get its
+/// own standalone `tspec ts ...` entry point, the way `check-refs` does.
+#[derive(Args)]
+pub struct DoctorCmd;
+
+impl Execute for DoctorCmd {
+    fn execute(&self, project_root: &Path, flags: &CargoFlags) -> Result<ExitCode> {
+        let _ = flags;
+        let workspace = WorkspaceInfo::discover(project_root)?;
+        let dangling = check_refs(&workspace)?;
+        if dangling.is_empty() {
+            println!("check-refs: ok");
+            return Ok(ExitCode::SUCCESS);
+        }
+        println!("check-refs: {} dangling reference(s)", dangling.len());
+        print_dangling(&dangling);
+        Ok(ExitCode::FAILURE)
+    }
+}
@@ -0,0 +1,62 @@
+use anyhow::Result;
+use clap::Args;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use super::Execute;
+use crate::examples::{assert_all_passed, for_command, registry, render_after_help, run_check};
+use crate::types::CargoFlags;
+
+/// (hidden) Print the example registry, or check it stays valid
+///
+/// Backs each subcommand's `--help` examples (see `crate::cli`) and, with
+/// `--run-check`, drives the example registry against real fixtures so the
+/// examples can't drift out of sync with the CLI they document.
+#[derive(Args)]
+pub struct ExamplesCmd {
+    /// Only show examples for this command (e.g. "build")
+    pub command: Option<String>,
+    /// Actually run each example against a copy of its fixture and report
+    /// pass/fail, instead of just printing the registry
+    #[arg(long = "run-check")]
+    pub run_check: bool,
+    /// Directory containing fixture projects (required with --run-check;
+    /// the test suite passes `tests/fixtures`)
+    #[arg(long = "fixtures-dir", requires = "run_check", value_name = "DIR")]
+    pub fixtures_dir: Option<PathBuf>,
+}
+
+impl Execute for ExamplesCmd {
+    fn execute(&self, _project_root: &Path, _flags: &CargoFlags) -> Result<ExitCode> {
+        if self.run_check {
+            let fixtures_dir = self
+                .fixtures_dir
+                .as_deref()
+                .ok_or_else(|| anyhow::anyhow!("--run-check requires --fixtures-dir"))?;
+            let results = run_check(fixtures_dir)?;
+            for result in &results {
+                let status = if result.passed { "ok" } else { "FAILED" };
+                println!("{status}  tspec {}", result.args.join(" "));
+            }
+            assert_all_passed(&results)?;
+            return Ok(ExitCode::SUCCESS);
+        }
+
+        match &self.command {
+            Some(command) => match for_command(command) {
+                Some(examples) => println!("{}", render_after_help(examples)),
+                None => println!("no examples registered for `{command}`"),
+            },
+            None => {
+                for (i, command_examples) in registry().iter().enumerate() {
+                    if i > 0 {
+                        println!();
+                    }
+                    println!("{}:", command_examples.command);
+                    println!("{}", render_after_help(command_examples));
+                }
+            }
+        }
+        Ok(ExitCode::SUCCESS)
+    }
+}
@@ -0,0 +1,180 @@
+use anyhow::{Result, anyhow};
+use clap::{Args, Subcommand};
+use std::path::Path;
+use std::process::ExitCode;
+
+use super::Execute;
+use crate::experiment;
+use crate::find_paths::{find_tspec, resolve_ts_package_dir};
+use crate::types::CargoFlags;
+
+/// Manage temporary spec experiments, selectable elsewhere as `-t @NAME`
+#[derive(Args)]
+pub struct ExperimentCmd {
+    #[command(subcommand)]
+    command: ExperimentCommands,
+}
+
+#[derive(Subcommand)]
+pub enum ExperimentCommands {
+    /// Create a new experiment spec under .tspec/experiments/, selectable
+    /// as `-t @NAME`
+    Start {
+        /// Experiment name
+        name: String,
+        /// Package name (defaults to current directory)
+        #[arg(short = 'p', long = "package")]
+        package: Option<String>,
+        /// Copy from an existing spec instead of starting blank
+        #[arg(short = 'f', long = "from")]
+        from: Option<String>,
+        /// Add .tspec/ to .gitignore without asking if it isn't already there
+        #[arg(long)]
+        yes: bool,
+    },
+    /// List a package's experiments
+    List {
+        /// Package name (defaults to current directory)
+        #[arg(short = 'p', long = "package")]
+        package: Option<String>,
+    },
+    /// Move an experiment into the package as a normal spec
+    Promote {
+        /// Experiment name
+        name: String,
+        /// Package name (defaults to current directory)
+        #[arg(short = 'p', long = "package")]
+        package: Option<String>,
+    },
+    /// Delete an experiment
+    Discard {
+        /// Experiment name
+        name: String,
+        /// Package name (defaults to current directory)
+        #[arg(short = 'p', long = "package")]
+        package: Option<String>,
+    },
+}
+
+impl Execute for ExperimentCmd {
+    fn execute(&self, project_root: &Path, _flags: &CargoFlags) -> Result<ExitCode> {
+        match &self.command {
+            ExperimentCommands::Start {
+                name,
+                package,
+                from,
+                yes,
+            } => {
+                let package_dir = resolve_ts_package_dir(project_root, package.as_deref())?;
+                let from_path = match from.as_deref() {
+                    Some(source) => Some(
+                        find_tspec(&package_dir, Some(source))?
+                            .ok_or_else(|| anyhow!("source tspec not found: {}", source))?,
+                    ),
+                    None => None,
+                };
+
+                let path = experiment::start_experiment(
+                    project_root,
+                    &package_dir,
+                    name,
+                    from_path.as_deref(),
+                    *yes,
+                )?;
+                println!(
+                    "Started experiment {} (select with -t @{})",
+                    path.strip_prefix(project_root).unwrap_or(&path).display(),
+                    name
+                );
+            }
+            ExperimentCommands::List { package } => {
+                let package_dir = resolve_ts_package_dir(project_root, package.as_deref())?;
+                let found = experiment::list_experiments(&package_dir)?;
+                if found.is_empty() {
+                    println!("No experiments");
+                } else {
+                    for path in &found {
+                        println!(
+                            "{}",
+                            path.strip_prefix(project_root).unwrap_or(path).display()
+                        );
+                    }
+                }
+            }
+            ExperimentCommands::Promote { name, package } => {
+                let package_dir = resolve_ts_package_dir(project_root, package.as_deref())?;
+                let dest = experiment::promote_experiment(&package_dir, name)?;
+                println!(
+                    "Promoted @{} to {}",
+                    name,
+                    dest.strip_prefix(project_root).unwrap_or(&dest).display()
+                );
+            }
+            ExperimentCommands::Discard { name, package } => {
+                let package_dir = resolve_ts_package_dir(project_root, package.as_deref())?;
+                let path = experiment::discard_experiment(&package_dir, name)?;
+                println!(
+                    "Discarded {}",
+                    path.strip_prefix(project_root).unwrap_or(&path).display()
+                );
+            }
+        }
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+
+    fn parse(args: &[&str]) -> ExperimentCmd {
+        let mut full = vec!["tspec", "experiment"];
+        full.extend_from_slice(args);
+        let cli = Cli::try_parse_from(full).unwrap();
+        match cli.command {
+            crate::cli::Commands::Experiment(cmd) => cmd,
+            _ => panic!("expected Experiment command"),
+        }
+    }
+
+    #[test]
+    fn start_requires_name() {
+        assert!(Cli::try_parse_from(["tspec", "experiment", "start"]).is_err());
+    }
+
+    #[test]
+    fn start_parses_from_and_yes() {
+        let cmd = parse(&["start", "scratch", "-f", "static", "--yes"]);
+        match cmd.command {
+            ExperimentCommands::Start {
+                name, from, yes, ..
+            } => {
+                assert_eq!(name, "scratch");
+                assert_eq!(from.as_deref(), Some("static"));
+                assert!(yes);
+            }
+            _ => panic!("expected Start"),
+        }
+    }
+
+    #[test]
+    fn list_parses() {
+        let cmd = parse(&["list", "-p", "myapp"]);
+        match cmd.command {
+            ExperimentCommands::List { package } => assert_eq!(package.as_deref(), Some("myapp")),
+            _ => panic!("expected List"),
+        }
+    }
+
+    #[test]
+    fn promote_requires_name() {
+        assert!(Cli::try_parse_from(["tspec", "experiment", "promote"]).is_err());
+    }
+
+    #[test]
+    fn discard_requires_name() {
+        assert!(Cli::try_parse_from(["tspec", "experiment", "discard"]).is_err());
+    }
+}
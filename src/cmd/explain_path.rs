@@ -0,0 +1,205 @@
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use std::path::Path;
+use std::process::ExitCode;
+
+use super::{Execute, current_package_name, resolve_package_arg};
+use crate::cargo_build::{PathExplanation, explain_binary_path};
+use crate::types::{CargoFlags, ProfileSource};
+
+/// Output format for `tspec explain-path`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ExplainFormat {
+    Text,
+    Json,
+}
+
+/// Explain how tspec computes a package's expected binary path.
+///
+/// Walks the exact same steps `build`/`test` take before invoking cargo —
+/// package resolution, spec loading, target-dir expansion, profile
+/// resolution — and reports the final path plus whether it currently
+/// exists on disk. Shares `get_binary_path`/`expand_target_dir` with the
+/// real build path, so the explanation can't drift from what a build
+/// would actually produce.
+#[derive(Args)]
+pub struct ExplainPathCmd {
+    /// Package to explain (name or path, e.g. "." for current dir)
+    #[arg(value_name = "PACKAGE")]
+    pub positional: Option<String>,
+    /// Package to explain (defaults to current directory)
+    #[arg(short = 'p', long = "package")]
+    pub package: Option<String>,
+    /// Spec file to explain with (defaults to package's tspec file)
+    #[arg(short = 't', long = "tspec")]
+    pub tspec: Option<String>,
+    /// Build profile (e.g., release, release-small, or any custom profile)
+    #[arg(long)]
+    pub profile: Option<String>,
+    /// Let --profile win when it conflicts with the spec's cargo.profile
+    #[arg(long = "force-profile")]
+    pub force_profile: bool,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = ExplainFormat::Text)]
+    pub format: ExplainFormat,
+}
+
+impl Execute for ExplainPathCmd {
+    fn execute(&self, project_root: &Path, flags: &CargoFlags) -> Result<ExitCode> {
+        let _ = flags;
+        let pkg = match self.positional.as_deref().or(self.package.as_deref()) {
+            Some(pkg) => pkg.to_string(),
+            None => match current_package_name(project_root) {
+                Some(name) => name,
+                None => {
+                    eprintln!(
+                        "Error: no package specified and cwd does not resolve to a single package"
+                    );
+                    return Ok(ExitCode::from(1));
+                }
+            },
+        };
+        // Resolve the package argument up front so a bare path like "." is
+        // accepted the same way other commands accept it.
+        let pkg_name = resolve_package_arg(&pkg, project_root)?.unwrap_or(pkg);
+
+        let explanation = explain_binary_path(
+            &pkg_name,
+            self.tspec.as_deref(),
+            self.profile.as_deref(),
+            self.force_profile,
+            project_root,
+        )?;
+
+        match self.format {
+            ExplainFormat::Json => {
+                println!("{}", serde_json::to_string_pretty(&explanation)?);
+            }
+            ExplainFormat::Text => print_text(&explanation),
+        }
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+fn print_text(e: &PathExplanation) {
+    println!("project root:    {}", e.project_root.display());
+    println!("package dir:      {}", e.package_dir.display());
+    println!("package name:     {}", e.package_name);
+    match &e.spec_path {
+        Some(p) => println!("spec:             {}", p.display()),
+        None => println!("spec:             (none)"),
+    }
+    if let Some(p) = &e.spec_profile {
+        println!("spec profile:     {}", p);
+    }
+    if let Some(t) = &e.target_triple {
+        println!("target triple:    {}", t);
+    } else if let Some(t) = &e.target_json_stem {
+        println!("target (json):    {}", t);
+    }
+    if let Some(td) = &e.target_dir_template {
+        println!("target_dir:       {} -> {:?}", td, e.expanded_target_dir);
+    }
+    println!("cli profile:      {:?}", e.cli_profile);
+    println!("force_profile:    {}", e.force_profile);
+    let source = match e.profile_source {
+        ProfileSource::Spec => "spec",
+        ProfileSource::Cli => "cli",
+        ProfileSource::Default => "default (debug)",
+    };
+    println!(
+        "resolved profile: {} (from {})",
+        e.resolved_profile.as_deref().unwrap_or("debug"),
+        source
+    );
+    if let Some((spec_profile, ignored_cli)) = &e.profile_conflict {
+        println!(
+            "conflict:         spec profile '{}' won over CLI profile '{}'",
+            spec_profile, ignored_cli
+        );
+    }
+    println!("binary path:      {}", e.binary_path.display());
+    println!("exists:           {}", e.exists);
+    if e.exists {
+        println!("size:             {:?} bytes", e.size);
+        println!("mtime (unix):     {:?}", e.mtime_unix);
+    }
+    match (
+        &e.codegen_units.codegen_units,
+        &e.codegen_units.codegen_units_source,
+    ) {
+        (Some(value), Some(source)) => {
+            println!("codegen-units:    {} (from {})", value, source)
+        }
+        _ => println!("codegen-units:    (not set; cargo default)"),
+    }
+    if let (Some(value), Some(source)) = (&e.codegen_units.lto, &e.codegen_units.lto_source) {
+        println!("lto:              {} (from {})", value, source);
+    }
+    if e.codegen_units.lto_forces_single_unit {
+        println!(
+            "note:             lto={:?} overrides codegen-units to effectively 1",
+            e.codegen_units.lto.as_deref().unwrap_or("")
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+
+    fn parse(args: &[&str]) -> ExplainPathCmd {
+        let mut full = vec!["tspec", "explain-path"];
+        full.extend_from_slice(args);
+        let cli = Cli::try_parse_from(full).unwrap();
+        match cli.command {
+            crate::cli::Commands::ExplainPath(cmd) => cmd,
+            _ => panic!("expected ExplainPath command"),
+        }
+    }
+
+    #[test]
+    fn package_optional() {
+        let cmd = parse(&[]);
+        assert!(cmd.package.is_none());
+    }
+
+    #[test]
+    fn package_explicit() {
+        let cmd = parse(&["-p", "myapp"]);
+        assert_eq!(cmd.package.as_deref(), Some("myapp"));
+    }
+
+    #[test]
+    fn tspec_flag() {
+        let cmd = parse(&["-t", "foo.ts.toml"]);
+        assert_eq!(cmd.tspec.as_deref(), Some("foo.ts.toml"));
+    }
+
+    #[test]
+    fn profile_flag() {
+        let cmd = parse(&["--profile", "release-small"]);
+        assert_eq!(cmd.profile.as_deref(), Some("release-small"));
+    }
+
+    #[test]
+    fn force_profile_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.force_profile);
+    }
+
+    #[test]
+    fn format_defaults_to_text() {
+        let cmd = parse(&[]);
+        assert_eq!(cmd.format, ExplainFormat::Text);
+    }
+
+    #[test]
+    fn format_json() {
+        let cmd = parse(&["--format", "json"]);
+        assert_eq!(cmd.format, ExplainFormat::Json);
+    }
+}
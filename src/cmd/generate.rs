@@ -0,0 +1,222 @@
+//! `tspec generate` - scaffolding generators for downstream workspaces
+//! adopting tspec.
+
+use anyhow::{Context, Result};
+use clap::{Args, Subcommand};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use super::Execute;
+use crate::types::CargoFlags;
+
+/// Generate scaffolding for a workspace adopting tspec.
+#[derive(Args)]
+pub struct GenerateCmd {
+    #[command(subcommand)]
+    command: GenerateCommands,
+}
+
+#[derive(Subcommand)]
+pub enum GenerateCommands {
+    /// Emit a Rust integration test file that smoke-tests this workspace
+    /// through the `tspec` binary on PATH (build -w, test -w, ts list).
+    CiTests {
+        /// Where to write the generated test file
+        #[arg(long = "out", default_value = "tests/tspec_ci.rs")]
+        out: PathBuf,
+        /// Regenerate an existing file, preserving its config block
+        #[arg(long)]
+        update: bool,
+    },
+}
+
+impl Execute for GenerateCmd {
+    fn execute(&self, project_root: &Path, _flags: &CargoFlags) -> Result<ExitCode> {
+        match &self.command {
+            GenerateCommands::CiTests { out, update } => {
+                let out_path = project_root.join(out);
+                generate_ci_tests(&out_path, *update)?;
+                println!(
+                    "Generated {}",
+                    out_path
+                        .strip_prefix(project_root)
+                        .unwrap_or(&out_path)
+                        .display()
+                );
+                Ok(ExitCode::SUCCESS)
+            }
+        }
+    }
+}
+
+const CONFIG_START: &str = "// <tspec:ci-tests:config>";
+const CONFIG_END: &str = "// </tspec:ci-tests:config>";
+
+/// The default, user-editable config block: which smoke ops to run and
+/// which env var names the `tspec` binary to invoke. Preserved verbatim by
+/// `--update` if the file already has one.
+fn default_config() -> &'static str {
+    "/// Env var naming the tspec binary to run (falls back to \"tspec\" on PATH).\n\
+     const TSPEC_BIN_ENV: &str = \"TSPEC_BIN\";\n\
+     /// Smoke operations to run, in order: \"build\", \"test\", \"ts-list\".\n\
+     const SMOKE_OPS: &[&str] = &[\"build\", \"test\", \"ts-list\"];"
+}
+
+/// Pull the text between the config markers out of a previously generated
+/// file, so `--update` can regenerate the harness below while leaving a
+/// user's edited op list/env var name alone.
+fn extract_config(content: &str) -> Option<String> {
+    let start = content.find(CONFIG_START)? + CONFIG_START.len();
+    let end = content[start..].find(CONFIG_END)? + start;
+    Some(content[start..end].trim_matches('\n').to_string())
+}
+
+fn render(config: &str) -> String {
+    format!(
+        "//! Generated by `tspec generate ci-tests`. Safe to regenerate with\n\
+         //! `tspec generate ci-tests --update`; edits between the config markers\n\
+         //! below are preserved across regeneration.\n\
+         \n\
+         use std::env;\n\
+         use std::process::{{Command, Output}};\n\
+         \n\
+         {CONFIG_START}\n\
+         {config}\n\
+         {CONFIG_END}\n\
+         \n\
+         fn tspec_bin() -> String {{\n\
+         \x20   env::var(TSPEC_BIN_ENV).unwrap_or_else(|_| \"tspec\".to_string())\n\
+         }}\n\
+         \n\
+         fn run_op(op: &str) -> Output {{\n\
+         \x20   let args: &[&str] = match op {{\n\
+         \x20       \"build\" => &[\"build\", \"-w\"],\n\
+         \x20       \"test\" => &[\"test\", \"-w\"],\n\
+         \x20       \"ts-list\" => &[\"ts\", \"list\", \"--all\"],\n\
+         \x20       other => panic!(\"unknown smoke op: {{other}}\"),\n\
+         \x20   }};\n\
+         \x20   Command::new(tspec_bin())\n\
+         \x20       .args(args)\n\
+         \x20       .output()\n\
+         \x20       .unwrap_or_else(|e| panic!(\"failed to run `tspec {{}}`: {{e}}\", args.join(\" \")))\n\
+         }}\n\
+         \n\
+         #[test]\n\
+         fn tspec_smoke_ops_succeed() {{\n\
+         \x20   for op in SMOKE_OPS {{\n\
+         \x20       let output = run_op(op);\n\
+         \x20       let stdout = String::from_utf8_lossy(&output.stdout);\n\
+         \x20       assert!(\n\
+         \x20           output.status.success(),\n\
+         \x20           \"`tspec {{op}}` failed:\\nstdout:\\n{{stdout}}\\nstderr:\\n{{}}\",\n\
+         \x20           String::from_utf8_lossy(&output.stderr)\n\
+         \x20       );\n\
+         \x20       if *op == \"build\" || *op == \"test\" {{\n\
+         \x20           assert!(\n\
+         \x20               stdout.contains(\"SUMMARY\"),\n\
+         \x20               \"`tspec {{op}}` output missing a summary:\\n{{stdout}}\"\n\
+         \x20           );\n\
+         \x20           assert!(\n\
+         \x20               !stdout.contains(\"[FAIL]\"),\n\
+         \x20               \"`tspec {{op}}` reported a failing row:\\n{{stdout}}\"\n\
+         \x20           );\n\
+         \x20       }}\n\
+         \x20   }}\n\
+         }}\n"
+    )
+}
+
+/// Write the generated harness to `out`. When `update` is set and `out`
+/// already exists, its config block is carried forward instead of being
+/// reset to the default.
+pub fn generate_ci_tests(out: &Path, update: bool) -> Result<()> {
+    let config = if update && out.exists() {
+        let existing = std::fs::read_to_string(out)
+            .with_context(|| format!("failed to read: {}", out.display()))?;
+        extract_config(&existing).unwrap_or_else(|| default_config().to_string())
+    } else {
+        default_config().to_string()
+    };
+
+    let content = render(&config);
+    if let Some(parent) = out.parent() {
+        std::fs::create_dir_all(parent)
+            .with_context(|| format!("failed to create: {}", parent.display()))?;
+    }
+    std::fs::write(out, content).with_context(|| format!("failed to write: {}", out.display()))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::process::Command;
+    use tempfile::TempDir;
+
+    #[test]
+    fn fresh_generate_contains_default_config_and_markers() {
+        let tmp = TempDir::new().unwrap();
+        let out = tmp.path().join("tests/tspec_ci.rs");
+        generate_ci_tests(&out, false).unwrap();
+        let content = std::fs::read_to_string(&out).unwrap();
+        assert!(content.contains(CONFIG_START));
+        assert!(content.contains(CONFIG_END));
+        assert!(content.contains("TSPEC_BIN_ENV"));
+        assert!(content.contains("fn tspec_smoke_ops_succeed"));
+    }
+
+    #[test]
+    fn update_preserves_edited_config_block() {
+        let tmp = TempDir::new().unwrap();
+        let out = tmp.path().join("tspec_ci.rs");
+        generate_ci_tests(&out, false).unwrap();
+
+        let mut content = std::fs::read_to_string(&out).unwrap();
+        let custom =
+            "const TSPEC_BIN_ENV: &str = \"MY_TSPEC\";\nconst SMOKE_OPS: &[&str] = &[\"build\"];";
+        let start = content.find(CONFIG_START).unwrap() + CONFIG_START.len();
+        let end = content.find(CONFIG_END).unwrap();
+        content.replace_range(start..end, &format!("\n{custom}\n"));
+        std::fs::write(&out, &content).unwrap();
+
+        generate_ci_tests(&out, true).unwrap();
+        let regenerated = std::fs::read_to_string(&out).unwrap();
+        assert!(regenerated.contains("MY_TSPEC"));
+        assert!(regenerated.contains("&[\"build\"]"));
+    }
+
+    #[test]
+    fn update_without_existing_file_falls_back_to_default() {
+        let tmp = TempDir::new().unwrap();
+        let out = tmp.path().join("tspec_ci.rs");
+        generate_ci_tests(&out, true).unwrap();
+        let content = std::fs::read_to_string(&out).unwrap();
+        assert!(content.contains("TSPEC_BIN_ENV"));
+    }
+
+    /// Golden-file test: the emitted harness must compile standalone as a
+    /// Rust test binary, with no dependency beyond std.
+    #[test]
+    fn generated_file_compiles_standalone() {
+        let tmp = TempDir::new().unwrap();
+        let src = tmp.path().join("tspec_ci.rs");
+        generate_ci_tests(&src, false).unwrap();
+
+        let exe = tmp.path().join("tspec_ci_test");
+        let output = Command::new("rustc")
+            .args(["--edition", "2024", "--test", "-o"])
+            .arg(&exe)
+            .arg(&src)
+            .output();
+
+        let Ok(output) = output else {
+            // No rustc on PATH in this environment - nothing to verify.
+            return;
+        };
+        assert!(
+            output.status.success(),
+            "generated ci-tests file failed to compile:\n{}",
+            String::from_utf8_lossy(&output.stderr)
+        );
+    }
+}
@@ -4,6 +4,7 @@ use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
 use super::Execute;
+use crate::cargo_build::cargo_program;
 use crate::find_paths::get_package_name;
 use crate::types::CargoFlags;
 
@@ -25,7 +26,7 @@ impl Execute for InstallCmd {
             .canonicalize()
             .with_context(|| format!("path not found: {}", self.path.display()))?;
 
-        let mut cmd = std::process::Command::new("cargo");
+        let mut cmd = std::process::Command::new(cargo_program());
         cmd.arg("install").arg("--path").arg(&resolved);
 
         // Pass package name if we can determine it (needed for workspaces)
@@ -0,0 +1,236 @@
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use serde::Serialize;
+use std::path::Path;
+use std::process::ExitCode;
+
+use super::Execute;
+use crate::find_paths::find_tspec;
+use crate::types::CargoFlags;
+use crate::workspace::{PackageKind, PackageMember, WorkspaceInfo};
+
+/// Output format for `tspec list`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ListFormat {
+    Table,
+    Json,
+}
+
+/// `--kind` filter values, mirroring [`PackageKind`] but lowercase for the CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum KindFilter {
+    App,
+    Lib,
+    Tool,
+    Test,
+    BuildTool,
+}
+
+impl KindFilter {
+    fn matches(self, kind: PackageKind) -> bool {
+        matches!(
+            (self, kind),
+            (KindFilter::App, PackageKind::App)
+                | (KindFilter::Lib, PackageKind::Lib)
+                | (KindFilter::Tool, PackageKind::Tool)
+                | (KindFilter::Test, PackageKind::Test)
+                | (KindFilter::BuildTool, PackageKind::BuildTool)
+        )
+    }
+}
+
+/// List the workspace members `-w` would operate on, with kind and spec annotations.
+#[derive(Args)]
+pub struct ListCmd {
+    /// Only show members with a binary target
+    #[arg(long)]
+    pub runnable: bool,
+    /// Only show members of this kind
+    #[arg(long, value_enum)]
+    pub kind: Option<KindFilter>,
+    /// Output format
+    #[arg(long, value_enum, default_value_t = ListFormat::Table)]
+    pub format: ListFormat,
+}
+
+/// One row of `tspec list`: a workspace member plus its default-spec probe.
+#[derive(Debug, Clone, Serialize)]
+struct ListRow {
+    name: String,
+    version: String,
+    kind: String,
+    has_binary: bool,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    default_spec: Option<String>,
+    path: String,
+}
+
+fn default_spec_name(member: &PackageMember) -> Option<String> {
+    find_tspec(&member.path, None)
+        .ok()
+        .flatten()
+        .and_then(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+}
+
+fn relative_path(root: &Path, member: &PackageMember) -> String {
+    member
+        .path
+        .strip_prefix(root)
+        .unwrap_or(&member.path)
+        .display()
+        .to_string()
+}
+
+fn collect_rows(
+    workspace: &WorkspaceInfo,
+    runnable: bool,
+    kind: Option<KindFilter>,
+) -> Vec<ListRow> {
+    workspace
+        .members
+        .iter()
+        .filter(|m| !runnable || m.has_binary)
+        .filter(|m| kind.is_none_or(|k| k.matches(m.kind)))
+        .map(|m| ListRow {
+            name: m.name.clone(),
+            version: m.version.clone(),
+            kind: m.kind.to_string(),
+            has_binary: m.has_binary,
+            default_spec: default_spec_name(m),
+            path: relative_path(&workspace.root, m),
+        })
+        .collect()
+}
+
+fn opt(s: &Option<String>) -> &str {
+    s.as_deref().unwrap_or("-")
+}
+
+fn print_table(rows: &[ListRow]) {
+    println!(
+        "{:<16} {:<10} {:<10} {:<10} {:<20} PATH",
+        "NAME", "VERSION", "KIND", "HAS_BIN", "DEFAULT_SPEC"
+    );
+    for row in rows {
+        println!(
+            "{:<16} {:<10} {:<10} {:<10} {:<20} {}",
+            row.name,
+            row.version,
+            row.kind,
+            row.has_binary,
+            opt(&row.default_spec),
+            row.path,
+        );
+    }
+}
+
+impl Execute for ListCmd {
+    fn execute(&self, project_root: &Path, flags: &CargoFlags) -> Result<ExitCode> {
+        let _ = flags;
+        let workspace = WorkspaceInfo::discover(project_root)?;
+        let rows = collect_rows(&workspace, self.runnable, self.kind);
+        match self.format {
+            ListFormat::Table => print_table(&rows),
+            ListFormat::Json => println!("{}", serde_json::to_string_pretty(&rows)?),
+        }
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+    use std::path::PathBuf;
+
+    fn parse(args: &[&str]) -> ListCmd {
+        let mut full = vec!["tspec", "list"];
+        full.extend_from_slice(args);
+        let cli = Cli::try_parse_from(full).unwrap();
+        match cli.command {
+            crate::cli::Commands::List(cmd) => cmd,
+            _ => panic!("expected List command"),
+        }
+    }
+
+    fn member(name: &str, kind: PackageKind, has_binary: bool) -> PackageMember {
+        PackageMember {
+            name: name.to_string(),
+            version: "0.1.0".to_string(),
+            path: PathBuf::from(format!("/tmp/ws/{name}")),
+            has_binary,
+            kind,
+        }
+    }
+
+    fn workspace() -> WorkspaceInfo {
+        WorkspaceInfo {
+            root: PathBuf::from("/tmp/ws"),
+            members: vec![
+                member("app-a", PackageKind::App, true),
+                member("lib-b", PackageKind::Lib, false),
+                member("multi-c", PackageKind::Tool, true),
+            ],
+            version: None,
+            default_members: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn runnable_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.runnable);
+    }
+
+    #[test]
+    fn runnable_flag() {
+        let cmd = parse(&["--runnable"]);
+        assert!(cmd.runnable);
+    }
+
+    #[test]
+    fn kind_filter_flag() {
+        let cmd = parse(&["--kind", "app"]);
+        assert_eq!(cmd.kind, Some(KindFilter::App));
+    }
+
+    #[test]
+    fn format_defaults_to_table() {
+        let cmd = parse(&[]);
+        assert_eq!(cmd.format, ListFormat::Table);
+    }
+
+    #[test]
+    fn collect_rows_includes_all_members_by_default() {
+        let rows = collect_rows(&workspace(), false, None);
+        assert_eq!(rows.len(), 3);
+    }
+
+    #[test]
+    fn collect_rows_runnable_filters_to_has_binary() {
+        let rows = collect_rows(&workspace(), true, None);
+        assert_eq!(rows.len(), 2);
+        assert!(rows.iter().all(|r| r.has_binary));
+    }
+
+    #[test]
+    fn collect_rows_kind_filters_to_matching_kind() {
+        let rows = collect_rows(&workspace(), false, Some(KindFilter::Lib));
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].name, "lib-b");
+    }
+
+    #[test]
+    fn collect_rows_reports_relative_path() {
+        let rows = collect_rows(&workspace(), false, Some(KindFilter::App));
+        assert_eq!(rows[0].path, "app-a");
+    }
+
+    #[test]
+    fn json_row_omits_missing_default_spec() {
+        let row = &collect_rows(&workspace(), false, Some(KindFilter::Lib))[0];
+        let json = serde_json::to_string(row).unwrap();
+        assert!(!json.contains("\"default_spec\""));
+    }
+}
@@ -1,23 +1,57 @@
+mod baselines;
+mod bench;
 mod build;
+mod ci;
 mod clean;
 mod clippy;
 mod compare;
+mod completions;
+mod deps;
+mod doctor;
+mod examples;
+mod experiment;
+mod explain_path;
 mod fmt;
+mod generate;
 mod install;
+mod list;
+mod print;
+mod report;
+mod repro;
 mod run;
+mod schema;
+mod targets;
 mod test;
 mod ts;
+mod usage;
 mod version;
 
+pub use baselines::{BaselinesCmd, BaselinesCommands};
+pub use bench::BenchCmd;
 pub use build::BuildCmd;
+pub use ci::{CiCmd, CiStage};
 pub use clean::CleanCmd;
 pub use clippy::ClippyCmd;
 pub use compare::CompareCmd;
+pub use completions::{CompleteCandidatesCmd, CompletionsCmd};
+pub use deps::{DepsCmd, DepsFormat};
+pub use doctor::DoctorCmd;
+pub use examples::ExamplesCmd;
+pub use experiment::ExperimentCmd;
+pub use explain_path::{ExplainFormat, ExplainPathCmd};
 pub use fmt::FmtCmd;
+pub use generate::{GenerateCmd, GenerateCommands};
 pub use install::InstallCmd;
+pub use list::{KindFilter, ListCmd, ListFormat};
+pub use print::{PrintCmd, PrintWhat};
+pub use report::{ReportCmd, ReportCommands, ReportFormat};
+pub use repro::ReproCmd;
 pub use run::RunCmd;
+pub use schema::{SchemaCmd, SchemaFormat};
+pub use targets::TargetsCmd;
 pub use test::{TestCmd, TestResult, parse_test_results};
 pub use ts::TsCmd;
+pub use usage::UsageCmd;
 pub use version::VersionCmd;
 
 use anyhow::{Context, Result, bail};
@@ -25,9 +59,12 @@ use std::ffi::OsString;
 use std::path::Path;
 use std::process::ExitCode;
 
-use crate::find_paths::{find_package_dir, get_package_name, is_pop};
+use crate::cargo_build::cargo_program;
+use crate::find_paths::PackageSelector;
 use crate::types::CargoFlags;
 
+pub use crate::find_paths::current_package_name;
+
 /// Trait for command execution.
 pub trait Execute {
     fn execute(&self, project_root: &Path, flags: &CargoFlags) -> Result<ExitCode>;
@@ -35,12 +72,13 @@ pub trait Execute {
 
 /// Resolve a `-p` argument (path or name) to the actual cargo package name.
 /// Returns Some(name) for a package, None if it resolves to a workspace root
-/// with no `[package]` section (meaning "operate on all packages").
+/// with no `[package]` section (meaning "operate on all packages"). Thin
+/// wrapper over `find_paths::resolve_package_selector`, the shared resolver
+/// every command's explicit package argument goes through.
 pub(crate) fn resolve_package_arg(pkg: &str, project_root: &Path) -> Result<Option<String>> {
-    let pkg_dir = find_package_dir(project_root, pkg)?;
-    match get_package_name(&pkg_dir) {
-        Ok(name) => Ok(Some(name)),
-        Err(_) => Ok(None),
+    match crate::find_paths::resolve_package_selector(project_root, pkg)? {
+        PackageSelector::Single { name, .. } => Ok(Some(name)),
+        PackageSelector::All => Ok(None),
     }
 }
 
@@ -51,7 +89,7 @@ pub fn execute_cargo_subcommand(
     project_root: &Path,
     flags: &CargoFlags,
 ) -> Result<ExitCode> {
-    let mut cmd = std::process::Command::new("cargo");
+    let mut cmd = std::process::Command::new(cargo_program());
     cmd.arg(subcommand);
     cmd.args(args);
     flags.apply_to_command(&mut cmd);
@@ -65,19 +103,3 @@ pub fn execute_cargo_subcommand(
         bail!("cargo {} failed", subcommand);
     }
 }
-
-/// Check if current directory is a package (has Cargo.toml with [package]).
-/// Returns None at a workspace root so that commands default to all-packages mode.
-/// Returns None if cwd is outside project_root (e.g., --manifest-path used).
-pub(crate) fn current_package_name(project_root: &Path) -> Option<String> {
-    let cwd = std::env::current_dir().ok()?;
-    // If cwd is outside project_root, fall back to all-packages mode
-    if !cwd.starts_with(project_root) {
-        return None;
-    }
-    // At a workspace root, don't treat it as a single package
-    if cwd.join("Cargo.toml").exists() && !is_pop(&cwd) {
-        return None;
-    }
-    get_package_name(&cwd).ok()
-}
@@ -0,0 +1,180 @@
+use anyhow::{Context, Result, bail};
+use clap::{Args, ValueEnum};
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, ExitCode};
+
+use super::{Execute, current_package_name, resolve_package_arg};
+use crate::cargo_build::{
+    resolve_cfg_args, resolve_env_overrides, resolve_link_args, resolve_target_json_path,
+};
+use crate::tspec::{expand_target_dir, resolve_spec, spec_name_from_path};
+use crate::types::CargoFlags;
+
+/// What to print about a spec's resolved build inputs.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum PrintWhat {
+    /// `rustc --print cfg` output under the spec's target and flags
+    Cfg,
+    /// The resolved custom target spec JSON file's contents
+    TargetSpecJson,
+    /// The final ordered linker argument list a build would inject
+    LinkArgs,
+    /// The environment variables a build would set
+    Env,
+}
+
+/// Print one facet of the exact rustc/cargo environment a spec's build would
+/// produce, without building.
+///
+/// Reuses the same plan functions the real build applies
+/// (`resolve_cfg_args`, `resolve_link_args`, `resolve_env_overrides`) so the
+/// preview can't drift from what `tspec build` would actually do — see
+/// `explain-path` for the equivalent idea applied to the binary path.
+#[derive(Args)]
+pub struct PrintCmd {
+    /// What to print
+    #[arg(value_enum)]
+    pub what: PrintWhat,
+    /// Package to print for (defaults to current directory)
+    #[arg(short = 'p', long = "package")]
+    pub package: Option<String>,
+    /// Spec file to print for (defaults to package's tspec file)
+    #[arg(short = 't', long = "tspec")]
+    pub tspec: Option<String>,
+}
+
+impl Execute for PrintCmd {
+    fn execute(&self, project_root: &Path, flags: &CargoFlags) -> Result<ExitCode> {
+        let _ = flags;
+        let pkg_name = match &self.package {
+            Some(pkg) => resolve_package_arg(pkg, project_root)?.unwrap_or_else(|| pkg.clone()),
+            None => match current_package_name(project_root) {
+                Some(name) => name,
+                None => {
+                    eprintln!(
+                        "Error: no package specified and cwd does not resolve to a single package"
+                    );
+                    return Ok(ExitCode::from(1));
+                }
+            },
+        };
+
+        let (spec_path, maybe_spec) =
+            resolve_spec(Some(&pkg_name), self.tspec.as_deref(), project_root)?;
+        let spec = maybe_spec.clone().unwrap_or_default();
+        let spec_name = spec_name_from_path(&spec_path);
+        // No --profile flag on `print`, so {profile}/{triple} resolve using
+        // only the spec's own settings (no CLI override to fold in).
+        let expanded_target_dir = expand_target_dir(&spec, &spec_name, None, false)?;
+
+        match self.what {
+            PrintWhat::Cfg => {
+                let args = resolve_cfg_args(&spec, project_root);
+                let output = Command::new("rustc")
+                    .args(&args)
+                    .output()
+                    .context("failed to run rustc --print cfg")?;
+                std::io::stdout().write_all(&output.stdout)?;
+                std::io::stderr().write_all(&output.stderr)?;
+                if !output.status.success() {
+                    bail!("rustc --print cfg failed");
+                }
+            }
+            PrintWhat::TargetSpecJson => match resolve_target_json_path(&spec, project_root) {
+                Some(path) => {
+                    let content = std::fs::read_to_string(&path).with_context(|| {
+                        format!("failed to read target spec json at {}", path.display())
+                    })?;
+                    print!("{content}");
+                }
+                None => {
+                    eprintln!("Error: spec has no cargo.target_json set");
+                    return Ok(ExitCode::from(1));
+                }
+            },
+            PrintWhat::LinkArgs => {
+                let args = resolve_link_args(&spec, project_root, expanded_target_dir.as_deref())?;
+                for arg in &args {
+                    println!("{arg}");
+                }
+            }
+            PrintWhat::Env => {
+                let overrides = match &maybe_spec {
+                    Some(spec) => resolve_env_overrides(spec, &spec_path),
+                    None => Vec::new(),
+                };
+                for (key, value) in &overrides {
+                    println!("{key}={value}");
+                }
+            }
+        }
+
+        Ok(ExitCode::SUCCESS)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+
+    fn parse(args: &[&str]) -> PrintCmd {
+        let mut full = vec!["tspec", "print"];
+        full.extend_from_slice(args);
+        let cli = Cli::try_parse_from(full).unwrap();
+        match cli.command {
+            crate::cli::Commands::Print(cmd) => cmd,
+            _ => panic!("expected Print command"),
+        }
+    }
+
+    #[test]
+    fn what_cfg() {
+        let cmd = parse(&["cfg"]);
+        assert_eq!(cmd.what, PrintWhat::Cfg);
+    }
+
+    #[test]
+    fn what_target_spec_json() {
+        let cmd = parse(&["target-spec-json"]);
+        assert_eq!(cmd.what, PrintWhat::TargetSpecJson);
+    }
+
+    #[test]
+    fn what_link_args() {
+        let cmd = parse(&["link-args"]);
+        assert_eq!(cmd.what, PrintWhat::LinkArgs);
+    }
+
+    #[test]
+    fn what_env() {
+        let cmd = parse(&["env"]);
+        assert_eq!(cmd.what, PrintWhat::Env);
+    }
+
+    #[test]
+    fn package_optional() {
+        let cmd = parse(&["cfg"]);
+        assert!(cmd.package.is_none());
+    }
+
+    #[test]
+    fn package_explicit() {
+        let cmd = parse(&["cfg", "-p", "myapp"]);
+        assert_eq!(cmd.package.as_deref(), Some("myapp"));
+    }
+
+    #[test]
+    fn tspec_flag() {
+        let cmd = parse(&["cfg", "-t", "foo.ts.toml"]);
+        assert_eq!(cmd.tspec.as_deref(), Some("foo.ts.toml"));
+    }
+
+    #[test]
+    fn missing_what_is_error() {
+        let result = Cli::try_parse_from(["tspec", "print"]);
+        assert!(result.is_err());
+    }
+}
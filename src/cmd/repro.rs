@@ -0,0 +1,144 @@
+use anyhow::Result;
+use clap::Args;
+use std::path::Path;
+use std::process::ExitCode;
+
+use super::{Execute, current_package_name, resolve_package_arg};
+use crate::repro::{ReproReport, check_reproducibility};
+use crate::types::CargoFlags;
+use crate::workspace::WorkspaceInfo;
+
+/// Build a package twice and diff the resulting binaries to check that the
+/// build is reproducible
+#[derive(Args)]
+pub struct ReproCmd {
+    /// Package to check (name or path, e.g. "." for current dir)
+    #[arg(value_name = "PACKAGE")]
+    pub positional: Option<String>,
+    /// Package to check (defaults to current directory)
+    #[arg(short = 'p', long = "package")]
+    pub package: Option<String>,
+    /// Check every runnable workspace member instead of a single package
+    #[arg(short = 'w', long = "workspace")]
+    pub workspace: bool,
+    /// Spec file to build with (defaults to the package's tspec file)
+    #[arg(short = 't', long = "tspec")]
+    pub tspec: Option<String>,
+}
+
+fn print_report(pkg_name: &str, report: &ReproReport) {
+    if report.identical {
+        println!("{pkg_name}: PASS (identical, {} bytes)", report.size_a);
+        return;
+    }
+    println!("{pkg_name}: FAIL");
+    if report.size_a != report.size_b {
+        println!(
+            "  size differs: {} vs {} bytes",
+            report.size_a, report.size_b
+        );
+    }
+    for diff in &report.diffs {
+        println!(
+            "  differing section: {} (offset {}, size {})",
+            diff.name, diff.offset, diff.size
+        );
+    }
+    if report.diffs.is_empty() && report.size_a == report.size_b {
+        println!("  binaries differ but no ELF section could be attributed");
+    }
+    for culprit in &report.culprits {
+        println!("  possible culprit: {culprit}");
+    }
+}
+
+impl Execute for ReproCmd {
+    fn execute(&self, project_root: &Path, flags: &CargoFlags) -> Result<ExitCode> {
+        let resolved = if self.workspace {
+            None
+        } else {
+            match self.positional.as_deref().or(self.package.as_deref()) {
+                Some(pkg) => resolve_package_arg(pkg, project_root)?,
+                None => current_package_name(project_root),
+            }
+        };
+
+        match resolved {
+            None => {
+                let workspace = WorkspaceInfo::discover(project_root)?;
+                let members = workspace.runnable_members();
+                if members.is_empty() {
+                    println!("no runnable workspace members to check");
+                    return Ok(ExitCode::SUCCESS);
+                }
+                let mut all_pass = true;
+                for member in members {
+                    let report = check_reproducibility(
+                        project_root,
+                        &member.name,
+                        self.tspec.as_deref(),
+                        flags,
+                    )?;
+                    all_pass &= report.identical;
+                    print_report(&member.name, &report);
+                }
+                Ok(if all_pass {
+                    ExitCode::SUCCESS
+                } else {
+                    ExitCode::from(1)
+                })
+            }
+            Some(name) => {
+                let report =
+                    check_reproducibility(project_root, &name, self.tspec.as_deref(), flags)?;
+                print_report(&name, &report);
+                Ok(if report.identical {
+                    ExitCode::SUCCESS
+                } else {
+                    ExitCode::from(1)
+                })
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+
+    fn parse(args: &[&str]) -> ReproCmd {
+        let mut full = vec!["tspec", "repro"];
+        full.extend_from_slice(args);
+        let cli = Cli::try_parse_from(full).unwrap();
+        match cli.command {
+            crate::cli::Commands::Repro(cmd) => cmd,
+            _ => panic!("expected Repro command"),
+        }
+    }
+
+    #[test]
+    fn package_optional() {
+        let cmd = parse(&[]);
+        assert!(cmd.package.is_none());
+    }
+
+    #[test]
+    fn workspace_flag() {
+        let cmd = parse(&["-w"]);
+        assert!(cmd.workspace);
+    }
+
+    #[test]
+    fn tspec_flag() {
+        let cmd = parse(&["-t", "tspec.min.ts.toml"]);
+        assert_eq!(cmd.tspec.as_deref(), Some("tspec.min.ts.toml"));
+    }
+
+    #[test]
+    fn package_and_positional() {
+        let cmd = parse(&["-p", "myapp"]);
+        assert_eq!(cmd.package.as_deref(), Some("myapp"));
+    }
+}
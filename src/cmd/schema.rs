@@ -0,0 +1,42 @@
+use anyhow::Result;
+use clap::{Args, ValueEnum};
+use std::path::Path;
+use std::process::ExitCode;
+
+use super::Execute;
+use crate::schema::{build_schema, render_toml_doc};
+use crate::types::CargoFlags;
+
+#[derive(Clone, Copy, Debug, Default, ValueEnum)]
+#[value(rename_all = "kebab-case")]
+pub enum SchemaFormat {
+    /// A JSON Schema document, for editor tooling like taplo.
+    #[default]
+    JsonSchema,
+    /// A flat, human-readable field listing.
+    TomlDoc,
+}
+
+/// Emit a schema for `*.ts.toml` spec files
+///
+/// Independent of any package — for editor tooling (e.g. a taplo
+/// `[[schema]]` entry) that validates/completes translation specs without
+/// running tspec itself. Always matches the code: `--format json-schema`'s
+/// output is generated from the same field descriptions this crate ships.
+#[derive(Args)]
+pub struct SchemaCmd {
+    /// Output format
+    #[arg(long, value_enum, default_value_t = SchemaFormat::JsonSchema)]
+    pub format: SchemaFormat,
+}
+
+impl Execute for SchemaCmd {
+    fn execute(&self, _project_root: &Path, _flags: &CargoFlags) -> Result<ExitCode> {
+        let schema = build_schema();
+        match self.format {
+            SchemaFormat::JsonSchema => println!("{}", serde_json::to_string_pretty(&schema)?),
+            SchemaFormat::TomlDoc => println!("{}", render_toml_doc(&schema)),
+        }
+        Ok(ExitCode::SUCCESS)
+    }
+}
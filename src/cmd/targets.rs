@@ -0,0 +1,36 @@
+use anyhow::Result;
+use clap::Args;
+use std::path::Path;
+use std::process::ExitCode;
+
+use super::Execute;
+use crate::target_check::{installed_targets, known_targets, render_targets};
+use crate::types::CargoFlags;
+
+/// List target triples, for picking a `cargo.target_triple` value
+///
+/// Independent of any package — useful while authoring a spec, before a
+/// `cargo.target_triple` value even exists to check.
+#[derive(Args)]
+pub struct TargetsCmd {
+    /// Also list every target rustc knows about, not just installed ones
+    #[arg(long)]
+    pub all: bool,
+}
+
+impl Execute for TargetsCmd {
+    fn execute(&self, _project_root: &Path, _flags: &CargoFlags) -> Result<ExitCode> {
+        let Some(installed) = installed_targets() else {
+            anyhow::bail!("could not list installed targets (is rustup on PATH?)");
+        };
+        if self.all {
+            let Some(known) = known_targets() else {
+                anyhow::bail!("could not list known targets (is rustc on PATH?)");
+            };
+            println!("{}", render_targets(&installed, Some(&known)));
+        } else {
+            println!("{}", render_targets(&installed, None));
+        }
+        Ok(ExitCode::SUCCESS)
+    }
+}
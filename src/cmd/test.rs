@@ -7,6 +7,7 @@ use super::{Execute, current_package_name, resolve_package_arg};
 use crate::all::{print_test_summary, test_all};
 use crate::cargo_build::test_package;
 use crate::find_paths::{find_tspecs, get_package_name, resolve_package_dir};
+use crate::tee::capture_bounded;
 use crate::types::CargoFlags;
 use crate::workspace::WorkspaceInfo;
 
@@ -17,6 +18,11 @@ pub struct TestResult {
     pub failed: u32,
     pub ignored: u32,
     pub filtered: u32,
+    /// Passed tests from the `Doc-tests` section, counted separately so a
+    /// package with only doctests (no unit/integration tests) isn't mistaken
+    /// for one where nothing ran.
+    pub doc_passed: u32,
+    pub doc_failed: u32,
 }
 
 impl TestResult {
@@ -26,11 +32,13 @@ impl TestResult {
         self.failed += other.failed;
         self.ignored += other.ignored;
         self.filtered += other.filtered;
+        self.doc_passed += other.doc_passed;
+        self.doc_failed += other.doc_failed;
     }
 
-    /// Total tests that actually ran (passed + failed).
+    /// Total tests that actually ran, including doctests.
     pub fn total_ran(&self) -> u32 {
-        self.passed + self.failed
+        self.passed + self.failed + self.doc_passed + self.doc_failed
     }
 }
 
@@ -65,11 +73,29 @@ pub fn parse_test_result_line(line: &str) -> Option<TestResult> {
     Some(result)
 }
 
-/// Parse and aggregate raw `test result:` lines into a single TestResult.
+/// Parse and aggregate raw `test result:`/`Doc-tests ` lines into a single TestResult.
+///
+/// Lines are expected in cargo's original output order. Cargo always runs
+/// doctests last, in their own `Doc-tests <name>` section; once such a header
+/// is seen, subsequent `test result:` lines are counted into `doc_passed`/
+/// `doc_failed` instead of `passed`/`failed`.
 pub fn parse_test_results(lines: &[String]) -> TestResult {
     let mut aggregated = TestResult::default();
+    let mut in_doctests = false;
     for line in lines {
-        if let Some(parsed) = parse_test_result_line(line) {
+        if line.trim_start().starts_with("Doc-tests ") {
+            in_doctests = true;
+            continue;
+        }
+        let Some(parsed) = parse_test_result_line(line) else {
+            continue;
+        };
+        if in_doctests {
+            aggregated.doc_passed += parsed.passed;
+            aggregated.doc_failed += parsed.failed;
+            aggregated.ignored += parsed.ignored;
+            aggregated.filtered += parsed.filtered;
+        } else {
             aggregated.merge(&parsed);
         }
     }
@@ -97,6 +123,9 @@ pub struct TestCmd {
     /// Build profile (e.g., release, release-small, or any custom profile)
     #[arg(long)]
     pub profile: Option<String>,
+    /// Let --profile win when it conflicts with the spec's cargo.profile
+    #[arg(long = "force-profile")]
+    pub force_profile: bool,
     /// Stop on first failure
     #[arg(short, long)]
     pub fail_fast: bool,
@@ -118,6 +147,23 @@ pub struct TestCmd {
     /// Extra arguments passed after -- to the test binary (e.g., --ignored)
     #[arg(last = true)]
     pub test_args: Vec<String>,
+    /// Force a synthetic per-spec target_dir, avoiding shared artifacts
+    /// between specs that don't set their own cargo.target_dir
+    #[arg(long = "isolate")]
+    pub isolate: bool,
+    /// Include BuildTool-kind members (e.g. xtask) in all-packages mode
+    /// instead of excluding them
+    #[arg(long = "include-build-tools")]
+    pub include_build_tools: bool,
+    /// Skip generating a temporary build.rs for linker.args and route them
+    /// through RUSTFLAGS `-C link-arg=` instead (applies to every target in
+    /// the package, not just the bin)
+    #[arg(long = "no-buildrs")]
+    pub no_buildrs: bool,
+    /// Leave a generated linker-args build.rs in place after the build for
+    /// inspection instead of deleting it
+    #[arg(long = "keep-buildrs")]
+    pub keep_buildrs: bool,
 }
 
 impl TestCmd {
@@ -206,20 +252,60 @@ impl Execute for TestCmd {
                     }
 
                     for member in &matching {
-                        println!("=== {} ===", member.name);
-                        test_package(&member.name, None, cli_profile, project_root, &flags)?;
+                        match cli_profile {
+                            Some(p) => println!("=== {} (profile: {p}) ===", member.name),
+                            None => println!("=== {} ===", member.name),
+                        }
+                        test_package(
+                            &member.name,
+                            None,
+                            cli_profile,
+                            self.force_profile,
+                            project_root,
+                            &flags,
+                            self.isolate,
+                            self.no_buildrs,
+                            self.keep_buildrs,
+                            None,
+                        )?;
                     }
                     return Ok(ExitCode::SUCCESS);
                 }
 
-                let results =
-                    test_all(&workspace, &self.tspec, cli_profile, self.fail_fast, &flags);
-                Ok(print_test_summary(&workspace.name_versioned(), &results))
+                let results = test_all(
+                    &workspace,
+                    &self.tspec,
+                    cli_profile,
+                    self.force_profile,
+                    self.fail_fast,
+                    &flags,
+                    self.workspace,
+                    self.isolate,
+                    self.include_build_tools,
+                    self.no_buildrs,
+                    self.keep_buildrs,
+                );
+                Ok(print_test_summary(
+                    &workspace.name_versioned(),
+                    &results,
+                    cli_profile,
+                ))
             }
             Some(name) => {
                 let mut all_lines = Vec::new();
                 if self.tspec.is_empty() {
-                    all_lines = test_package(&name, None, cli_profile, project_root, &flags)?;
+                    all_lines = test_package(
+                        &name,
+                        None,
+                        cli_profile,
+                        self.force_profile,
+                        project_root,
+                        &flags,
+                        self.isolate,
+                        self.no_buildrs,
+                        self.keep_buildrs,
+                        None,
+                    )?;
                 } else {
                     let package_dir = resolve_package_dir(project_root, Some(&name))?;
                     let pkg_name = get_package_name(&package_dir)?;
@@ -230,8 +316,13 @@ impl Execute for TestCmd {
                             &pkg_name,
                             Some(&spec_str),
                             cli_profile,
+                            self.force_profile,
                             project_root,
                             &flags,
+                            self.isolate,
+                            self.no_buildrs,
+                            self.keep_buildrs,
+                            None,
                         )?;
                         all_lines.extend(lines);
                     }
@@ -277,10 +368,20 @@ fn format_target_header(raw: &str) -> String {
     }
 }
 
+/// True for the "Executable tests/..." / "Running tests/..." headers
+/// `cargo test --no-run` prints on stderr for each integration test target.
+fn is_target_header_line(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.starts_with("Executable tests/") || trimmed.starts_with("Running tests/")
+}
+
 /// List available target names for `--test`.
 ///
-/// Runs `cargo test --no-run` and parses stderr for "Running tests/..." lines,
-/// extracting the basename (without .rs) as the target name.
+/// Runs `cargo test --no-run` and parses the "Running tests/..." lines it
+/// prints on stderr, extracting the basename (without .rs) as the target
+/// name. Streams stdout/stderr incrementally with a bounded ring buffer
+/// (see [`capture_bounded`]) rather than buffering the whole run in memory,
+/// so a dependency's noisy build script can't balloon memory usage here.
 fn list_target_names(
     package: Option<&str>,
     project_root: &Path,
@@ -294,19 +395,18 @@ fn list_target_names(
     flags.apply_to_command(&mut cmd);
     cmd.current_dir(project_root);
 
-    let output = cmd.output().context("failed to run cargo test --no-run")?;
+    let captured = capture_bounded(&mut cmd, |_| false, is_target_header_line)
+        .context("failed to run cargo test --no-run")?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        eprintln!("{}", stderr);
+    if !captured.status.success() {
+        eprintln!("{}", captured.stderr_buffer.render());
         return Ok(ExitCode::from(1));
     }
 
-    let stderr = String::from_utf8_lossy(&output.stderr);
     let mut found = false;
 
     println!("Available target names for --test:");
-    for line in stderr.lines() {
+    for line in &captured.stderr_matched {
         let trimmed = line.trim();
         // --no-run emits "Executable tests/foo.rs (target/...)" for integration tests
         if let Some(rest) = trimmed
@@ -332,10 +432,28 @@ fn list_target_names(
     Ok(ExitCode::SUCCESS)
 }
 
+/// True for a `cargo test -- --list` stdout line this command needs to keep:
+/// either a test/bench entry or the "N tests, M benchmarks" group summary.
+fn is_list_output_line(line: &str) -> bool {
+    line.ends_with(": test")
+        || line.ends_with(": bench")
+        || (line.contains("tests,") && line.contains("benchmarks"))
+}
+
+/// True for a `cargo test -- --list` stderr line naming a target
+/// ("Running ..." or "Doc-tests ...").
+fn is_list_target_header_line(line: &str) -> bool {
+    let trimmed = line.trim();
+    trimmed.starts_with("Running ") || trimmed.starts_with("Doc-tests ")
+}
+
 /// Run `cargo test -- --list` and format the output.
 ///
 /// Groups test functions under their target headers, showing counts per target
-/// and a total. Skips targets with zero tests.
+/// and a total. Skips targets with zero tests. Streams stdout/stderr
+/// incrementally with a bounded ring buffer (see [`capture_bounded`]) rather
+/// than buffering the whole run in memory, so a dependency's noisy build
+/// script can't balloon memory usage here.
 fn list_tests(
     package: Option<&str>,
     name_filter: &[String],
@@ -353,30 +471,25 @@ fn list_tests(
     cmd.arg("--list");
     cmd.current_dir(project_root);
 
-    let output = cmd.output().context("failed to run cargo test -- --list")?;
+    let captured = capture_bounded(&mut cmd, is_list_output_line, is_list_target_header_line)
+        .context("failed to run cargo test -- --list")?;
 
-    if !output.status.success() {
-        let stderr = String::from_utf8_lossy(&output.stderr);
-        eprintln!("{}", stderr);
+    if !captured.status.success() {
+        eprintln!("{}", captured.stderr_buffer.render());
         return Ok(ExitCode::from(1));
     }
 
-    let stderr = String::from_utf8_lossy(&output.stderr);
-    let stdout = String::from_utf8_lossy(&output.stdout);
-
     // Parse target headers from stderr ("Running ..." and "Doc-tests ..." lines)
     // Produce human-friendly labels that show the --test name when applicable.
-    let targets: Vec<String> = stderr
-        .lines()
-        .filter_map(|line| {
+    let targets: Vec<String> = captured
+        .stderr_matched
+        .iter()
+        .map(|line| {
             let trimmed = line.trim();
-            if trimmed.starts_with("Running ") {
-                let rest = trimmed.strip_prefix("Running ").unwrap();
-                Some(format_target_header(rest))
-            } else if trimmed.starts_with("Doc-tests ") {
-                Some(trimmed.to_string())
+            if let Some(rest) = trimmed.strip_prefix("Running ") {
+                format_target_header(rest)
             } else {
-                None
+                trimmed.to_string()
             }
         })
         .collect();
@@ -384,11 +497,11 @@ fn list_tests(
     // Parse stdout into groups split by "N tests, M benchmarks" summary lines
     let mut groups: Vec<Vec<&str>> = Vec::new();
     let mut current: Vec<&str> = Vec::new();
-    for line in stdout.lines() {
+    for line in &captured.stdout_matched {
         if line.contains("tests,") && line.contains("benchmarks") {
             groups.push(std::mem::take(&mut current));
         } else if line.ends_with(": test") || line.ends_with(": bench") {
-            current.push(line);
+            current.push(line.as_str());
         }
     }
 
@@ -527,6 +640,54 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn force_profile_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.force_profile);
+    }
+
+    #[test]
+    fn force_profile_flag() {
+        let cmd = parse(&["--force-profile", "--profile", "release-small"]);
+        assert!(cmd.force_profile);
+    }
+
+    #[test]
+    fn isolate_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.isolate);
+    }
+
+    #[test]
+    fn isolate_flag() {
+        let cmd = parse(&["--isolate"]);
+        assert!(cmd.isolate);
+    }
+
+    #[test]
+    fn no_buildrs_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.no_buildrs);
+    }
+
+    #[test]
+    fn no_buildrs_flag() {
+        let cmd = parse(&["--no-buildrs"]);
+        assert!(cmd.no_buildrs);
+    }
+
+    #[test]
+    fn keep_buildrs_default_false() {
+        let cmd = parse(&[]);
+        assert!(!cmd.keep_buildrs);
+    }
+
+    #[test]
+    fn keep_buildrs_flag() {
+        let cmd = parse(&["--keep-buildrs"]);
+        assert!(cmd.keep_buildrs);
+    }
+
     #[test]
     fn target_names_flag() {
         let cmd = parse(&["--target-names"]);
@@ -679,12 +840,14 @@ mod tests {
             failed: 1,
             ignored: 2,
             filtered: 3,
+            ..Default::default()
         };
         let b = TestResult {
             passed: 5,
             failed: 0,
             ignored: 1,
             filtered: 2,
+            ..Default::default()
         };
         a.merge(&b);
         assert_eq!(a.passed, 15);
@@ -705,4 +868,81 @@ mod tests {
         assert_eq!(r.ignored, 1);
         assert_eq!(r.filtered, 3);
     }
+
+    // parse_test_results doctest-section unit tests
+
+    #[test]
+    fn parse_test_results_unit_tests_only_no_doctests() {
+        // Real transcript shape for a package with unit tests but an empty
+        // Doc-tests section (no lib target, or a lib with no doc examples).
+        let lines = vec![
+            "test result: ok. 3 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.01s".to_string(),
+            "Doc-tests tspec".to_string(),
+            "test result: ok. 0 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.00s".to_string(),
+        ];
+        let r = parse_test_results(&lines);
+        assert_eq!(r.passed, 3);
+        assert_eq!(r.failed, 0);
+        assert_eq!(r.doc_passed, 0);
+        assert_eq!(r.doc_failed, 0);
+        assert_eq!(r.total_ran(), 3);
+    }
+
+    #[test]
+    fn parse_test_results_doctests_only_package() {
+        // A lib-only package with no unit/integration tests, only doctests.
+        // Previously this looked indistinguishable from "0 tests ran" because
+        // the doctest result line was merged into the same passed/failed
+        // counters with nothing to tell it apart from a genuinely empty run.
+        let lines = vec![
+            "Doc-tests pop_doctest_fixture".to_string(),
+            "test result: ok. 1 passed; 0 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.15s"
+                .to_string(),
+        ];
+        let r = parse_test_results(&lines);
+        assert_eq!(r.passed, 0);
+        assert_eq!(r.failed, 0);
+        assert_eq!(r.doc_passed, 1);
+        assert_eq!(r.doc_failed, 0);
+        assert_eq!(
+            r.total_ran(),
+            1,
+            "doctest-only run must not look like 0 tests ran"
+        );
+    }
+
+    #[test]
+    fn parse_test_results_unit_tests_and_doctests_combined() {
+        let lines = vec![
+            "test result: ok. 40 passed; 0 failed; 2 ignored; 0 measured; 0 filtered out; finished in 0.42s"
+                .to_string(),
+            "Doc-tests tspec".to_string(),
+            "test result: FAILED. 2 passed; 1 failed; 0 ignored; 0 measured; 0 filtered out; finished in 0.05s"
+                .to_string(),
+        ];
+        let r = parse_test_results(&lines);
+        assert_eq!(r.passed, 40);
+        assert_eq!(r.failed, 0);
+        assert_eq!(r.ignored, 2);
+        assert_eq!(r.doc_passed, 2);
+        assert_eq!(r.doc_failed, 1);
+        assert_eq!(r.total_ran(), 43);
+    }
+
+    #[test]
+    fn test_result_doc_counts_merge() {
+        let mut a = TestResult {
+            doc_passed: 2,
+            doc_failed: 1,
+            ..Default::default()
+        };
+        let b = TestResult {
+            doc_passed: 1,
+            doc_failed: 0,
+            ..Default::default()
+        };
+        a.merge(&b);
+        assert_eq!(a.doc_passed, 3);
+        assert_eq!(a.doc_failed, 1);
+    }
 }
@@ -1,6 +1,6 @@
 use anyhow::Result;
 use clap::{Args, Subcommand};
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::process::ExitCode;
 
 use super::Execute;
@@ -37,6 +37,27 @@ pub enum TsCommands {
         #[arg(short = 't', long = "tspec")]
         tspec: Option<String>,
     },
+    /// Strictly check tspec files for unknown/unrecognized keys
+    Validate {
+        /// Package name (defaults to current directory)
+        #[arg(short = 'p', long = "package")]
+        package: Option<String>,
+        /// Validate all workspace packages (even when in a package directory)
+        #[arg(short = 'w', long = "workspace")]
+        all: bool,
+        /// Tspec name (defaults to all tspec files)
+        #[arg(short = 't', long = "tspec")]
+        tspec: Option<String>,
+    },
+    /// Rewrite legacy key paths (e.g. `rustc.panic`) to their modern equivalent
+    Migrate {
+        /// Package name (defaults to current directory)
+        #[arg(short = 'p', long = "package")]
+        package: Option<String>,
+        /// Tspec to migrate (defaults to package's tspec.ts.toml)
+        #[arg(short = 't', long = "tspec")]
+        tspec: Option<String>,
+    },
     /// Show the content hash of a tspec
     Hash {
         /// Package name (defaults to current directory)
@@ -60,23 +81,46 @@ pub enum TsCommands {
         /// Copy from existing tspec (package/spec or just spec name in same package)
         #[arg(short = 'f', long = "from")]
         from: Option<String>,
+        /// Create a truly blank spec instead of the commented-out template default
+        #[arg(long = "empty", conflicts_with = "from")]
+        empty: bool,
+        /// Create it in every workspace member instead of a single package
+        #[arg(short = 'w', long = "workspace")]
+        all: bool,
     },
+    /// Roll back an incomplete multi-file tspec operation left behind by a
+    /// process that died mid-write (e.g. `ts new --from -w`)
+    Rollback,
     /// Set a field in a tspec (scalar value or replace entire array)
     ///
     /// For scalars: tspec ts set key value
     /// For arrays: tspec ts set key val1 val2 ...
+    /// For batches: tspec ts set --from-file edits.txt (one "key = value" or
+    /// "key += value" assignment per line, applied in order and written once)
     Set {
-        /// Field key (e.g., "cargo.profile", "linker.args")
-        key: String,
+        /// Field key (e.g., "cargo.profile", "linker.args") — omit with --from-file
+        #[arg(required_unless_present = "from_file")]
+        key: Option<String>,
         /// Value(s). For scalars, one value. For arrays, each arg is an element.
-        #[arg(required = true, allow_hyphen_values = true)]
+        #[arg(allow_hyphen_values = true)]
         value: Vec<String>,
+        /// Apply a batch of "key = value" / "key += value" assignments from a file
+        #[arg(long = "from-file", conflicts_with_all = ["key", "value"])]
+        from_file: Option<PathBuf>,
+        /// Only set the field if it isn't already present; report "already
+        /// set" and leave the file untouched otherwise
+        #[arg(long = "if-unset", conflicts_with = "from_file")]
+        if_unset: bool,
         /// Package name (defaults to current directory)
         #[arg(short = 'p', long = "package")]
         package: Option<String>,
         /// Tspec to modify (defaults to package's tspec.ts.toml)
         #[arg(short = 't', long = "tspec")]
         tspec: Option<String>,
+        /// Skip the confirmation required when multiple tspecs exist and
+        /// no -t was given to say which one to edit
+        #[arg(long = "yes")]
+        yes: bool,
     },
     /// Remove a field from a tspec (preserves comments)
     Unset {
@@ -89,6 +133,18 @@ pub enum TsCommands {
         #[arg(short = 't', long = "tspec")]
         tspec: Option<String>,
     },
+    /// Flip a boolean field's value (unset counts as `false`), e.g.
+    /// `tspec ts toggle cargo.hermetic_env`
+    Toggle {
+        /// Field key (must be a boolean field, e.g., "cargo.hermetic_env")
+        key: String,
+        /// Package name (defaults to current directory)
+        #[arg(short = 'p', long = "package")]
+        package: Option<String>,
+        /// Tspec to modify (defaults to package's tspec.ts.toml)
+        #[arg(short = 't', long = "tspec")]
+        tspec: Option<String>,
+    },
     /// Add items to an array field (append by default, or insert at position)
     Add {
         /// Field key (must be an array field, e.g., "linker.args")
@@ -96,9 +152,10 @@ pub enum TsCommands {
         /// Items to add
         #[arg(required = true, allow_hyphen_values = true)]
         value: Vec<String>,
-        /// Insert at this index instead of appending
-        #[arg(short = 'i', long = "index")]
-        index: Option<usize>,
+        /// Insert at this index instead of appending. Negative values count
+        /// from the end (Python-style): -1 inserts before the last element.
+        #[arg(short = 'i', long = "index", allow_hyphen_values = true)]
+        index: Option<isize>,
         /// Package name (defaults to current directory)
         #[arg(short = 'p', long = "package")]
         package: Option<String>,
@@ -116,6 +173,9 @@ pub enum TsCommands {
         /// Remove item at this index instead of by value
         #[arg(short = 'i', long = "index")]
         index: Option<usize>,
+        /// Empty the array entirely instead of removing specific items
+        #[arg(long = "all", conflicts_with_all = ["index", "value"])]
+        all: bool,
         /// Package name (defaults to current directory)
         #[arg(short = 'p', long = "package")]
         package: Option<String>,
@@ -141,6 +201,53 @@ pub enum TsCommands {
         #[arg(short = 't', long = "tspec")]
         tspec: String,
     },
+    /// Rewrite a tspec with canonical section/key ordering and whitespace
+    Normalize {
+        /// Package name (defaults to current directory)
+        #[arg(short = 'p', long = "package")]
+        package: Option<String>,
+        /// Normalize all workspace packages (even when in a package directory)
+        #[arg(short = 'w', long = "workspace")]
+        all: bool,
+        /// Tspec name (defaults to all tspec files)
+        #[arg(short = 't', long = "tspec")]
+        tspec: Option<String>,
+        /// Report whether the file is already normalized instead of writing;
+        /// exits non-zero if it isn't (for CI)
+        #[arg(long)]
+        check: bool,
+    },
+    /// Show a tspec's resolution tree (currently always a single node — no
+    /// extends/include mechanism exists yet to chain to a base or fragment)
+    Tree {
+        /// Package name (defaults to current directory)
+        #[arg(short = 'p', long = "package")]
+        package: Option<String>,
+        /// Tspec name (defaults to package's tspec.ts.toml)
+        #[arg(short = 't', long = "tspec")]
+        tspec: Option<String>,
+    },
+    /// Find spec references (e.g. default_spec pins) that no longer
+    /// resolve to an existing spec, across the whole workspace
+    CheckRefs,
+    /// Pin the resolved tspec's hash into Cargo.toml's [package.metadata.tspec]
+    Pin {
+        /// Package name (defaults to current directory)
+        #[arg(short = 'p', long = "package")]
+        package: Option<String>,
+        /// Tspec to pin (defaults to package's tspec.ts.toml)
+        #[arg(short = 't', long = "tspec")]
+        tspec: Option<String>,
+    },
+    /// Pin a spec's cargo.target_json file's hash into cargo.target_json_hash
+    PinTarget {
+        /// Package name (defaults to current directory)
+        #[arg(short = 'p', long = "package")]
+        package: Option<String>,
+        /// Tspec to pin (defaults to package's tspec.ts.toml)
+        #[arg(short = 't', long = "tspec")]
+        tspec: Option<String>,
+    },
 }
 
 impl Execute for TsCmd {
@@ -156,6 +263,24 @@ impl Execute for TsCmd {
             } => {
                 ts_cmd::show_tspec(project_root, package.as_deref(), *all, tspec.as_deref())?;
             }
+            TsCommands::Validate {
+                package,
+                all,
+                tspec,
+            } => {
+                let ok = ts_cmd::validate_tspec(
+                    project_root,
+                    package.as_deref(),
+                    *all,
+                    tspec.as_deref(),
+                )?;
+                if !ok {
+                    return Ok(ExitCode::FAILURE);
+                }
+            }
+            TsCommands::Migrate { package, tspec } => {
+                ts_cmd::migrate_tspec(project_root, package.as_deref(), tspec.as_deref())?;
+            }
             TsCommands::Hash {
                 package,
                 all,
@@ -167,22 +292,52 @@ impl Execute for TsCmd {
                 name,
                 package,
                 from,
+                empty,
+                all,
             } => {
-                ts_cmd::new_tspec(project_root, package.as_deref(), name, from.as_deref())?;
+                ts_cmd::new_tspec(
+                    project_root,
+                    package.as_deref(),
+                    name,
+                    from.as_deref(),
+                    *empty,
+                    *all,
+                )?;
+            }
+            TsCommands::Rollback => {
+                ts_cmd::rollback_tspec(project_root)?;
             }
             TsCommands::Set {
                 key,
                 value,
+                from_file,
+                if_unset,
                 package,
                 tspec,
+                yes,
             } => {
-                ts_cmd::set_value(
-                    project_root,
-                    package.as_deref(),
-                    key,
-                    value,
-                    tspec.as_deref(),
-                )?;
+                if let Some(from_file) = from_file {
+                    ts_cmd::set_from_file(
+                        project_root,
+                        package.as_deref(),
+                        from_file,
+                        tspec.as_deref(),
+                    )?;
+                } else {
+                    let key = key.as_ref().expect("clap requires key without --from-file");
+                    if value.is_empty() {
+                        anyhow::bail!("'ts set' requires at least one value");
+                    }
+                    ts_cmd::set_value(
+                        project_root,
+                        package.as_deref(),
+                        key,
+                        value,
+                        tspec.as_deref(),
+                        *if_unset,
+                        *yes,
+                    )?;
+                }
             }
             TsCommands::Unset {
                 key,
@@ -191,6 +346,13 @@ impl Execute for TsCmd {
             } => {
                 ts_cmd::unset_value(project_root, package.as_deref(), key, tspec.as_deref())?;
             }
+            TsCommands::Toggle {
+                key,
+                package,
+                tspec,
+            } => {
+                ts_cmd::toggle_value(project_root, package.as_deref(), key, tspec.as_deref())?;
+            }
             TsCommands::Add {
                 key,
                 value,
@@ -211,6 +373,7 @@ impl Execute for TsCmd {
                 key,
                 value,
                 index,
+                all,
                 package,
                 tspec,
             } => {
@@ -220,8 +383,26 @@ impl Execute for TsCmd {
                     key,
                     value,
                     *index,
+                    *all,
+                    tspec.as_deref(),
+                )?;
+            }
+            TsCommands::Normalize {
+                package,
+                all,
+                tspec,
+                check,
+            } => {
+                let normalized = ts_cmd::normalize_tspec(
+                    project_root,
+                    package.as_deref(),
+                    *all,
                     tspec.as_deref(),
+                    *check,
                 )?;
+                if *check && !normalized {
+                    return Ok(ExitCode::FAILURE);
+                }
             }
             TsCommands::Backup { package, tspec } => {
                 ts_cmd::backup_tspec(project_root, package.as_deref(), tspec.as_deref())?;
@@ -229,6 +410,20 @@ impl Execute for TsCmd {
             TsCommands::Restore { package, tspec } => {
                 ts_cmd::restore_tspec(project_root, package.as_deref(), tspec)?;
             }
+            TsCommands::Tree { package, tspec } => {
+                ts_cmd::tree_tspec(project_root, package.as_deref(), tspec.as_deref())?;
+            }
+            TsCommands::CheckRefs => {
+                if !ts_cmd::check_refs_tspec(project_root)? {
+                    return Ok(ExitCode::FAILURE);
+                }
+            }
+            TsCommands::Pin { package, tspec } => {
+                ts_cmd::pin_tspec(project_root, package.as_deref(), tspec.as_deref())?;
+            }
+            TsCommands::PinTarget { package, tspec } => {
+                ts_cmd::pin_target(project_root, package.as_deref(), tspec.as_deref())?;
+            }
         }
         Ok(ExitCode::SUCCESS)
     }
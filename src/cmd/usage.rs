@@ -0,0 +1,126 @@
+use anyhow::Result;
+use clap::{Args, Subcommand};
+use std::path::Path;
+use std::process::ExitCode;
+
+use super::Execute;
+use crate::types::CargoFlags;
+use crate::usage::{UsageReport, aggregate, read_log};
+use crate::{print_header, print_hline};
+
+/// Inspect the opt-in local usage log
+#[derive(Args)]
+pub struct UsageCmd {
+    #[command(subcommand)]
+    command: UsageCommands,
+}
+
+#[derive(Subcommand)]
+pub enum UsageCommands {
+    /// Aggregate the usage log into per-command/spec/profile counts
+    Report {
+        /// Only include records on or after this date (YYYY-MM-DD)
+        #[arg(long)]
+        since: Option<String>,
+        /// Number of slowest operations to list
+        #[arg(long, default_value_t = 10)]
+        slowest: usize,
+    },
+}
+
+impl Execute for UsageCmd {
+    fn execute(&self, project_root: &Path, _flags: &CargoFlags) -> Result<ExitCode> {
+        match &self.command {
+            UsageCommands::Report { since, slowest } => {
+                let records = read_log(project_root, since.as_deref());
+                if records.is_empty() {
+                    println!(
+                        "no usage records found (usage logging may be disabled — \
+                         set `usage_log` under [workspace.metadata.tspec] to enable it)"
+                    );
+                    return Ok(ExitCode::SUCCESS);
+                }
+                print_report(&aggregate(records, *slowest));
+                Ok(ExitCode::SUCCESS)
+            }
+        }
+    }
+}
+
+fn print_report(report: &UsageReport) {
+    println!();
+    print_header!("USAGE REPORT");
+
+    println!("By command:");
+    for (command, count) in &report.by_command {
+        println!("  {:<20} {count}", command);
+    }
+
+    if !report.by_spec.is_empty() {
+        println!("\nBy spec:");
+        for (spec, count) in &report.by_spec {
+            println!("  {:<30} {count}", spec);
+        }
+    }
+
+    if !report.by_profile.is_empty() {
+        println!("\nBy profile:");
+        for (profile, count) in &report.by_profile {
+            println!("  {:<20} {count}", profile);
+        }
+    }
+
+    println!("\nSlowest recent operations:");
+    for record in &report.slowest {
+        let packages = if record.packages.is_empty() {
+            "-".to_string()
+        } else {
+            record.packages.join(",")
+        };
+        println!(
+            "  {:>8} ms  {:<10} {}  {}",
+            record.duration_ms, record.command, record.date, packages
+        );
+    }
+    print_hline!();
+    println!();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::Cli;
+    use clap::Parser;
+
+    fn parse(args: &[&str]) -> UsageCmd {
+        let mut full = vec!["tspec", "usage"];
+        full.extend_from_slice(args);
+        let cli = Cli::try_parse_from(full).unwrap();
+        match cli.command {
+            crate::cli::Commands::Usage(cmd) => cmd,
+            _ => panic!("expected Usage command"),
+        }
+    }
+
+    #[test]
+    fn report_since_optional() {
+        let cmd = parse(&["report"]);
+        match cmd.command {
+            UsageCommands::Report { since, slowest } => {
+                assert!(since.is_none());
+                assert_eq!(slowest, 10);
+            }
+        }
+    }
+
+    #[test]
+    fn report_since_and_slowest() {
+        let cmd = parse(&["report", "--since", "2026-01-01", "--slowest", "5"]);
+        match cmd.command {
+            UsageCommands::Report { since, slowest } => {
+                assert_eq!(since.as_deref(), Some("2026-01-01"));
+                assert_eq!(slowest, 5);
+            }
+        }
+    }
+}
@@ -1,18 +1,84 @@
 use anyhow::Result;
 use clap::Args;
 use std::path::Path;
-use std::process::ExitCode;
+use std::process::{Command, ExitCode};
 
 use super::Execute;
 use crate::types::CargoFlags;
 
 /// Print version information
+///
+/// `--verbose` would collide with the global `-v`/`--verbose` cargo-passthrough
+/// flag (it counts occurrences and is shared by every subcommand), so the
+/// toolchain-info flag here is named `--full` instead.
 #[derive(Args)]
-pub struct VersionCmd;
+pub struct VersionCmd {
+    /// Also print rustc/cargo versions, the default toolchain, and the git
+    /// commit tspec was built from
+    #[arg(long)]
+    pub full: bool,
+}
 
 impl Execute for VersionCmd {
     fn execute(&self, _project_root: &Path, _flags: &CargoFlags) -> Result<ExitCode> {
         println!("tspec {}", env!("CARGO_PKG_VERSION"));
+        if self.full {
+            println!("commit: {}", env!("TSPEC_GIT_SHA"));
+            for line in toolchain_lines() {
+                println!("{line}");
+            }
+        }
         Ok(ExitCode::SUCCESS)
     }
 }
+
+/// First line of `program`'s stdout, or `None` if it isn't on PATH or exits
+/// non-zero. Mirrors `target_check::command_lines`'s shell-out pattern.
+fn command_first_line(program: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(program).args(args).output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()
+        .map(|s| s.trim().to_string())
+}
+
+/// Lines describing the toolchain in use, skipping any tool that isn't on
+/// PATH rather than erroring - `--full` is a bug-report aid, not a
+/// toolchain check.
+fn toolchain_lines() -> Vec<String> {
+    let mut lines = Vec::new();
+    if let Some(line) = command_first_line("rustc", &["--version"]) {
+        lines.push(line);
+    }
+    if let Some(line) = command_first_line("cargo", &["--version"]) {
+        lines.push(line);
+    }
+    if let Some(line) = command_first_line("rustup", &["show", "active-toolchain"]) {
+        lines.push(format!("default toolchain: {line}"));
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn toolchain_lines_includes_rustc_when_available() {
+        let lines = toolchain_lines();
+        if command_first_line("rustc", &["--version"]).is_some() {
+            assert!(
+                lines.iter().any(|l| l.starts_with("rustc")),
+                "expected a rustc line in {lines:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn git_sha_env_is_set_at_build_time() {
+        assert!(!env!("TSPEC_GIT_SHA").is_empty());
+    }
+}
@@ -1,40 +1,217 @@
 use std::path::Path;
 
 use anyhow::Result;
+use unicode_width::UnicodeWidthStr;
 
-use crate::binary::{binary_size, strip_binary};
-use crate::cargo_build::{build_package, plain_cargo_build_release};
-use crate::tspec::{hash_spec, load_spec};
+use crate::baseline::{BaselineDiffRow, split_name_hash};
+use crate::binary::{
+    ElfSegments, StripOutcome, binary_size, read_elf_segments, strip_binary_with_report,
+};
+use crate::cargo_build::{build_package, plain_cargo_build_release, test_package};
+use crate::cmd::{TestResult, parse_test_results};
+use crate::tspec::{hash_spec, is_dev_overlay_target_dir, load_spec};
 use crate::types::CargoFlags;
 use crate::{print_header, print_hline};
 
+/// Right-pad `s` to `width` display columns (not bytes or `char`s), so a
+/// spec name with wide/combining unicode still lines up the columns after
+/// it. `std::fmt`'s `{:width$}` pads by `char` count, which over- or
+/// under-pads as soon as a name isn't plain ASCII.
+fn pad_display(s: &str, width: usize) -> String {
+    let w = s.width();
+    if w >= width {
+        s.to_string()
+    } else {
+        format!("{s}{}", " ".repeat(width - w))
+    }
+}
+
+/// Keep only the spec paths that appear (by filename) in `changed_files`,
+/// a list of repo-relative paths as `git diff --name-only` would report.
+/// Pure and injectable so `--changed-specs` doesn't need a real git repo to
+/// test the filtering logic.
+pub fn filter_changed_specs(
+    spec_paths: &[std::path::PathBuf],
+    changed_files: &[String],
+) -> Vec<std::path::PathBuf> {
+    spec_paths
+        .iter()
+        .filter(|p| {
+            let Some(name) = p.file_name().and_then(|n| n.to_str()) else {
+                return false;
+            };
+            changed_files
+                .iter()
+                .any(|f| std::path::Path::new(f).file_name().and_then(|n| n.to_str()) == Some(name))
+        })
+        .cloned()
+        .collect()
+}
+
 /// Result of building a spec
 pub struct SpecResult {
     pub name: String,
     pub size: u64,
+    /// Size after running `strip` on the built artifact, when stripping was
+    /// attempted and the artifact was a format `strip_binary` recognizes.
+    /// Always measured alongside `size` so a run's Size column is never
+    /// ambiguous about whether it reflects a spec's own `strip` setting —
+    /// `size` is what the build produced, `stripped_size` is what stripping
+    /// it further would yield.
+    pub stripped_size: Option<u64>,
+    /// The target triple this row was built for, or `"host"` for a plain
+    /// `cargo --release` baseline row or a spec with no `cargo.target_triple`.
+    ///
+    /// A spec only ever resolves to one triple today (`cargo.target_triple`
+    /// is a single `Option<String>`, not a list), so in practice every row
+    /// in one `compare` run currently shares the same triple and
+    /// `print_comparison` never shows the Triple column. It's carried on
+    /// every row anyway so that if `cargo.target_triple` ever grows into a
+    /// list, per-triple rows fall out of the existing build loop instead of
+    /// needing a second table shape.
+    pub triple: String,
+    /// Loadable-segment stats, present when `--segments` was requested and the
+    /// binary is an ELF variant this reader understands.
+    pub segments: Option<ElfSegments>,
+    /// Test counts from re-running the spec's tests with `--with-tests`, if
+    /// requested. `None` for rows `--with-tests` doesn't apply to (the cargo
+    /// --release baseline rows) or when the flag wasn't passed at all.
+    pub tests: Option<TestResult>,
+}
+
+/// Name to show for a build with no explicit target triple.
+const HOST_TRIPLE: &str = "host";
+
+/// Run a spec's tests and classify the outcome for the compare table.
+///
+/// Reuses the spec's own target_dir (same as the build that was just
+/// measured), so the test build lands alongside rather than clobbering the
+/// measured binary. A hard cargo failure (couldn't even run) is reported as
+/// zero passed/failed — same loss of detail `tspec test` already accepts.
+fn run_spec_tests(
+    pkg_name: &str,
+    spec_path: &Path,
+    project_root: &Path,
+    flags: &CargoFlags,
+    isolate: bool,
+) -> TestResult {
+    let spec_str = spec_path.to_string_lossy();
+    match test_package(
+        pkg_name,
+        Some(&spec_str),
+        None,
+        false,
+        project_root,
+        flags,
+        isolate,
+        false,
+        false,
+        None,
+    ) {
+        Ok(lines) => parse_test_results(&lines),
+        Err(_) => TestResult::default(),
+    }
+}
+
+/// Format a spec's test outcome as `[PASS 34]` / `[FAIL 2]` / `[SKIP]`.
+fn fmt_test_column(tests: Option<&TestResult>) -> String {
+    match tests {
+        None => "-".to_string(),
+        Some(counts) if counts.total_ran() == 0 => "[SKIP]".to_string(),
+        Some(counts) if counts.failed + counts.doc_failed > 0 => {
+            format!("[FAIL {}]", counts.failed + counts.doc_failed)
+        }
+        Some(counts) => format!("[PASS {}]", counts.passed + counts.doc_passed),
+    }
+}
+
+/// A spec's tests failed (or never ran) under `--require-pass`.
+pub(crate) fn tests_failed(tests: Option<&TestResult>) -> bool {
+    match tests {
+        None => false,
+        Some(counts) => counts.total_ran() == 0 || counts.failed + counts.doc_failed > 0,
+    }
+}
+
+/// Group `(name, hash)` pairs that share an identical hash, in first-seen
+/// order. Only groups with two or more members are returned; a `None` hash
+/// (spec failed to load) never groups with anything, including another spec
+/// with a `None` hash.
+///
+/// Pure over the pairs so `compare_specs` can compute groups before running
+/// any (possibly multi-minute) builds, and so the grouping itself is testable
+/// without a real package tree.
+pub(crate) fn duplicate_groups(specs: &[(String, Option<String>)]) -> Vec<Vec<String>> {
+    let mut by_hash: Vec<(&str, Vec<String>)> = Vec::new();
+    for (name, hash) in specs {
+        let Some(hash) = hash else { continue };
+        match by_hash.iter_mut().find(|(h, _)| *h == hash) {
+            Some((_, names)) => names.push(name.clone()),
+            None => by_hash.push((hash, vec![name.clone()])),
+        }
+    }
+    by_hash
+        .into_iter()
+        .map(|(_, names)| names)
+        .filter(|names| names.len() > 1)
+        .collect()
+}
+
+/// Print a notice for each duplicate-hash group found by `duplicate_groups`.
+fn print_duplicate_notice(groups: &[Vec<String>]) {
+    for group in groups {
+        println!(
+            "  note: identical builds, skipping redundant ones: {}",
+            group.join(", ")
+        );
+    }
+    println!();
 }
 
-/// Compare multiple specs for a package
+/// Compare multiple specs for a package.
+///
+/// When `segments` is true, also reports per-spec ELF loadable-segment sizes
+/// (flash/RAM/BSS); binaries that aren't a supported ELF variant print a
+/// notice and fall back to size-only reporting for that entry.
+///
+/// When `with_tests` is true, each spec (not the cargo --release baseline) is
+/// also run through its own tests after building, and the outcome is carried
+/// on `SpecResult::tests` for `print_comparison` to render. With
+/// `require_pass` additionally set, specs whose tests failed (or didn't run)
+/// are sorted after every passing spec, so a smaller-but-broken build can't
+/// be mistaken for the best result.
+///
+/// Unless `allow_duplicate_builds` is set, specs whose resolved (post-load)
+/// hash matches an earlier spec in this run are not rebuilt — the earlier
+/// spec's size/segments/tests are reused and the row's name gets a
+/// `(same as <first spec>)` suffix so the table still lists every spec.
+#[allow(clippy::too_many_arguments)]
 pub fn compare_specs(
     pkg_name: &str,
     spec_paths: &[impl AsRef<Path> + std::fmt::Debug],
     project_root: &Path,
     flags: &CargoFlags,
+    segments: bool,
+    with_tests: bool,
+    require_pass: bool,
+    isolate: bool,
+    allow_duplicate_builds: bool,
 ) -> Result<Vec<SpecResult>> {
     println!("Comparing {} builds:\n", pkg_name);
 
     let mut results = Vec::new();
 
-    // Always build cargo --release baseline first (unstripped + stripped)
-    match build_baseline(pkg_name, project_root, flags) {
-        Ok((size, stripped_size)) => {
+    // Always build the cargo --release baseline first, reporting both its
+    // unstripped and stripped size in one row (see `SpecResult::stripped_size`).
+    match build_baseline(pkg_name, project_root, flags, segments) {
+        Ok((size, segs, stripped_size)) => {
             results.push(SpecResult {
                 name: "cargo --release".to_string(),
                 size,
-            });
-            results.push(SpecResult {
-                name: "cargo --release-strip".to_string(),
-                size: stripped_size,
+                stripped_size,
+                triple: HOST_TRIPLE.to_string(),
+                segments: segs,
+                tests: None,
             });
         }
         Err(_) => {
@@ -43,41 +220,187 @@ pub fn compare_specs(
     }
     println!();
 
-    for spec_path in spec_paths {
-        let spec_path = spec_path.as_ref();
-        let filename = spec_path
-            .file_name()
-            .map(|s| s.to_string_lossy().to_string())
-            .unwrap_or_else(|| spec_path.display().to_string());
-        let name = match load_spec(spec_path).and_then(|s| hash_spec(&s)) {
-            Ok(hash) => format!("{filename} [{hash}]"),
-            Err(_) => filename,
+    // Load every spec up front so hashes are available for duplicate
+    // detection before any (possibly multi-minute) build runs.
+    struct LoadedSpec<'a> {
+        path: &'a Path,
+        name: String,
+        triple: String,
+        hash: Option<String>,
+    }
+    let loaded: Vec<LoadedSpec> = spec_paths
+        .iter()
+        .map(|p| {
+            let path = p.as_ref();
+            let filename = path
+                .file_name()
+                .map(|s| s.to_string_lossy().to_string())
+                .unwrap_or_else(|| path.display().to_string());
+            let loaded_spec = load_spec(path).ok();
+            let hash = loaded_spec.as_ref().and_then(|s| hash_spec(s).ok());
+            let name = match &hash {
+                Some(hash) => format!("{filename} [{hash}]"),
+                None => filename,
+            };
+            let triple = loaded_spec
+                .as_ref()
+                .and_then(|s| s.cargo.target_triple.clone())
+                .unwrap_or_else(|| HOST_TRIPLE.to_string());
+            LoadedSpec {
+                path,
+                name,
+                triple,
+                hash,
+            }
+        })
+        .collect();
+
+    if !allow_duplicate_builds {
+        let pairs: Vec<(String, Option<String>)> = loaded
+            .iter()
+            .map(|l| (l.name.clone(), l.hash.clone()))
+            .collect();
+        let groups = duplicate_groups(&pairs);
+        if !groups.is_empty() {
+            print_duplicate_notice(&groups);
+        }
+    }
+
+    // hash -> (name of the spec that was actually built, its size/stripped
+    // size/segments/tests)
+    #[allow(clippy::type_complexity)]
+    let mut built: std::collections::HashMap<
+        String,
+        (
+            String,
+            u64,
+            Option<u64>,
+            Option<ElfSegments>,
+            Option<TestResult>,
+        ),
+    > = std::collections::HashMap::new();
+
+    for entry in &loaded {
+        let spec_path = entry.path;
+        let loaded_spec = load_spec(spec_path).ok();
+        if let Some(td) = loaded_spec
+            .as_ref()
+            .and_then(|s| s.cargo.target_dir.as_deref())
+            && is_dev_overlay_target_dir(td)
+        {
+            anyhow::bail!(
+                "{} targets a --dev-overlay target_dir ({td}); compare measures \
+                 production-representative artifacts, not relaxed dev builds",
+                entry.name
+            );
+        }
+
+        let reused = if allow_duplicate_builds {
+            None
+        } else {
+            entry.hash.as_ref().and_then(|h| built.get(h).cloned())
         };
 
-        let size = build_spec(pkg_name, spec_path, project_root, flags)?;
-        results.push(SpecResult { name, size });
+        let (name, size, stripped_size, segs, tests) =
+            if let Some((built_name, size, stripped_size, segs, tests)) = reused {
+                println!(
+                    "  {} (same as {built_name}): reusing build, skipping",
+                    entry.name
+                );
+                (
+                    format!("{} (same as {built_name})", entry.name),
+                    size,
+                    stripped_size,
+                    segs,
+                    tests,
+                )
+            } else {
+                let (size, segs, stripped_size) =
+                    build_spec(pkg_name, spec_path, project_root, flags, segments, isolate)?;
+                let tests = if with_tests {
+                    let outcome = run_spec_tests(pkg_name, spec_path, project_root, flags, isolate);
+                    println!("    tests: {}", fmt_test_column(Some(&outcome)));
+                    Some(outcome)
+                } else {
+                    None
+                };
+                if let Some(hash) = &entry.hash {
+                    built.insert(
+                        hash.clone(),
+                        (entry.name.clone(), size, stripped_size, segs, tests.clone()),
+                    );
+                }
+                (entry.name.clone(), size, stripped_size, segs, tests)
+            };
+
+        results.push(SpecResult {
+            name,
+            size,
+            stripped_size,
+            triple: entry.triple.clone(),
+            segments: segs,
+            tests,
+        });
         println!();
     }
 
-    // Sort by size (smallest first)
-    results.sort_by_key(|r| r.size);
+    // Sort by size (smallest first); with --require-pass, failing specs sort
+    // after every passing one regardless of size.
+    if require_pass {
+        results.sort_by_key(|r| (tests_failed(r.tests.as_ref()), r.size));
+    } else {
+        results.sort_by_key(|r| r.size);
+    }
 
     Ok(results)
 }
 
-/// Build baseline and return (unstripped_size, stripped_size)
-fn build_baseline(pkg_name: &str, project_root: &Path, flags: &CargoFlags) -> Result<(u64, u64)> {
+/// Read loadable-segment stats when `want` is set, printing a fallback
+/// notice when the binary isn't a supported ELF variant.
+fn read_segments_if_requested(path: &Path, want: bool) -> Option<ElfSegments> {
+    if !want {
+        return None;
+    }
+    match read_elf_segments(path) {
+        Ok(Some(segs)) => Some(segs),
+        Ok(None) => {
+            println!("    (not a supported ELF binary — segment stats unavailable)");
+            None
+        }
+        Err(e) => {
+            println!("    (failed to read segment stats: {e})");
+            None
+        }
+    }
+}
+
+/// Build baseline and return (unstripped_size, unstripped_segments, stripped_size)
+fn build_baseline(
+    pkg_name: &str,
+    project_root: &Path,
+    flags: &CargoFlags,
+    segments: bool,
+) -> Result<(u64, Option<ElfSegments>, Option<u64>)> {
     println!("  cargo --release:");
 
     let build_result = plain_cargo_build_release(pkg_name, project_root, flags)?;
 
     let size = binary_size(&build_result.binary_path)?;
     println!("    size: {} bytes", format_size(size));
+    let segs = read_segments_if_requested(&build_result.binary_path, segments);
 
-    strip_binary(&build_result.binary_path)?;
-    let stripped_size = binary_size(&build_result.binary_path)?;
+    let stripped_size = measure_stripped_size(&build_result.binary_path)?;
 
-    Ok((size, stripped_size))
+    Ok((size, segs, stripped_size))
+}
+
+/// Strip a built artifact and return its post-strip size, or `None` when
+/// `strip_binary_with_report` skipped it as not a native binary.
+fn measure_stripped_size(path: &Path) -> Result<Option<u64>> {
+    match strip_binary_with_report(path)? {
+        StripOutcome::Stripped(savings) => Ok(Some(savings.after)),
+        StripOutcome::Skipped(_) => Ok(None),
+    }
 }
 
 fn build_spec(
@@ -85,7 +408,9 @@ fn build_spec(
     spec_path: &Path,
     project_root: &Path,
     flags: &CargoFlags,
-) -> Result<u64> {
+    segments: bool,
+    isolate: bool,
+) -> Result<(u64, Option<ElfSegments>, Option<u64>)> {
     let spec_str = spec_path.to_string_lossy();
     println!(
         "  {}:",
@@ -93,47 +418,314 @@ fn build_spec(
     );
 
     // Build using spec settings (profile, strip, etc. are all in the spec)
-    let build_result = build_package(pkg_name, Some(&spec_str), None, project_root, flags)?;
+    let build_result = build_package(
+        pkg_name,
+        Some(&spec_str),
+        false,
+        false,
+        false,
+        None,
+        false,
+        project_root,
+        flags,
+        isolate,
+        false,
+        false,
+        false,
+        false,
+        false,
+        false,
+        None,
+    )?;
 
     let size = binary_size(&build_result.binary_path)?;
     println!("    size: {} bytes", format_size(size));
+    let segs = read_segments_if_requested(&build_result.binary_path, segments);
 
-    Ok(size)
+    // Report the further-strippable size too, regardless of the spec's own
+    // strip setting — a spec that already strips via `-C strip=` yields
+    // size == stripped_size here, making that equivalence visible instead
+    // of ambiguous.
+    let stripped_size = measure_stripped_size(&build_result.binary_path)?;
+
+    Ok((size, segs, stripped_size))
 }
 
-pub fn print_comparison(pkg_name: &str, results: &[SpecResult]) {
-    let largest_size = results.iter().map(|r| r.size).max().unwrap_or(0);
-    let max_name_len = results.iter().map(|r| r.name.len()).max().unwrap_or(4);
+/// Pick the baseline row's index for delta/percent comparisons.
+///
+/// `baseline_spec`, when given, selects the first row whose name contains it
+/// as a substring (so `--baseline-spec tspec.min` matches
+/// `tspec.min.toml [abcd1234]`). Falls back to the first row — with a notice
+/// printed — when nothing matches, and to the first row unconditionally when
+/// `baseline_spec` is `None`.
+fn select_baseline(results: &[SpecResult], baseline_spec: Option<&str>) -> usize {
+    if let Some(needle) = baseline_spec
+        && let Some(idx) = results.iter().position(|r| r.name.contains(needle))
+    {
+        return idx;
+    }
+    if let Some(needle) = baseline_spec {
+        println!("  (no spec matching baseline '{needle}', using first row as baseline)");
+    }
+    0
+}
 
-    // Format percent change: show reduction with minus sign, baseline as 0.0%
-    let fmt_pct = |size: u64| -> String {
-        if largest_size == 0 {
-            return "   0.0%".to_string();
-        }
-        let pct = ((largest_size as f64 - size as f64) / largest_size as f64) * 100.0;
-        if pct > 0.0 {
-            format!("{:>7.1}%", -pct)
+/// Format a size delta relative to a baseline as `-12.3K (-18.0%)`.
+///
+/// The baseline row itself prints `(baseline)`. A zero-size baseline can't
+/// produce a percentage, so only the absolute delta (always 0 in that case
+/// since size is unsigned) is shown.
+fn fmt_delta(size: u64, baseline_size: u64) -> String {
+    if size == baseline_size {
+        return "(baseline)".to_string();
+    }
+
+    let delta = size as i64 - baseline_size as i64;
+    let sign = if delta < 0 { "-" } else { "+" };
+    let delta_str = format!("{sign}{}", format_size(delta.unsigned_abs()));
+
+    if baseline_size == 0 {
+        return delta_str;
+    }
+
+    let pct = (delta as f64 / baseline_size as f64) * 100.0;
+    format!("{delta_str} ({sign}{:.1}%)", pct.abs())
+}
+
+pub fn print_comparison(pkg_name: &str, results: &[SpecResult], baseline_spec: Option<&str>) {
+    let max_name_len = results.iter().map(|r| r.name.width()).max().unwrap_or(4);
+    let show_segments = results.iter().any(|r| r.segments.is_some());
+    let show_tests = results.iter().any(|r| r.tests.is_some());
+    // A spec only ever resolves to one triple today, so this column stays
+    // hidden until that changes and a `compare` run actually mixes triples
+    // (see `SpecResult::triple`); the table degrades to today's single Size
+    // column whenever every row shares one triple.
+    let show_triples = results
+        .iter()
+        .map(|r| &r.triple)
+        .collect::<std::collections::HashSet<_>>()
+        .len()
+        > 1;
+    let baseline_size = results
+        .get(select_baseline(results, baseline_spec))
+        .map(|r| r.size)
+        .unwrap_or(0);
+
+    let fmt_opt_size = |segs: Option<ElfSegments>, field: fn(ElfSegments) -> u64| -> String {
+        segs.map(field)
+            .map(format_size)
+            .unwrap_or_else(|| "-".to_string())
+    };
+    let fmt_stripped = |stripped_size: Option<u64>| -> String {
+        stripped_size
+            .map(format_size)
+            .unwrap_or_else(|| "-".to_string())
+    };
+    let max_tests_len = if show_tests {
+        results
+            .iter()
+            .map(|r| fmt_test_column(r.tests.as_ref()).len())
+            .max()
+            .unwrap_or(5)
+            .max(5)
+    } else {
+        0
+    };
+    let max_triple_len = if show_triples {
+        results
+            .iter()
+            .map(|r| r.triple.len())
+            .max()
+            .unwrap_or(6)
+            .max(6)
+    } else {
+        0
+    };
+    let triple_header = |triple: &str| {
+        if show_triples {
+            format!("  {:tw$}", triple, tw = max_triple_len)
         } else {
-            "   0.0%".to_string()
+            String::new()
         }
     };
 
     println!();
     print_header!(format!("{} COMPARE SUMMARY", pkg_name));
+    if show_segments {
+        println!(
+            "  {}{triple_col}  {:>10}  {:>10}  {:>18}  {:>10}  {:>10}  {:>10}{tests_header}",
+            pad_display("Spec", max_name_len),
+            "Size",
+            "Stripped",
+            "Change",
+            "Flash",
+            "RAM",
+            "BSS",
+            triple_col = triple_header("Triple"),
+            tests_header = if show_tests {
+                format!("  {:tw$}", "Tests", tw = max_tests_len)
+            } else {
+                String::new()
+            }
+        );
+        for result in results {
+            println!(
+                "  {}{triple_col}  {:>10}  {:>10}  {:>18}  {:>10}  {:>10}  {:>10}{tests_col}",
+                pad_display(&result.name, max_name_len),
+                format_size(result.size),
+                fmt_stripped(result.stripped_size),
+                fmt_delta(result.size, baseline_size),
+                fmt_opt_size(result.segments, |s| s.flash),
+                fmt_opt_size(result.segments, |s| s.ram),
+                fmt_opt_size(result.segments, |s| s.bss),
+                triple_col = triple_header(&result.triple),
+                tests_col = if show_tests {
+                    format!(
+                        "  {:tw$}",
+                        fmt_test_column(result.tests.as_ref()),
+                        tw = max_tests_len
+                    )
+                } else {
+                    String::new()
+                }
+            );
+        }
+    } else {
+        println!(
+            "  {}{triple_col}  {:>10}  {:>10}  {:>18}{tests_header}",
+            pad_display("Spec", max_name_len),
+            "Size",
+            "Stripped",
+            "Change",
+            triple_col = triple_header("Triple"),
+            tests_header = if show_tests {
+                format!("  {:tw$}", "Tests", tw = max_tests_len)
+            } else {
+                String::new()
+            }
+        );
+        for result in results {
+            println!(
+                "  {}{triple_col}  {:>10}  {:>10}  {:>18}{tests_col}",
+                pad_display(&result.name, max_name_len),
+                format_size(result.size),
+                fmt_stripped(result.stripped_size),
+                fmt_delta(result.size, baseline_size),
+                triple_col = triple_header(&result.triple),
+                tests_col = if show_tests {
+                    format!(
+                        "  {:tw$}",
+                        fmt_test_column(result.tests.as_ref()),
+                        tw = max_tests_len
+                    )
+                } else {
+                    String::new()
+                }
+            );
+        }
+    }
+    print_hline!();
+    println!();
+}
+
+/// Quote a CSV field per RFC 4180: wrap in double quotes (doubling any
+/// embedded quotes) whenever it contains a comma, quote, or newline —
+/// spec names can legitimately contain commas (e.g. a taplo-style label).
+fn csv_field(s: &str) -> String {
+    if s.contains([',', '"', '\n']) {
+        format!("\"{}\"", s.replace('"', "\"\""))
+    } else {
+        s.to_string()
+    }
+}
+
+/// Render `results` as CSV: `spec,hash,size_bytes`, plus `flash,ram,bss`
+/// columns when any row carries segment stats (`--segments`). `spec` is the
+/// bare name with any trailing `[hash]` split into its own `hash` column.
+///
+/// Pure so `--csv` is testable without a real build.
+pub(crate) fn render_csv(results: &[SpecResult]) -> String {
+    let show_segments = results.iter().any(|r| r.segments.is_some());
+    let mut out = String::from("spec,hash,size_bytes");
+    if show_segments {
+        out.push_str(",flash,ram,bss");
+    }
+    out.push('\n');
+    for result in results {
+        let (spec, hash) = split_name_hash(&result.name);
+        out.push_str(&csv_field(&spec));
+        out.push(',');
+        out.push_str(&hash.unwrap_or_default());
+        out.push(',');
+        out.push_str(&result.size.to_string());
+        if show_segments {
+            let seg = |field: fn(ElfSegments) -> u64| {
+                result
+                    .segments
+                    .map(field)
+                    .map(|v| v.to_string())
+                    .unwrap_or_default()
+            };
+            out.push(',');
+            out.push_str(&seg(|s| s.flash));
+            out.push(',');
+            out.push_str(&seg(|s| s.ram));
+            out.push(',');
+            out.push_str(&seg(|s| s.bss));
+        }
+        out.push('\n');
+    }
+    out
+}
+
+/// Print a baseline-vs-current delta table: size delta (absolute and
+/// percent), a `HASH CHANGED` marker, and explicit `(missing)`/`(new)` rows
+/// for specs only on one side.
+pub fn print_baseline_diff(label: &str, rows: &[BaselineDiffRow]) {
+    let max_name_len = rows.iter().map(|r| r.spec.width()).max().unwrap_or(4);
+
+    println!();
+    print_header!(format!("BASELINE DIFF vs {label}"));
     println!(
-        "  {:width$}  {:>10}  {:>8}",
-        "Spec",
-        "Size",
-        "Change",
-        width = max_name_len
+        "  {}  {:>12}  {:>12}  {:>18}  {:>14}",
+        pad_display("Spec", max_name_len),
+        "Baseline",
+        "Current",
+        "Delta",
+        "Hash",
     );
-    for result in results {
+    for row in rows {
+        let baseline_col = row
+            .baseline_size
+            .map(format_size)
+            .unwrap_or_else(|| "(new)".to_string());
+        let current_col = row
+            .current_size
+            .map(format_size)
+            .unwrap_or_else(|| "(missing)".to_string());
+        let delta_col = match row.delta() {
+            Some(0) => "(unchanged)".to_string(),
+            Some(delta) => {
+                let sign = if delta < 0 { "-" } else { "+" };
+                match row.percent() {
+                    Some(pct) => format!(
+                        "{sign}{} ({sign}{:.1}%)",
+                        format_size(delta.unsigned_abs()),
+                        pct.abs()
+                    ),
+                    None => format!("{sign}{}", format_size(delta.unsigned_abs())),
+                }
+            }
+            None => "-".to_string(),
+        };
+        let hash_col = if row.hash_changed { "CHANGED" } else { "-" };
         println!(
-            "  {:width$}  {:>10}  {}",
-            result.name,
-            format_size(result.size),
-            fmt_pct(result.size),
-            width = max_name_len
+            "  {}  {:>12}  {:>12}  {:>18}  {:>14}",
+            pad_display(&row.spec, max_name_len),
+            baseline_col,
+            current_col,
+            delta_col,
+            hash_col,
         );
     }
     print_hline!();
@@ -149,3 +741,347 @@ fn format_size(bytes: u64) -> String {
         format!("{}", bytes)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn result(name: &str, size: u64) -> SpecResult {
+        SpecResult {
+            name: name.to_string(),
+            size,
+            stripped_size: None,
+            triple: HOST_TRIPLE.to_string(),
+            segments: None,
+            tests: None,
+        }
+    }
+
+    #[test]
+    fn fmt_delta_baseline_row_itself() {
+        assert_eq!(fmt_delta(1000, 1000), "(baseline)");
+    }
+
+    #[test]
+    fn filter_changed_specs_keeps_only_matching_filenames() {
+        let specs = vec![
+            Path::new("/app/tspec.ts.toml").to_path_buf(),
+            Path::new("/app/tspec.min.ts.toml").to_path_buf(),
+        ];
+        let changed = vec!["app/tspec.min.ts.toml".to_string()];
+        let kept = filter_changed_specs(&specs, &changed);
+        assert_eq!(
+            kept,
+            vec![Path::new("/app/tspec.min.ts.toml").to_path_buf()]
+        );
+    }
+
+    #[test]
+    fn filter_changed_specs_empty_when_nothing_changed() {
+        let specs = vec![Path::new("/app/tspec.ts.toml").to_path_buf()];
+        let changed = vec!["app/src/main.rs".to_string()];
+        assert!(filter_changed_specs(&specs, &changed).is_empty());
+    }
+
+    #[test]
+    fn fmt_delta_reduction() {
+        // 12300 vs baseline 15000 -> -2.700K (-18.0%)
+        assert_eq!(fmt_delta(12_300, 15_000), "-2.700K (-18.0%)");
+    }
+
+    #[test]
+    fn fmt_delta_increase() {
+        assert_eq!(fmt_delta(1_100, 1_000), "+100 (+10.0%)");
+    }
+
+    #[test]
+    fn fmt_delta_zero_baseline_skips_percent() {
+        assert_eq!(fmt_delta(500, 0), "+500");
+    }
+
+    #[test]
+    fn select_baseline_defaults_to_first() {
+        let results = vec![result("a", 100), result("b", 50)];
+        assert_eq!(select_baseline(&results, None), 0);
+    }
+
+    #[test]
+    fn select_baseline_matches_by_substring() {
+        let results = vec![
+            result("tspec.min.toml [abcd1234]", 100),
+            result("tspec.max.toml [deadbeef]", 200),
+        ];
+        assert_eq!(select_baseline(&results, Some("tspec.max")), 1);
+    }
+
+    #[test]
+    fn select_baseline_falls_back_when_unmatched() {
+        let results = vec![result("a", 100), result("b", 50)];
+        assert_eq!(select_baseline(&results, Some("nonexistent")), 0);
+    }
+
+    #[test]
+    fn format_size_thresholds() {
+        assert_eq!(format_size(999), "999");
+        assert_eq!(format_size(1_000), "1.000K");
+        assert_eq!(format_size(1_000_000), "1.000M");
+    }
+
+    fn counts(passed: u32, failed: u32) -> TestResult {
+        TestResult {
+            passed,
+            failed,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn fmt_test_column_none_is_dash() {
+        assert_eq!(fmt_test_column(None), "-");
+    }
+
+    #[test]
+    fn fmt_test_column_skip_when_nothing_ran() {
+        assert_eq!(fmt_test_column(Some(&TestResult::default())), "[SKIP]");
+    }
+
+    #[test]
+    fn fmt_test_column_pass() {
+        assert_eq!(fmt_test_column(Some(&counts(34, 0))), "[PASS 34]");
+    }
+
+    #[test]
+    fn fmt_test_column_fail() {
+        assert_eq!(fmt_test_column(Some(&counts(10, 2))), "[FAIL 2]");
+    }
+
+    #[test]
+    fn fmt_test_column_counts_doctests() {
+        let mut c = counts(5, 0);
+        c.doc_passed = 3;
+        assert_eq!(fmt_test_column(Some(&c)), "[PASS 8]");
+    }
+
+    #[test]
+    fn tests_failed_none_is_not_required() {
+        assert!(!tests_failed(None));
+    }
+
+    #[test]
+    fn tests_failed_when_tests_failed() {
+        assert!(tests_failed(Some(&counts(10, 2))));
+    }
+
+    #[test]
+    fn tests_failed_when_nothing_ran() {
+        assert!(tests_failed(Some(&TestResult::default())));
+    }
+
+    #[test]
+    fn tests_failed_false_when_all_passed() {
+        assert!(!tests_failed(Some(&counts(34, 0))));
+    }
+
+    #[test]
+    fn require_pass_sorts_failing_specs_after_smaller_passing_ones() {
+        // A tiny failing spec should not outrank a larger passing one once
+        // --require-pass is in effect.
+        let mut results = [
+            SpecResult {
+                name: "tiny-but-broken".to_string(),
+                size: 10,
+                stripped_size: None,
+                triple: HOST_TRIPLE.to_string(),
+                segments: None,
+                tests: Some(counts(0, 1)),
+            },
+            SpecResult {
+                name: "bigger-and-passing".to_string(),
+                size: 100,
+                stripped_size: None,
+                triple: HOST_TRIPLE.to_string(),
+                segments: None,
+                tests: Some(counts(5, 0)),
+            },
+        ];
+        results.sort_by_key(|r| (tests_failed(r.tests.as_ref()), r.size));
+        assert_eq!(results[0].name, "bigger-and-passing");
+        assert_eq!(results[1].name, "tiny-but-broken");
+    }
+
+    #[test]
+    fn print_comparison_tests_column_width_accounts_for_longest_value() {
+        // Regression guard for the Tests column's width calculation: a
+        // 3-digit pass count must not be truncated against the "Tests"
+        // header width.
+        let results = vec![
+            SpecResult {
+                name: "a".to_string(),
+                size: 10,
+                stripped_size: None,
+                triple: HOST_TRIPLE.to_string(),
+                segments: None,
+                tests: Some(counts(123, 0)),
+            },
+            SpecResult {
+                name: "b".to_string(),
+                size: 20,
+                stripped_size: None,
+                triple: HOST_TRIPLE.to_string(),
+                segments: None,
+                tests: Some(counts(1, 0)),
+            },
+        ];
+        let widest = results
+            .iter()
+            .map(|r| fmt_test_column(r.tests.as_ref()).len())
+            .max()
+            .unwrap();
+        assert_eq!(widest, "[PASS 123]".len());
+        // Smoke-test that rendering with the wider value doesn't panic.
+        print_comparison("pkg", &results, None);
+    }
+
+    #[test]
+    fn two_results_with_different_triples_are_both_kept() {
+        // No spec in this codebase resolves to more than one triple today
+        // (see `SpecResult::triple`), but two results from separate specs
+        // that each target a different triple must still both come through
+        // `compare_specs`'s results intact, and `print_comparison` must
+        // render both without panicking.
+        let mut a = result("arm-spec", 100);
+        a.triple = "aarch64-unknown-linux-gnu".to_string();
+        let mut b = result("x86-spec", 200);
+        b.triple = "x86_64-unknown-linux-gnu".to_string();
+        let results = vec![a, b];
+        assert_eq!(results[0].triple, "aarch64-unknown-linux-gnu");
+        assert_eq!(results[1].triple, "x86_64-unknown-linux-gnu");
+        assert_ne!(results[0].triple, results[1].triple);
+        print_comparison("pkg", &results, None);
+    }
+
+    #[test]
+    fn print_comparison_renders_stripped_size_column() {
+        // Fake before/after sizes: `size` is what the build produced and
+        // `stripped_size` is the further-strippable size, so both must
+        // reach the renderer without needing a real build.
+        let mut a = result("tspec.ts.toml [abcd1234]", 15_000);
+        a.stripped_size = Some(9_500);
+        let mut b = result("cargo --release", 20_000);
+        b.stripped_size = Some(11_200);
+        let results = vec![a, b];
+        assert_eq!(results[0].stripped_size, Some(9_500));
+        assert_eq!(results[1].stripped_size, Some(11_200));
+        // Smoke-test that rendering with real stripped sizes doesn't panic.
+        print_comparison("pkg", &results, None);
+    }
+
+    #[test]
+    fn duplicate_groups_finds_matching_hashes() {
+        let specs = vec![
+            ("a.ts.toml".to_string(), Some("hash1".to_string())),
+            ("b.ts.toml".to_string(), Some("hash2".to_string())),
+            ("c.ts.toml".to_string(), Some("hash1".to_string())),
+        ];
+        let groups = duplicate_groups(&specs);
+        assert_eq!(
+            groups,
+            vec![vec!["a.ts.toml".to_string(), "c.ts.toml".to_string()]]
+        );
+    }
+
+    #[test]
+    fn duplicate_groups_empty_when_all_unique() {
+        let specs = vec![
+            ("a.ts.toml".to_string(), Some("hash1".to_string())),
+            ("b.ts.toml".to_string(), Some("hash2".to_string())),
+        ];
+        assert!(duplicate_groups(&specs).is_empty());
+    }
+
+    #[test]
+    fn duplicate_groups_ignores_unhashed_specs() {
+        // A spec that failed to load has no hash and never groups with
+        // another unhashed spec, even though both are `None`.
+        let specs = vec![
+            ("a.ts.toml".to_string(), None),
+            ("b.ts.toml".to_string(), None),
+        ];
+        assert!(duplicate_groups(&specs).is_empty());
+    }
+
+    #[test]
+    fn duplicate_groups_handles_three_way_group() {
+        let specs = vec![
+            ("a.ts.toml".to_string(), Some("hash1".to_string())),
+            ("b.ts.toml".to_string(), Some("hash1".to_string())),
+            ("c.ts.toml".to_string(), Some("hash1".to_string())),
+        ];
+        let groups = duplicate_groups(&specs);
+        assert_eq!(
+            groups,
+            vec![vec![
+                "a.ts.toml".to_string(),
+                "b.ts.toml".to_string(),
+                "c.ts.toml".to_string(),
+            ]]
+        );
+    }
+
+    #[test]
+    fn print_comparison_renders_same_as_attribution_without_panicking() {
+        // Regression guard for the "(same as X)" naming convention that
+        // `compare_specs` applies to reused (deduplicated) builds - the
+        // table renderer just treats it as an ordinary (longer) name.
+        let results = vec![
+            result("tspec.a.ts.toml [abcd1234]", 100),
+            result(
+                "tspec.b.ts.toml [abcd1234] (same as tspec.a.ts.toml [abcd1234])",
+                100,
+            ),
+        ];
+        print_comparison("pkg", &results, None);
+    }
+
+    #[test]
+    fn render_csv_splits_name_into_spec_and_hash() {
+        let results = vec![result("tspec.min.ts.toml [abcd1234]", 1_000)];
+        assert_eq!(
+            render_csv(&results),
+            "spec,hash,size_bytes\ntspec.min.ts.toml,abcd1234,1000\n"
+        );
+    }
+
+    #[test]
+    fn render_csv_leaves_hash_blank_for_baseline_rows() {
+        let results = vec![result("cargo --release", 500)];
+        assert_eq!(
+            render_csv(&results),
+            "spec,hash,size_bytes\ncargo --release,,500\n"
+        );
+    }
+
+    #[test]
+    fn render_csv_quotes_names_containing_a_comma() {
+        let results = vec![result("weird, name.ts.toml", 42)];
+        assert_eq!(
+            render_csv(&results),
+            "spec,hash,size_bytes\n\"weird, name.ts.toml\",,42\n"
+        );
+    }
+
+    #[test]
+    fn render_csv_adds_section_columns_when_segments_present() {
+        let mut r = result("tspec.ts.toml [deadbeef]", 2_000);
+        r.segments = Some(ElfSegments {
+            flash: 1_500,
+            ram: 300,
+            bss: 200,
+        });
+        let results = vec![r];
+        assert_eq!(
+            render_csv(&results),
+            "spec,hash,size_bytes,flash,ram,bss\ntspec.ts.toml,deadbeef,2000,1500,300,200\n"
+        );
+    }
+}
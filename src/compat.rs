@@ -0,0 +1,55 @@
+//! Per-package spec compatibility list.
+//!
+//! A package's `compat.toml` (hand-maintained for now — there are no
+//! `tspec compat` management commands yet) records spec hashes known to be
+//! broken for that package, so `-w` builds can skip them with
+//! `--only-compatible` instead of re-discovering the failure every time.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+/// Filename for a package's list of known-incompatible spec hashes.
+pub const COMPAT_FILE: &str = "compat.toml";
+
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+struct CompatList {
+    #[serde(default)]
+    incompatible: Vec<String>,
+}
+
+/// Whether `hash` is on `package_dir`'s incompatible list (from
+/// `compat.toml`, if present). Returns `false` when no compat.toml exists.
+pub fn is_incompatible(package_dir: &Path, hash: &str) -> Result<bool> {
+    let path = package_dir.join(COMPAT_FILE);
+    if !path.exists() {
+        return Ok(false);
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let list: CompatList =
+        toml::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))?;
+    Ok(list.incompatible.iter().any(|h| h == hash))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_file_is_compatible() {
+        let dir = tempfile::tempdir().unwrap();
+        assert!(!is_incompatible(dir.path(), "abc123").unwrap());
+    }
+
+    #[test]
+    fn hash_on_list_is_incompatible() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(COMPAT_FILE),
+            "incompatible = [\"abc123\"]\n",
+        )
+        .unwrap();
+        assert!(is_incompatible(dir.path(), "abc123").unwrap());
+        assert!(!is_incompatible(dir.path(), "def456").unwrap());
+    }
+}
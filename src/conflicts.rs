@@ -0,0 +1,518 @@
+//! Detect the same build knob being set through more than one spec channel
+//! with differing values.
+//!
+//! A spec can set `opt-level`, `panic`, `lto`, `codegen-units`, or `strip`
+//! through `cargo.config`/`profile_overrides` (translated into `--config`
+//! args), through the high-level `panic`/`strip` fields, or through a raw
+//! `-C` flag in `rustflags` — and cargo/rustc's precedence between those
+//! channels is easy to get wrong. This module normalizes all of them into a
+//! single "effective settings with sources" view and flags the knobs where
+//! the channels disagree.
+
+use std::collections::{BTreeMap, BTreeSet};
+
+use crate::types::{Spec, flatten_config, profile_override_config_args};
+
+/// One channel's contribution to a knob.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettingSource {
+    /// Where the value came from, e.g. `"cargo.config.profile.release.opt-level"`.
+    pub source: String,
+    pub value: String,
+}
+
+/// A knob set through more than one channel with differing values.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SettingConflict {
+    pub key: &'static str,
+    /// All contributing sources, in ascending precedence order.
+    pub sources: Vec<SettingSource>,
+    /// The source whose value actually takes effect (last in `sources`).
+    pub effective: SettingSource,
+}
+
+/// Knobs this analyzer understands, matched by the last dotted segment of a
+/// `--config` key or the key half of a `-C key=value` rustflag.
+const KNOBS: &[&str] = &["opt-level", "panic", "lto", "codegen-units", "strip"];
+
+/// Parse a `-C key=value` (or `-Ckey=value`) rustc flag into `(key, value)`.
+fn parse_dash_c(flag: &str) -> Option<(&str, &str)> {
+    let rest = flag
+        .strip_prefix("-C")
+        .or_else(|| flag.strip_prefix("-c"))?;
+    rest.trim_start().split_once('=')
+}
+
+/// Knobs set via `cargo.config`, `profile_overrides`, and the
+/// `cargo.opt_level_deps` shorthand — all three end up as `--config`
+/// key/value pairs applied before `RUSTFLAGS`, so they're the lowest
+/// precedence channel.
+fn config_sources(spec: &Spec) -> BTreeMap<&'static str, SettingSource> {
+    let mut found = BTreeMap::new();
+
+    for (key, value) in flatten_config(&spec.cargo.config) {
+        let last_segment = key.rsplit('.').next().unwrap_or(&key);
+        if let Some(&knob) = KNOBS.iter().find(|&&k| k == last_segment) {
+            found.insert(
+                knob,
+                SettingSource {
+                    source: format!("cargo.config.{key}"),
+                    value,
+                },
+            );
+        }
+    }
+
+    if let Ok(overrides) = profile_override_config_args(&spec.profile_overrides) {
+        for (key, value) in overrides {
+            let last_segment = key.rsplit('.').next().unwrap_or(&key);
+            if let Some(&knob) = KNOBS.iter().find(|&&k| k == last_segment) {
+                found.insert(
+                    knob,
+                    SettingSource {
+                        source: format!("profile_overrides ({key})"),
+                        value,
+                    },
+                );
+            }
+        }
+    }
+
+    if let Some(opt_level) = &spec.cargo.opt_level_deps {
+        found.insert(
+            "opt-level",
+            SettingSource {
+                source: "cargo.opt_level_deps".to_string(),
+                value: opt_level.to_string(),
+            },
+        );
+    }
+
+    found
+}
+
+/// Knobs set via the high-level `panic`/`strip` fields — translated into
+/// `-C` flags ahead of the explicit `rustflags` list (see
+/// `resolve_base_rustflags`), so an explicit `rustflags` entry for the same
+/// knob overrides one of these.
+fn high_level_sources(spec: &Spec) -> BTreeMap<&'static str, SettingSource> {
+    let mut found = BTreeMap::new();
+
+    if let Some(panic) = spec.panic
+        && let Some(value) = panic.rustc_panic_value()
+    {
+        found.insert(
+            "panic",
+            SettingSource {
+                source: "panic".to_string(),
+                value: value.to_string(),
+            },
+        );
+    }
+
+    if let Some(strip) = spec.strip
+        && let Some(value) = strip.rustc_strip_value()
+    {
+        found.insert(
+            "strip",
+            SettingSource {
+                source: "strip".to_string(),
+                value: value.to_string(),
+            },
+        );
+    }
+
+    found
+}
+
+/// Knobs set via a raw `-C key=value` entry in `rustflags`. Explicit
+/// `rustflags` entries are appended last in `RUSTFLAGS`, so they win over
+/// every other channel for the same knob.
+fn rustflags_sources(spec: &Spec) -> BTreeMap<&'static str, SettingSource> {
+    let mut found = BTreeMap::new();
+
+    for flag in &spec.rustflags {
+        if let Some((key, value)) = parse_dash_c(flag)
+            && let Some(&knob) = KNOBS.iter().find(|&&k| k == key)
+        {
+            found.insert(
+                knob,
+                SettingSource {
+                    source: "rustflags".to_string(),
+                    value: value.to_string(),
+                },
+            );
+        }
+    }
+
+    found
+}
+
+/// Link args contributed by `linker.args` (translated into a per-bin
+/// `rustc-link-arg-bin` by the generated `build.rs`) and by `-C link-arg=`
+/// entries in `rustflags` — two different mechanisms that both end up on the
+/// final link line, easy to lose track of when used together.
+fn link_arg_sources(spec: &Spec) -> Vec<SettingSource> {
+    let mut found = Vec::new();
+
+    if !spec.linker.args.is_empty() {
+        found.push(SettingSource {
+            source: "linker.args".to_string(),
+            value: spec.linker.args.join(" "),
+        });
+    }
+
+    let from_rustflags: Vec<&str> = spec
+        .rustflags
+        .iter()
+        .filter_map(|f| parse_dash_c(f))
+        .filter(|(key, _)| *key == "link-arg" || *key == "link-args")
+        .map(|(_, value)| value)
+        .collect();
+    if !from_rustflags.is_empty() {
+        found.push(SettingSource {
+            source: "rustflags".to_string(),
+            value: from_rustflags.join(" "),
+        });
+    }
+
+    found
+}
+
+/// Detect every knob set through more than one channel with differing
+/// values. Channels are merged in ascending precedence order (`cargo.config`
+/// / `profile_overrides` / `opt_level_deps`, then `panic`/`strip`, then
+/// explicit `rustflags`), so `SettingConflict::effective` always names the
+/// source that wins according to that precedence.
+pub fn detect_conflicts(spec: &Spec) -> Vec<SettingConflict> {
+    let mut conflicts = Vec::new();
+    let channels = [
+        config_sources(spec),
+        high_level_sources(spec),
+        rustflags_sources(spec),
+    ];
+
+    for &knob in KNOBS {
+        let sources: Vec<SettingSource> = channels
+            .iter()
+            .filter_map(|channel| channel.get(knob).cloned())
+            .collect();
+
+        let distinct_values: BTreeSet<&String> = sources.iter().map(|s| &s.value).collect();
+        if sources.len() < 2 || distinct_values.len() < 2 {
+            continue;
+        }
+
+        let effective = sources.last().expect("checked len >= 2").clone();
+        conflicts.push(SettingConflict {
+            key: knob,
+            sources,
+            effective,
+        });
+    }
+
+    let link_args = link_arg_sources(spec);
+    if link_args.len() >= 2 {
+        let effective = link_args.last().expect("checked len >= 2").clone();
+        conflicts.push(SettingConflict {
+            key: "link-arg",
+            sources: link_args,
+            effective,
+        });
+    }
+
+    conflicts
+}
+
+/// The `codegen-units` and `lto` settings a build would actually use,
+/// resolved across every channel in the same precedence order
+/// `detect_conflicts` uses. For `tspec explain-path`'s "tying config to
+/// reality" report: `codegen_units` is the configured value whichever
+/// channel it came from, and `lto_forces_single_unit` flags the common trap
+/// of setting `codegen-units` to something other than 1 while `lto` is also
+/// enabled in a mode that ignores it.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct CodegenUnitsInfo {
+    pub codegen_units: Option<String>,
+    pub codegen_units_source: Option<String>,
+    pub lto: Option<String>,
+    pub lto_source: Option<String>,
+    pub lto_forces_single_unit: bool,
+}
+
+/// Resolve the effective `codegen-units`/`lto` settings for `spec`. See
+/// [`CodegenUnitsInfo`].
+pub fn effective_codegen_units(spec: &Spec) -> CodegenUnitsInfo {
+    let channels = [
+        config_sources(spec),
+        high_level_sources(spec),
+        rustflags_sources(spec),
+    ];
+    let resolve = |knob: &str| -> Option<SettingSource> {
+        channels
+            .iter()
+            .filter_map(|c| c.get(knob).cloned())
+            .next_back()
+    };
+
+    let codegen_units = resolve("codegen-units");
+    let lto = resolve("lto");
+    let lto_forces_single_unit = lto
+        .as_ref()
+        .is_some_and(|s| matches!(s.value.as_str(), "true" | "fat" | "yes" | "on"));
+
+    CodegenUnitsInfo {
+        codegen_units: codegen_units.as_ref().map(|s| s.value.clone()),
+        codegen_units_source: codegen_units.map(|s| s.source),
+        lto: lto.as_ref().map(|s| s.value.clone()),
+        lto_source: lto.map(|s| s.source),
+        lto_forces_single_unit,
+    }
+}
+
+/// Format a conflict as a human-readable warning line for
+/// `check_spec_misconfigurations`.
+pub fn format_conflict(conflict: &SettingConflict) -> String {
+    let sources = conflict
+        .sources
+        .iter()
+        .map(|s| format!("{}={}", s.source, s.value))
+        .collect::<Vec<_>>()
+        .join(", ");
+    format!(
+        "Warning: '{}' is set through multiple spec channels with differing values ({sources}); \
+         {} will take effect",
+        conflict.key, conflict.effective.source
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::options::{PanicMode, StripMode};
+    use crate::types::ConfigValue;
+
+    fn config_entry(key: &str, value: ConfigValue) -> (String, ConfigValue) {
+        (key.to_string(), value)
+    }
+
+    #[test]
+    fn no_conflicts_for_default_spec() {
+        assert!(detect_conflicts(&Spec::default()).is_empty());
+    }
+
+    #[test]
+    fn no_conflict_when_only_one_channel_sets_a_knob() {
+        let spec = Spec {
+            panic: Some(PanicMode::Abort),
+            ..Default::default()
+        };
+        assert!(detect_conflicts(&spec).is_empty());
+    }
+
+    #[test]
+    fn no_conflict_when_channels_agree() {
+        let mut spec = Spec {
+            panic: Some(PanicMode::Abort),
+            ..Default::default()
+        };
+        spec.rustflags.push("-C panic=abort".to_string());
+        assert!(detect_conflicts(&spec).is_empty());
+    }
+
+    #[test]
+    fn opt_level_conflict_between_config_and_rustflags() {
+        let mut spec = Spec::default();
+        spec.cargo.config.extend([config_entry(
+            "profile.release.opt-level",
+            ConfigValue::String("z".to_string()),
+        )]);
+        spec.rustflags.push("-C opt-level=2".to_string());
+
+        let conflicts = detect_conflicts(&spec);
+        assert_eq!(conflicts.len(), 1);
+        let conflict = &conflicts[0];
+        assert_eq!(conflict.key, "opt-level");
+        assert_eq!(conflict.sources.len(), 2);
+        assert_eq!(conflict.effective.source, "rustflags");
+        assert_eq!(conflict.effective.value, "2");
+    }
+
+    #[test]
+    fn panic_conflict_between_high_level_and_rustflags() {
+        let mut spec = Spec {
+            panic: Some(PanicMode::Abort),
+            ..Default::default()
+        };
+        spec.rustflags.push("-C panic=unwind".to_string());
+
+        let conflicts = detect_conflicts(&spec);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].key, "panic");
+        assert_eq!(conflicts[0].effective.source, "rustflags");
+        assert_eq!(conflicts[0].effective.value, "unwind");
+    }
+
+    #[test]
+    fn lto_conflict_between_config_and_rustflags() {
+        let mut spec = Spec::default();
+        spec.cargo
+            .config
+            .extend([config_entry("profile.release.lto", ConfigValue::Bool(true))]);
+        spec.rustflags.push("-C lto=off".to_string());
+
+        let conflicts = detect_conflicts(&spec);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].key, "lto");
+        assert_eq!(conflicts[0].effective.value, "off");
+    }
+
+    #[test]
+    fn codegen_units_conflict_between_profile_overrides_and_rustflags() {
+        let mut spec = Spec::default();
+        spec.profile_overrides.extend([config_entry(
+            "release.deps.codegen-units",
+            ConfigValue::Integer(16),
+        )]);
+        spec.rustflags.push("-C codegen-units=1".to_string());
+
+        let conflicts = detect_conflicts(&spec);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].key, "codegen-units");
+        assert_eq!(conflicts[0].effective.value, "1");
+    }
+
+    #[test]
+    fn strip_conflict_between_high_level_and_config() {
+        let mut spec = Spec {
+            strip: Some(StripMode::Symbols),
+            ..Default::default()
+        };
+        spec.cargo.config.extend([config_entry(
+            "profile.release.strip",
+            ConfigValue::String("none".to_string()),
+        )]);
+
+        let conflicts = detect_conflicts(&spec);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].key, "strip");
+        // strip high-level field outranks cargo.config (second channel merged).
+        assert_eq!(conflicts[0].effective.source, "strip");
+        assert_eq!(conflicts[0].effective.value, "symbols");
+    }
+
+    #[test]
+    fn link_arg_conflict_between_linker_args_and_rustflags() {
+        let mut spec = Spec::default();
+        spec.linker.args.push("-static".to_string());
+        spec.rustflags
+            .push("-C link-arg=-Wl,--gc-sections".to_string());
+
+        let conflicts = detect_conflicts(&spec);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].key, "link-arg");
+        assert_eq!(conflicts[0].sources.len(), 2);
+        assert_eq!(conflicts[0].effective.source, "rustflags");
+    }
+
+    #[test]
+    fn opt_level_deps_conflict_with_explicit_profile_override() {
+        let mut spec = Spec::default();
+        spec.cargo.opt_level_deps = Some(ConfigValue::String("s".to_string()));
+        spec.rustflags.push("-C opt-level=1".to_string());
+
+        let conflicts = detect_conflicts(&spec);
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].key, "opt-level");
+        assert_eq!(conflicts[0].effective.value, "1");
+    }
+
+    #[test]
+    fn effective_codegen_units_reports_unset_spec_as_none() {
+        let info = effective_codegen_units(&Spec::default());
+        assert_eq!(info, CodegenUnitsInfo::default());
+    }
+
+    #[test]
+    fn effective_codegen_units_reads_configured_value_and_source() {
+        let mut spec = Spec::default();
+        spec.cargo.config.extend([config_entry(
+            "profile.release.codegen-units",
+            ConfigValue::Integer(16),
+        )]);
+
+        let info = effective_codegen_units(&spec);
+        assert_eq!(info.codegen_units.as_deref(), Some("16"));
+        assert_eq!(
+            info.codegen_units_source.as_deref(),
+            Some("cargo.config.profile.release.codegen-units")
+        );
+        assert!(!info.lto_forces_single_unit);
+    }
+
+    #[test]
+    fn effective_codegen_units_flags_lto_overriding_nonunit_codegen_units() {
+        let mut spec = Spec::default();
+        spec.cargo.config.extend([
+            config_entry("profile.release.codegen-units", ConfigValue::Integer(16)),
+            config_entry("profile.release.lto", ConfigValue::Bool(true)),
+        ]);
+
+        let info = effective_codegen_units(&spec);
+        assert_eq!(info.codegen_units.as_deref(), Some("16"));
+        assert_eq!(info.lto.as_deref(), Some("true"));
+        assert!(info.lto_forces_single_unit);
+    }
+
+    #[test]
+    fn effective_codegen_units_thin_lto_does_not_force_single_unit() {
+        let mut spec = Spec::default();
+        spec.cargo.config.extend([config_entry(
+            "profile.release.lto",
+            ConfigValue::String("thin".to_string()),
+        )]);
+
+        let info = effective_codegen_units(&spec);
+        assert!(!info.lto_forces_single_unit);
+    }
+
+    #[test]
+    fn effective_codegen_units_prefers_rustflags_over_config() {
+        let mut spec = Spec::default();
+        spec.cargo.config.extend([config_entry(
+            "profile.release.codegen-units",
+            ConfigValue::Integer(16),
+        )]);
+        spec.rustflags.push("-C codegen-units=1".to_string());
+
+        let info = effective_codegen_units(&spec);
+        assert_eq!(info.codegen_units.as_deref(), Some("1"));
+        assert_eq!(info.codegen_units_source.as_deref(), Some("rustflags"));
+    }
+
+    #[test]
+    fn format_conflict_lists_all_sources_and_the_winner() {
+        let conflict = SettingConflict {
+            key: "opt-level",
+            sources: vec![
+                SettingSource {
+                    source: "cargo.config.profile.release.opt-level".to_string(),
+                    value: "z".to_string(),
+                },
+                SettingSource {
+                    source: "rustflags".to_string(),
+                    value: "2".to_string(),
+                },
+            ],
+            effective: SettingSource {
+                source: "rustflags".to_string(),
+                value: "2".to_string(),
+            },
+        };
+        let message = format_conflict(&conflict);
+        assert!(message.contains("opt-level"));
+        assert!(message.contains("cargo.config.profile.release.opt-level=z"));
+        assert!(message.contains("rustflags=2"));
+        assert!(message.contains("rustflags will take effect"));
+    }
+}
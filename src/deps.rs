@@ -0,0 +1,210 @@
+//! Dependency resolution and diffing for `tspec deps`.
+//!
+//! Resolves the dependency set via `cargo metadata`'s resolve graph rather
+//! than parsing `cargo tree`'s text output, which is easier to get wrong and
+//! harder to test. `--filter-platform <triple>` makes the resolve graph
+//! match what a real build under that target would pull in, so
+//! target-gated dependencies are included/excluded correctly.
+
+use anyhow::{Context, Result};
+use cargo_metadata::MetadataCommand;
+use serde::Serialize;
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::Path;
+
+/// One resolved dependency: a crate name at a specific version.
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+pub struct DepInfo {
+    pub name: String,
+    pub version: String,
+}
+
+/// Resolve the full dependency set of the package at `manifest_path`,
+/// filtered to `target_triple` the same way a build under that target
+/// would see it. `None` resolves the host-default graph.
+pub fn resolve_dependencies(
+    manifest_path: &Path,
+    target_triple: Option<&str>,
+) -> Result<Vec<DepInfo>> {
+    let mut cmd = MetadataCommand::new();
+    cmd.manifest_path(manifest_path);
+    if let Some(triple) = target_triple {
+        cmd.other_options(vec!["--filter-platform".to_string(), triple.to_string()]);
+    }
+    let metadata = cmd.exec().with_context(|| {
+        format!(
+            "failed to run cargo metadata for {}",
+            manifest_path.display()
+        )
+    })?;
+
+    let resolve = metadata
+        .resolve
+        .context("cargo metadata returned no resolve graph")?;
+    let live_ids: BTreeSet<_> = resolve.nodes.iter().map(|n| n.id.clone()).collect();
+
+    let mut deps: Vec<DepInfo> = metadata
+        .packages
+        .iter()
+        .filter(|pkg| live_ids.contains(&pkg.id))
+        .map(|pkg| DepInfo {
+            name: pkg.name.clone(),
+            version: pkg.version.to_string(),
+        })
+        .collect();
+    deps.sort();
+    deps.dedup();
+    Ok(deps)
+}
+
+/// A crate present on both sides of a diff but resolved to different
+/// version(s).
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct VersionDiff {
+    pub name: String,
+    pub versions_a: Vec<String>,
+    pub versions_b: Vec<String>,
+}
+
+/// Result of diffing two resolved dependency sets.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct DepsDiff {
+    pub only_a: Vec<DepInfo>,
+    pub only_b: Vec<DepInfo>,
+    pub version_diffs: Vec<VersionDiff>,
+    /// Crates present on both sides (including those in `version_diffs`).
+    pub common_count: usize,
+}
+
+/// Diff two resolved dependency sets: crates only in `a`, crates only in
+/// `b`, and crates present in both but at different version(s).
+///
+/// A crate name present on both sides counts as common as soon as at least
+/// one version is shared; any extra pinned versions on either side are
+/// reported per-version in `only_a`/`only_b` rather than as a version
+/// diff. Only a name whose resolved version sets don't overlap at all
+/// (e.g. a spec upgrading a dependency wholesale) becomes a `VersionDiff`.
+pub fn diff_dependencies(a: &[DepInfo], b: &[DepInfo]) -> DepsDiff {
+    let mut versions_a: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for d in a {
+        versions_a
+            .entry(&d.name)
+            .or_default()
+            .insert(d.version.as_str());
+    }
+    let mut versions_b: BTreeMap<&str, BTreeSet<&str>> = BTreeMap::new();
+    for d in b {
+        versions_b
+            .entry(&d.name)
+            .or_default()
+            .insert(d.version.as_str());
+    }
+
+    let mut diff = DepsDiff::default();
+    let all_names: BTreeSet<&str> = versions_a
+        .keys()
+        .chain(versions_b.keys())
+        .copied()
+        .collect();
+    for name in all_names {
+        match (versions_a.get(name), versions_b.get(name)) {
+            (Some(va), None) => diff.only_a.extend(va.iter().map(|v| DepInfo {
+                name: name.to_string(),
+                version: v.to_string(),
+            })),
+            (None, Some(vb)) => diff.only_b.extend(vb.iter().map(|v| DepInfo {
+                name: name.to_string(),
+                version: v.to_string(),
+            })),
+            (Some(va), Some(vb)) => {
+                diff.common_count += 1;
+                let shared = va.intersection(vb).next().is_some();
+                if shared {
+                    diff.only_a.extend(va.difference(vb).map(|v| DepInfo {
+                        name: name.to_string(),
+                        version: v.to_string(),
+                    }));
+                    diff.only_b.extend(vb.difference(va).map(|v| DepInfo {
+                        name: name.to_string(),
+                        version: v.to_string(),
+                    }));
+                } else {
+                    diff.version_diffs.push(VersionDiff {
+                        name: name.to_string(),
+                        versions_a: va.iter().map(|v| v.to_string()).collect(),
+                        versions_b: vb.iter().map(|v| v.to_string()).collect(),
+                    });
+                }
+            }
+            (None, None) => unreachable!("name came from the union of both key sets"),
+        }
+    }
+    diff
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn dep(name: &str, version: &str) -> DepInfo {
+        DepInfo {
+            name: name.to_string(),
+            version: version.to_string(),
+        }
+    }
+
+    #[test]
+    fn diff_dependencies_finds_only_a_and_only_b() {
+        let a = vec![dep("serde", "1.0.0"), dep("libc", "0.2.0")];
+        let b = vec![dep("serde", "1.0.0"), dep("rand", "0.8.0")];
+
+        let diff = diff_dependencies(&a, &b);
+        assert_eq!(diff.only_a, vec![dep("libc", "0.2.0")]);
+        assert_eq!(diff.only_b, vec![dep("rand", "0.8.0")]);
+        assert!(diff.version_diffs.is_empty());
+        assert_eq!(diff.common_count, 1);
+    }
+
+    #[test]
+    fn diff_dependencies_finds_version_mismatch() {
+        let a = vec![dep("serde", "1.0.0")];
+        let b = vec![dep("serde", "1.0.200")];
+
+        let diff = diff_dependencies(&a, &b);
+        assert!(diff.only_a.is_empty());
+        assert!(diff.only_b.is_empty());
+        assert_eq!(diff.common_count, 1);
+        assert_eq!(
+            diff.version_diffs,
+            vec![VersionDiff {
+                name: "serde".to_string(),
+                versions_a: vec!["1.0.0".to_string()],
+                versions_b: vec!["1.0.200".to_string()],
+            }]
+        );
+    }
+
+    #[test]
+    fn diff_dependencies_identical_sets_are_empty() {
+        let a = vec![dep("serde", "1.0.0"), dep("libc", "0.2.0")];
+        let diff = diff_dependencies(&a, &a.clone());
+        assert!(diff.only_a.is_empty());
+        assert!(diff.only_b.is_empty());
+        assert!(diff.version_diffs.is_empty());
+        assert_eq!(diff.common_count, 2);
+    }
+
+    #[test]
+    fn diff_dependencies_handles_multiple_resolved_versions_of_one_crate() {
+        // cargo's resolver can keep two semver-incompatible versions of the
+        // same crate alive at once (e.g. via different major versions).
+        let a = vec![dep("syn", "1.0.0"), dep("syn", "2.0.0")];
+        let b = vec![dep("syn", "2.0.0")];
+
+        let diff = diff_dependencies(&a, &b);
+        assert_eq!(diff.only_a, vec![dep("syn", "1.0.0")]);
+        assert!(diff.only_b.is_empty());
+        assert_eq!(diff.common_count, 1);
+        assert!(diff.version_diffs.is_empty());
+    }
+}
@@ -0,0 +1,305 @@
+//! Registry of real per-command example invocations.
+//!
+//! Each [`Example`] names the fixture (under `tests/fixtures`) it needs so
+//! [`run_check`] can copy that fixture into a scratch dir and actually run
+//! the example against it — `tspec build --help`'s examples can't drift out
+//! of sync with the CLI they document without a test failing.
+//!
+//! [`crate::cli`] renders [`registry`] entries into each subcommand's
+//! `after_help`; `tspec examples` (hidden, see `cmd::ExamplesCmd`) prints
+//! the same registry on demand and, with `--run-check`, drives this file's
+//! [`run_check`].
+
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// One example invocation for a command, e.g. `tspec build -p pop-fixture`.
+pub struct Example {
+    /// Args after `tspec`, e.g. `&["build", "-p", "pop-fixture"]`.
+    pub args: &'static [&'static str],
+    /// One-line explanation shown next to the example in `--help`.
+    pub explanation: &'static str,
+    /// Fixture directory name (under `tests/fixtures`) to run this example
+    /// against in `--run-check`. `None` for examples that don't touch a
+    /// package (e.g. `tspec version`).
+    pub fixture: Option<&'static str>,
+}
+
+/// Examples for one top-level subcommand, keyed by the name clap registers
+/// it under (matches `Commands::variant.command().get_name()`).
+pub struct CommandExamples {
+    pub command: &'static str,
+    pub examples: &'static [Example],
+}
+
+/// The example registry. Add an entry here to grow both `--help` output
+/// (via `crate::cli::augment_with_examples`) and `tspec examples`/
+/// `--run-check` coverage — nothing else to wire up.
+pub fn registry() -> &'static [CommandExamples] {
+    &[
+        CommandExamples {
+            command: "build",
+            examples: &[
+                Example {
+                    args: &["build", "-p", "pop-fixture"],
+                    explanation: "Build one package with its default spec",
+                    fixture: Some("pop"),
+                },
+                Example {
+                    args: &["build", "-w"],
+                    explanation: "Build every workspace member",
+                    fixture: Some("pows"),
+                },
+                Example {
+                    args: &["build", "-p", "pop-fixture", "--print-rustflags"],
+                    explanation: "Preview the RUSTFLAGS a spec resolves to, without building",
+                    fixture: Some("pop"),
+                },
+                Example {
+                    args: &["build", "-p", "pop-fixture", "-r"],
+                    explanation: "Release build, overriding the spec's own profile",
+                    fixture: Some("pop"),
+                },
+            ],
+        },
+        CommandExamples {
+            command: "test",
+            examples: &[
+                Example {
+                    args: &["test", "-p", "app-a"],
+                    explanation: "Run a package's tests with its default spec",
+                    fixture: Some("popws-3p"),
+                },
+                Example {
+                    args: &["test", "-w"],
+                    explanation: "Test every workspace member",
+                    fixture: Some("popws-3p"),
+                },
+                Example {
+                    args: &["test", "-p", "pop-fixture", "--list"],
+                    explanation: "List test targets and functions without running them",
+                    fixture: Some("pop"),
+                },
+            ],
+        },
+        CommandExamples {
+            command: "compare",
+            examples: &[
+                Example {
+                    args: &["compare", "-p", "pop-fixture"],
+                    explanation: "Compare a package's specs by binary size",
+                    fixture: Some("pop"),
+                },
+                Example {
+                    args: &["compare", "-w"],
+                    explanation: "Compare every workspace member's specs",
+                    fixture: Some("pows"),
+                },
+            ],
+        },
+        CommandExamples {
+            command: "ci",
+            examples: &[
+                Example {
+                    args: &["ci"],
+                    explanation: "Run the default fmt-check, clippy, build, test pipeline",
+                    fixture: Some("pop"),
+                },
+                Example {
+                    args: &["ci", "--stage", "build", "--stage", "test"],
+                    explanation: "Run only the selected stages, in order",
+                    fixture: Some("pop"),
+                },
+            ],
+        },
+        CommandExamples {
+            command: "ts",
+            examples: &[
+                Example {
+                    args: &["ts", "list", "-p", "pop-fixture"],
+                    explanation: "List a package's spec files",
+                    fixture: Some("pop"),
+                },
+                Example {
+                    args: &["ts", "show", "-p", "pop-fixture"],
+                    explanation: "Show a spec's resolved contents",
+                    fixture: Some("pop"),
+                },
+            ],
+        },
+    ]
+}
+
+/// Examples registered for `command`, if any.
+pub fn for_command(command: &str) -> Option<&'static CommandExamples> {
+    registry().iter().find(|c| c.command == command)
+}
+
+/// Render a command's examples as `--help` `after_help` text.
+pub fn render_after_help(examples: &CommandExamples) -> String {
+    let mut out = String::from("Examples:\n");
+    for example in examples.examples {
+        out.push_str(&format!(
+            "  tspec {}\n      {}\n",
+            example.args.join(" "),
+            example.explanation
+        ));
+    }
+    // Trim the trailing newline; clap adds its own spacing after after_help.
+    out.pop();
+    out
+}
+
+/// Outcome of running one example against its fixture.
+pub struct ExampleCheckResult {
+    pub command: &'static str,
+    pub args: &'static [&'static str],
+    pub passed: bool,
+    pub detail: String,
+}
+
+/// Recursively copy a directory, skipping `target/` subdirectories.
+///
+/// Mirrors `tests/fixture.rs::copy_dir_recursive` — that helper lives in the
+/// integration-test crate and isn't reachable from the library, so
+/// `--run-check` (a real `tspec` subcommand, not test-only code) carries its
+/// own copy.
+fn copy_dir_recursive(src: &Path, dst: &Path) -> std::io::Result<()> {
+    std::fs::create_dir_all(dst)?;
+    for entry in std::fs::read_dir(src)? {
+        let entry = entry?;
+        let file_name = entry.file_name();
+        if file_name == "target" {
+            continue;
+        }
+        let src_path = entry.path();
+        let dst_path = dst.join(&file_name);
+        if src_path.is_dir() {
+            copy_dir_recursive(&src_path, &dst_path)?;
+        } else {
+            std::fs::copy(&src_path, &dst_path)?;
+        }
+    }
+    Ok(())
+}
+
+/// Run every registered example against a fresh copy of its fixture,
+/// invoking the current `tspec` binary the same way a user would. Examples
+/// with no fixture (`fixture: None`) run from `fixtures_dir` itself.
+pub fn run_check(fixtures_dir: &Path) -> Result<Vec<ExampleCheckResult>> {
+    let tspec_bin = std::env::current_exe().context("failed to locate the running tspec binary")?;
+    let scratch = tempfile::tempdir().context("failed to create a scratch dir for --run-check")?;
+
+    let mut results = Vec::new();
+    for command_examples in registry() {
+        for example in command_examples.examples {
+            let cwd: PathBuf = match example.fixture {
+                Some(fixture) => {
+                    let src = fixtures_dir.join(fixture);
+                    if !src.is_dir() {
+                        results.push(ExampleCheckResult {
+                            command: command_examples.command,
+                            args: example.args,
+                            passed: false,
+                            detail: format!("fixture not found: {}", src.display()),
+                        });
+                        continue;
+                    }
+                    let dst = scratch.path().join(fixture).join(example.args.join("-"));
+                    copy_dir_recursive(&src, &dst).with_context(|| {
+                        format!("failed to copy fixture {fixture} into {}", dst.display())
+                    })?;
+                    dst
+                }
+                None => fixtures_dir.to_path_buf(),
+            };
+
+            let output = Command::new(&tspec_bin)
+                .args(example.args)
+                .current_dir(&cwd)
+                .output()
+                .with_context(|| format!("failed to run `tspec {}`", example.args.join(" ")))?;
+
+            results.push(ExampleCheckResult {
+                command: command_examples.command,
+                args: example.args,
+                passed: output.status.success(),
+                detail: if output.status.success() {
+                    "ok".to_string()
+                } else {
+                    String::from_utf8_lossy(&output.stderr).trim().to_string()
+                },
+            });
+        }
+    }
+    Ok(results)
+}
+
+/// Bail with a clear error if any check failed, after all have run.
+pub fn assert_all_passed(results: &[ExampleCheckResult]) -> Result<()> {
+    let failed: Vec<_> = results.iter().filter(|r| !r.passed).collect();
+    if failed.is_empty() {
+        return Ok(());
+    }
+    let mut msg = format!("{} example(s) failed --run-check:\n", failed.len());
+    for r in &failed {
+        msg.push_str(&format!("  tspec {}: {}\n", r.args.join(" "), r.detail));
+    }
+    bail!(msg.trim_end().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn every_example_with_a_fixture_names_an_existing_one() {
+        let fixtures_dir = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures");
+        for command_examples in registry() {
+            for example in command_examples.examples {
+                if let Some(fixture) = example.fixture {
+                    assert!(
+                        fixtures_dir.join(fixture).is_dir(),
+                        "example `tspec {}` names missing fixture {fixture}",
+                        example.args.join(" ")
+                    );
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn for_command_finds_registered_command() {
+        assert!(for_command("build").is_some());
+        assert!(for_command("no-such-command").is_none());
+    }
+
+    #[test]
+    fn render_after_help_lists_every_example() {
+        let examples = for_command("build").unwrap();
+        let rendered = render_after_help(examples);
+        assert!(rendered.starts_with("Examples:\n"));
+        for example in examples.examples {
+            assert!(rendered.contains(&example.args.join(" ")));
+            assert!(rendered.contains(example.explanation));
+        }
+    }
+
+    #[test]
+    fn assert_all_passed_ok_when_empty() {
+        assert!(assert_all_passed(&[]).is_ok());
+    }
+
+    #[test]
+    fn assert_all_passed_reports_failures() {
+        let results = vec![ExampleCheckResult {
+            command: "build",
+            args: &["build", "-p", "pop-fixture"],
+            passed: false,
+            detail: "boom".to_string(),
+        }];
+        let err = assert_all_passed(&results).unwrap_err();
+        assert!(err.to_string().contains("boom"));
+    }
+}
@@ -0,0 +1,307 @@
+//! Workspace-scoped temporary spec experiments (`tspec experiment ...`)
+//!
+//! An experiment is a tspec that lives outside a package's normal spec
+//! files, under `<package>/.tspec/experiments/NAME.ts.toml`, so scratch
+//! configs never show up in `ts list`/`ts new` output or get picked up by a
+//! default glob. It's selected anywhere a `-t`/tspec-name argument is
+//! accepted via the `@NAME` syntax, resolved by `find_tspec`/`find_tspecs`
+//! in `find_paths.rs`. `promote` turns an experiment into a normal spec;
+//! `discard` deletes it.
+
+use anyhow::{Context, Result, bail};
+use std::path::{Path, PathBuf};
+
+use crate::TSPEC_SUFFIX;
+use crate::tspec::serialize_spec;
+use crate::types::Spec;
+
+/// Directory (relative to a package dir) experiments are stored under.
+const EXPERIMENTS_DIR: &str = ".tspec/experiments";
+
+/// Prefix marking a tspec name as an experiment reference, e.g. `-t @scratch`.
+const EXPERIMENT_PREFIX: char = '@';
+
+/// The experiments directory for a package.
+fn experiments_dir(pkg_dir: &Path) -> PathBuf {
+    pkg_dir.join(EXPERIMENTS_DIR)
+}
+
+/// Resolve an experiment reference (a name starting with `@`) to the file
+/// path it would live at under `pkg_dir`'s experiments directory. Returns
+/// `None` if `name` doesn't start with `@`, in which case the caller should
+/// fall through to its normal (non-experiment) resolution.
+pub fn resolve_experiment_ref(pkg_dir: &Path, name: &str) -> Option<PathBuf> {
+    let stripped = name.strip_prefix(EXPERIMENT_PREFIX)?;
+    Some(experiment_path(pkg_dir, stripped))
+}
+
+/// Path an experiment named `name` lives (or would be created) at.
+fn experiment_path(pkg_dir: &Path, name: &str) -> PathBuf {
+    if name.contains('.') {
+        experiments_dir(pkg_dir).join(name)
+    } else {
+        experiments_dir(pkg_dir).join(format!("{}{}", name, TSPEC_SUFFIX))
+    }
+}
+
+/// Create a new experiment spec, optionally copied byte-for-byte from
+/// `from` (an existing spec's path). Adds the experiments directory to the
+/// workspace `.gitignore` if it isn't already covered.
+///
+/// There's no interactive-confirmation convention anywhere else in this
+/// codebase (see `ts set`'s `--if-unset` for the same shape of "would this
+/// silently do something surprising" concern, solved there with a flag
+/// instead of a prompt), so this follows suit: `yes` must be passed to
+/// write to `.gitignore`, otherwise this bails with the line to add.
+pub fn start_experiment(
+    workspace: &Path,
+    pkg_dir: &Path,
+    name: &str,
+    from: Option<&Path>,
+    yes: bool,
+) -> Result<PathBuf> {
+    let path = experiment_path(pkg_dir, name);
+    if path.exists() {
+        bail!("experiment '{}' already exists: {}", name, path.display());
+    }
+
+    ensure_gitignored(workspace, yes)?;
+
+    let dir = path.parent().expect("experiment path always has a parent");
+    std::fs::create_dir_all(dir)
+        .with_context(|| format!("failed to create directory: {}", dir.display()))?;
+
+    match from {
+        Some(source) => {
+            std::fs::copy(source, &path).with_context(|| {
+                format!("failed to copy {} to {}", source.display(), path.display())
+            })?;
+        }
+        None => {
+            std::fs::write(&path, serialize_spec(&Spec::default())?)
+                .with_context(|| format!("failed to write: {}", path.display()))?;
+        }
+    }
+
+    Ok(path)
+}
+
+/// List a package's experiment spec files, sorted by path.
+pub fn list_experiments(pkg_dir: &Path) -> Result<Vec<PathBuf>> {
+    let dir = experiments_dir(pkg_dir);
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let mut found = Vec::new();
+    for entry in std::fs::read_dir(&dir)
+        .with_context(|| format!("cannot read directory: {}", dir.display()))?
+    {
+        let entry =
+            entry.with_context(|| format!("cannot read directory entry in {}", dir.display()))?;
+        if entry.path().is_file() {
+            found.push(entry.path());
+        }
+    }
+    found.sort();
+    Ok(found)
+}
+
+/// Move an experiment into the package as a normal spec (byte-preserving,
+/// matching `ts backup`/`ts restore`'s raw-copy strategy). Bails if a spec
+/// already exists at the destination.
+pub fn promote_experiment(pkg_dir: &Path, name: &str) -> Result<PathBuf> {
+    let source = experiment_path(pkg_dir, name);
+    if !source.exists() {
+        bail!("experiment not found: {}", name);
+    }
+
+    let dest = pkg_dir.join(format!("{}{}", name, TSPEC_SUFFIX));
+    if dest.exists() {
+        bail!(
+            "cannot promote '{}': {} already exists",
+            name,
+            dest.display()
+        );
+    }
+
+    std::fs::copy(&source, &dest)
+        .with_context(|| format!("failed to copy {} to {}", source.display(), dest.display()))?;
+    std::fs::remove_file(&source)
+        .with_context(|| format!("failed to remove {}", source.display()))?;
+
+    Ok(dest)
+}
+
+/// Delete an experiment.
+pub fn discard_experiment(pkg_dir: &Path, name: &str) -> Result<PathBuf> {
+    let path = experiment_path(pkg_dir, name);
+    if !path.exists() {
+        bail!("experiment not found: {}", name);
+    }
+    std::fs::remove_file(&path).with_context(|| format!("failed to remove {}", path.display()))?;
+    Ok(path)
+}
+
+/// Add the experiments directory to the workspace `.gitignore` if it (or
+/// its `.tspec` parent) isn't already ignored. `yes` gates the write since
+/// there's no confirmation-prompt precedent to reuse (see `start_experiment`).
+fn ensure_gitignored(workspace: &Path, yes: bool) -> Result<()> {
+    let ignore_line = ".tspec/";
+    let gitignore_path = workspace.join(".gitignore");
+
+    let existing = std::fs::read_to_string(&gitignore_path).unwrap_or_default();
+    if existing
+        .lines()
+        .any(|l| matches!(l.trim(), ".tspec" | ".tspec/"))
+    {
+        return Ok(());
+    }
+
+    if !yes {
+        bail!(
+            "'{}' is not in .gitignore, and experiments must not be tracked. \
+             Re-run with --yes to add it automatically, or add it yourself.",
+            ignore_line
+        );
+    }
+
+    let mut content = existing;
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    content.push_str(ignore_line);
+    content.push('\n');
+    std::fs::write(&gitignore_path, content)
+        .with_context(|| format!("failed to write {}", gitignore_path.display()))?;
+    println!("Added '{}' to .gitignore", ignore_line);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn resolve_experiment_ref_none_without_at_prefix() {
+        let dir = TempDir::new().unwrap();
+        assert!(resolve_experiment_ref(dir.path(), "scratch").is_none());
+    }
+
+    #[test]
+    fn resolve_experiment_ref_appends_suffix() {
+        let dir = TempDir::new().unwrap();
+        let path = resolve_experiment_ref(dir.path(), "@scratch").unwrap();
+        assert_eq!(path, dir.path().join(".tspec/experiments/scratch.ts.toml"));
+    }
+
+    #[test]
+    fn resolve_experiment_ref_respects_explicit_extension() {
+        let dir = TempDir::new().unwrap();
+        let path = resolve_experiment_ref(dir.path(), "@scratch.toml").unwrap();
+        assert_eq!(path, dir.path().join(".tspec/experiments/scratch.toml"));
+    }
+
+    #[test]
+    fn start_experiment_writes_default_spec_and_gitignore() {
+        let dir = TempDir::new().unwrap();
+        let path = start_experiment(dir.path(), dir.path(), "scratch", None, true).unwrap();
+        assert!(path.exists());
+        let ignore = std::fs::read_to_string(dir.path().join(".gitignore")).unwrap();
+        assert!(ignore.lines().any(|l| l.trim() == ".tspec/"));
+    }
+
+    #[test]
+    fn start_experiment_requires_yes_without_existing_gitignore_entry() {
+        let dir = TempDir::new().unwrap();
+        let err = start_experiment(dir.path(), dir.path(), "scratch", None, false).unwrap_err();
+        assert!(err.to_string().contains("--yes"));
+        assert!(!experiment_path(dir.path(), "scratch").exists());
+    }
+
+    #[test]
+    fn start_experiment_skips_gitignore_write_when_already_covered() {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(dir.path().join(".gitignore"), "target/\n.tspec/\n").unwrap();
+        start_experiment(dir.path(), dir.path(), "scratch", None, false).unwrap();
+    }
+
+    #[test]
+    fn start_experiment_rejects_duplicate_name() {
+        let dir = TempDir::new().unwrap();
+        start_experiment(dir.path(), dir.path(), "scratch", None, true).unwrap();
+        let err = start_experiment(dir.path(), dir.path(), "scratch", None, true).unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+    }
+
+    #[test]
+    fn start_experiment_copies_from_source_byte_for_byte() {
+        let dir = TempDir::new().unwrap();
+        let source = dir.path().join("base.ts.toml");
+        std::fs::write(&source, "panic = \"abort\"\n").unwrap();
+        let path =
+            start_experiment(dir.path(), dir.path(), "scratch", Some(&source), true).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(path).unwrap(),
+            "panic = \"abort\"\n"
+        );
+    }
+
+    #[test]
+    fn list_experiments_empty_without_directory() {
+        let dir = TempDir::new().unwrap();
+        assert!(list_experiments(dir.path()).unwrap().is_empty());
+    }
+
+    #[test]
+    fn list_experiments_sorted() {
+        let dir = TempDir::new().unwrap();
+        start_experiment(dir.path(), dir.path(), "b", None, true).unwrap();
+        start_experiment(dir.path(), dir.path(), "a", None, true).unwrap();
+        let found = list_experiments(dir.path()).unwrap();
+        assert_eq!(found.len(), 2);
+        assert!(found[0].to_string_lossy().ends_with("a.ts.toml"));
+        assert!(found[1].to_string_lossy().ends_with("b.ts.toml"));
+    }
+
+    #[test]
+    fn promote_experiment_moves_file_and_removes_source() {
+        let dir = TempDir::new().unwrap();
+        start_experiment(dir.path(), dir.path(), "scratch", None, true).unwrap();
+        let dest = promote_experiment(dir.path(), "scratch").unwrap();
+        assert_eq!(dest, dir.path().join("scratch.ts.toml"));
+        assert!(dest.exists());
+        assert!(!experiment_path(dir.path(), "scratch").exists());
+    }
+
+    #[test]
+    fn promote_experiment_fails_on_destination_collision() {
+        let dir = TempDir::new().unwrap();
+        start_experiment(dir.path(), dir.path(), "scratch", None, true).unwrap();
+        std::fs::write(dir.path().join("scratch.ts.toml"), "").unwrap();
+        let err = promote_experiment(dir.path(), "scratch").unwrap_err();
+        assert!(err.to_string().contains("already exists"));
+    }
+
+    #[test]
+    fn promote_experiment_missing_is_error() {
+        let dir = TempDir::new().unwrap();
+        assert!(promote_experiment(dir.path(), "nope").is_err());
+    }
+
+    #[test]
+    fn discard_experiment_removes_file() {
+        let dir = TempDir::new().unwrap();
+        start_experiment(dir.path(), dir.path(), "scratch", None, true).unwrap();
+        let path = discard_experiment(dir.path(), "scratch").unwrap();
+        assert!(!path.exists());
+    }
+
+    #[test]
+    fn discard_experiment_missing_is_error() {
+        let dir = TempDir::new().unwrap();
+        assert!(discard_experiment(dir.path(), "nope").is_err());
+    }
+}
@@ -1,9 +1,42 @@
 use anyhow::{Context, Result, bail};
+use clap::ValueEnum;
 use glob::Pattern;
 use std::path::{Path, PathBuf};
 
 use crate::TSPEC_SUFFIX;
-use crate::types::{Spec, profile_dir_name};
+use crate::error::TspecError;
+use crate::types::{Spec, profile_dir_name, resolve_profile, resolve_target_triple};
+
+/// Name of the env var used to override how [`find_project_root`] resolves
+/// a package nested inside (but not a member of) an enclosing workspace.
+pub const TSPEC_ROOT_MODE_ENV: &str = "TSPEC_ROOT_MODE";
+
+/// How [`find_project_root`] should resolve a package that sits inside an
+/// enclosing workspace without being a member (and without being excluded).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum RootMode {
+    /// Current behavior: prefer the enclosing workspace root, unless the
+    /// package is explicitly excluded from it.
+    #[default]
+    Workspace,
+    /// Always stop at the nearest `[package]` Cargo.toml, even if an
+    /// enclosing workspace would otherwise be preferred. Useful for
+    /// monorepos with detached sub-projects that happen to sit inside a
+    /// workspace directory tree without being part of it.
+    Nearest,
+}
+
+impl RootMode {
+    /// Read the mode from `TSPEC_ROOT_MODE`, defaulting to `Workspace` when
+    /// unset. Bails if the value is set but not `nearest`/`workspace`.
+    pub fn from_env() -> Result<Self> {
+        match std::env::var(TSPEC_ROOT_MODE_ENV) {
+            Ok(value) => Self::from_str(&value, false)
+                .map_err(|e| anyhow::anyhow!("invalid {TSPEC_ROOT_MODE_ENV} value '{value}': {e}")),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+}
 
 /// Check if TOML content has an exact section header like `[workspace]` or `[package]`
 /// Only matches lines where the trimmed content equals `[section]` exactly
@@ -12,11 +45,60 @@ fn has_toml_section_exact(content: &str, section: &str) -> bool {
     content.lines().any(|line| line.trim() == header)
 }
 
-/// Extract package name from Cargo.toml
-pub fn get_package_name(crate_dir: &Path) -> Result<String> {
-    let cargo_toml = crate_dir.join("Cargo.toml");
+/// Convert a byte offset into 1-indexed (line, column) within `content`.
+fn line_col_at(content: &str, byte_offset: usize) -> (usize, usize) {
+    let mut line = 1;
+    let mut col = 1;
+    for ch in content[..byte_offset.min(content.len())].chars() {
+        if ch == '\n' {
+            line += 1;
+            col = 1;
+        } else {
+            col += 1;
+        }
+    }
+    (line, col)
+}
+
+/// Sanity-check that `project_root`'s Cargo.toml parses as valid TOML.
+///
+/// `find_project_root`'s own read only needs the file to be readable text —
+/// a broken manifest (stray bracket, unterminated string) still resolves a
+/// root, then fails later with one of several unrelated-looking errors from
+/// `is_excluded_from_workspace`, `validate_profile`, or
+/// `WorkspaceInfo::discover`, none of which point at the file or line. Call
+/// this once per invocation, right after the root is resolved, so a broken
+/// manifest fails fast with a single message naming the exact location.
+pub fn check_root_manifest(project_root: &Path) -> Result<()> {
+    let cargo_toml = project_root.join("Cargo.toml");
     let content = std::fs::read_to_string(&cargo_toml)
         .with_context(|| format!("failed to read {}", cargo_toml.display()))?;
+    if let Err(e) = content.parse::<toml::Value>() {
+        let location = e
+            .span()
+            .map(|span| {
+                let (line, col) = line_col_at(&content, span.start);
+                format!("{}:{line}:{col}", cargo_toml.display())
+            })
+            .unwrap_or_else(|| cargo_toml.display().to_string());
+        bail!(
+            "root manifest is not valid TOML: {location}: {}\n\
+             hint: every tspec operation needs a valid root manifest — fix {} and try again",
+            e.message(),
+            cargo_toml.display()
+        );
+    }
+    Ok(())
+}
+
+/// Extract package name from Cargo.toml
+pub fn get_package_name(crate_dir: &Path) -> Result<String, TspecError> {
+    let cargo_toml = crate_dir.join("Cargo.toml");
+    let content =
+        std::fs::read_to_string(&cargo_toml).map_err(|source| TspecError::ReadFailed {
+            path: cargo_toml.clone(),
+            source,
+        })?;
 
     // Simple parsing - look for name = "..." in [package] section
     let mut in_package = false;
@@ -39,7 +121,10 @@ pub fn get_package_name(crate_dir: &Path) -> Result<String> {
             return Ok(value.to_string());
         }
     }
-    bail!("could not find package name in {}", cargo_toml.display());
+    Err(TspecError::ManifestFieldMissing {
+        field: "package name".to_string(),
+        path: cargo_toml,
+    })
 }
 
 /// Read the version from a Cargo.toml [package] section.
@@ -71,26 +156,50 @@ pub fn get_package_version(crate_dir: &Path) -> Result<String> {
     bail!("could not find package version in {}", cargo_toml.display());
 }
 
+/// Find the project root by looking for Cargo.toml with [workspace] or [package].
+/// Reads [`RootMode::from_env`] to decide POP-vs-workspace ambiguity; see
+/// [`find_project_root_with_mode`] for the resolution rules themselves.
+pub fn find_project_root() -> Result<PathBuf> {
+    find_project_root_with_mode(RootMode::from_env()?)
+}
+
 /// Find the project root by looking for Cargo.toml with [workspace] or [package]
 /// For workspaces, returns the directory containing the workspace Cargo.toml
 /// For POPs (Plain Old Packages), returns the directory containing the package Cargo.toml
 /// Respects workspace `exclude` — a package inside an excluded path is treated as a POP.
-pub fn find_project_root() -> Result<PathBuf> {
-    let mut dir = std::env::current_dir()?;
+/// With `mode == RootMode::Nearest`, a package nested inside an enclosing
+/// workspace stops at its own Cargo.toml even when it's a regular (not
+/// excluded) descendant — the same outcome `exclude` already produces.
+pub fn find_project_root_with_mode(mode: RootMode) -> Result<PathBuf> {
+    let dir = std::env::current_dir()?;
+    walk_up_for_root(&dir, mode)?.ok_or_else(|| {
+        anyhow::anyhow!("could not find project root (no Cargo.toml with [workspace] or [package])")
+    })
+}
+
+/// Walk up from `start_dir` looking for the nearest `[workspace]` or
+/// `[package]` Cargo.toml, shared by [`find_project_root_with_mode`] and
+/// [`resolve_manifest_path`]. Returns `None` (rather than erroring) when the
+/// walk reaches the filesystem root without finding either, so callers can
+/// word their own "not found" message.
+fn walk_up_for_root(start_dir: &Path, mode: RootMode) -> Result<Option<PathBuf>> {
+    let mut dir = start_dir.to_path_buf();
     let mut package_root: Option<PathBuf> = None;
 
     loop {
         let cargo_toml = dir.join("Cargo.toml");
         if cargo_toml.exists() {
             let content = std::fs::read_to_string(&cargo_toml)?;
-            // Workspace takes precedence — unless the package we found is excluded
+            // Workspace takes precedence — unless the package we found is
+            // excluded, or the caller forced "nearest package" resolution.
             if has_toml_section_exact(&content, "workspace") {
                 if let Some(ref pkg_root) = package_root
-                    && is_excluded_from_workspace(&content, &dir, pkg_root)
+                    && (mode == RootMode::Nearest
+                        || is_excluded_from_workspace(&content, &dir, pkg_root))
                 {
-                    return Ok(pkg_root.clone());
+                    return Ok(Some(pkg_root.clone()));
                 }
-                return Ok(dir);
+                return Ok(Some(dir));
             }
             // Remember the first (deepest) package we find as potential POP root
             if package_root.is_none() && has_toml_section_exact(&content, "package") {
@@ -99,10 +208,7 @@ pub fn find_project_root() -> Result<PathBuf> {
         }
         if !dir.pop() {
             // No workspace found, use the POP root if we found one
-            if let Some(root) = package_root {
-                return Ok(root);
-            }
-            bail!("could not find project root (no Cargo.toml with [workspace] or [package])");
+            return Ok(package_root);
         }
     }
 }
@@ -130,33 +236,8 @@ pub fn resolve_manifest_path(path: &Path) -> Result<PathBuf> {
         bail!("no Cargo.toml found at {}", start_dir.display());
     }
 
-    // Walk up to find workspace root, same logic as find_project_root()
-    let mut dir = start_dir.clone();
-    let mut package_root: Option<PathBuf> = None;
-
-    loop {
-        let cargo_toml = dir.join("Cargo.toml");
-        if cargo_toml.exists() {
-            let content = std::fs::read_to_string(&cargo_toml)?;
-            if has_toml_section_exact(&content, "workspace") {
-                if let Some(ref pkg_root) = package_root
-                    && is_excluded_from_workspace(&content, &dir, pkg_root)
-                {
-                    return Ok(pkg_root.clone());
-                }
-                return Ok(dir);
-            }
-            if package_root.is_none() && has_toml_section_exact(&content, "package") {
-                package_root = Some(dir.clone());
-            }
-        }
-        if !dir.pop() {
-            if let Some(root) = package_root {
-                return Ok(root);
-            }
-            bail!("no project root found from {}", start_dir.display());
-        }
-    }
+    walk_up_for_root(&start_dir, RootMode::Workspace)?
+        .ok_or_else(|| anyhow::anyhow!("no project root found from {}", start_dir.display()))
 }
 
 /// Check if a package directory is excluded from a workspace.
@@ -200,6 +281,23 @@ fn is_excluded_from_workspace(ws_content: &str, ws_dir: &Path, pkg_dir: &Path) -
     false
 }
 
+/// Check if the current directory names a package within `project_root`.
+/// Returns Some(name) when cwd is a package directory, None when it's a
+/// workspace root (all-packages mode) or outside `project_root` entirely
+/// (e.g. `--manifest-path` was used from an unrelated directory).
+pub fn current_package_name(project_root: &Path) -> Option<String> {
+    let cwd = std::env::current_dir().ok()?;
+    // If cwd is outside project_root, fall back to all-packages mode
+    if !cwd.starts_with(project_root) {
+        return None;
+    }
+    // At a workspace root, don't treat it as a single package
+    if cwd.join("Cargo.toml").exists() && !is_pop(&cwd) {
+        return None;
+    }
+    get_package_name(&cwd).ok()
+}
+
 /// Check if a project root is a POP (Plain Old Package) vs a workspace
 pub fn is_pop(project_root: &Path) -> bool {
     let cargo_toml = project_root.join("Cargo.toml");
@@ -212,12 +310,42 @@ pub fn is_pop(project_root: &Path) -> bool {
     }
 }
 
+/// Result of resolving an explicit `-p`/positional package argument.
+#[derive(Debug, PartialEq, Eq)]
+pub enum PackageSelector {
+    /// The argument names one package.
+    Single { name: String, dir: PathBuf },
+    /// The argument resolved to a pure workspace root (a `[workspace]`
+    /// directory with no `[package]` section of its own) — "every member".
+    All,
+}
+
+/// Resolve an explicit package argument — a bare name, a relative or
+/// absolute path, or `.` — the same way for every command.
+///
+/// Precedence: the argument is tried as a path first (relative to the
+/// current directory, via [`find_package_dir`]); if the directory it
+/// resolves to has a `[package]` section, that's a `Single`. If it resolves
+/// to the root of a pure workspace (no `[package]` section there), it's
+/// `All` — e.g. `-p .` at a POWS root means "every member", the same way a
+/// bare `cargo build --workspace` does. This is the one place commands
+/// should resolve a raw `-p`/positional argument; callers that must target
+/// exactly one package (the `ts set`/`add`/`remove`/... family) should
+/// treat `All` as an error rather than guessing which member was meant.
+pub fn resolve_package_selector(project_root: &Path, arg: &str) -> Result<PackageSelector> {
+    let dir = find_package_dir(project_root, arg)?;
+    match get_package_name(&dir) {
+        Ok(name) => Ok(PackageSelector::Single { name, dir }),
+        Err(_) => Ok(PackageSelector::All),
+    }
+}
+
 /// Resolve package directory from optional name, defaulting to current directory
 /// If package is None, uses current directory (must contain Cargo.toml)
 /// If package is Some, looks up the package by name
 pub fn resolve_package_dir(workspace: &Path, package: Option<&str>) -> Result<PathBuf> {
     match package {
-        Some(name) => find_package_dir(workspace, name),
+        Some(name) => Ok(find_package_dir(workspace, name)?),
         None => {
             let cwd = std::env::current_dir()?;
             if cwd.join("Cargo.toml").exists() {
@@ -231,9 +359,40 @@ pub fn resolve_package_dir(workspace: &Path, package: Option<&str>) -> Result<Pa
     }
 }
 
+/// Resolve the package directory for a single-package `ts` subcommand
+/// (set/unset/add/remove/backup/restore/new/pin).
+///
+/// Unlike `resolve_package_dir`, the `None` case goes through
+/// `current_package_name(project_root)` instead of reading
+/// `std::env::current_dir()` directly, so `--manifest-path`/`--mp` from a
+/// cwd outside `project_root` is reported as "not in a package directory"
+/// rather than silently resolving against the wrong Cargo.toml. A POP is
+/// unambiguous (it has exactly one package), so it's used as-is even when
+/// cwd doesn't imply a package, matching `build`/`test`'s all-packages
+/// fallback degenerating to the same single package for a POP.
+pub fn resolve_ts_package_dir(project_root: &Path, package: Option<&str>) -> Result<PathBuf> {
+    match package {
+        Some(name) => match resolve_package_selector(project_root, name)? {
+            PackageSelector::Single { dir, .. } => Ok(dir),
+            PackageSelector::All => bail!(
+                "'{}' resolves to the workspace root, which has no [package] of its own; \
+                 this command modifies a single package's tspec — use -p <name> to pick one",
+                name
+            ),
+        },
+        None => match current_package_name(project_root) {
+            Some(name) => Ok(find_package_dir(project_root, &name)?),
+            None if is_pop(project_root) => Ok(project_root.to_path_buf()),
+            None => bail!(
+                "not in a package directory (no Cargo.toml found, or --manifest-path points elsewhere). Use -p to specify a package."
+            ),
+        },
+    }
+}
+
 /// Find a package's directory - tries as path first, then searches standard locations
 /// For POPs, checks if name matches the root package
-pub fn find_package_dir(project_root: &Path, name: &str) -> Result<PathBuf> {
+pub fn find_package_dir(project_root: &Path, name: &str) -> Result<PathBuf, TspecError> {
     // Try as path first (relative or absolute)
     let as_path = PathBuf::from(name);
     if as_path.join("Cargo.toml").exists() {
@@ -249,11 +408,13 @@ pub fn find_package_dir(project_root: &Path, name: &str) -> Result<PathBuf> {
 
     // For POPs, nothing else to search
     if is_pop(project_root) {
-        bail!(
-            "package '{}' not found (this is a single-package project with package '{}')",
-            name,
-            get_package_name(project_root).unwrap_or_else(|_| "unknown".to_string())
-        );
+        return Err(TspecError::PackageNotFound {
+            name: name.to_string(),
+            searched: Some(format!(
+                "this is a single-package project with package '{}'",
+                get_package_name(project_root).unwrap_or_else(|_| "unknown".to_string())
+            )),
+        });
     }
 
     // Workspace: search root-level members, then libs/, apps/, tools/
@@ -286,20 +447,60 @@ pub fn find_package_dir(project_root: &Path, name: &str) -> Result<PathBuf> {
         }
     }
 
-    bail!("package '{}' not found", name);
+    Err(TspecError::PackageNotFound {
+        name: name.to_string(),
+        searched: None,
+    })
 }
 
-/// Find the tspec for a package - tries as path first, then relative to pkg_dir
+/// Name of the env var holding extra spec search directories, set from
+/// `--spec-dir` (repeatable) and joined with the platform path-list
+/// separator. Backs [`spec_search_dirs`].
+pub const TSPEC_SPEC_DIR_ENV: &str = "TSPEC_SPEC_DIR";
+
+/// Extra directories to search for a named spec before falling back to the
+/// package directory. Populated once in `main.rs` from `--spec-dir`, already
+/// resolved to absolute paths against the project root, so `find_tspec`/
+/// `find_tspecs` don't need a project-root parameter of their own.
+fn spec_search_dirs() -> Vec<PathBuf> {
+    match std::env::var_os(TSPEC_SPEC_DIR_ENV) {
+        Some(value) => std::env::split_paths(&value).collect(),
+        None => Vec::new(),
+    }
+}
+
+/// Find the tspec for a package - tries as path first, then any
+/// `--spec-dir` search directory, then relative to pkg_dir.
 /// Returns None if no tspec exists (plain cargo build will be used)
 pub fn find_tspec(pkg_dir: &Path, explicit: Option<&str>) -> Result<Option<PathBuf>> {
     match explicit {
         Some(name) => {
+            if let Some(path) = crate::experiment::resolve_experiment_ref(pkg_dir, name) {
+                if path.exists() {
+                    return Ok(Some(path));
+                }
+                bail!("experiment not found: {}", name);
+            }
+
             // Try as path first (relative to cwd or absolute)
             let as_path = PathBuf::from(name);
             if as_path.exists() {
                 return Ok(Some(as_path.canonicalize().unwrap_or(as_path)));
             }
 
+            for dir in spec_search_dirs() {
+                let in_dir = dir.join(name);
+                if in_dir.exists() {
+                    return Ok(Some(in_dir));
+                }
+                if !name.contains('.') {
+                    let with_suffix = dir.join(format!("{}{}", name, TSPEC_SUFFIX));
+                    if with_suffix.exists() {
+                        return Ok(Some(with_suffix));
+                    }
+                }
+            }
+
             // Fallback: relative to package directory
             let in_pkg = pkg_dir.join(name);
             if in_pkg.exists() {
@@ -317,7 +518,21 @@ pub fn find_tspec(pkg_dir: &Path, explicit: Option<&str>) -> Result<Option<PathB
             bail!("tspec not found: {}", name);
         }
         None => {
-            let default = pkg_dir.join(format!("tspec{}", TSPEC_SUFFIX));
+            // Cargo.toml may pin a default spec via [package.metadata.tspec]
+            let metadata = crate::metadata::read_tspec_metadata(pkg_dir)?;
+            if let Some(name) = &metadata.default_spec {
+                return find_tspec(pkg_dir, Some(name));
+            }
+
+            let default_name = format!("tspec{}", TSPEC_SUFFIX);
+            for dir in spec_search_dirs() {
+                let in_dir = dir.join(&default_name);
+                if in_dir.exists() {
+                    return Ok(Some(in_dir));
+                }
+            }
+
+            let default = pkg_dir.join(default_name);
             if default.exists() {
                 Ok(Some(default))
             } else {
@@ -327,6 +542,26 @@ pub fn find_tspec(pkg_dir: &Path, explicit: Option<&str>) -> Result<Option<PathB
     }
 }
 
+/// Returns true if `name` is not valid UTF-8 but still looks like it could
+/// be a spec file (ends in the tspec suffix or plain `.toml`). A name like
+/// this can't be safely compared against a glob pattern: lossily
+/// converting it first risks a silent false match or false miss, so the
+/// caller should reject it with a clear error instead.
+#[cfg(unix)]
+fn is_unmatchable_spec_name(name: &std::ffi::OsStr) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+    if name.to_str().is_some() {
+        return false;
+    }
+    let bytes = name.as_bytes();
+    bytes.ends_with(TSPEC_SUFFIX.as_bytes()) || bytes.ends_with(b".toml")
+}
+
+#[cfg(not(unix))]
+fn is_unmatchable_spec_name(_name: &std::ffi::OsStr) -> bool {
+    false
+}
+
 /// Find multiple tspecs by glob patterns
 /// If no patterns given, defaults to "tspec*{TSPEC_SUFFIX}"
 /// Returns sorted list of paths, errors if none found
@@ -338,9 +573,18 @@ pub fn find_tspecs(pkg_dir: &Path, patterns: &[String]) -> Result<Vec<PathBuf>>
         patterns.iter().map(|s| s.as_str()).collect()
     };
 
+    let search_dirs = spec_search_dirs();
     let mut results = Vec::new();
 
     for pattern in &patterns {
+        if let Some(path) = crate::experiment::resolve_experiment_ref(pkg_dir, pattern) {
+            if path.exists() {
+                results.push(path);
+                continue;
+            }
+            bail!("experiment not found: {}", pattern);
+        }
+
         // Try as literal path first (relative to cwd or absolute)
         let as_path = PathBuf::from(pattern);
         if as_path.exists() && as_path.is_file() {
@@ -348,6 +592,16 @@ pub fn find_tspecs(pkg_dir: &Path, patterns: &[String]) -> Result<Vec<PathBuf>>
             continue;
         }
 
+        // Try as literal path in a --spec-dir search directory
+        if let Some(in_dir) = search_dirs
+            .iter()
+            .map(|dir| dir.join(pattern))
+            .find(|p| p.exists() && p.is_file())
+        {
+            results.push(in_dir);
+            continue;
+        }
+
         // Try as literal path relative to pkg_dir
         let in_pkg = pkg_dir.join(pattern);
         if in_pkg.exists() && in_pkg.is_file() {
@@ -359,17 +613,27 @@ pub fn find_tspecs(pkg_dir: &Path, patterns: &[String]) -> Result<Vec<PathBuf>>
         let glob_pattern =
             Pattern::new(pattern).with_context(|| format!("invalid glob pattern: {}", pattern))?;
 
-        let entries: Vec<_> = std::fs::read_dir(pkg_dir)
+        for entry in std::fs::read_dir(pkg_dir)
             .with_context(|| format!("cannot read directory: {}", pkg_dir.display()))?
-            .filter_map(|e| e.ok())
-            .filter(|e| {
-                let name = e.file_name().to_string_lossy().to_string();
-                e.path().is_file() && glob_pattern.matches(&name)
-            })
-            .map(|e| e.path())
-            .collect();
-
-        results.extend(entries);
+        {
+            let entry = entry
+                .with_context(|| format!("cannot read directory entry in {}", pkg_dir.display()))?;
+            let file_name = entry.file_name();
+            if is_unmatchable_spec_name(&file_name) {
+                bail!(
+                    "cannot match glob pattern '{}' against non-UTF-8 filename {:?} in {}",
+                    pattern,
+                    file_name,
+                    pkg_dir.display()
+                );
+            }
+            let Some(name) = file_name.to_str() else {
+                continue;
+            };
+            if entry.path().is_file() && glob_pattern.matches(name) {
+                results.push(entry.path());
+            }
+        }
     }
 
     // Remove duplicates and sort
@@ -388,6 +652,31 @@ pub fn find_tspecs(pkg_dir: &Path, patterns: &[String]) -> Result<Vec<PathBuf>>
     Ok(results)
 }
 
+/// The name of a package's default binary target, e.g. `foo-cli` for a
+/// package `foo` with `[[bin]] name = "foo-cli"`. Falls back to `pkg_name`
+/// (cargo's own default when a package sets no explicit bin name) whenever
+/// `cargo metadata` fails or the package defines no bin target at all, so a
+/// lib-only package or a metadata hiccup degrades to the pre-existing
+/// behavior rather than surfacing an error from what is purely a path
+/// lookup.
+pub fn resolve_bin_name(pkg_dir: &Path, pkg_name: &str) -> String {
+    let manifest_path = pkg_dir.join("Cargo.toml");
+    let Ok(metadata) = cargo_metadata::MetadataCommand::new()
+        .manifest_path(&manifest_path)
+        .no_deps()
+        .exec()
+    else {
+        return pkg_name.to_string();
+    };
+    metadata
+        .packages
+        .iter()
+        .find(|p| p.manifest_path.as_std_path() == manifest_path)
+        .and_then(|p| p.targets.iter().find(|t| t.is_bin()))
+        .map(|t| t.name.clone())
+        .unwrap_or_else(|| pkg_name.to_string())
+}
+
 /// Get binary path for a build with a spec.
 /// `cli_profile` is the CLI-specified profile (None = debug default).
 pub fn get_binary_path(
@@ -396,22 +685,15 @@ pub fn get_binary_path(
     spec: &Spec,
     cli_profile: Option<&str>,
     expanded_target_dir: Option<&str>,
+    force_profile: bool,
 ) -> PathBuf {
-    let target = spec.cargo.target_triple.clone().or_else(|| {
-        spec.cargo
-            .target_json
-            .as_ref()
-            .and_then(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
-    });
-
-    // Determine profile directory: spec profile takes precedence, then CLI profile
-    let dir = match spec.cargo.profile.as_deref() {
-        Some(p) => profile_dir_name(p),
-        None => match cli_profile {
-            Some(p) => profile_dir_name(p),
-            None => "debug",
-        },
-    };
+    let target = resolve_target_triple(&spec.cargo);
+
+    let resolved = resolve_profile(spec.cargo.profile.as_deref(), cli_profile, force_profile);
+    let dir = resolved
+        .profile
+        .as_deref()
+        .map_or("debug", profile_dir_name);
 
     let base = match expanded_target_dir {
         Some(td) => workspace.join("target").join(td),
@@ -446,6 +728,31 @@ mod tests {
     use std::path::PathBuf;
     use tempfile::TempDir;
 
+    // ==================== check_root_manifest tests ====================
+
+    #[test]
+    fn check_root_manifest_accepts_valid_toml() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("Cargo.toml"), "[package]\nname = \"ok\"\n").unwrap();
+        assert!(check_root_manifest(tmp.path()).is_ok());
+    }
+
+    #[test]
+    fn check_root_manifest_reports_path_and_location_on_parse_error() {
+        let tmp = TempDir::new().unwrap();
+        let cargo_toml = tmp.path().join("Cargo.toml");
+        fs::write(&cargo_toml, "[package\nname = \"broken\"\n").unwrap();
+        let err = check_root_manifest(tmp.path()).unwrap_err().to_string();
+        assert!(err.contains(&cargo_toml.display().to_string()));
+        assert!(err.contains("hint:"));
+    }
+
+    #[test]
+    fn line_col_at_finds_second_line() {
+        let content = "first\nsecond\n";
+        assert_eq!(line_col_at(content, 6), (2, 1));
+    }
+
     // ==================== get_package_name tests ====================
 
     #[test]
@@ -622,6 +929,78 @@ version = "0.1.0"
         assert_eq!(found, libs_foo);
     }
 
+    // ==================== resolve_package_selector tests ====================
+
+    #[test]
+    fn resolve_package_selector_by_name_is_single() {
+        let tmp = TempDir::new().unwrap();
+        let libs_dir = tmp.path().join("libs").join("my-lib");
+        fs::create_dir_all(&libs_dir).unwrap();
+        fs::write(
+            libs_dir.join("Cargo.toml"),
+            "[package]\nname = \"my-lib\"\n",
+        )
+        .unwrap();
+
+        let selector = resolve_package_selector(tmp.path(), "my-lib").unwrap();
+        assert_eq!(
+            selector,
+            PackageSelector::Single {
+                name: "my-lib".to_string(),
+                dir: libs_dir,
+            }
+        );
+    }
+
+    #[test]
+    fn resolve_package_selector_by_absolute_path_is_single() {
+        let tmp = TempDir::new().unwrap();
+        let app_dir = tmp.path().join("apps").join("app-a");
+        fs::create_dir_all(&app_dir).unwrap();
+        fs::write(app_dir.join("Cargo.toml"), "[package]\nname = \"app-a\"\n").unwrap();
+
+        let selector = resolve_package_selector(tmp.path(), app_dir.to_str().unwrap()).unwrap();
+        assert_eq!(
+            selector,
+            PackageSelector::Single {
+                name: "app-a".to_string(),
+                dir: app_dir,
+            }
+        );
+    }
+
+    #[test]
+    fn resolve_package_selector_at_pure_workspace_root_is_all() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"app-a\"]\n",
+        )
+        .unwrap();
+
+        let selector = resolve_package_selector(tmp.path(), tmp.path().to_str().unwrap()).unwrap();
+        assert_eq!(selector, PackageSelector::All);
+    }
+
+    #[test]
+    fn resolve_ts_package_dir_explicit_workspace_root_is_an_error() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"app-a\"]\n",
+        )
+        .unwrap();
+
+        let result = resolve_ts_package_dir(tmp.path(), Some(tmp.path().to_str().unwrap()));
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("this command modifies a single package")
+        );
+    }
+
     #[test]
     fn find_package_dir_not_found() {
         let tmp = TempDir::new().unwrap();
@@ -742,6 +1121,159 @@ version = "0.1.0"
         assert!(result.is_err());
     }
 
+    #[test]
+    fn find_tspec_default_spec_from_metadata() {
+        let tmp = TempDir::new().unwrap();
+        let crate_dir = tmp.path().join("crate");
+        fs::create_dir(&crate_dir).unwrap();
+
+        fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\n\n\
+             [package.metadata.tspec]\ndefault_spec = \"tspec-small\"\n",
+        )
+        .unwrap();
+        let small_name = format!("tspec-small{}", SUFFIX);
+        fs::write(crate_dir.join(&small_name), "# small").unwrap();
+        fs::write(crate_dir.join(format!("tspec{}", SUFFIX)), "# default").unwrap();
+
+        // Metadata's default_spec wins over the plain tspec.ts.toml convention
+        let found = find_tspec(&crate_dir, None).unwrap();
+        assert!(found.unwrap().to_string_lossy().contains(&small_name));
+    }
+
+    #[test]
+    fn find_tspec_explicit_tspec_overrides_metadata_default_spec() {
+        let tmp = TempDir::new().unwrap();
+        let crate_dir = tmp.path().join("crate");
+        fs::create_dir(&crate_dir).unwrap();
+
+        fs::write(
+            crate_dir.join("Cargo.toml"),
+            "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\n\n\
+             [package.metadata.tspec]\ndefault_spec = \"tspec-small\"\n",
+        )
+        .unwrap();
+        fs::write(crate_dir.join(format!("tspec-small{}", SUFFIX)), "# small").unwrap();
+        let other_name = format!("other{}", SUFFIX);
+        fs::write(crate_dir.join(&other_name), "# other").unwrap();
+
+        // An explicit -t beats the metadata default
+        let found = find_tspec(&crate_dir, Some("other")).unwrap();
+        assert!(found.unwrap().to_string_lossy().contains(&other_name));
+    }
+
+    #[test]
+    fn find_tspec_spec_dir_resolves_before_pkg_dir_fallback() {
+        let tmp = TempDir::new().unwrap();
+        let crate_dir = tmp.path().join("crate");
+        let spec_dir = tmp.path().join("specs");
+        fs::create_dir(&crate_dir).unwrap();
+        fs::create_dir(&spec_dir).unwrap();
+        fs::write(spec_dir.join(format!("static{}", SUFFIX)), "# static").unwrap();
+
+        // SAFETY: single-threaded test env mutation scoped to this check;
+        // no other test reads TSPEC_SPEC_DIR.
+        unsafe {
+            std::env::set_var(TSPEC_SPEC_DIR_ENV, &spec_dir);
+        }
+        let found = find_tspec(&crate_dir, Some("static"));
+        unsafe {
+            std::env::remove_var(TSPEC_SPEC_DIR_ENV);
+        }
+
+        assert!(
+            found
+                .unwrap()
+                .unwrap()
+                .to_string_lossy()
+                .contains(&format!("static{}", SUFFIX))
+        );
+    }
+
+    #[test]
+    fn find_tspec_pkg_dir_fallback_when_spec_dir_misses() {
+        let tmp = TempDir::new().unwrap();
+        let crate_dir = tmp.path().join("crate");
+        let spec_dir = tmp.path().join("specs");
+        fs::create_dir(&crate_dir).unwrap();
+        fs::create_dir(&spec_dir).unwrap();
+        // Spec lives only in the package dir, not in the --spec-dir.
+        fs::write(crate_dir.join(format!("static{}", SUFFIX)), "# static").unwrap();
+
+        // SAFETY: single-threaded test env mutation scoped to this check;
+        // no other test reads TSPEC_SPEC_DIR.
+        unsafe {
+            std::env::set_var(TSPEC_SPEC_DIR_ENV, &spec_dir);
+        }
+        let found = find_tspec(&crate_dir, Some("static"));
+        unsafe {
+            std::env::remove_var(TSPEC_SPEC_DIR_ENV);
+        }
+
+        assert!(
+            found
+                .unwrap()
+                .unwrap()
+                .to_string_lossy()
+                .contains(&format!("static{}", SUFFIX))
+        );
+    }
+
+    #[test]
+    fn find_tspec_default_spec_dir_resolves_before_pkg_dir_fallback() {
+        let tmp = TempDir::new().unwrap();
+        let crate_dir = tmp.path().join("crate");
+        let spec_dir = tmp.path().join("specs");
+        fs::create_dir(&crate_dir).unwrap();
+        fs::create_dir(&spec_dir).unwrap();
+        fs::write(spec_dir.join(format!("tspec{}", SUFFIX)), "# default").unwrap();
+
+        // SAFETY: single-threaded test env mutation scoped to this check;
+        // no other test reads TSPEC_SPEC_DIR.
+        unsafe {
+            std::env::set_var(TSPEC_SPEC_DIR_ENV, &spec_dir);
+        }
+        let found = find_tspec(&crate_dir, None);
+        unsafe {
+            std::env::remove_var(TSPEC_SPEC_DIR_ENV);
+        }
+
+        assert!(
+            found
+                .unwrap()
+                .unwrap()
+                .to_string_lossy()
+                .contains(&format!("tspec{}", SUFFIX))
+        );
+    }
+
+    #[test]
+    fn find_tspec_at_prefix_resolves_experiment() {
+        let tmp = TempDir::new().unwrap();
+        let crate_dir = tmp.path().join("crate");
+        fs::create_dir(&crate_dir).unwrap();
+        crate::experiment::start_experiment(&crate_dir, &crate_dir, "scratch", None, true).unwrap();
+
+        let found = find_tspec(&crate_dir, Some("@scratch")).unwrap().unwrap();
+        assert!(
+            found
+                .to_string_lossy()
+                .contains(".tspec/experiments/scratch")
+        );
+    }
+
+    #[test]
+    fn find_tspec_at_prefix_missing_experiment_is_error() {
+        let tmp = TempDir::new().unwrap();
+        let crate_dir = tmp.path().join("crate");
+        fs::create_dir(&crate_dir).unwrap();
+
+        let result = find_tspec(&crate_dir, Some("@nope"));
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("experiment"));
+    }
+
     // ==================== find_tspecs tests ====================
 
     #[test]
@@ -835,6 +1367,32 @@ version = "0.1.0"
         );
     }
 
+    #[test]
+    #[cfg(unix)]
+    fn find_tspecs_non_utf8_spec_name_errors_instead_of_mismatching() {
+        use std::ffi::OsStr;
+        use std::os::unix::ffi::OsStrExt;
+
+        let tmp = TempDir::new().unwrap();
+        let crate_dir = tmp.path().join("crate");
+        fs::create_dir(&crate_dir).unwrap();
+
+        // 0x66 0x6f 0xff 0xff ".ts.toml" is not valid UTF-8 but still
+        // carries the tspec suffix, so it could plausibly have matched a
+        // "tspec*.ts.toml"-style glob under a lossy conversion.
+        let mut bytes = vec![0x66, 0x6f, 0xff, 0xff];
+        bytes.extend_from_slice(SUFFIX.as_bytes());
+        let bad_name = OsStr::from_bytes(&bytes);
+        fs::write(crate_dir.join(bad_name), "# bad").unwrap();
+
+        let result = find_tspecs(&crate_dir, &["*".to_string()]);
+        let err = result.unwrap_err().to_string();
+        assert!(
+            err.contains("non-UTF-8"),
+            "expected a clear non-UTF-8 error, got: {err}"
+        );
+    }
+
     #[test]
     fn find_tspecs_glob_matches_multi_dot_filenames() {
         let tmp = TempDir::new().unwrap();
@@ -867,6 +1425,136 @@ version = "0.1.0"
         assert_eq!(found_all.len(), 3);
     }
 
+    #[test]
+    fn find_tspecs_spec_dir_resolves_before_pkg_dir_fallback() {
+        let tmp = TempDir::new().unwrap();
+        let crate_dir = tmp.path().join("crate");
+        let spec_dir = tmp.path().join("specs");
+        fs::create_dir(&crate_dir).unwrap();
+        fs::create_dir(&spec_dir).unwrap();
+        fs::write(spec_dir.join("static.toml"), "# static").unwrap();
+
+        // SAFETY: single-threaded test env mutation scoped to this check;
+        // no other test reads TSPEC_SPEC_DIR.
+        unsafe {
+            std::env::set_var(TSPEC_SPEC_DIR_ENV, &spec_dir);
+        }
+        let found = find_tspecs(&crate_dir, &["static.toml".to_string()]);
+        unsafe {
+            std::env::remove_var(TSPEC_SPEC_DIR_ENV);
+        }
+
+        let found = found.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].file_name().unwrap(), "static.toml");
+    }
+
+    #[test]
+    fn find_tspecs_pkg_dir_fallback_when_spec_dir_misses() {
+        let tmp = TempDir::new().unwrap();
+        let crate_dir = tmp.path().join("crate");
+        let spec_dir = tmp.path().join("specs");
+        fs::create_dir(&crate_dir).unwrap();
+        fs::create_dir(&spec_dir).unwrap();
+        // Spec lives only in the package dir, not in the --spec-dir.
+        fs::write(crate_dir.join("static.toml"), "# static").unwrap();
+
+        // SAFETY: single-threaded test env mutation scoped to this check;
+        // no other test reads TSPEC_SPEC_DIR.
+        unsafe {
+            std::env::set_var(TSPEC_SPEC_DIR_ENV, &spec_dir);
+        }
+        let found = find_tspecs(&crate_dir, &["static.toml".to_string()]);
+        unsafe {
+            std::env::remove_var(TSPEC_SPEC_DIR_ENV);
+        }
+
+        let found = found.unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].file_name().unwrap(), "static.toml");
+    }
+
+    #[test]
+    fn find_tspecs_at_prefix_resolves_experiment() {
+        let tmp = TempDir::new().unwrap();
+        let crate_dir = tmp.path().join("crate");
+        fs::create_dir(&crate_dir).unwrap();
+        crate::experiment::start_experiment(&crate_dir, &crate_dir, "scratch", None, true).unwrap();
+
+        let found = find_tspecs(&crate_dir, &["@scratch".to_string()]).unwrap();
+        assert_eq!(found.len(), 1);
+        assert!(
+            found[0]
+                .to_string_lossy()
+                .contains(".tspec/experiments/scratch")
+        );
+    }
+
+    #[test]
+    fn find_tspecs_default_glob_excludes_experiments() {
+        let tmp = TempDir::new().unwrap();
+        let crate_dir = tmp.path().join("crate");
+        fs::create_dir(&crate_dir).unwrap();
+        fs::write(crate_dir.join(format!("tspec{}", SUFFIX)), "# default").unwrap();
+        crate::experiment::start_experiment(&crate_dir, &crate_dir, "scratch", None, true).unwrap();
+
+        let found = find_tspecs(&crate_dir, &[]).unwrap();
+        assert_eq!(found.len(), 1);
+        assert_eq!(
+            found[0].file_name().unwrap(),
+            format!("tspec{}", SUFFIX).as_str()
+        );
+    }
+
+    // ==================== resolve_bin_name tests ====================
+
+    #[test]
+    fn resolve_bin_name_uses_renamed_bin_target() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("src")).unwrap();
+        fs::write(
+            tmp.path().join("Cargo.toml"),
+            r#"[package]
+name = "foo"
+version = "0.1.0"
+edition = "2024"
+
+[[bin]]
+name = "foo-cli"
+path = "src/main.rs"
+"#,
+        )
+        .unwrap();
+        fs::write(tmp.path().join("src/main.rs"), "fn main() {}").unwrap();
+
+        assert_eq!(resolve_bin_name(tmp.path(), "foo"), "foo-cli");
+    }
+
+    #[test]
+    fn resolve_bin_name_falls_back_to_package_name_without_explicit_bin() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("src")).unwrap();
+        fs::write(
+            tmp.path().join("Cargo.toml"),
+            r#"[package]
+name = "plain-app"
+version = "0.1.0"
+edition = "2024"
+"#,
+        )
+        .unwrap();
+        fs::write(tmp.path().join("src/main.rs"), "fn main() {}").unwrap();
+
+        assert_eq!(resolve_bin_name(tmp.path(), "plain-app"), "plain-app");
+    }
+
+    #[test]
+    fn resolve_bin_name_falls_back_to_pkg_name_when_metadata_fails() {
+        let tmp = TempDir::new().unwrap();
+        // No Cargo.toml at all: `cargo metadata` errors out.
+        assert_eq!(resolve_bin_name(tmp.path(), "whatever"), "whatever");
+    }
+
     // ==================== get_binary_path tests ====================
 
     #[test]
@@ -894,7 +1582,7 @@ version = "0.1.0"
     fn get_binary_path_empty_spec_debug() {
         let workspace = Path::new("/workspace");
         let spec = Spec::default();
-        let path = get_binary_path(workspace, "myapp", &spec, None, None);
+        let path = get_binary_path(workspace, "myapp", &spec, None, None, false);
         assert_eq!(path, PathBuf::from("/workspace/target/debug/myapp"));
     }
 
@@ -902,7 +1590,7 @@ version = "0.1.0"
     fn get_binary_path_empty_spec_release_flag() {
         let workspace = Path::new("/workspace");
         let spec = Spec::default();
-        let path = get_binary_path(workspace, "myapp", &spec, Some("release"), None);
+        let path = get_binary_path(workspace, "myapp", &spec, Some("release"), None, false);
         assert_eq!(path, PathBuf::from("/workspace/target/release/myapp"));
     }
 
@@ -918,7 +1606,7 @@ version = "0.1.0"
             ..Default::default()
         };
         // cli_profile=None but spec says release
-        let path = get_binary_path(workspace, "myapp", &spec, None, None);
+        let path = get_binary_path(workspace, "myapp", &spec, None, None, false);
         assert_eq!(path, PathBuf::from("/workspace/target/release/myapp"));
     }
 
@@ -934,10 +1622,26 @@ version = "0.1.0"
             ..Default::default()
         };
         // cli_profile says release, but spec says release-small — spec wins
-        let path = get_binary_path(workspace, "myapp", &spec, Some("release"), None);
+        let path = get_binary_path(workspace, "myapp", &spec, Some("release"), None, false);
         assert_eq!(path, PathBuf::from("/workspace/target/release-small/myapp"));
     }
 
+    #[test]
+    fn get_binary_path_force_profile_makes_cli_win() {
+        use crate::types::CargoConfig;
+        let workspace = Path::new("/workspace");
+        let spec = Spec {
+            cargo: CargoConfig {
+                profile: Some("release-small".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        // force_profile=true: CLI's --profile release wins over the spec
+        let path = get_binary_path(workspace, "myapp", &spec, Some("release"), None, true);
+        assert_eq!(path, PathBuf::from("/workspace/target/release/myapp"));
+    }
+
     #[test]
     fn get_binary_path_with_target_triple() {
         use crate::types::CargoConfig;
@@ -949,7 +1653,7 @@ version = "0.1.0"
             },
             ..Default::default()
         };
-        let path = get_binary_path(workspace, "myapp", &spec, Some("release"), None);
+        let path = get_binary_path(workspace, "myapp", &spec, Some("release"), None, false);
         assert_eq!(
             path,
             PathBuf::from("/workspace/target/x86_64-unknown-linux-musl/release/myapp")
@@ -967,7 +1671,7 @@ version = "0.1.0"
             },
             ..Default::default()
         };
-        let path = get_binary_path(workspace, "myapp", &spec, Some("release"), None);
+        let path = get_binary_path(workspace, "myapp", &spec, Some("release"), None, false);
         assert_eq!(
             path,
             PathBuf::from("/workspace/target/x86_64-unknown-linux-rlibcx2/release/myapp")
@@ -985,7 +1689,7 @@ version = "0.1.0"
             },
             ..Default::default()
         };
-        let path = get_binary_path(workspace, "myapp", &spec, None, None);
+        let path = get_binary_path(workspace, "myapp", &spec, None, None, false);
         assert_eq!(
             path,
             PathBuf::from("/workspace/target/x86_64-unknown-linux-musl/debug/myapp")
@@ -1003,7 +1707,7 @@ version = "0.1.0"
             },
             ..Default::default()
         };
-        let path = get_binary_path(workspace, "myapp", &spec, None, None);
+        let path = get_binary_path(workspace, "myapp", &spec, None, None, false);
         assert_eq!(path, PathBuf::from("/workspace/target/debug/myapp"));
     }
 
@@ -1018,7 +1722,7 @@ version = "0.1.0"
             },
             ..Default::default()
         };
-        let path = get_binary_path(workspace, "myapp", &spec, None, None);
+        let path = get_binary_path(workspace, "myapp", &spec, None, None, false);
         assert_eq!(path, PathBuf::from("/workspace/target/release-small/myapp"));
     }
 
@@ -1033,7 +1737,7 @@ version = "0.1.0"
             },
             ..Default::default()
         };
-        let path = get_binary_path(workspace, "myapp", &spec, None, None);
+        let path = get_binary_path(workspace, "myapp", &spec, None, None, false);
         assert_eq!(path, PathBuf::from("/workspace/target/debug/myapp"));
     }
 
@@ -1056,6 +1760,7 @@ version = "0.1.0"
             &spec,
             Some("release"),
             Some("static-opt"),
+            false,
         );
         assert_eq!(
             path,
@@ -1067,10 +1772,49 @@ version = "0.1.0"
     fn get_binary_path_with_target_dir_no_triple() {
         let workspace = Path::new("/workspace");
         let spec = Spec::default();
-        let path = get_binary_path(workspace, "myapp", &spec, None, Some("custom"));
+        let path = get_binary_path(workspace, "myapp", &spec, None, Some("custom"), false);
         assert_eq!(path, PathBuf::from("/workspace/target/custom/debug/myapp"));
     }
 
+    #[test]
+    fn get_binary_path_target_dir_profile_triple_placeholders_match_resolved_path() {
+        use crate::tspec::expand_target_dir;
+        use crate::types::CargoConfig;
+
+        let workspace = Path::new("/workspace");
+        let spec = Spec {
+            cargo: CargoConfig {
+                target_dir: Some("iso/{triple}/{profile}".to_string()),
+                target_triple: Some("x86_64-unknown-linux-musl".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+
+        let expanded_td = expand_target_dir(&spec, "foo", Some("release"), false)
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            expanded_td,
+            "iso/x86_64-unknown-linux-musl/release".to_string()
+        );
+
+        let path = get_binary_path(
+            workspace,
+            "myapp",
+            &spec,
+            Some("release"),
+            Some(&expanded_td),
+            false,
+        );
+        assert_eq!(
+            path,
+            PathBuf::from(
+                "/workspace/target/iso/x86_64-unknown-linux-musl/release/x86_64-unknown-linux-musl/release/myapp"
+            )
+        );
+    }
+
     // ==================== is_excluded_from_workspace tests ====================
 
     #[test]
@@ -1160,4 +1904,98 @@ members = ["crates/foo"]
         let result = resolve_manifest_path(Path::new("/no/such/path"));
         assert!(result.is_err());
     }
+
+    // ==================== RootMode / walk_up_for_root tests ====================
+
+    /// Build a temp tree with an outer `[workspace]` Cargo.toml and an inner
+    /// `[package]` Cargo.toml that is neither a member nor excluded.
+    fn detached_package_fixture() -> (TempDir, PathBuf) {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/foo\"]\n",
+        )
+        .unwrap();
+        let pkg_dir = tmp.path().join("detached").join("inner");
+        fs::create_dir_all(&pkg_dir).unwrap();
+        fs::write(pkg_dir.join("Cargo.toml"), "[package]\nname = \"inner\"\n").unwrap();
+        (tmp, pkg_dir)
+    }
+
+    #[test]
+    fn walk_up_workspace_mode_prefers_enclosing_workspace() {
+        let (tmp, pkg_dir) = detached_package_fixture();
+        let root = walk_up_for_root(&pkg_dir, RootMode::Workspace)
+            .unwrap()
+            .unwrap();
+        assert_eq!(root, tmp.path());
+    }
+
+    #[test]
+    fn walk_up_nearest_mode_stops_at_the_package() {
+        let (_tmp, pkg_dir) = detached_package_fixture();
+        let root = walk_up_for_root(&pkg_dir, RootMode::Nearest)
+            .unwrap()
+            .unwrap();
+        assert_eq!(root, pkg_dir);
+    }
+
+    #[test]
+    fn walk_up_nearest_mode_stops_at_a_real_member_too() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(
+            tmp.path().join("Cargo.toml"),
+            "[workspace]\nmembers = [\"crates/foo\"]\n",
+        )
+        .unwrap();
+        let member_dir = tmp.path().join("crates").join("foo");
+        fs::create_dir_all(&member_dir).unwrap();
+        fs::write(member_dir.join("Cargo.toml"), "[package]\nname = \"foo\"\n").unwrap();
+
+        // Nearest mode is unconditional — it stops at the first [package]
+        // found regardless of whether it's an actual workspace member, same
+        // as `cd`-ing into the member and treating it as standalone.
+        let root = walk_up_for_root(&member_dir, RootMode::Nearest)
+            .unwrap()
+            .unwrap();
+        assert_eq!(root, member_dir);
+    }
+
+    #[test]
+    fn root_mode_from_env_defaults_to_workspace() {
+        // SAFETY: single-threaded test env mutation scoped to this check;
+        // no other test reads TSPEC_ROOT_MODE.
+        unsafe {
+            std::env::remove_var(TSPEC_ROOT_MODE_ENV);
+        }
+        assert_eq!(RootMode::from_env().unwrap(), RootMode::Workspace);
+    }
+
+    #[test]
+    fn root_mode_from_env_parses_nearest() {
+        // SAFETY: single-threaded test env mutation scoped to this check;
+        // no other test reads TSPEC_ROOT_MODE.
+        unsafe {
+            std::env::set_var(TSPEC_ROOT_MODE_ENV, "nearest");
+        }
+        let result = RootMode::from_env();
+        unsafe {
+            std::env::remove_var(TSPEC_ROOT_MODE_ENV);
+        }
+        assert_eq!(result.unwrap(), RootMode::Nearest);
+    }
+
+    #[test]
+    fn root_mode_from_env_rejects_unknown_value() {
+        // SAFETY: single-threaded test env mutation scoped to this check;
+        // no other test reads TSPEC_ROOT_MODE.
+        unsafe {
+            std::env::set_var(TSPEC_ROOT_MODE_ENV, "bogus");
+        }
+        let result = RootMode::from_env();
+        unsafe {
+            std::env::remove_var(TSPEC_ROOT_MODE_ENV);
+        }
+        assert!(result.is_err());
+    }
 }
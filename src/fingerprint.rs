@@ -0,0 +1,204 @@
+//! Build-skip fingerprinting for `tspec build`.
+//!
+//! A fingerprint is the resolved spec's content hash (or a fixed marker when
+//! there's no spec) combined with the path+mtime of every file under the
+//! package directory. It's recorded next to the built binary after a
+//! successful build; on the next invocation, an unchanged fingerprint plus an
+//! existing binary means cargo has nothing to do, so `run_cargo` can skip
+//! invoking it entirely and just report "up to date". `--force` bypasses the
+//! check.
+
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::tspec::hash_spec;
+use crate::types::Spec;
+
+const FINGERPRINT_DIR_NAME: &str = ".tspec-fingerprints";
+
+/// Where the cached fingerprint for `pkg_name` under `target_base` lives.
+pub fn fingerprint_path(target_base: &Path, pkg_name: &str) -> PathBuf {
+    target_base
+        .join(FINGERPRINT_DIR_NAME)
+        .join(format!("{pkg_name}.txt"))
+}
+
+fn mtime_nanos(path: &Path) -> u128 {
+    std::fs::metadata(path)
+        .and_then(|m| m.modified())
+        .ok()
+        .and_then(|t| t.duration_since(SystemTime::UNIX_EPOCH).ok())
+        .map(|d| d.as_nanos())
+        .unwrap_or(0)
+}
+
+/// Every file under `pkg_dir`, skipping `target`/`.git` and other hidden
+/// directories, sorted for a deterministic hash order. Mirrors
+/// `metadata_cache::find_manifest_paths`'s walk, but collects every file
+/// (source changes anywhere in the package should invalidate the build),
+/// not just `Cargo.toml`.
+fn source_files(pkg_dir: &Path) -> Vec<PathBuf> {
+    fn walk(dir: &Path, out: &mut Vec<PathBuf>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                if name == "target" || name == ".git" || name.starts_with('.') {
+                    continue;
+                }
+                walk(&path, out);
+            } else {
+                out.push(path);
+            }
+        }
+    }
+
+    let mut files = Vec::new();
+    walk(pkg_dir, &mut files);
+    files.sort();
+    files
+}
+
+fn hash_source_files(pkg_dir: &Path, hasher: &mut Sha256) {
+    for path in source_files(pkg_dir) {
+        hasher.update(path.to_string_lossy().as_bytes());
+        hasher.update(mtime_nanos(&path).to_le_bytes());
+    }
+}
+
+/// Hash of `spec`'s canonical content (or a fixed marker when there's no
+/// spec) plus every source file's path and mtime under `pkg_dir`. Two calls
+/// return the same value iff the spec and every file's mtime are unchanged.
+pub fn compute_fingerprint(pkg_dir: &Path, spec: Option<&Spec>) -> String {
+    let mut hasher = Sha256::new();
+    match spec.and_then(|s| hash_spec(s).ok()) {
+        Some(h) => hasher.update(h.as_bytes()),
+        None => hasher.update(b"no-spec"),
+    }
+    hash_source_files(pkg_dir, &mut hasher);
+    hex::encode(hasher.finalize())
+}
+
+/// Hash of every source file's path and mtime under `pkg_dir`, independent
+/// of any spec. Used by `--smart-rebuild` (see [`crate::smart_rebuild`]) to
+/// tell a source edit apart from a safe spec-only change, since
+/// `classify_rebuild` only ever compares specs and can't see this on its own.
+pub fn compute_source_fingerprint(pkg_dir: &Path) -> String {
+    let mut hasher = Sha256::new();
+    hash_source_files(pkg_dir, &mut hasher);
+    hex::encode(hasher.finalize())
+}
+
+/// Read a previously recorded fingerprint, if any. Any read/parse failure is
+/// just treated as "no cached fingerprint" rather than an error.
+pub fn read_fingerprint(path: &Path) -> Option<String> {
+    std::fs::read_to_string(path)
+        .ok()
+        .map(|s| s.trim().to_string())
+}
+
+/// Record `fingerprint` at `path`, creating parent directories as needed.
+/// Best-effort: a write failure only means the next build won't be able to
+/// skip, not a build failure, so callers should ignore the error.
+pub fn write_fingerprint(path: &Path, fingerprint: &str) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, fingerprint)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use tempfile::TempDir;
+
+    #[test]
+    fn fingerprint_matches_for_unchanged_package() {
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("Cargo.toml"), "[package]\nname=\"x\"").unwrap();
+        let a = compute_fingerprint(tmp.path(), None);
+        let b = compute_fingerprint(tmp.path(), None);
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn fingerprint_changes_when_a_source_file_is_edited() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("src")).unwrap();
+        let main_rs = tmp.path().join("src/main.rs");
+        fs::write(&main_rs, "fn main() {}").unwrap();
+        let before = compute_fingerprint(tmp.path(), None);
+
+        // Force a different mtime than "before" without relying on real time
+        // passing within the same test run.
+        let newer = SystemTime::now() + std::time::Duration::from_secs(60);
+        let file = fs::File::open(&main_rs).unwrap();
+        file.set_modified(newer).unwrap();
+
+        let after = compute_fingerprint(tmp.path(), None);
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn source_fingerprint_changes_when_a_source_file_is_edited() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("src")).unwrap();
+        let main_rs = tmp.path().join("src/main.rs");
+        fs::write(&main_rs, "fn main() {}").unwrap();
+        let before = compute_source_fingerprint(tmp.path());
+
+        let newer = SystemTime::now() + std::time::Duration::from_secs(60);
+        let file = fs::File::open(&main_rs).unwrap();
+        file.set_modified(newer).unwrap();
+
+        let after = compute_source_fingerprint(tmp.path());
+        assert_ne!(before, after);
+    }
+
+    #[test]
+    fn source_fingerprint_is_independent_of_spec() {
+        // compute_fingerprint changes with the spec; compute_source_fingerprint
+        // must not, since --smart-rebuild uses it specifically to detect a
+        // source edit while the spec is held constant.
+        let tmp = TempDir::new().unwrap();
+        fs::write(tmp.path().join("Cargo.toml"), "[package]\nname=\"x\"").unwrap();
+        let a = compute_source_fingerprint(tmp.path());
+        let b = compute_source_fingerprint(tmp.path());
+        assert_eq!(a, b);
+    }
+
+    #[test]
+    fn fingerprint_ignores_target_directory() {
+        let tmp = TempDir::new().unwrap();
+        fs::create_dir_all(tmp.path().join("target")).unwrap();
+        fs::write(tmp.path().join("target/stale.txt"), "junk").unwrap();
+        let before = compute_fingerprint(tmp.path(), None);
+        fs::write(tmp.path().join("target/new.txt"), "more junk").unwrap();
+        let after = compute_fingerprint(tmp.path(), None);
+        assert_eq!(before, after);
+    }
+
+    #[test]
+    fn write_then_read_fingerprint_round_trips() {
+        let tmp = TempDir::new().unwrap();
+        let path = fingerprint_path(tmp.path(), "myapp");
+        write_fingerprint(&path, "abc123").unwrap();
+        assert_eq!(read_fingerprint(&path).as_deref(), Some("abc123"));
+    }
+
+    #[test]
+    fn read_fingerprint_missing_file_returns_none() {
+        let tmp = TempDir::new().unwrap();
+        let path = fingerprint_path(tmp.path(), "myapp");
+        assert_eq!(read_fingerprint(&path), None);
+    }
+}
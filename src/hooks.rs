@@ -0,0 +1,199 @@
+//! Event hooks invoked at the end of `ci` runs (see `[workspace.metadata.tspec] hooks`
+//! in `cmd::ci`).
+//!
+//! A hook is a shell command that receives a JSON summary of the run on
+//! stdin, plus a couple of the same facts as plain environment variables for
+//! callers that would rather not parse JSON. Hook failures are reported but
+//! never change `tspec`'s own exit code — a broken notify script shouldn't
+//! block CI.
+
+use serde::Serialize;
+use std::io::Write;
+use std::process::{Command, Stdio};
+
+/// `TSPEC_RESULT` env var value.
+const RESULT_PASS: &str = "pass";
+const RESULT_FAIL: &str = "fail";
+
+/// JSON payload written to a hook's stdin, and the source of the
+/// `TSPEC_RESULT`/`TSPEC_FAILED_PACKAGES` env vars set for it.
+#[derive(Debug, Serialize)]
+pub struct SummaryPayload {
+    /// Which tspec command produced this summary, e.g. "ci".
+    pub command: String,
+    pub result: String,
+    pub failed_packages: Vec<String>,
+}
+
+impl SummaryPayload {
+    pub fn new(command: &str, failed_packages: Vec<String>) -> Self {
+        SummaryPayload {
+            command: command.to_string(),
+            result: if failed_packages.is_empty() {
+                RESULT_PASS.to_string()
+            } else {
+                RESULT_FAIL.to_string()
+            },
+            failed_packages,
+        }
+    }
+
+    fn env_result(&self) -> &'static str {
+        if self.result == RESULT_PASS {
+            RESULT_PASS
+        } else {
+            RESULT_FAIL
+        }
+    }
+}
+
+/// Outcome of running one hook command.
+pub struct HookOutcome {
+    pub command: String,
+    pub success: bool,
+    /// Empty on success; the failure reason (spawn error or stderr) otherwise.
+    pub detail: String,
+}
+
+/// Run each hook command in order, piping `payload` as JSON on stdin and
+/// setting `TSPEC_RESULT`/`TSPEC_FAILED_PACKAGES` in its environment. A hook
+/// that fails to spawn or exits non-zero is recorded as a failed
+/// [`HookOutcome`] but doesn't stop the remaining hooks from running.
+pub fn run_hooks(hooks: &[String], payload: &SummaryPayload) -> Vec<HookOutcome> {
+    let json = serde_json::to_string(payload).unwrap_or_default();
+    hooks
+        .iter()
+        .map(|hook| run_one_hook(hook, &json, payload))
+        .collect()
+}
+
+fn run_one_hook(hook: &str, json: &str, payload: &SummaryPayload) -> HookOutcome {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c")
+        .arg(hook)
+        .env("TSPEC_RESULT", payload.env_result())
+        .env("TSPEC_FAILED_PACKAGES", payload.failed_packages.join(","))
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped());
+
+    let mut child = match cmd.spawn() {
+        Ok(child) => child,
+        Err(e) => {
+            return HookOutcome {
+                command: hook.to_string(),
+                success: false,
+                detail: format!("failed to run hook: {e}"),
+            };
+        }
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        let _ = stdin.write_all(json.as_bytes());
+    }
+
+    match child.wait_with_output() {
+        Ok(output) => HookOutcome {
+            command: hook.to_string(),
+            success: output.status.success(),
+            detail: if output.status.success() {
+                String::new()
+            } else {
+                String::from_utf8_lossy(&output.stderr).trim().to_string()
+            },
+        },
+        Err(e) => HookOutcome {
+            command: hook.to_string(),
+            success: false,
+            detail: format!("failed to wait on hook: {e}"),
+        },
+    }
+}
+
+/// Print `(hook failed: ...)` for every failed outcome, so a broken hook is
+/// visible without affecting the caller's exit code.
+pub fn report_failed_hooks(outcomes: &[HookOutcome]) {
+    for outcome in outcomes.iter().filter(|o| !o.success) {
+        eprintln!("hook failed: {}: {}", outcome.command, outcome.detail);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    /// A hook script that copies its stdin verbatim to `out_path`, so tests
+    /// can assert on the exact payload a hook receives.
+    fn write_stdin_capture_script(dir: &TempDir, out_path: &std::path::Path) -> String {
+        let script_path = dir.path().join("capture.sh");
+        std::fs::write(
+            &script_path,
+            format!("#!/bin/sh\ncat > {}\n", out_path.display()),
+        )
+        .unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            std::fs::set_permissions(&script_path, std::fs::Permissions::from_mode(0o755)).unwrap();
+        }
+        format!("sh {}", script_path.display())
+    }
+
+    #[test]
+    fn run_hooks_writes_json_payload_to_stdin() {
+        let dir = TempDir::new().unwrap();
+        let out_path = dir.path().join("captured.json");
+        let hook = write_stdin_capture_script(&dir, &out_path);
+
+        let payload = SummaryPayload::new("ci", vec!["pkg-a".to_string()]);
+        let outcomes = run_hooks(&[hook], &payload);
+
+        assert_eq!(outcomes.len(), 1);
+        assert!(outcomes[0].success, "hook failed: {}", outcomes[0].detail);
+        let captured = std::fs::read_to_string(&out_path).unwrap();
+        assert!(captured.contains("\"command\":\"ci\""));
+        assert!(captured.contains("\"result\":\"fail\""));
+        assert!(captured.contains("pkg-a"));
+    }
+
+    #[test]
+    fn run_hooks_reports_success_when_no_packages_failed() {
+        let dir = TempDir::new().unwrap();
+        let out_path = dir.path().join("captured.json");
+        let hook = write_stdin_capture_script(&dir, &out_path);
+
+        let payload = SummaryPayload::new("ci", Vec::new());
+        run_hooks(&[hook], &payload);
+
+        let captured = std::fs::read_to_string(&out_path).unwrap();
+        assert!(captured.contains("\"result\":\"pass\""));
+    }
+
+    #[test]
+    fn run_hooks_records_failure_without_stopping_later_hooks() {
+        let dir = TempDir::new().unwrap();
+        let out_path = dir.path().join("captured.json");
+        let capture_hook = write_stdin_capture_script(&dir, &out_path);
+
+        let payload = SummaryPayload::new("ci", Vec::new());
+        let outcomes = run_hooks(&["exit 1".to_string(), capture_hook], &payload);
+
+        assert_eq!(outcomes.len(), 2);
+        assert!(!outcomes[0].success);
+        assert!(outcomes[1].success);
+        assert!(out_path.exists(), "second hook should still have run");
+    }
+
+    #[test]
+    fn payload_result_is_pass_when_no_failed_packages() {
+        let payload = SummaryPayload::new("ci", Vec::new());
+        assert_eq!(payload.result, "pass");
+    }
+
+    #[test]
+    fn payload_result_is_fail_when_packages_failed() {
+        let payload = SummaryPayload::new("ci", vec!["a".to_string(), "b".to_string()]);
+        assert_eq!(payload.result, "fail");
+    }
+}
@@ -0,0 +1,256 @@
+//! A minimal transactional journal for multi-file tspec writes.
+//!
+//! Operations that write more than one file (e.g. `ts new --from -w`
+//! copying a spec into every workspace member) snapshot each file's
+//! pre-image before touching it, under `.tspec/journal/active/`, and
+//! discard the journal once every write has succeeded. If the process
+//! dies mid-operation, the journal is left behind; starting the next
+//! journaled operation finds it and refuses to proceed, prompting the
+//! user to run `tspec ts rollback` first, which restores every file to
+//! its pre-image and deletes files it had created — so the workspace
+//! never gets stuck half-migrated with no record of what changed, and
+//! no file is touched without the user asking for it. Deliberately
+//! minimal: one active journal at a time, no nested transactions.
+
+use anyhow::{Context, Result, bail};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+/// Where the active journal lives, relative to the workspace root.
+const JOURNAL_DIR: &str = ".tspec/journal/active";
+
+/// What a tracked path looked like before this operation touched it.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+enum PreImage {
+    /// The file didn't exist; rollback deletes it.
+    Absent,
+    /// The file existed; its prior bytes were copied to `snapshot` (a
+    /// path under the journal directory) so rollback can restore them.
+    Present { snapshot: PathBuf },
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct JournalEntry {
+    /// Path written, relative to the workspace root.
+    path: PathBuf,
+    pre_image: PreImage,
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+struct JournalRecord {
+    entries: Vec<JournalEntry>,
+}
+
+/// A multi-file write in progress.
+///
+/// Call [`Journal::write`] for each file, then [`Journal::commit`] once
+/// every write has succeeded. Dropping a `Journal` without committing
+/// (e.g. the process is killed) leaves the journal on disk for
+/// [`rollback_pending`] to clean up later.
+#[derive(Debug)]
+pub struct Journal {
+    workspace: PathBuf,
+    dir: PathBuf,
+    record: JournalRecord,
+}
+
+impl Journal {
+    /// Start a new journal. Fails if one is already active — a previous
+    /// operation crashed mid-write and needs `tspec ts rollback` first.
+    /// Deliberately doesn't roll back automatically: rollback overwrites
+    /// files, and that's not something to do without the user asking for it.
+    pub fn begin(workspace: &Path) -> Result<Journal> {
+        let dir = workspace.join(JOURNAL_DIR);
+        if dir.exists() {
+            bail!(
+                "a previous multi-file tspec operation left an incomplete journal at {}; run `tspec ts rollback` first",
+                dir.display()
+            );
+        }
+        std::fs::create_dir_all(&dir)
+            .with_context(|| format!("failed to create journal directory: {}", dir.display()))?;
+        Ok(Journal {
+            workspace: workspace.to_path_buf(),
+            dir,
+            record: JournalRecord::default(),
+        })
+    }
+
+    /// Snapshot `path`'s pre-image (if it exists) and atomically write
+    /// `contents` to it.
+    pub fn write(&mut self, path: &Path, contents: &[u8]) -> Result<()> {
+        let pre_image = if path.exists() {
+            let snapshot = self
+                .dir
+                .join(format!("{:04}.preimage", self.record.entries.len()));
+            std::fs::copy(path, &snapshot)
+                .with_context(|| format!("failed to snapshot pre-image of {}", path.display()))?;
+            PreImage::Present { snapshot }
+        } else {
+            PreImage::Absent
+        };
+        let rel = path
+            .strip_prefix(&self.workspace)
+            .unwrap_or(path)
+            .to_path_buf();
+        self.record.entries.push(JournalEntry {
+            path: rel,
+            pre_image,
+        });
+        self.save_record()?;
+
+        write_atomic(path, contents)
+    }
+
+    fn save_record(&self) -> Result<()> {
+        let record_path = self.dir.join("journal.json");
+        let json = serde_json::to_string_pretty(&self.record)
+            .context("failed to serialize journal record")?;
+        std::fs::write(&record_path, json)
+            .with_context(|| format!("failed to write journal record: {}", record_path.display()))
+    }
+
+    /// Mark the operation complete and discard the journal — every write
+    /// succeeded, so the pre-image snapshots are no longer needed.
+    pub fn commit(self) -> Result<()> {
+        std::fs::remove_dir_all(&self.dir)
+            .with_context(|| format!("failed to remove journal directory: {}", self.dir.display()))
+    }
+}
+
+/// Write `contents` to `path` via a sibling temp file plus rename, so a
+/// crash mid-write never leaves a truncated file behind.
+fn write_atomic(path: &Path, contents: &[u8]) -> Result<()> {
+    let mut tmp_name = path
+        .file_name()
+        .context("journal-tracked path has no file name")?
+        .to_os_string();
+    tmp_name.push(".tmp");
+    let tmp = path.with_file_name(tmp_name);
+    std::fs::write(&tmp, contents)
+        .with_context(|| format!("failed to write temp file: {}", tmp.display()))?;
+    std::fs::rename(&tmp, path)
+        .with_context(|| format!("failed to rename {} to {}", tmp.display(), path.display()))?;
+    Ok(())
+}
+
+/// What [`rollback_pending`] did, for a short user-facing summary.
+#[derive(Debug, Default)]
+pub struct RollbackReport {
+    /// Files restored to their pre-image.
+    pub restored: Vec<PathBuf>,
+    /// Files deleted because the journal had created them.
+    pub removed: Vec<PathBuf>,
+}
+
+/// If an incomplete journal is sitting at `.tspec/journal/active` (left
+/// behind by a process that died mid-operation), restore every file it
+/// touched to its pre-image, delete files it had created, then discard
+/// the journal. Returns `None` if there was nothing to roll back.
+pub fn rollback_pending(workspace: &Path) -> Result<Option<RollbackReport>> {
+    let dir = workspace.join(JOURNAL_DIR);
+    if !dir.exists() {
+        return Ok(None);
+    }
+
+    let record_path = dir.join("journal.json");
+    let record: JournalRecord =
+        serde_json::from_str(&std::fs::read_to_string(&record_path).with_context(|| {
+            format!("failed to read journal record: {}", record_path.display())
+        })?)
+        .with_context(|| format!("failed to parse journal record: {}", record_path.display()))?;
+
+    let mut report = RollbackReport::default();
+    for entry in &record.entries {
+        let path = workspace.join(&entry.path);
+        match &entry.pre_image {
+            PreImage::Present { snapshot } => {
+                std::fs::copy(snapshot, &path).with_context(|| {
+                    format!("failed to restore {} from pre-image", path.display())
+                })?;
+                report.restored.push(entry.path.clone());
+            }
+            PreImage::Absent => {
+                if path.exists() {
+                    std::fs::remove_file(&path)
+                        .with_context(|| format!("failed to remove {}", path.display()))?;
+                }
+                report.removed.push(entry.path.clone());
+            }
+        }
+    }
+
+    std::fs::remove_dir_all(&dir)
+        .with_context(|| format!("failed to remove journal directory: {}", dir.display()))?;
+
+    Ok(Some(report))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn commit_discards_the_journal() {
+        let dir = TempDir::new().unwrap();
+        let workspace = dir.path();
+        let target = workspace.join("a.ts.toml");
+
+        let mut journal = Journal::begin(workspace).unwrap();
+        journal.write(&target, b"new content").unwrap();
+        journal.commit().unwrap();
+
+        assert_eq!(std::fs::read_to_string(&target).unwrap(), "new content");
+        assert!(!workspace.join(JOURNAL_DIR).exists());
+        assert!(rollback_pending(workspace).unwrap().is_none());
+    }
+
+    #[test]
+    fn begin_rejects_a_second_journal_while_one_is_active() {
+        let dir = TempDir::new().unwrap();
+        let workspace = dir.path();
+
+        let _journal = Journal::begin(workspace).unwrap();
+        let err = Journal::begin(workspace).unwrap_err();
+        assert!(err.to_string().contains("tspec ts rollback"));
+    }
+
+    #[test]
+    fn rollback_restores_modified_files_and_deletes_created_ones() {
+        let dir = TempDir::new().unwrap();
+        let workspace = dir.path();
+        let existing = workspace.join("existing.ts.toml");
+        let created = workspace.join("created.ts.toml");
+        std::fs::write(&existing, "original").unwrap();
+
+        // Simulate an operation that dies after its second write: a
+        // Journal that's dropped instead of committed leaves the journal
+        // directory on disk for rollback_pending to find.
+        {
+            let mut journal = Journal::begin(workspace).unwrap();
+            journal.write(&existing, b"modified").unwrap();
+            journal.write(&created, b"brand new").unwrap();
+            // `journal` is dropped here without calling commit() — the
+            // injected mid-operation failure.
+        }
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), "modified");
+        assert!(created.exists());
+
+        let report = rollback_pending(workspace)
+            .unwrap()
+            .expect("journal pending");
+        assert_eq!(report.restored, vec![PathBuf::from("existing.ts.toml")]);
+        assert_eq!(report.removed, vec![PathBuf::from("created.ts.toml")]);
+
+        assert_eq!(std::fs::read_to_string(&existing).unwrap(), "original");
+        assert!(!created.exists());
+        assert!(!workspace.join(JOURNAL_DIR).exists());
+    }
+
+    #[test]
+    fn rollback_pending_is_none_with_no_journal() {
+        let dir = TempDir::new().unwrap();
+        assert!(rollback_pending(dir.path()).unwrap().is_none());
+    }
+}
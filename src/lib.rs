@@ -1,18 +1,43 @@
 pub mod all;
+pub mod app;
+pub mod audit;
+pub mod baseline;
 pub mod binary;
 pub mod cargo_build;
+pub mod cargo_json;
 pub mod cli;
 pub mod cmd;
 pub mod compare;
+pub mod compat;
+pub mod conflicts;
+pub mod deps;
+pub mod error;
+pub mod examples;
+pub mod experiment;
 pub mod find_paths;
+pub mod fingerprint;
+pub mod hooks;
+pub mod journal;
+pub mod metadata;
+pub mod metadata_cache;
 pub mod options;
 pub mod print_header;
 pub mod print_hline;
+pub mod refcheck;
+pub mod repro;
+pub mod ring_buffer;
 pub mod run;
+pub mod schema;
+pub mod smart_rebuild;
+pub mod target_check;
 pub mod tee;
+pub mod term_width;
 pub mod ts_cmd;
 pub mod tspec;
 pub mod types;
+pub mod units;
+pub mod usage;
+pub mod warnings;
 pub mod workspace;
 
 /// File suffix for tspec files (e.g., "tspec.ts.toml")
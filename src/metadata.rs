@@ -0,0 +1,163 @@
+//! Read `[package.metadata.tspec]` from a package's Cargo.toml.
+//!
+//! Lets a team commit the spec choice next to the package instead of relying
+//! on the `tspec.ts.toml` filename convention, and optionally pin the spec's
+//! content hash so an unreviewed edit to the spec fails the build instead of
+//! silently changing it. See `find_tspec` (consults `default_spec`) and
+//! `verify_spec_hash` (consults `spec_hash`).
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use std::path::Path;
+
+use crate::tspec::hash_spec;
+use crate::types::Spec;
+
+/// Settings under `[package.metadata.tspec]` in a package's Cargo.toml.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize)]
+pub struct TspecMetadata {
+    /// Spec to use when no `-t`/`--tspec` is given on the command line.
+    pub default_spec: Option<String>,
+    /// Content hash (from `hash_spec`) the resolved spec is pinned to.
+    /// Set/updated with `tspec ts pin`.
+    pub spec_hash: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PackageMetadata {
+    #[serde(default)]
+    tspec: TspecMetadata,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct PackageSection {
+    #[serde(default)]
+    metadata: PackageMetadata,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CargoToml {
+    #[serde(default)]
+    package: PackageSection,
+}
+
+/// Read `[package.metadata.tspec]` from `pkg_dir/Cargo.toml`.
+///
+/// Returns the default (all fields `None`) if Cargo.toml is missing the
+/// table, or missing entirely.
+pub fn read_tspec_metadata(pkg_dir: &Path) -> Result<TspecMetadata> {
+    let manifest_path = pkg_dir.join("Cargo.toml");
+    let Ok(content) = std::fs::read_to_string(&manifest_path) else {
+        return Ok(TspecMetadata::default());
+    };
+    let parsed: CargoToml = toml::from_str(&content)
+        .with_context(|| format!("failed to parse: {}", manifest_path.display()))?;
+    Ok(parsed.package.metadata.tspec)
+}
+
+/// Verify a resolved spec against `metadata.spec_hash`, if pinned.
+pub fn verify_spec_hash(metadata: &TspecMetadata, spec: &Spec, pkg_name: &str) -> Result<()> {
+    let Some(pinned) = &metadata.spec_hash else {
+        return Ok(());
+    };
+    let current = hash_spec(spec)?;
+    if &current != pinned {
+        anyhow::bail!(
+            "spec drifted from the hash pinned in Cargo.toml for package '{pkg_name}' \
+             (pinned {pinned}, resolved {current}) — run `tspec ts pin -p {pkg_name}` to update it"
+        );
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::CargoConfig;
+    use tempfile::TempDir;
+
+    fn write_cargo_toml(dir: &Path, content: &str) {
+        std::fs::write(dir.join("Cargo.toml"), content).unwrap();
+    }
+
+    #[test]
+    fn read_tspec_metadata_missing_manifest_is_default() {
+        let dir = TempDir::new().unwrap();
+        let metadata = read_tspec_metadata(dir.path()).unwrap();
+        assert_eq!(metadata, TspecMetadata::default());
+    }
+
+    #[test]
+    fn read_tspec_metadata_missing_table_is_default() {
+        let dir = TempDir::new().unwrap();
+        write_cargo_toml(
+            dir.path(),
+            "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\n",
+        );
+        let metadata = read_tspec_metadata(dir.path()).unwrap();
+        assert_eq!(metadata, TspecMetadata::default());
+    }
+
+    #[test]
+    fn read_tspec_metadata_default_spec() {
+        let dir = TempDir::new().unwrap();
+        write_cargo_toml(
+            dir.path(),
+            "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\n\n\
+             [package.metadata.tspec]\ndefault_spec = \"tspec-small\"\n",
+        );
+        let metadata = read_tspec_metadata(dir.path()).unwrap();
+        assert_eq!(metadata.default_spec.as_deref(), Some("tspec-small"));
+        assert_eq!(metadata.spec_hash, None);
+    }
+
+    #[test]
+    fn read_tspec_metadata_default_spec_and_hash() {
+        let dir = TempDir::new().unwrap();
+        write_cargo_toml(
+            dir.path(),
+            "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\n\n\
+             [package.metadata.tspec]\ndefault_spec = \"tspec-small\"\nspec_hash = \"abcd1234\"\n",
+        );
+        let metadata = read_tspec_metadata(dir.path()).unwrap();
+        assert_eq!(metadata.default_spec.as_deref(), Some("tspec-small"));
+        assert_eq!(metadata.spec_hash.as_deref(), Some("abcd1234"));
+    }
+
+    #[test]
+    fn verify_spec_hash_no_pin_always_ok() {
+        let metadata = TspecMetadata::default();
+        let spec = Spec::default();
+        verify_spec_hash(&metadata, &spec, "pkg").unwrap();
+    }
+
+    #[test]
+    fn verify_spec_hash_matching_pin_ok() {
+        let spec = Spec::default();
+        let hash = hash_spec(&spec).unwrap();
+        let metadata = TspecMetadata {
+            default_spec: None,
+            spec_hash: Some(hash),
+        };
+        verify_spec_hash(&metadata, &spec, "pkg").unwrap();
+    }
+
+    #[test]
+    fn verify_spec_hash_mismatch_errors_with_pin_command() {
+        let spec = Spec {
+            cargo: CargoConfig {
+                profile: Some("release".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        let metadata = TspecMetadata {
+            default_spec: None,
+            spec_hash: Some("deadbeef".to_string()),
+        };
+        let err = verify_spec_hash(&metadata, &spec, "mypkg").unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("drifted"), "{msg}");
+        assert!(msg.contains("tspec ts pin -p mypkg"), "{msg}");
+    }
+}
@@ -0,0 +1,326 @@
+//! On-disk cache for [`crate::workspace::WorkspaceInfo::discover`].
+//!
+//! `cargo metadata` is the slowest step in most tspec invocations on a large
+//! workspace, and most of them (`ts list`, `ts show`, the upcoming shell
+//! completion helper) only need the member list, not a fresh metadata walk.
+//! This cache stores the parsed result in `target/.tspec-metadata.json`
+//! alongside the mtimes of every Cargo.toml that contributed to it; a cache
+//! is valid only if every recorded mtime still matches and the toolchain
+//! hasn't changed underneath it. Any other mismatch (missing file, wrong
+//! schema version, corrupted JSON) is treated as a plain cache miss rather
+//! than an error.
+
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use crate::workspace::WorkspaceInfo;
+
+/// Bumped whenever the on-disk shape changes, so a cache left over from an
+/// older tspec version is treated as a miss instead of misparsed.
+const SCHEMA_VERSION: u32 = 1;
+
+const CACHE_FILE_NAME: &str = ".tspec-metadata.json";
+
+/// Set (to anything) to force a refresh and skip reading an existing cache.
+/// Backs `tspec --refresh-metadata`.
+pub const REFRESH_METADATA_ENV: &str = "TSPEC_REFRESH_METADATA";
+
+/// One Cargo.toml the cached result depends on, plus its mtime when the
+/// cache was written. The cache is valid only if every entry's mtime is
+/// still exactly this value.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ManifestFingerprint {
+    path: PathBuf,
+    mtime_unix_nanos: u128,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedWorkspace {
+    schema_version: u32,
+    /// `RUSTUP_TOOLCHAIN`/`CARGO` env values recorded when the cache was
+    /// written; a mismatch means the active toolchain changed, which can
+    /// change package resolution independently of any mtime.
+    toolchain_fingerprint: String,
+    manifests: Vec<ManifestFingerprint>,
+    /// Hash of every `Cargo.toml` path found anywhere under the workspace
+    /// root, sorted. Catches a member appearing or disappearing under a glob
+    /// `members` pattern (e.g. `crates/*`), which touches no mtime `manifests`
+    /// already tracks since the new/removed file was never in that list.
+    manifest_set_fingerprint: String,
+    workspace: WorkspaceInfo,
+}
+
+fn cache_path(root: &Path) -> PathBuf {
+    root.join("target").join(CACHE_FILE_NAME)
+}
+
+fn toolchain_fingerprint() -> String {
+    format!(
+        "{}|{}",
+        std::env::var("RUSTUP_TOOLCHAIN").unwrap_or_default(),
+        std::env::var("CARGO").unwrap_or_default(),
+    )
+}
+
+fn mtime_nanos(path: &Path) -> Option<u128> {
+    std::fs::metadata(path)
+        .ok()?
+        .modified()
+        .ok()?
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .ok()
+        .map(|d| d.as_nanos())
+}
+
+/// The root Cargo.toml plus every member's Cargo.toml, fingerprinted by
+/// mtime. A manifest that can't be stat'd (e.g. removed since) is simply
+/// left out, which makes `load` treat it as changed (no fingerprint to
+/// compare against) rather than panicking.
+fn fingerprints_for(info: &WorkspaceInfo) -> Vec<ManifestFingerprint> {
+    std::iter::once(info.root.join("Cargo.toml"))
+        .chain(info.members.iter().map(|m| m.path.join("Cargo.toml")))
+        .filter_map(|path| {
+            let mtime_unix_nanos = mtime_nanos(&path)?;
+            Some(ManifestFingerprint {
+                path,
+                mtime_unix_nanos,
+            })
+        })
+        .collect()
+}
+
+/// Every `Cargo.toml` found by walking `root`, skipping `target`/`.git` and
+/// other hidden directories. Deliberately broader than `cargo`'s own
+/// `members`/`exclude` glob resolution: a directory outside the real
+/// membership set only costs an extra cache miss, while missing one would
+/// silently keep serving a stale cache, which is the bug this guards against.
+fn find_manifest_paths(root: &Path) -> Vec<PathBuf> {
+    fn walk(dir: &Path, out: &mut Vec<PathBuf>) {
+        let Ok(entries) = std::fs::read_dir(dir) else {
+            return;
+        };
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let Ok(file_type) = entry.file_type() else {
+                continue;
+            };
+            if file_type.is_dir() {
+                let name = entry.file_name();
+                let name = name.to_string_lossy();
+                if name == "target" || name == ".git" || name.starts_with('.') {
+                    continue;
+                }
+                walk(&path, out);
+            } else if entry.file_name() == "Cargo.toml" {
+                out.push(path);
+            }
+        }
+    }
+
+    let mut paths = Vec::new();
+    walk(root, &mut paths);
+    paths.sort();
+    paths
+}
+
+/// Hash of the sorted list of `Cargo.toml` paths under `root`, relative to
+/// `root` so the fingerprint is stable across checkouts at different
+/// absolute paths.
+fn manifest_set_fingerprint(root: &Path) -> String {
+    let mut hasher = Sha256::new();
+    for path in find_manifest_paths(root) {
+        let rel = path.strip_prefix(root).unwrap_or(&path);
+        hasher.update(rel.to_string_lossy().as_bytes());
+        hasher.update(b"\n");
+    }
+    hex::encode(hasher.finalize())
+}
+
+/// Try to load a still-valid cache for `root`. Returns `None` on any kind of
+/// miss: `--refresh-metadata`/`TSPEC_REFRESH_METADATA` set, no cache file,
+/// wrong schema version, toolchain changed, a manifest missing or touched,
+/// or a corrupted/unparsable cache file.
+pub fn load(root: &Path) -> Option<WorkspaceInfo> {
+    if std::env::var_os(REFRESH_METADATA_ENV).is_some() {
+        return None;
+    }
+    let content = std::fs::read_to_string(cache_path(root)).ok()?;
+    let cached: CachedWorkspace = serde_json::from_str(&content).ok()?;
+    if cached.schema_version != SCHEMA_VERSION {
+        return None;
+    }
+    if cached.toolchain_fingerprint != toolchain_fingerprint() {
+        return None;
+    }
+    for fp in &cached.manifests {
+        if mtime_nanos(&fp.path) != Some(fp.mtime_unix_nanos) {
+            return None;
+        }
+    }
+    if cached.manifest_set_fingerprint != manifest_set_fingerprint(root) {
+        return None;
+    }
+    Some(cached.workspace)
+}
+
+/// Write a fresh cache for `info`. Best effort: a write failure (e.g. a
+/// read-only or missing `target/` dir) is silently ignored, since the cache
+/// is purely a speed optimization and the caller already has a correct
+/// `WorkspaceInfo` either way.
+pub fn store(info: &WorkspaceInfo) {
+    let cached = CachedWorkspace {
+        schema_version: SCHEMA_VERSION,
+        toolchain_fingerprint: toolchain_fingerprint(),
+        manifests: fingerprints_for(info),
+        manifest_set_fingerprint: manifest_set_fingerprint(&info.root),
+        workspace: info.clone(),
+    };
+    let Ok(json) = serde_json::to_string(&cached) else {
+        return;
+    };
+    let path = cache_path(&info.root);
+    if let Some(parent) = path.parent() {
+        let _ = std::fs::create_dir_all(parent);
+    }
+    let _ = std::fs::write(path, json);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::{PackageKind, PackageMember};
+    use tempfile::TempDir;
+
+    fn write_manifest(dir: &Path, rel: &str) -> PathBuf {
+        let path = dir.join(rel);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent).unwrap();
+        }
+        std::fs::write(&path, "[workspace]\n").unwrap();
+        path
+    }
+
+    fn sample_workspace(root: &Path) -> WorkspaceInfo {
+        WorkspaceInfo {
+            root: root.to_path_buf(),
+            members: vec![PackageMember {
+                name: "app".to_string(),
+                version: "0.1.0".to_string(),
+                path: root.join("app"),
+                has_binary: true,
+                kind: PackageKind::App,
+            }],
+            version: None,
+            default_members: Vec::new(),
+        }
+    }
+
+    fn clear_refresh_env() {
+        unsafe {
+            std::env::remove_var(REFRESH_METADATA_ENV);
+        }
+    }
+
+    #[test]
+    fn load_misses_with_no_cache_file() {
+        clear_refresh_env();
+        let tmp = TempDir::new().unwrap();
+        assert!(load(tmp.path()).is_none());
+    }
+
+    #[test]
+    fn store_then_load_hits_when_manifests_are_unchanged() {
+        clear_refresh_env();
+        let tmp = TempDir::new().unwrap();
+        write_manifest(tmp.path(), "Cargo.toml");
+        write_manifest(tmp.path(), "app/Cargo.toml");
+        let info = sample_workspace(tmp.path());
+
+        store(&info);
+        let loaded = load(tmp.path()).expect("cache should hit");
+        assert_eq!(loaded.members.len(), 1);
+        assert_eq!(loaded.members[0].name, "app");
+    }
+
+    #[test]
+    fn load_misses_after_a_manifest_is_touched() {
+        clear_refresh_env();
+        let tmp = TempDir::new().unwrap();
+        write_manifest(tmp.path(), "Cargo.toml");
+        let manifest = write_manifest(tmp.path(), "app/Cargo.toml");
+        let info = sample_workspace(tmp.path());
+        store(&info);
+        assert!(load(tmp.path()).is_some());
+
+        // Touch the manifest with a distinctly newer mtime.
+        let newer = SystemTime::now() + std::time::Duration::from_secs(60);
+        let file = std::fs::File::open(&manifest).unwrap();
+        file.set_modified(newer).unwrap();
+
+        assert!(load(tmp.path()).is_none());
+    }
+
+    #[test]
+    fn load_misses_after_a_new_glob_discovered_member_appears() {
+        clear_refresh_env();
+        let tmp = TempDir::new().unwrap();
+        write_manifest(tmp.path(), "Cargo.toml");
+        write_manifest(tmp.path(), "crates/app/Cargo.toml");
+        let info = sample_workspace(tmp.path());
+        store(&info);
+        assert!(load(tmp.path()).is_some());
+
+        // A brand-new member under a glob `members = ["crates/*"]` pattern
+        // touches no file already in the recorded fingerprint set.
+        write_manifest(tmp.path(), "crates/new-member/Cargo.toml");
+
+        assert!(load(tmp.path()).is_none());
+    }
+
+    #[test]
+    fn load_misses_on_corrupted_cache_file() {
+        clear_refresh_env();
+        let tmp = TempDir::new().unwrap();
+        write_manifest(tmp.path(), "Cargo.toml");
+        std::fs::create_dir_all(tmp.path().join("target")).unwrap();
+        std::fs::write(cache_path(tmp.path()), "not valid json{{{").unwrap();
+
+        assert!(load(tmp.path()).is_none());
+    }
+
+    #[test]
+    fn load_misses_on_schema_version_mismatch() {
+        clear_refresh_env();
+        let tmp = TempDir::new().unwrap();
+        write_manifest(tmp.path(), "Cargo.toml");
+        let info = sample_workspace(tmp.path());
+        store(&info);
+
+        let content = std::fs::read_to_string(cache_path(tmp.path())).unwrap();
+        let bumped = content.replace(
+            &format!("\"schema_version\":{SCHEMA_VERSION}"),
+            "\"schema_version\":999999",
+        );
+        std::fs::write(cache_path(tmp.path()), bumped).unwrap();
+
+        assert!(load(tmp.path()).is_none());
+    }
+
+    #[test]
+    fn refresh_metadata_env_forces_a_miss_even_when_cache_is_valid() {
+        let tmp = TempDir::new().unwrap();
+        write_manifest(tmp.path(), "Cargo.toml");
+        let info = sample_workspace(tmp.path());
+        store(&info);
+        assert!(load(tmp.path()).is_some());
+
+        unsafe {
+            std::env::set_var(REFRESH_METADATA_ENV, "1");
+        }
+        let result = load(tmp.path());
+        clear_refresh_env();
+        assert!(result.is_none());
+    }
+}
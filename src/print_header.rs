@@ -1,14 +1,10 @@
+use crate::term_width::terminal_width;
+
 #[macro_export]
 macro_rules! print_header {
-    ($title:expr) => {{
-        $crate::print_hline!();
-        println!(
-            "{:^width$}",
-            $title,
-            width = $crate::print_hline::LINE_WIDTH
-        );
-        $crate::print_hline!();
-    }};
+    ($title:expr) => {
+        $crate::print_header::print_header_auto(&$title.to_string())
+    };
     ($title:expr, $width:expr) => {{
         $crate::print_hline!($width);
         println!("{:^width$}", $title, width = $width);
@@ -20,3 +16,14 @@ macro_rules! print_header {
         $crate::print_hline!($width, $ch);
     }};
 }
+
+/// Print a title centered between two rules sized to the detected terminal
+/// width (see [`terminal_width`]), rather than the old fixed 44-column
+/// layout. Used by the single-arg `print_header!(title)` form; callers
+/// that pass an explicit width go straight through the macro's other arms.
+pub fn print_header_auto(title: &str) {
+    let width = terminal_width(None);
+    crate::print_hline::print_hline_impl(width, crate::print_hline::LINE_CHAR);
+    println!("{:^width$}", title, width = width);
+    crate::print_hline::print_hline_impl(width, crate::print_hline::LINE_CHAR);
+}
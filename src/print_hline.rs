@@ -1,13 +1,16 @@
+use crate::term_width::terminal_width;
+
+/// Fallback rule width used only when the terminal width can't be
+/// determined at all (see [`terminal_width`]) — kept as the old fixed
+/// constant so an explicit-width caller's behavior doesn't change, only
+/// the zero-arg default stops being hardcoded.
 pub const LINE_WIDTH: usize = 44;
 pub const LINE_CHAR: char = '=';
 
 #[macro_export]
 macro_rules! print_hline {
     () => {
-        $crate::print_hline::print_hline_impl(
-            $crate::print_hline::LINE_WIDTH,
-            $crate::print_hline::LINE_CHAR,
-        )
+        $crate::print_hline::print_hline_auto($crate::print_hline::LINE_CHAR)
     };
     ($width:expr) => {
         $crate::print_hline::print_hline_impl($width, $crate::print_hline::LINE_CHAR)
@@ -17,6 +20,16 @@ macro_rules! print_hline {
     };
 }
 
+/// Print a horizontal rule sized to the detected terminal width (see
+/// [`terminal_width`]) rather than a fixed column count, so it neither
+/// looks stubby on a wide terminal nor wraps on a narrow one. Used by the
+/// zero-arg `print_hline!()` form; callers that pass an explicit width
+/// (e.g. to match a table they've already sized) go through
+/// [`print_hline_impl`] instead.
+pub fn print_hline_auto(ch: char) {
+    print_hline_impl(terminal_width(None), ch);
+}
+
 pub fn print_hline_impl(width: usize, ch: char) {
     let line: String = std::iter::repeat_n(ch, width).collect();
     println!("{line}");
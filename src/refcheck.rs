@@ -0,0 +1,257 @@
+//! Cross-reference integrity check for spec references scattered across a
+//! workspace (`tspec doctor`, and standalone as `tspec ts check-refs`).
+//!
+//! As specs get renamed or deleted, references to them elsewhere go stale
+//! and each feature that reads one only discovers that at point-of-use,
+//! with a different error every time. This collects every reference in one
+//! pass and reports the dangling ones together, with the file/key they
+//! live in and the closest still-existing spec name as a suggestion.
+//!
+//! Today there's exactly one reference-holding subsystem: a package's
+//! `[package.metadata.tspec] default_spec` (see [`crate::metadata`]).
+//! `compat.toml`'s `incompatible` list (see [`crate::compat`]) looks similar
+//! but holds content hashes, not spec names, so renaming a spec can't make
+//! an entry stale — there's nothing for it to register here. Workspace-level
+//! spec sets and a tspec lock file don't exist in this codebase yet. New
+//! reference-holding features register a [`RefSource`] and
+//! [`builtin_sources`] picks them up automatically.
+
+use anyhow::Result;
+
+use crate::find_paths::{find_tspec, find_tspecs};
+use crate::metadata::read_tspec_metadata;
+use crate::workspace::{PackageMember, WorkspaceInfo};
+
+/// One reference to a spec by name, as enumerated by a [`RefSource`].
+pub struct SpecRef {
+    /// File the reference lives in, relative to the package directory.
+    pub file: &'static str,
+    /// Key/field the reference was read from.
+    pub key: &'static str,
+    /// The spec name as written (not yet resolved).
+    pub referenced: String,
+}
+
+/// A subsystem that holds references to specs by name. Implement this and
+/// add an instance to [`builtin_sources`] to have new references covered.
+pub trait RefSource {
+    /// Short name for this source, used to label findings.
+    fn name(&self) -> &'static str;
+    /// All spec references this source holds for `member`.
+    fn enumerate(&self, member: &PackageMember) -> Result<Vec<SpecRef>>;
+}
+
+/// `[package.metadata.tspec] default_spec` in a package's Cargo.toml.
+struct MetadataDefaultSpecSource;
+
+impl RefSource for MetadataDefaultSpecSource {
+    fn name(&self) -> &'static str {
+        "metadata-default-spec"
+    }
+
+    fn enumerate(&self, member: &PackageMember) -> Result<Vec<SpecRef>> {
+        let metadata = read_tspec_metadata(&member.path)?;
+        Ok(match metadata.default_spec {
+            Some(referenced) => vec![SpecRef {
+                file: "Cargo.toml",
+                key: "package.metadata.tspec.default_spec",
+                referenced,
+            }],
+            None => Vec::new(),
+        })
+    }
+}
+
+/// Every reference source this build of tspec knows about.
+pub fn builtin_sources() -> Vec<Box<dyn RefSource>> {
+    vec![Box::new(MetadataDefaultSpecSource)]
+}
+
+/// One reference that doesn't resolve to an existing spec.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DanglingRef {
+    pub package: String,
+    pub source: &'static str,
+    pub file: &'static str,
+    pub key: &'static str,
+    pub referenced: String,
+    pub suggestion: Option<String>,
+}
+
+/// Spec names that actually exist in `member`'s directory.
+fn known_spec_names(member: &PackageMember) -> Vec<String> {
+    find_tspecs(&member.path, &[])
+        .map(|paths| {
+            paths
+                .into_iter()
+                .filter_map(|p| p.file_name().map(|n| n.to_string_lossy().into_owned()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Levenshtein edit distance, for suggesting the closest existing spec name.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+    let mut row: Vec<usize> = (0..=b.len()).collect();
+    for i in 1..=a.len() {
+        let mut prev = row[0];
+        row[0] = i;
+        for j in 1..=b.len() {
+            let cur = row[j];
+            row[j] = if a[i - 1] == b[j - 1] {
+                prev
+            } else {
+                1 + prev.min(row[j]).min(row[j - 1])
+            };
+            prev = cur;
+        }
+    }
+    row[b.len()]
+}
+
+fn closest_match(referenced: &str, candidates: &[String]) -> Option<String> {
+    candidates
+        .iter()
+        .min_by_key(|candidate| edit_distance(referenced, candidate))
+        .cloned()
+}
+
+/// Run every [`RefSource`] against every workspace member and report
+/// references that don't resolve to an existing spec.
+pub fn check_refs(workspace: &WorkspaceInfo) -> Result<Vec<DanglingRef>> {
+    let sources = builtin_sources();
+    let mut dangling = Vec::new();
+
+    for member in &workspace.members {
+        for source in &sources {
+            for spec_ref in source.enumerate(member)? {
+                // `find_tspec` errors (rather than returning `None`) when an
+                // explicit name doesn't resolve — that error *is* the
+                // "dangling" signal here.
+                if find_tspec(&member.path, Some(&spec_ref.referenced)).is_ok() {
+                    continue;
+                }
+                let suggestion = closest_match(&spec_ref.referenced, &known_spec_names(member));
+                dangling.push(DanglingRef {
+                    package: member.name.clone(),
+                    source: source.name(),
+                    file: spec_ref.file,
+                    key: spec_ref.key,
+                    referenced: spec_ref.referenced,
+                    suggestion,
+                });
+            }
+        }
+    }
+
+    Ok(dangling)
+}
+
+/// Print one line per dangling reference, including a suggestion when one
+/// was found. Used by both `tspec doctor` and `tspec ts check-refs`.
+pub fn print_dangling(dangling: &[DanglingRef]) {
+    for d in dangling {
+        match &d.suggestion {
+            Some(suggestion) => println!(
+                "{}: {} ({}) references unknown spec `{}` — did you mean `{}`?",
+                d.package, d.file, d.key, d.referenced, suggestion
+            ),
+            None => println!(
+                "{}: {} ({}) references unknown spec `{}`",
+                d.package, d.file, d.key, d.referenced
+            ),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::workspace::PackageKind;
+    use std::path::Path;
+    use tempfile::TempDir;
+
+    fn member(path: &Path, name: &str) -> PackageMember {
+        PackageMember {
+            name: name.to_string(),
+            version: "0.1.0".to_string(),
+            path: path.to_path_buf(),
+            has_binary: false,
+            kind: PackageKind::Lib,
+        }
+    }
+
+    fn workspace_of(members: Vec<PackageMember>) -> WorkspaceInfo {
+        WorkspaceInfo {
+            root: members.first().map(|m| m.path.clone()).unwrap_or_default(),
+            members,
+            version: None,
+            default_members: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn no_metadata_is_clean() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        let workspace = workspace_of(vec![member(tmp.path(), "app")]);
+        assert!(check_refs(&workspace).unwrap().is_empty());
+    }
+
+    #[test]
+    fn default_spec_pointing_at_existing_file_is_clean() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\
+             [package.metadata.tspec]\ndefault_spec = \"tspec-small.ts.toml\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("tspec-small.ts.toml"),
+            "panic = \"abort\"\n",
+        )
+        .unwrap();
+        let workspace = workspace_of(vec![member(tmp.path(), "app")]);
+        assert!(check_refs(&workspace).unwrap().is_empty());
+    }
+
+    #[test]
+    fn dangling_default_spec_is_reported_with_suggestion() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n\
+             [package.metadata.tspec]\ndefault_spec = \"tspec-smal.ts.toml\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join("tspec-small.ts.toml"),
+            "panic = \"abort\"\n",
+        )
+        .unwrap();
+        let workspace = workspace_of(vec![member(tmp.path(), "app")]);
+
+        let dangling = check_refs(&workspace).unwrap();
+        assert_eq!(dangling.len(), 1);
+        assert_eq!(dangling[0].package, "app");
+        assert_eq!(dangling[0].key, "package.metadata.tspec.default_spec");
+        assert_eq!(dangling[0].referenced, "tspec-smal.ts.toml");
+        assert_eq!(
+            dangling[0].suggestion.as_deref(),
+            Some("tspec-small.ts.toml")
+        );
+    }
+
+    #[test]
+    fn edit_distance_matches_known_values() {
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("same", "same"), 0);
+    }
+}
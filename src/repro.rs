@@ -0,0 +1,304 @@
+//! Byte-for-byte reproducibility checking: build a package twice into
+//! isolated target dirs and diff the resulting binaries.
+//!
+//! `diff_binaries` reports whether two binaries are identical, and, when
+//! they aren't and both parse as ELF64, which named sections differ.
+//! `find_culprits` scans a binary's printable strings for the two most
+//! common causes of non-reproducible builds: absolute paths baked in by
+//! the compiler (debug info, panic locations) and embedded ISO-date
+//! timestamps. Both are heuristics — real culprit, not proof.
+
+use anyhow::{Context, Result};
+use std::path::Path;
+
+use crate::binary::read_elf_sections;
+use crate::cargo_build::build_package;
+use crate::tspec::{resolve_spec, save_spec};
+use crate::types::CargoFlags;
+
+/// A named section whose bytes differ between two builds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SectionDiff {
+    pub name: String,
+    pub offset: u64,
+    pub size: u64,
+}
+
+/// Result of comparing two builds of the same package/spec.
+#[derive(Debug, Clone, Default)]
+pub struct ReproReport {
+    pub identical: bool,
+    pub size_a: u64,
+    pub size_b: u64,
+    /// Named sections that differ. Empty if `identical`, or if the
+    /// binaries aren't same-length ELF64 files (no section-level detail
+    /// available — `identical: false` is still accurate).
+    pub diffs: Vec<SectionDiff>,
+    /// Heuristically-detected likely causes, deduplicated across both
+    /// binaries. Empty when `identical`.
+    pub culprits: Vec<String>,
+}
+
+/// Compare two binaries byte-for-byte, and — if both are same-length
+/// ELF64 files — attribute the differing bytes to named sections.
+pub fn diff_binaries(a: &Path, b: &Path) -> Result<ReproReport> {
+    let data_a = std::fs::read(a).with_context(|| format!("failed to read {}", a.display()))?;
+    let data_b = std::fs::read(b).with_context(|| format!("failed to read {}", b.display()))?;
+
+    let identical = data_a == data_b;
+    let mut diffs = Vec::new();
+    if !identical
+        && data_a.len() == data_b.len()
+        && let Some(sections) = read_elf_sections(a)?
+    {
+        for section in sections {
+            let start = section.offset as usize;
+            let end = start + section.size as usize;
+            if end <= data_a.len() && data_a[start..end] != data_b[start..end] {
+                diffs.push(SectionDiff {
+                    name: section.name,
+                    offset: section.offset,
+                    size: section.size,
+                });
+            }
+        }
+    }
+
+    let mut culprits = Vec::new();
+    if !identical {
+        culprits.extend(find_culprits(&data_a));
+        for c in find_culprits(&data_b) {
+            if !culprits.contains(&c) {
+                culprits.push(c);
+            }
+        }
+    }
+
+    Ok(ReproReport {
+        identical,
+        size_a: data_a.len() as u64,
+        size_b: data_b.len() as u64,
+        diffs,
+        culprits,
+    })
+}
+
+/// Build `pkg_name` twice, each into its own target dir, and diff the
+/// resulting binaries. The two target dirs are scoped to this process id,
+/// so they never collide with a concurrent repro run or a normal build,
+/// and neither build shares cached artifacts with the other — every
+/// repro run is a fresh build of both binaries by construction, so there's
+/// no separate "force fresh" mode to ask for.
+pub fn check_reproducibility(
+    project_root: &Path,
+    pkg_name: &str,
+    tspec: Option<&str>,
+    flags: &CargoFlags,
+) -> Result<ReproReport> {
+    let (_, spec) = resolve_spec(Some(pkg_name), tspec, project_root)?;
+    let base_spec = spec.unwrap_or_default();
+    let run_id = std::process::id();
+
+    let scratch_dir = project_root.join(".tspec/repro").join(pkg_name);
+    std::fs::create_dir_all(&scratch_dir)
+        .with_context(|| format!("failed to create {}", scratch_dir.display()))?;
+
+    let mut binary_paths = Vec::with_capacity(2);
+    for label in ["a", "b"] {
+        let mut run_spec = base_spec.clone();
+        run_spec.cargo.target_dir = Some(format!("repro/{pkg_name}-{run_id}-{label}"));
+        let run_spec_path = scratch_dir.join(format!("{label}{}", crate::TSPEC_SUFFIX));
+        save_spec(&run_spec, &run_spec_path)?;
+
+        let result = build_package(
+            pkg_name,
+            Some(run_spec_path.to_str().unwrap()),
+            false,
+            false,
+            true, // force: each repro run must actually invoke cargo, never skip
+            None,
+            false,
+            project_root,
+            flags,
+            false,
+            true, // quiet_cargo: two full builds' "Compiling" spam isn't useful twice
+            false,
+            false,
+            false,
+            false,
+            false,
+            None,
+        )?;
+        binary_paths.push(result.binary_path);
+    }
+
+    // Best-effort: only the temp spec copies live here, not the builds.
+    let _ = std::fs::remove_dir_all(&scratch_dir);
+
+    diff_binaries(&binary_paths[0], &binary_paths[1])
+}
+
+/// Minimum run length to treat as a "string" when scanning raw bytes,
+/// same threshold the `strings` utility defaults to.
+const MIN_STRING_LEN: usize = 4;
+
+/// Extract printable-ASCII runs of at least `MIN_STRING_LEN` bytes.
+fn extract_strings(data: &[u8]) -> Vec<&str> {
+    let mut strings = Vec::new();
+    let mut start = None;
+    for (i, &b) in data.iter().enumerate() {
+        let printable = (0x20..=0x7e).contains(&b);
+        match (printable, start) {
+            (true, None) => start = Some(i),
+            (false, Some(s)) => {
+                if i - s >= MIN_STRING_LEN {
+                    // SAFETY-free: the run is verified ASCII above, so this is always valid UTF-8.
+                    strings.push(std::str::from_utf8(&data[s..i]).unwrap());
+                }
+                start = None;
+            }
+            _ => {}
+        }
+    }
+    if let Some(s) = start
+        && data.len() - s >= MIN_STRING_LEN
+    {
+        strings.push(std::str::from_utf8(&data[s..]).unwrap());
+    }
+    strings
+}
+
+/// `true` if `s` contains a `YYYY-MM-DD`-shaped run with a plausible year.
+fn contains_iso_date(s: &str) -> bool {
+    let bytes = s.as_bytes();
+    if bytes.len() < 10 {
+        return false;
+    }
+    for start in 0..=bytes.len() - 10 {
+        let chunk = &bytes[start..start + 10];
+        let digits_ok = chunk[0..4].iter().all(u8::is_ascii_digit)
+            && chunk[5..7].iter().all(u8::is_ascii_digit)
+            && chunk[8..10].iter().all(u8::is_ascii_digit);
+        if !digits_ok || chunk[4] != b'-' || chunk[7] != b'-' {
+            continue;
+        }
+        let year: u32 = std::str::from_utf8(&chunk[0..4]).unwrap().parse().unwrap();
+        if (1990..=2099).contains(&year) {
+            return true;
+        }
+    }
+    false
+}
+
+/// Scan `data` for likely causes of build non-reproducibility: absolute
+/// paths (which bake in the checkout location) and embedded ISO-date
+/// timestamps. Heuristic — findings are suggestive, not certain.
+pub fn find_culprits(data: &[u8]) -> Vec<String> {
+    let mut culprits = Vec::new();
+    for s in extract_strings(data) {
+        if s.starts_with('/') && s.matches('/').count() >= 2 {
+            let finding = format!("embedded absolute path: {s}");
+            if !culprits.contains(&finding) {
+                culprits.push(finding);
+            }
+        } else if contains_iso_date(s) {
+            let finding = format!("embedded timestamp: {s}");
+            if !culprits.contains(&finding) {
+                culprits.push(finding);
+            }
+        }
+    }
+    culprits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::binary::build_synthetic_elf;
+
+    #[test]
+    fn diff_binaries_identical_files_report_no_diffs() {
+        let dir = tempfile::tempdir().unwrap();
+        let data = build_synthetic_elf(&[(".text", b"\x90\x90\x90\x90")]);
+        let a = dir.path().join("a");
+        let b = dir.path().join("b");
+        std::fs::write(&a, &data).unwrap();
+        std::fs::write(&b, &data).unwrap();
+
+        let report = diff_binaries(&a, &b).unwrap();
+        assert!(report.identical);
+        assert!(report.diffs.is_empty());
+        assert!(report.culprits.is_empty());
+    }
+
+    #[test]
+    fn diff_binaries_attributes_differing_bytes_to_sections() {
+        let dir = tempfile::tempdir().unwrap();
+        let data_a = build_synthetic_elf(&[(".text", b"\x90\x90\x90\x90"), (".data", b"fixed!!!")]);
+        let data_b = build_synthetic_elf(&[(".text", b"\x91\x91\x91\x91"), (".data", b"fixed!!!")]);
+        let a = dir.path().join("a");
+        let b = dir.path().join("b");
+        std::fs::write(&a, &data_a).unwrap();
+        std::fs::write(&b, &data_b).unwrap();
+
+        let report = diff_binaries(&a, &b).unwrap();
+        assert!(!report.identical);
+        assert_eq!(report.diffs.len(), 1);
+        assert_eq!(report.diffs[0].name, ".text");
+    }
+
+    #[test]
+    fn diff_binaries_different_sizes_reports_no_section_detail() {
+        let dir = tempfile::tempdir().unwrap();
+        let a = dir.path().join("a");
+        let b = dir.path().join("b");
+        std::fs::write(&a, b"short").unwrap();
+        std::fs::write(&b, b"a bit longer").unwrap();
+
+        let report = diff_binaries(&a, &b).unwrap();
+        assert!(!report.identical);
+        assert!(report.diffs.is_empty());
+        assert_eq!(report.size_a, 5);
+        assert_eq!(report.size_b, 12);
+    }
+
+    #[test]
+    fn find_culprits_detects_planted_absolute_path() {
+        let mut data = b"\x00\x00\x00".to_vec();
+        data.extend_from_slice(b"/home/builder/project/src/main.rs\x00");
+        data.extend_from_slice(b"\x00\x00\x00");
+
+        let culprits = find_culprits(&data);
+        assert!(
+            culprits
+                .iter()
+                .any(|c| c.contains("/home/builder/project/src/main.rs"))
+        );
+    }
+
+    #[test]
+    fn find_culprits_detects_planted_timestamp() {
+        let mut data = b"\x00\x00".to_vec();
+        data.extend_from_slice(b"build time: 2024-03-15T09:30:00\x00");
+
+        let culprits = find_culprits(&data);
+        assert!(culprits.iter().any(|c| c.contains("2024-03-15")));
+    }
+
+    #[test]
+    fn find_culprits_ignores_short_and_relative_strings() {
+        let data = b"\x00short\x00./relative/path\x00".to_vec();
+        let culprits = find_culprits(&data);
+        assert!(culprits.is_empty());
+    }
+
+    #[test]
+    fn find_culprits_deduplicates_repeated_findings() {
+        let mut data = Vec::new();
+        for _ in 0..3 {
+            data.extend_from_slice(b"/home/builder/project\x00");
+        }
+        let culprits = find_culprits(&data);
+        assert_eq!(culprits.len(), 1);
+    }
+}
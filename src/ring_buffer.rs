@@ -0,0 +1,134 @@
+//! Bounded first-N/last-M line retention for streamed child process output.
+//!
+//! Some build scripts print tens of megabytes of output; buffering it all in
+//! memory (as `Command::output()` does) is wasteful and makes interleaving
+//! with other output unpredictable. [`LineRingBuffer`] keeps only the first
+//! `N` lines and the last `M` lines seen, tracking how many lines in between
+//! were dropped so error messages and summaries can say so honestly.
+
+use std::collections::VecDeque;
+
+/// Keeps the first `cap_first` lines and the last `cap_last` lines pushed to
+/// it, discarding everything in between while still counting the total.
+pub struct LineRingBuffer {
+    first: Vec<String>,
+    last: VecDeque<String>,
+    cap_first: usize,
+    cap_last: usize,
+    total: usize,
+}
+
+impl LineRingBuffer {
+    /// Create a buffer retaining up to `cap_first` lines from the start and
+    /// up to `cap_last` lines from the end.
+    pub fn new(cap_first: usize, cap_last: usize) -> Self {
+        Self {
+            first: Vec::with_capacity(cap_first),
+            last: VecDeque::with_capacity(cap_last),
+            cap_first,
+            cap_last,
+            total: 0,
+        }
+    }
+
+    /// Record one line of output.
+    pub fn push(&mut self, line: String) {
+        self.total += 1;
+        if self.first.len() < self.cap_first {
+            self.first.push(line);
+            return;
+        }
+        if self.cap_last == 0 {
+            return;
+        }
+        if self.last.len() == self.cap_last {
+            self.last.pop_front();
+        }
+        self.last.push_back(line);
+    }
+
+    /// Total number of lines ever pushed, including dropped ones.
+    pub fn total_lines(&self) -> usize {
+        self.total
+    }
+
+    /// Number of lines held in neither `first` nor `last`.
+    pub fn dropped(&self) -> usize {
+        self.total
+            .saturating_sub(self.first.len() + self.last.len())
+    }
+
+    /// Render the retained lines for display, with an `... N lines omitted
+    /// ...` marker where lines were dropped.
+    pub fn render(&self) -> String {
+        let mut out = self.first.join("\n");
+        let dropped = self.dropped();
+        if dropped > 0 {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&format!("... {dropped} line(s) omitted ..."));
+        }
+        if !self.last.is_empty() {
+            if !out.is_empty() {
+                out.push('\n');
+            }
+            out.push_str(&self.last.iter().cloned().collect::<Vec<_>>().join("\n"));
+        }
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keeps_everything_when_under_capacity() {
+        let mut buf = LineRingBuffer::new(5, 5);
+        for i in 0..3 {
+            buf.push(format!("line {i}"));
+        }
+        assert_eq!(buf.total_lines(), 3);
+        assert_eq!(buf.dropped(), 0);
+        assert_eq!(buf.render(), "line 0\nline 1\nline 2");
+    }
+
+    #[test]
+    fn retains_first_and_last_dropping_the_middle() {
+        let mut buf = LineRingBuffer::new(2, 2);
+        for i in 0..10 {
+            buf.push(format!("line {i}"));
+        }
+        assert_eq!(buf.total_lines(), 10);
+        assert_eq!(buf.dropped(), 6);
+        assert_eq!(
+            buf.render(),
+            "line 0\nline 1\n... 6 line(s) omitted ...\nline 8\nline 9"
+        );
+    }
+
+    #[test]
+    fn bounds_memory_on_a_multi_megabyte_stream() {
+        let mut buf = LineRingBuffer::new(100, 100);
+        let line = "x".repeat(1024);
+        for _ in 0..50_000 {
+            buf.push(line.clone());
+        }
+        assert_eq!(buf.total_lines(), 50_000);
+        assert_eq!(buf.dropped(), 50_000 - 200);
+        // Only the retained 200 lines are held, not all 50,000.
+        assert_eq!(buf.first.len(), 100);
+        assert_eq!(buf.last.len(), 100);
+    }
+
+    #[test]
+    fn zero_caps_drops_everything_but_still_counts() {
+        let mut buf = LineRingBuffer::new(0, 0);
+        buf.push("a".to_string());
+        buf.push("b".to_string());
+        assert_eq!(buf.total_lines(), 2);
+        assert_eq!(buf.dropped(), 2);
+        assert_eq!(buf.render(), "... 2 line(s) omitted ...");
+    }
+}
@@ -1,14 +1,101 @@
 use anyhow::{Context, Result};
 use std::path::Path;
-use std::process::Command;
+use std::process::{Command, ExitStatus};
 
-/// Run a binary with optional arguments and return its exit code
-pub fn run_binary(binary_path: &Path, args: &[String]) -> Result<i32> {
+/// How a binary finished. Distinguishes a signal kill from a normal exit so
+/// `[run] expect_exit` can treat the two differently: a signal never
+/// satisfies an exit-code expectation, even if its conventional [`code`]
+/// happens to equal it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RunOutcome {
+    /// Exited normally with this code.
+    Exited(i32),
+    /// Terminated by a signal (Unix only; the signal number, when known).
+    Signaled(Option<i32>),
+}
+
+impl RunOutcome {
+    /// Conventional exit code for this outcome: the real code when exited
+    /// normally, or 1 (matching the previous unconditional fallback) when
+    /// killed by a signal.
+    pub fn code(&self) -> i32 {
+        match self {
+            RunOutcome::Exited(code) => *code,
+            RunOutcome::Signaled(_) => 1,
+        }
+    }
+
+    /// Whether this outcome satisfies `[run] expect_exit`/`--expect-exit`. A
+    /// signal never matches, regardless of `expected`.
+    pub fn matches_expectation(&self, expected: i32) -> bool {
+        matches!(self, RunOutcome::Exited(code) if *code == expected)
+    }
+}
+
+#[cfg(unix)]
+fn outcome_of(status: ExitStatus) -> RunOutcome {
+    use std::os::unix::process::ExitStatusExt;
+    match status.code() {
+        Some(code) => RunOutcome::Exited(code),
+        None => RunOutcome::Signaled(status.signal()),
+    }
+}
+
+#[cfg(not(unix))]
+fn outcome_of(status: ExitStatus) -> RunOutcome {
+    RunOutcome::Exited(status.code().unwrap_or(1))
+}
+
+/// Run a binary with optional arguments and return how it finished.
+/// `cwd`, if given, becomes the child's working directory (see `[run] cwd`
+/// in the spec); `None` keeps the default of inheriting the caller's cwd.
+pub fn run_binary(binary_path: &Path, args: &[String], cwd: Option<&Path>) -> Result<RunOutcome> {
     println!("Running {}", binary_path.display());
-    let status = Command::new(binary_path)
-        .args(args)
+    let mut cmd = Command::new(binary_path);
+    cmd.args(args);
+    if let Some(dir) = cwd {
+        cmd.current_dir(dir);
+    }
+    let status = cmd
         .status()
         .with_context(|| format!("failed to run {}", binary_path.display()))?;
 
-    Ok(status.code().unwrap_or(1))
+    Ok(outcome_of(status))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn exited_matches_equal_expectation() {
+        assert!(RunOutcome::Exited(3).matches_expectation(3));
+    }
+
+    #[test]
+    fn exited_does_not_match_different_expectation() {
+        assert!(!RunOutcome::Exited(1).matches_expectation(3));
+    }
+
+    #[test]
+    fn signaled_never_matches_even_when_code_would_coincide() {
+        // code() conventionally reports 1 for a signal, but it must never
+        // satisfy an expectation of 1 (or anything else).
+        let outcome = RunOutcome::Signaled(Some(9));
+        assert_eq!(outcome.code(), 1);
+        assert!(!outcome.matches_expectation(1));
+        assert!(!outcome.matches_expectation(0));
+    }
+
+    #[test]
+    fn run_binary_reports_nonzero_exit() {
+        let outcome = run_binary(Path::new("/bin/false"), &[], None).unwrap();
+        assert_eq!(outcome, RunOutcome::Exited(1));
+    }
+
+    #[test]
+    fn run_binary_missing_binary_errors() {
+        let result = run_binary(Path::new("/no/such/binary"), &[], None);
+        assert!(result.is_err());
+    }
 }
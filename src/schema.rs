@@ -0,0 +1,356 @@
+//! JSON Schema generation for the `*.ts.toml` spec shape, so editor tooling
+//! (e.g. taplo's TOML language server) can validate and complete translation
+//! specs without running tspec itself.
+//!
+//! Hand-maintained rather than derived from [`crate::types::Spec`] — this
+//! crate has no schema-derive machinery, and `Spec`'s own doc comments are
+//! the source of truth this module's descriptions are kept in sync with by
+//! hand. [`validate`] is exercised against real fixture specs in tests so
+//! schema/code drift shows up as a test failure rather than silently.
+
+use serde_json::{Map, Value, json};
+
+/// `PanicMode`'s kebab-case variant names (`#[serde(rename_all = "kebab-case")]`).
+const PANIC_VALUES: &[&str] = &["unwind", "abort", "immediate-abort"];
+/// `StripMode`'s kebab-case variant names.
+const STRIP_VALUES: &[&str] = &["none", "debuginfo", "symbols"];
+
+/// Build the JSON Schema document describing a `*.ts.toml` spec.
+///
+/// `cargo.profile` and `cargo.opt_level_deps` are documented with `examples`
+/// rather than `enum`: unlike `panic`/`strip`, this codebase has no `Profile`
+/// or `OptLevel` enum type to source an exhaustive value list from — a
+/// profile is any cargo profile name (built-in or custom) and opt-level-deps
+/// accepts anything rustc's own `-C opt-level` does.
+pub fn build_schema() -> Value {
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "title": "tspec translation spec",
+        "description": "Schema for *.ts.toml translation spec files (see `tspec ts --help`).",
+        "type": "object",
+        "additionalProperties": false,
+        "properties": {
+            "panic": {
+                "description": "High-level panic mode (sets both cargo -Z and rustc -C flags)",
+                "type": "string",
+                "enum": PANIC_VALUES
+            },
+            "strip": {
+                "description": "High-level strip mode (sets rustc -C strip=)",
+                "type": "string",
+                "enum": STRIP_VALUES
+            },
+            "toolchain": {
+                "description": "Rust toolchain override, e.g. \"nightly\", \"stable\", \"1.75\"",
+                "type": "string"
+            },
+            "rustflags": {
+                "description": "Raw flags passed through to RUSTFLAGS",
+                "type": "array",
+                "items": {"type": "string"}
+            },
+            "cargo": {
+                "description": "Cargo-specific configuration",
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "profile": {
+                        "description": "Build profile name (e.g. \"debug\", \"release\", \"release-small\", or any custom profile)",
+                        "type": "string",
+                        "examples": ["debug", "release", "release-small"]
+                    },
+                    "target_triple": {
+                        "description": "Target triple, e.g. \"x86_64-unknown-linux-musl\" (see `tspec targets`)",
+                        "type": "string"
+                    },
+                    "target_json": {
+                        "description": "Custom target JSON file path",
+                        "type": "string"
+                    },
+                    "unstable": {
+                        "description": "Nightly-only -Z flags, e.g. [\"panic-immediate-abort\"]",
+                        "type": "array",
+                        "items": {"type": "string"}
+                    },
+                    "target_dir": {
+                        "description": "Custom target directory subdirectory for per-spec isolation. Supports {name} and {hash} placeholders.",
+                        "type": "string"
+                    },
+                    "config": {
+                        "description": "Config values passed as --config 'KEY=VALUE' to cargo, as flat dotted keys or nested tables",
+                        "type": "object"
+                    },
+                    "build_std": {
+                        "description": "Crates to rebuild with -Z build-std (nightly only)",
+                        "type": "array",
+                        "items": {"type": "string"}
+                    },
+                    "opt_level_deps": {
+                        "description": "Shorthand for profile_overrides.<effective profile>.deps.opt-level",
+                        "examples": ["0", "1", "2", "3", "s", "z"]
+                    },
+                    "hermetic_env": {
+                        "description": "Build with a scrubbed environment, dropping everything not on the fixed allowlist or env_allowlist",
+                        "type": "boolean"
+                    },
+                    "env_allowlist": {
+                        "description": "Extra environment variable names to keep when hermetic_env is set",
+                        "type": "array",
+                        "items": {"type": "string"}
+                    }
+                }
+            },
+            "linker": {
+                "description": "Linker configuration",
+                "type": "object",
+                "additionalProperties": false,
+                "properties": {
+                    "args": {
+                        "description": "Linker arguments, e.g. [\"-static\", \"-nostdlib\"]",
+                        "type": "array",
+                        "items": {"type": "string"}
+                    },
+                    "version_script": {
+                        "description": "Version script for symbol visibility (enables --gc-sections optimization)",
+                        "type": "object",
+                        "additionalProperties": false,
+                        "properties": {
+                            "global": {
+                                "description": "Symbols to keep global, e.g. [\"_start\"]",
+                                "type": "array",
+                                "items": {"type": "string"}
+                            },
+                            "local": {
+                                "description": "Pattern for local symbols (typically \"*\")",
+                                "type": "string"
+                            }
+                        }
+                    }
+                }
+            },
+            "profile_overrides": {
+                "description": "Per-package profile overrides, keyed by dotted <profile>.deps.<key> or <profile>.package.<name>.<key>",
+                "type": "object"
+            }
+        }
+    })
+}
+
+/// Render the schema as a flat, human-readable field listing (`tspec schema
+/// --format toml-doc`) — one line per field, dotted path and description,
+/// with allowed values shown for enum fields.
+pub fn render_toml_doc(schema: &Value) -> String {
+    let mut lines = Vec::new();
+    render_properties("", schema, &mut lines);
+    lines.join("\n")
+}
+
+fn render_properties(prefix: &str, schema: &Value, lines: &mut Vec<String>) {
+    let Some(properties) = schema.get("properties").and_then(Value::as_object) else {
+        return;
+    };
+    for (key, field) in properties {
+        let full_key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{prefix}.{key}")
+        };
+        let description = field
+            .get("description")
+            .and_then(Value::as_str)
+            .unwrap_or("");
+        let allowed = field.get("enum").and_then(Value::as_array).map(|values| {
+            let names: Vec<&str> = values.iter().filter_map(Value::as_str).collect();
+            format!(" [one of: {}]", names.join(", "))
+        });
+        lines.push(format!(
+            "{full_key}: {description}{}",
+            allowed.unwrap_or_default()
+        ));
+        if field.get("type").and_then(Value::as_str) == Some("object") {
+            render_properties(&full_key, field, lines);
+        }
+    }
+}
+
+/// A taplo `[[schema]]` stanza associating this schema with every `*.ts.toml`
+/// file in a workspace, once the schema JSON has been written to a file at
+/// `schema_path` relative to the `.taplo.toml` that holds this stanza.
+pub fn taplo_schema_stanza(schema_path: &str) -> String {
+    format!(
+        "[[schema]]\n\
+         name = \"tspec\"\n\
+         file-match = [\"**/*.ts.toml\", \"**/tspec*.toml\"]\n\
+         url = \"./{schema_path}\"\n"
+    )
+}
+
+/// Check `spec` (a parsed spec, as JSON) against `schema`'s `properties` and
+/// `enum` declarations, returning every violation found.
+///
+/// This is not a general JSON Schema validator — just enough to catch what
+/// `*.ts.toml` authors most commonly get wrong: an unknown key (under an
+/// `"additionalProperties": false` object) or an enum field set to a value
+/// the schema doesn't allow.
+pub fn validate(schema: &Value, spec: &Value) -> Vec<String> {
+    let mut errors = Vec::new();
+    walk("", schema, spec, &mut errors);
+    errors
+}
+
+fn walk(path: &str, schema: &Value, value: &Value, errors: &mut Vec<String>) {
+    let Some(fields) = value.as_object() else {
+        return;
+    };
+    let empty = Map::new();
+    let properties = schema
+        .get("properties")
+        .and_then(Value::as_object)
+        .unwrap_or(&empty);
+    let additional_ok = schema
+        .get("additionalProperties")
+        .and_then(Value::as_bool)
+        .unwrap_or(true);
+
+    for (key, val) in fields {
+        let full_path = if path.is_empty() {
+            key.clone()
+        } else {
+            format!("{path}.{key}")
+        };
+        match properties.get(key) {
+            Some(field_schema) => {
+                if let (Some(allowed), Some(actual)) = (
+                    field_schema.get("enum").and_then(Value::as_array),
+                    val.as_str(),
+                ) && !allowed.iter().any(|a| a.as_str() == Some(actual))
+                {
+                    errors.push(format!(
+                        "{full_path}: '{actual}' is not one of the schema's allowed values"
+                    ));
+                }
+                if field_schema.get("type").and_then(Value::as_str) == Some("object") {
+                    walk(&full_path, field_schema, val, errors);
+                }
+            }
+            None if !additional_ok => {
+                errors.push(format!("{full_path}: not a field this schema knows about"));
+            }
+            None => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn schema_declares_panic_and_strip_enums() {
+        let schema = build_schema();
+        let panic_enum = schema["properties"]["panic"]["enum"].as_array().unwrap();
+        assert_eq!(
+            panic_enum
+                .iter()
+                .map(|v| v.as_str().unwrap())
+                .collect::<Vec<_>>(),
+            PANIC_VALUES
+        );
+        let strip_enum = schema["properties"]["strip"]["enum"].as_array().unwrap();
+        assert_eq!(
+            strip_enum
+                .iter()
+                .map(|v| v.as_str().unwrap())
+                .collect::<Vec<_>>(),
+            STRIP_VALUES
+        );
+    }
+
+    #[test]
+    fn validate_accepts_a_minimal_valid_spec() {
+        let schema = build_schema();
+        let spec = json!({"panic": "abort", "cargo": {"profile": "release"}});
+        assert!(validate(&schema, &spec).is_empty());
+    }
+
+    #[test]
+    fn validate_rejects_unknown_top_level_key() {
+        let schema = build_schema();
+        let spec = json!({"nonexistent_field": true});
+        let errors = validate(&schema, &spec);
+        assert_eq!(
+            errors,
+            vec!["nonexistent_field: not a field this schema knows about"]
+        );
+    }
+
+    #[test]
+    fn validate_rejects_bad_enum_value() {
+        let schema = build_schema();
+        let spec = json!({"panic": "explode"});
+        let errors = validate(&schema, &spec);
+        assert_eq!(
+            errors,
+            vec!["panic: 'explode' is not one of the schema's allowed values"]
+        );
+    }
+
+    #[test]
+    fn validate_recurses_into_nested_objects() {
+        let schema = build_schema();
+        let spec = json!({"cargo": {"typo_field": 1}});
+        let errors = validate(&schema, &spec);
+        assert_eq!(
+            errors,
+            vec!["cargo.typo_field: not a field this schema knows about"]
+        );
+    }
+
+    #[test]
+    fn validate_allows_free_form_config_table() {
+        // cargo.config has no declared `properties`, so any nested key is
+        // allowed - it's a passthrough for arbitrary --config values.
+        let schema = build_schema();
+        let spec = json!({"cargo": {"config": {"profile": {"release": {"opt-level": "z"}}}}});
+        assert!(validate(&schema, &spec).is_empty());
+    }
+
+    #[test]
+    fn fixture_specs_validate_against_the_schema() {
+        let schema = build_schema();
+        for fixture in [
+            "tests/fixtures/pop/tspec.ts.toml",
+            "tests/fixtures/pop-ws/tspec.ts.toml",
+            "tests/fixtures/popws-3p/app-a/tspec.ts.toml",
+        ] {
+            let path = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join(fixture);
+            let contents = std::fs::read_to_string(&path)
+                .unwrap_or_else(|e| panic!("failed to read {}: {e}", path.display()));
+            let toml_value: toml::Value = toml::from_str(&contents)
+                .unwrap_or_else(|e| panic!("failed to parse {}: {e}", path.display()));
+            let json_value = serde_json::to_value(&toml_value).unwrap();
+            let errors = validate(&schema, &json_value);
+            assert!(
+                errors.is_empty(),
+                "{} failed schema validation: {errors:?}",
+                path.display()
+            );
+        }
+    }
+
+    #[test]
+    fn render_toml_doc_includes_field_and_enum_values() {
+        let schema = build_schema();
+        let doc = render_toml_doc(&schema);
+        assert!(doc.contains("panic:"));
+        assert!(doc.contains("[one of: unwind, abort, immediate-abort]"));
+        assert!(doc.contains("cargo.profile:"));
+    }
+
+    #[test]
+    fn taplo_schema_stanza_references_the_given_path() {
+        let stanza = taplo_schema_stanza("tspec-schema.json");
+        assert!(stanza.contains("url = \"./tspec-schema.json\""));
+        assert!(stanza.contains("*.ts.toml"));
+    }
+}
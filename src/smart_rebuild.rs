@@ -0,0 +1,221 @@
+//! `--smart-rebuild` classification for `tspec build`.
+//!
+//! The existing fingerprint skip (see [`crate::fingerprint`]) only fires
+//! when the spec and every source file are byte-for-byte unchanged. This
+//! module handles the narrower case: the spec itself changed, but not in a
+//! way that affects what cargo needs to do. It compares the spec used for
+//! the last successful build against the one about to be built and
+//! classifies the delta so `run_cargo` can skip invoking cargo entirely for
+//! changes that don't touch the build at all (e.g. `[run]`/`[test]`
+//! defaults, which only affect `tspec run`/`tspec test`, not compilation).
+//!
+//! [`classify_rebuild`] is conservatively correct by construction: any field
+//! outside the known-safe `linker`/`run`/`test` sections counts as
+//! build-affecting, so a future field added to [`Spec`] falls back to
+//! forcing a full build rather than silently being treated as safe.
+//!
+//! `classify_rebuild` only ever compares specs, so it's blind to a source
+//! file changing while the spec doesn't — that's why [`LastBuild`] also
+//! carries the source-only fingerprint (see [`crate::fingerprint`]) from the
+//! last successful build: the caller must check it's still current before
+//! trusting a [`RebuildKind::NoRebuildNeeded`] classification.
+
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+
+use crate::types::{LinkerConfig, RunConfig, Spec, TestConfig};
+
+const SMART_REBUILD_DIR_NAME: &str = ".tspec-smart-rebuild";
+
+/// What kind of rebuild a spec change requires, relative to the spec used
+/// for the last successful build.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum RebuildKind {
+    /// Something outside `linker`/`run`/`test` changed (or is unknown to
+    /// this classifier) — invoke cargo normally.
+    Full,
+    /// Only `linker` changed — cargo needs to run (the generated build.rs
+    /// or RUSTFLAGS differ) but only relinks the final binary.
+    RelinkOnly,
+    /// Only `run`/`test` changed — neither affects cargo's build inputs,
+    /// so cargo has nothing to do at all.
+    NoRebuildNeeded,
+}
+
+/// Classify the delta between `old` (the last spec a build succeeded with)
+/// and `new` (the spec about to be built).
+pub(crate) fn classify_rebuild(old: &Spec, new: &Spec) -> RebuildKind {
+    let build_affecting = |spec: &Spec| Spec {
+        linker: LinkerConfig::default(),
+        run: RunConfig::default(),
+        test: TestConfig::default(),
+        ..spec.clone()
+    };
+    if build_affecting(old) != build_affecting(new) {
+        return RebuildKind::Full;
+    }
+    if old.linker != new.linker {
+        return RebuildKind::RelinkOnly;
+    }
+    RebuildKind::NoRebuildNeeded
+}
+
+/// Where the last-built spec for `pkg_name` under `target_base` is recorded.
+pub(crate) fn last_spec_path(target_base: &Path, pkg_name: &str) -> PathBuf {
+    target_base
+        .join(SMART_REBUILD_DIR_NAME)
+        .join(format!("{pkg_name}.json"))
+}
+
+/// The spec a build last succeeded with, plus the source-only fingerprint
+/// (see [`crate::fingerprint::compute_source_fingerprint`]) taken right
+/// after that build, so a later run can tell a source edit apart from a
+/// safe spec-only change even when the spec itself didn't change at all.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub(crate) struct LastBuild {
+    pub(crate) spec: Spec,
+    pub(crate) source_fingerprint: String,
+}
+
+/// Read the last build recorded at `path`, if any. Any read/parse failure is
+/// treated as "no recorded build" (forcing a normal build) rather than an
+/// error — this is a best-effort optimization, not a correctness gate.
+pub(crate) fn read_last_build(path: &Path) -> Option<LastBuild> {
+    let content = std::fs::read_to_string(path).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Record `spec`/`source_fingerprint` at `path` as the last successful
+/// build, creating parent directories as needed. Best-effort: a write
+/// failure only means the next build can't take the smart-rebuild
+/// shortcut, not a build failure.
+pub(crate) fn write_last_build(
+    path: &Path,
+    spec: &Spec,
+    source_fingerprint: &str,
+) -> std::io::Result<()> {
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let json = serde_json::to_string(&LastBuild {
+        spec: spec.clone(),
+        source_fingerprint: source_fingerprint.to_string(),
+    })?;
+    std::fs::write(path, json)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::VersionScript;
+
+    #[test]
+    fn identical_specs_need_no_rebuild() {
+        let spec = Spec::default();
+        assert_eq!(classify_rebuild(&spec, &spec), RebuildKind::NoRebuildNeeded);
+    }
+
+    #[test]
+    fn run_only_change_needs_no_rebuild() {
+        let old = Spec::default();
+        let mut new = Spec::default();
+        new.run.args = vec!["--flag".to_string()];
+        assert_eq!(classify_rebuild(&old, &new), RebuildKind::NoRebuildNeeded);
+    }
+
+    #[test]
+    fn test_only_change_needs_no_rebuild() {
+        let old = Spec::default();
+        let mut new = Spec::default();
+        new.test.args = vec!["--test-threads=1".to_string()];
+        assert_eq!(classify_rebuild(&old, &new), RebuildKind::NoRebuildNeeded);
+    }
+
+    #[test]
+    fn run_and_test_change_together_need_no_rebuild() {
+        let old = Spec::default();
+        let mut new = Spec::default();
+        new.run.args = vec!["--flag".to_string()];
+        new.test.args = vec!["--test-threads=1".to_string()];
+        assert_eq!(classify_rebuild(&old, &new), RebuildKind::NoRebuildNeeded);
+    }
+
+    #[test]
+    fn linker_args_change_is_relink_only() {
+        let old = Spec::default();
+        let mut new = Spec::default();
+        new.linker.args = vec!["-static".to_string()];
+        assert_eq!(classify_rebuild(&old, &new), RebuildKind::RelinkOnly);
+    }
+
+    #[test]
+    fn version_script_change_is_relink_only() {
+        let old = Spec::default();
+        let mut new = Spec::default();
+        new.linker.version_script = Some(VersionScript {
+            global: vec!["_start".to_string()],
+            local: "*".to_string(),
+        });
+        assert_eq!(classify_rebuild(&old, &new), RebuildKind::RelinkOnly);
+    }
+
+    #[test]
+    fn linker_and_run_change_together_is_relink_only() {
+        // linker still dominates run/test, since relinking is still needed.
+        let old = Spec::default();
+        let mut new = Spec::default();
+        new.linker.args = vec!["-static".to_string()];
+        new.run.args = vec!["--flag".to_string()];
+        assert_eq!(classify_rebuild(&old, &new), RebuildKind::RelinkOnly);
+    }
+
+    #[test]
+    fn panic_mode_change_forces_full_build() {
+        let old = Spec::default();
+        let new = Spec {
+            panic: Some(crate::options::PanicMode::Abort),
+            ..Spec::default()
+        };
+        assert_eq!(classify_rebuild(&old, &new), RebuildKind::Full);
+    }
+
+    #[test]
+    fn cargo_config_change_forces_full_build() {
+        let old = Spec::default();
+        let mut new = Spec::default();
+        new.cargo.profile = Some("release".to_string());
+        assert_eq!(classify_rebuild(&old, &new), RebuildKind::Full);
+    }
+
+    #[test]
+    fn build_affecting_and_linker_change_together_forces_full_build() {
+        // A build-affecting field always wins over linker/run/test, however
+        // many of those also changed alongside it.
+        let old = Spec::default();
+        let mut new = Spec {
+            rustflags: vec!["-C".to_string(), "target-cpu=native".to_string()],
+            ..Spec::default()
+        };
+        new.linker.args = vec!["-static".to_string()];
+        assert_eq!(classify_rebuild(&old, &new), RebuildKind::Full);
+    }
+
+    #[test]
+    fn read_last_build_missing_file_returns_none() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = last_spec_path(tmp.path(), "myapp");
+        assert!(read_last_build(&path).is_none());
+    }
+
+    #[test]
+    fn write_then_read_last_build_round_trips() {
+        let tmp = tempfile::TempDir::new().unwrap();
+        let path = last_spec_path(tmp.path(), "myapp");
+        let mut spec = Spec::default();
+        spec.cargo.profile = Some("release".to_string());
+        write_last_build(&path, &spec, "abc123").unwrap();
+        let last = read_last_build(&path).unwrap();
+        assert_eq!(last.spec, spec);
+        assert_eq!(last.source_fingerprint, "abc123");
+    }
+}
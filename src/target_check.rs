@@ -0,0 +1,172 @@
+//! Warn when a spec's `cargo.target_triple` isn't installed via rustup.
+//!
+//! Without the std component installed, cargo fails partway through the
+//! build trying to fetch it. This only matters when `cargo.build_std` is
+//! empty — with build_std set, std is compiled from source instead of
+//! fetched as a prebuilt component, so an uninstalled target is fine.
+
+use std::process::Command;
+
+/// Installed/known status of a target triple.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TargetStatus {
+    /// Installed and ready to build against.
+    Installed,
+    /// A real rustc target, but its std component isn't installed.
+    KnownNotInstalled,
+    /// Not a triple rustc recognizes at all (typo, or needs target_json).
+    Unknown,
+}
+
+/// Classify `triple` against the known/installed target lists.
+///
+/// Pure function over the two lists so the installed-vs-known distinction
+/// is testable without shelling out to rustc/rustup.
+pub fn classify(triple: &str, known: &[String], installed: &[String]) -> TargetStatus {
+    if installed.iter().any(|t| t == triple) {
+        TargetStatus::Installed
+    } else if known.iter().any(|t| t == triple) {
+        TargetStatus::KnownNotInstalled
+    } else {
+        TargetStatus::Unknown
+    }
+}
+
+fn command_lines(mut cmd: Command) -> Option<Vec<String>> {
+    let output = cmd.output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    Some(
+        String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|l| l.trim().to_string())
+            .filter(|l| !l.is_empty())
+            .collect(),
+    )
+}
+
+pub(crate) fn known_targets() -> Option<Vec<String>> {
+    let mut cmd = Command::new("rustc");
+    cmd.arg("--print").arg("target-list");
+    command_lines(cmd)
+}
+
+pub(crate) fn installed_targets() -> Option<Vec<String>> {
+    let mut cmd = Command::new("rustup");
+    cmd.args(["target", "list", "--installed"]);
+    command_lines(cmd)
+}
+
+/// Render `tspec targets`' output: one row per target with an "installed"
+/// marker. Without `known`, only `installed` triples are listed (all
+/// "installed"); with `known` (`--all`), every known triple is listed and
+/// each is marked "installed" or "-".
+///
+/// Pure over the two lists so the installed-vs-known marking is testable
+/// without shelling out to rustc/rustup.
+pub(crate) fn render_targets(installed: &[String], known: Option<&[String]>) -> String {
+    let triples: &[String] = known.unwrap_or(installed);
+    let width = triples.iter().map(|t| t.len()).max().unwrap_or(0);
+    triples
+        .iter()
+        .map(|triple| {
+            let marker = if installed.iter().any(|t| t == triple) {
+                "installed"
+            } else {
+                "-"
+            };
+            format!("{triple:width$}  {marker}")
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Return a misconfiguration warning for `triple`, or `None` if it's
+/// installed, `build_std` is non-empty (std gets compiled, not fetched), or
+/// the target lists couldn't be determined (no rustc/rustup on PATH —
+/// don't warn about something we can't verify).
+pub fn check_target_triple(triple: &str, build_std_set: bool) -> Option<String> {
+    if build_std_set {
+        return None;
+    }
+    let known = known_targets()?;
+    let installed = installed_targets()?;
+    match classify(triple, &known, &installed) {
+        TargetStatus::Installed => None,
+        TargetStatus::KnownNotInstalled => Some(format!(
+            "target_triple '{triple}' is a known rustc target but isn't installed \
+             — the build will fail fetching std. Run `rustup target add {triple}`, \
+             or set cargo.build_std to compile std from source instead."
+        )),
+        TargetStatus::Unknown => Some(format!(
+            "target_triple '{triple}' isn't a target rustc recognizes — check for \
+             a typo, or use cargo.target_json for a custom target spec."
+        )),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn strs(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn classify_installed_target() {
+        let known = strs(&["x86_64-unknown-linux-musl", "x86_64-unknown-linux-gnu"]);
+        let installed = strs(&["x86_64-unknown-linux-gnu"]);
+        assert_eq!(
+            classify("x86_64-unknown-linux-gnu", &known, &installed),
+            TargetStatus::Installed
+        );
+    }
+
+    #[test]
+    fn classify_known_but_not_installed() {
+        let known = strs(&["x86_64-unknown-linux-musl", "x86_64-unknown-linux-gnu"]);
+        let installed = strs(&["x86_64-unknown-linux-gnu"]);
+        assert_eq!(
+            classify("x86_64-unknown-linux-musl", &known, &installed),
+            TargetStatus::KnownNotInstalled
+        );
+    }
+
+    #[test]
+    fn classify_unknown_target() {
+        let known = strs(&["x86_64-unknown-linux-gnu"]);
+        let installed = strs(&["x86_64-unknown-linux-gnu"]);
+        assert_eq!(
+            classify("not-a-real-triple", &known, &installed),
+            TargetStatus::Unknown
+        );
+    }
+
+    #[test]
+    fn render_targets_marks_installed_triple_among_known() {
+        let known = strs(&["x86_64-unknown-linux-musl", "x86_64-unknown-linux-gnu"]);
+        let installed = strs(&["x86_64-unknown-linux-gnu"]);
+        let rendered = render_targets(&installed, Some(&known));
+        let installed_line = rendered
+            .lines()
+            .find(|l| l.starts_with("x86_64-unknown-linux-gnu"))
+            .unwrap();
+        assert!(installed_line.ends_with("installed"));
+        let not_installed_line = rendered
+            .lines()
+            .find(|l| l.starts_with("x86_64-unknown-linux-musl"))
+            .unwrap();
+        assert!(not_installed_line.ends_with('-'));
+    }
+
+    #[test]
+    fn render_targets_without_known_lists_only_installed() {
+        let installed = strs(&["x86_64-unknown-linux-gnu"]);
+        let rendered = render_targets(&installed, None);
+        assert_eq!(rendered.lines().count(), 1);
+        assert!(rendered.contains("x86_64-unknown-linux-gnu"));
+        assert!(rendered.ends_with("installed"));
+    }
+}
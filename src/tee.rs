@@ -3,6 +3,9 @@
 use anyhow::{Context, Result};
 use std::io::{BufRead, BufReader};
 use std::process::{Command, ExitStatus, Stdio};
+use std::thread;
+
+use crate::ring_buffer::LineRingBuffer;
 
 /// Result of a tee'd command execution.
 pub struct TeeResult {
@@ -48,3 +51,86 @@ where
         matched_lines,
     })
 }
+
+/// Number of lines kept from the start/end of a captured stream when
+/// bounding memory via [`capture_bounded`].
+const RING_BUFFER_CAP: usize = 200;
+
+/// Result of a [`capture_bounded`] run: lines matching each stream's filter
+/// (kept in full — callers only match sparse, known-shape lines) plus a
+/// bounded ring buffer of each stream for error reporting.
+pub struct CapturedOutput {
+    pub status: ExitStatus,
+    pub stdout_matched: Vec<String>,
+    pub stderr_matched: Vec<String>,
+    pub stdout_buffer: LineRingBuffer,
+    pub stderr_buffer: LineRingBuffer,
+}
+
+/// Spawn a command with piped stdout and stderr, reading both incrementally
+/// on separate threads instead of buffering them whole (as
+/// `Command::output()` does). Each stream is kept in a bounded
+/// [`LineRingBuffer`] (first/last `RING_BUFFER_CAP` lines) for error
+/// reporting, while lines matching `stdout_filter`/`stderr_filter` are
+/// additionally collected in full — callers use this for sparse, known-shape
+/// lines (e.g. cargo's own "Running tests/..." headers), not arbitrary
+/// high-volume output like build script spam.
+pub fn capture_bounded<F, G>(
+    cmd: &mut Command,
+    stdout_filter: F,
+    stderr_filter: G,
+) -> Result<CapturedOutput>
+where
+    F: Fn(&str) -> bool + Send + 'static,
+    G: Fn(&str) -> bool + Send + 'static,
+{
+    let mut child = cmd
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .context("failed to spawn command")?;
+
+    let stdout = child.stdout.take().expect("stdout was piped");
+    let stderr = child.stderr.take().expect("stderr was piped");
+
+    let stdout_thread = thread::spawn(move || -> Result<(Vec<String>, LineRingBuffer)> {
+        let mut matched = Vec::new();
+        let mut buffer = LineRingBuffer::new(RING_BUFFER_CAP, RING_BUFFER_CAP);
+        for line in BufReader::new(stdout).lines() {
+            let line = line.context("failed to read stdout line")?;
+            if stdout_filter(&line) {
+                matched.push(line.clone());
+            }
+            buffer.push(line);
+        }
+        Ok((matched, buffer))
+    });
+    let stderr_thread = thread::spawn(move || -> Result<(Vec<String>, LineRingBuffer)> {
+        let mut matched = Vec::new();
+        let mut buffer = LineRingBuffer::new(RING_BUFFER_CAP, RING_BUFFER_CAP);
+        for line in BufReader::new(stderr).lines() {
+            let line = line.context("failed to read stderr line")?;
+            if stderr_filter(&line) {
+                matched.push(line.clone());
+            }
+            buffer.push(line);
+        }
+        Ok((matched, buffer))
+    });
+
+    let status = child.wait().context("failed to wait for command")?;
+    let (stdout_matched, stdout_buffer) = stdout_thread
+        .join()
+        .map_err(|_| anyhow::anyhow!("stdout reader thread panicked"))??;
+    let (stderr_matched, stderr_buffer) = stderr_thread
+        .join()
+        .map_err(|_| anyhow::anyhow!("stderr reader thread panicked"))??;
+
+    Ok(CapturedOutput {
+        status,
+        stdout_matched,
+        stderr_matched,
+        stdout_buffer,
+        stderr_buffer,
+    })
+}
@@ -0,0 +1,170 @@
+use unicode_width::UnicodeWidthStr;
+
+/// Fallback width used when nothing else says otherwise: no explicit
+/// override, no `TSPEC_WIDTH`/`COLUMNS` in the environment (e.g. output is
+/// piped or run under CI with no controlling terminal).
+pub const DEFAULT_WIDTH: usize = 100;
+
+/// Environment variable checked after an explicit override and before
+/// `COLUMNS`, so callers can pin a width without a real terminal (CI logs,
+/// recorded demos) without needing a `--width` flag on every command.
+const WIDTH_ENV_VAR: &str = "TSPEC_WIDTH";
+
+/// Pick the width to render tables and rules at, given an optional explicit
+/// override (a `--width N` flag, where one exists) and the two environment
+/// variables that can supply one, in precedence order: `explicit`, then
+/// `TSPEC_WIDTH`, then `COLUMNS`, then [`DEFAULT_WIDTH`]. Values that don't
+/// parse as a positive integer are ignored rather than treated as an error,
+/// since a malformed `COLUMNS` shouldn't break table output.
+///
+/// Pure and separated from [`terminal_width`] so the precedence logic is
+/// testable without touching real environment variables.
+fn resolve_width(explicit: Option<usize>, width_env: Option<&str>, columns: Option<&str>) -> usize {
+    let valid = |w: usize| (w > 0).then_some(w);
+    explicit
+        .and_then(valid)
+        .or_else(|| width_env.and_then(|v| v.parse().ok()).and_then(valid))
+        .or_else(|| columns.and_then(|v| v.parse().ok()).and_then(valid))
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// The width to render tables and rules at: `explicit` (e.g. a `--width N`
+/// flag) if given, else `TSPEC_WIDTH`, else the shell's `COLUMNS`, else
+/// [`DEFAULT_WIDTH`]. We don't probe the terminal directly (no `ioctl`
+/// dependency in this crate) — `COLUMNS` is exported by every shell this
+/// tool targets, and piped/non-interactive output falls through to the
+/// default.
+pub fn terminal_width(explicit: Option<usize>) -> usize {
+    resolve_width(
+        explicit,
+        std::env::var(WIDTH_ENV_VAR).ok().as_deref(),
+        std::env::var("COLUMNS").ok().as_deref(),
+    )
+}
+
+/// Shrink `s` to at most `max_width` display columns by replacing its
+/// middle with `"…"`, keeping the head and tail visible — e.g. a long path
+/// becomes `/very/long/pat…/the/file.rs`. Widths are measured with
+/// `unicode-width` (not byte or `char` count) so combining marks and wide
+/// CJK characters don't throw off the budget. Returns `s` unchanged if it
+/// already fits.
+pub fn elide_middle(s: &str, max_width: usize) -> String {
+    if s.width() <= max_width {
+        return s.to_string();
+    }
+    // Too narrow for even a single ellipsis: just cut without one.
+    if max_width == 0 {
+        return String::new();
+    }
+    if max_width == 1 {
+        return "…".to_string();
+    }
+
+    let budget = max_width - 1; // reserve one column for "…"
+    let head_budget = budget.div_ceil(2);
+    let tail_budget = budget - head_budget;
+
+    let chars: Vec<char> = s.chars().collect();
+    let mut head = String::new();
+    let mut head_width = 0;
+    for &c in &chars {
+        let cw = c.to_string().width();
+        if head_width + cw > head_budget {
+            break;
+        }
+        head.push(c);
+        head_width += cw;
+    }
+
+    let mut tail = String::new();
+    let mut tail_width = 0;
+    for &c in chars.iter().rev() {
+        let cw = c.to_string().width();
+        if tail_width + cw > tail_budget {
+            break;
+        }
+        tail.push(c);
+        tail_width += cw;
+    }
+    let tail: String = tail.chars().rev().collect();
+
+    format!("{head}…{tail}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_width_prefers_explicit() {
+        assert_eq!(resolve_width(Some(60), Some("80"), Some("120")), 60);
+    }
+
+    #[test]
+    fn resolve_width_falls_back_to_width_env() {
+        assert_eq!(resolve_width(None, Some("80"), Some("120")), 80);
+    }
+
+    #[test]
+    fn resolve_width_falls_back_to_columns() {
+        assert_eq!(resolve_width(None, None, Some("120")), 120);
+    }
+
+    #[test]
+    fn resolve_width_falls_back_to_default() {
+        assert_eq!(resolve_width(None, None, None), DEFAULT_WIDTH);
+    }
+
+    #[test]
+    fn resolve_width_ignores_unparseable_env_values() {
+        assert_eq!(
+            resolve_width(None, Some("wide"), Some("also-wide")),
+            DEFAULT_WIDTH
+        );
+    }
+
+    #[test]
+    fn resolve_width_ignores_zero() {
+        assert_eq!(resolve_width(Some(0), None, Some("90")), 90);
+    }
+
+    #[test]
+    fn elide_middle_returns_short_strings_unchanged() {
+        assert_eq!(elide_middle("short", 20), "short");
+    }
+
+    #[test]
+    fn elide_middle_shrinks_long_strings() {
+        let long = "/very/long/path/to/some/deeply/nested/file.rs";
+        let elided = elide_middle(long, 20);
+        assert_eq!(elided.width(), 20);
+        assert!(elided.contains('…'));
+        assert!(long.starts_with(elided.split('…').next().unwrap()));
+    }
+
+    #[test]
+    fn elide_middle_keeps_head_and_tail_visible() {
+        let elided = elide_middle("abcdefghijklmnopqrstuvwxyz", 11);
+        assert!(elided.starts_with("abcde"));
+        assert!(elided.ends_with("vwxyz"));
+    }
+
+    #[test]
+    fn elide_middle_handles_wide_unicode_chars() {
+        // Each CJK character below is 2 display columns wide.
+        let s = "文文文文文文文文文文";
+        let elided = elide_middle(s, 7);
+        assert!(elided.width() <= 7);
+        assert!(elided.contains('…'));
+    }
+
+    #[test]
+    fn elide_middle_max_width_one_is_just_ellipsis() {
+        assert_eq!(elide_middle("anything longer than one", 1), "…");
+    }
+
+    #[test]
+    fn elide_middle_max_width_zero_is_empty() {
+        assert_eq!(elide_middle("anything", 0), "");
+    }
+}
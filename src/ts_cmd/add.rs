@@ -5,7 +5,7 @@ use std::path::Path;
 use toml_edit::DocumentMut;
 
 use super::edit::{self, FieldKind};
-use crate::find_paths::{find_tspec, resolve_package_dir};
+use crate::find_paths::{find_tspec, resolve_ts_package_dir};
 
 /// Add items to an array field in a tspec
 pub fn add_value(
@@ -13,11 +13,11 @@ pub fn add_value(
     package: Option<&str>,
     key: &str,
     values: &[String],
-    index: Option<usize>,
+    index: Option<isize>,
     tspec: Option<&str>,
 ) -> Result<()> {
     let workspace = project_root;
-    let package_dir = resolve_package_dir(workspace, package)?;
+    let package_dir = resolve_ts_package_dir(workspace, package)?;
 
     let output_path = match find_tspec(&package_dir, tspec)? {
         Some(path) => path,
@@ -45,6 +45,7 @@ pub fn add_value(
 
     std::fs::write(&output_path, doc.to_string())
         .with_context(|| format!("failed to write: {}", output_path.display()))?;
+    crate::audit::record("add", key, &values.join(", "), &output_path);
 
     println!(
         "Saved {}",
@@ -74,7 +75,7 @@ mod tests {
         content: &str,
         key: &str,
         values: &[String],
-        index: Option<usize>,
+        index: Option<isize>,
     ) -> (TempDir, std::path::PathBuf, String) {
         let dir = TempDir::new().unwrap();
         let path = dir.path().join(format!("tspec{}", SUFFIX));
@@ -145,9 +146,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn insert_before_last_with_negative_one() {
+        let input = "[linker]\nargs = [\"-static\", \"-nostdlib\"]\n";
+        let (_dir, path, _) =
+            add_in_file(input, "linker.args", &vs(&["-Wl,--gc-sections"]), Some(-1));
+        let spec = load_spec(&path).unwrap();
+        assert_eq!(
+            spec.linker.args,
+            vec![
+                "-static".to_string(),
+                "-Wl,--gc-sections".to_string(),
+                "-nostdlib".to_string()
+            ]
+        );
+    }
+
     #[test]
     fn scalar_key_rejected() {
         let kind = edit::validate_key("cargo.profile").unwrap();
         assert_eq!(kind, edit::FieldKind::Scalar);
     }
+
+    #[test]
+    fn numeric_looking_values_stay_quoted_strings() {
+        // Array elements must never go through `parse_scalar_value`'s
+        // int/bool smart-typing - a page-size arg like `0x1000` or a bare
+        // `1` has to round-trip as a TOML string, not a number.
+        let (_dir, path, output) = add_in_file("", "linker.args", &vs(&["0x1000", "1"]), None);
+        assert!(output.contains("\"0x1000\""));
+        assert!(output.contains("\"1\""));
+
+        let spec = load_spec(&path).unwrap();
+        assert_eq!(
+            spec.linker.args,
+            vec!["0x1000".to_string(), "1".to_string()]
+        );
+    }
 }
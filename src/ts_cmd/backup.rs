@@ -3,13 +3,13 @@
 use anyhow::{Result, bail};
 use std::path::Path;
 
-use crate::find_paths::{find_tspec, resolve_package_dir};
+use crate::find_paths::{find_tspec, resolve_ts_package_dir};
 use crate::tspec::{copy_spec_snapshot, spec_name_from_path};
 
 /// Create a versioned backup snapshot of a tspec
 pub fn backup_tspec(project_root: &Path, package: Option<&str>, tspec: Option<&str>) -> Result<()> {
     let workspace = project_root;
-    let package_dir = resolve_package_dir(workspace, package)?;
+    let package_dir = resolve_ts_package_dir(workspace, package)?;
 
     let spec_path = match find_tspec(&package_dir, tspec)? {
         Some(path) => path,
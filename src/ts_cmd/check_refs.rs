@@ -0,0 +1,23 @@
+//! `tspec ts check-refs` - Find stale spec references across the workspace
+//!
+//! Standalone entry point for the same check `tspec doctor` runs; see
+//! [`crate::refcheck`] for the registry of reference-holding subsystems.
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::refcheck::{check_refs, print_dangling};
+use crate::workspace::WorkspaceInfo;
+
+/// Run the cross-reference integrity check. Returns `true` if no dangling
+/// references were found.
+pub fn check_refs_tspec(project_root: &Path) -> Result<bool> {
+    let workspace = WorkspaceInfo::discover(project_root)?;
+    let dangling = check_refs(&workspace)?;
+    if dangling.is_empty() {
+        println!("No dangling spec references found.");
+        return Ok(true);
+    }
+    print_dangling(&dangling);
+    Ok(false)
+}
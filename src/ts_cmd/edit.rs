@@ -3,12 +3,19 @@
 use anyhow::{Result, bail};
 use toml_edit::{Array, DocumentMut, Item, Value};
 
-/// Whether a field holds a scalar, an array, or a table.
+/// Whether a field holds a scalar, an array, a table, or a boolean.
+///
+/// `Bool` is its own kind rather than a `Scalar` because scalars are always
+/// written as TOML strings (see [`parse_scalar_value`]) — a boolean-typed
+/// [`crate::types::Spec`] field like `cargo.hermetic_env` needs a real TOML
+/// `true`/`false`, and `ts toggle` (see [`toggle_field`]) only makes sense
+/// for fields with exactly two states.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum FieldKind {
     Scalar,
     Array,
     Table,
+    Bool,
 }
 
 /// Registry entry: (dotted key path, kind).
@@ -19,12 +26,18 @@ const FIELD_REGISTRY: &[(&str, FieldKind)] = &[
     ("cargo.profile", FieldKind::Scalar),
     ("cargo.target_triple", FieldKind::Scalar),
     ("cargo.target_json", FieldKind::Scalar),
+    ("cargo.target_json_hash", FieldKind::Scalar),
     ("cargo.target_dir", FieldKind::Scalar),
     ("cargo.unstable", FieldKind::Array),
     ("cargo.config", FieldKind::Table),
     ("cargo.build_std", FieldKind::Array),
+    ("cargo.opt_level_deps", FieldKind::Scalar),
+    ("cargo.hermetic_env", FieldKind::Bool),
     ("rustflags", FieldKind::Array),
     ("linker.args", FieldKind::Array),
+    ("linker.version_script.global", FieldKind::Array),
+    ("linker.version_script.local", FieldKind::Scalar),
+    ("profile_overrides", FieldKind::Table),
 ];
 
 /// Validate that a key is in the registry and return its kind.
@@ -39,6 +52,21 @@ pub fn validate_key(key: &str) -> Result<FieldKind> {
     if parse_table_key(key).is_some() {
         return Ok(FieldKind::Table);
     }
+    let suggestions: Vec<&str> = FIELD_REGISTRY
+        .iter()
+        .map(|(k, _)| *k)
+        .filter(|k| k.starts_with(key))
+        .collect();
+    if let [only] = suggestions[..] {
+        bail!("unknown key: {} (did you mean `{}`?)", key, only);
+    }
+    if !suggestions.is_empty() {
+        bail!(
+            "unknown key: {} (did you mean one of: {}?)",
+            key,
+            suggestions.join(", ")
+        );
+    }
     let valid_keys: Vec<&str> = FIELD_REGISTRY.iter().map(|(k, _)| *k).collect();
     bail!(
         "unknown key: {} (valid keys: {})",
@@ -95,15 +123,40 @@ pub fn validate_value(key: &str, value: &str) -> Result<()> {
             ),
         },
         "cargo.profile" => Ok(()), // Any profile name is valid (debug, release, custom, etc.)
+        "cargo.opt_level_deps" => match value {
+            "0" | "1" | "2" | "3" | "s" | "z" => Ok(()),
+            _ => bail!(
+                "invalid opt_level_deps: {} (expected: 0, 1, 2, 3, s, z)",
+                value
+            ),
+        },
         _ => Ok(()),
     }
 }
 
-/// Parse a key into (optional table, field).
+/// Normalize gcc-style `-O<level>` shorthand (`-O2`, `O2`, `-Oz`) to the bare
+/// level tspec stores (`2`, `z`), so the muscle memory of typing `-O2`
+/// still works. Only applies to `cargo.opt_level_deps`; every other field's
+/// value passes through unchanged. Normalization happens before
+/// [`validate_value`], so an out-of-range level like `-O9` still fails
+/// validation on its normalized form (`9`).
+pub fn normalize_value(key: &str, value: &str) -> String {
+    if key != "cargo.opt_level_deps" {
+        return value.to_string();
+    }
+    let without_dash = value.strip_prefix('-').unwrap_or(value);
+    without_dash
+        .strip_prefix('O')
+        .unwrap_or(without_dash)
+        .to_string()
+}
+
+/// Parse a key into (optional table path, field).
 /// "panic" -> (None, "panic")
 /// "rustc.lto" -> (Some("rustc"), "lto")
+/// "linker.version_script.global" -> (Some("linker.version_script"), "global")
 fn parse_key(key: &str) -> (Option<&str>, &str) {
-    match key.split_once('.') {
+    match key.rsplit_once('.') {
         Some((table, field)) => (Some(table), field),
         None => (None, key),
     }
@@ -115,12 +168,30 @@ fn parse_scalar_value(_key: &str, raw: &str) -> Value {
     Value::from(raw)
 }
 
+/// Parse a `--set`/`--toggle` value string for a `FieldKind::Bool` field.
+fn parse_bool_value(key: &str, raw: &str) -> Result<bool> {
+    match raw {
+        "true" => Ok(true),
+        "false" => Ok(false),
+        _ => bail!("invalid value for boolean field '{key}': {raw} (expected: true, false)"),
+    }
+}
+
+/// Look up a (possibly dotted) table path, returning `None` if any segment
+/// along the way is missing.
+fn lookup_table<'a>(doc: &'a DocumentMut, table: &str) -> Option<&'a dyn toml_edit::TableLike> {
+    let mut current: &dyn toml_edit::TableLike = doc.as_table();
+    for segment in table.split('.') {
+        current = current.get(segment)?.as_table_like()?;
+    }
+    Some(current)
+}
+
 /// Get the existing array for a field, or an empty array if it doesn't exist.
 fn get_existing_array(doc: &DocumentMut, key: &str) -> Array {
     let (table_name, field) = parse_key(key);
     match table_name {
-        Some(table) => doc
-            .get(table)
+        Some(table) => lookup_table(doc, table)
             .and_then(|t| t.get(field))
             .and_then(|v| v.as_array())
             .cloned()
@@ -139,7 +210,8 @@ fn set_array_in_doc(doc: &mut DocumentMut, key: &str, arr: Array) {
     match table_name {
         Some(table) => {
             ensure_table(doc, table);
-            doc[table][field] = Item::Value(Value::Array(arr));
+            let tbl = lookup_table_mut(doc, table).expect("just ensured");
+            tbl.insert(field, Item::Value(Value::Array(arr)));
         }
         None => {
             doc[field] = Item::Value(Value::Array(arr));
@@ -147,10 +219,31 @@ fn set_array_in_doc(doc: &mut DocumentMut, key: &str, arr: Array) {
     }
 }
 
-/// Ensure a table exists in the document.
+/// Mutable variant of [`lookup_table`].
+fn lookup_table_mut<'a>(
+    doc: &'a mut DocumentMut,
+    table: &str,
+) -> Option<&'a mut dyn toml_edit::TableLike> {
+    let mut current: &mut dyn toml_edit::TableLike = doc.as_table_mut();
+    for segment in table.split('.') {
+        current = current.get_mut(segment)?.as_table_like_mut()?;
+    }
+    Some(current)
+}
+
+/// Ensure a table exists in the document, creating intermediate tables for
+/// each dotted segment of `table` (e.g. "linker.version_script" creates
+/// `[linker]` then `[linker.version_script]` if either is missing).
 fn ensure_table(doc: &mut DocumentMut, table: &str) {
-    if doc.get(table).is_none() {
-        doc[table] = Item::Table(toml_edit::Table::new());
+    let mut current: &mut dyn toml_edit::TableLike = doc.as_table_mut();
+    for segment in table.split('.') {
+        if current.get(segment).is_none() {
+            current.insert(segment, Item::Table(toml_edit::Table::new()));
+        }
+        current = current
+            .get_mut(segment)
+            .and_then(Item::as_table_like_mut)
+            .expect("just inserted or pre-existing table");
     }
 }
 
@@ -178,7 +271,8 @@ pub fn set_field(
             match table_name {
                 Some(table) => {
                     ensure_table(doc, table);
-                    doc[table][field] = Item::Value(val);
+                    let tbl = lookup_table_mut(doc, table).expect("just ensured");
+                    tbl.insert(field, Item::Value(val));
                 }
                 None => {
                     doc[field] = Item::Value(val);
@@ -192,6 +286,26 @@ pub fn set_field(
             }
             set_array_in_doc(doc, key, arr);
         }
+        FieldKind::Bool => {
+            if values.len() != 1 {
+                bail!(
+                    "boolean field '{}' requires exactly one value, got {}",
+                    key,
+                    values.len()
+                );
+            }
+            let val = parse_bool_value(key, &values[0])?;
+            match table_name {
+                Some(table) => {
+                    ensure_table(doc, table);
+                    let tbl = lookup_table_mut(doc, table).expect("just ensured");
+                    tbl.insert(field, Item::Value(Value::from(val)));
+                }
+                None => {
+                    doc[field] = Item::Value(Value::from(val));
+                }
+            }
+        }
         FieldKind::Table => {
             bail!(
                 "use set_table_value() for table field '{}'; set_field() does not handle tables",
@@ -203,29 +317,72 @@ pub fn set_field(
     Ok(())
 }
 
-/// Add items to an array field. Appends by default, or inserts at `index`.
-/// Deduplicates on append; insert adds at position without dedup.
+/// Read a `FieldKind::Bool` field's current value, or `false` if unset.
+fn get_bool(doc: &DocumentMut, key: &str) -> bool {
+    let (table_name, field) = parse_key(key);
+    match table_name {
+        Some(table) => lookup_table(doc, table)
+            .and_then(|t| t.get(field))
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false),
+        None => doc.get(field).and_then(|v| v.as_bool()).unwrap_or(false),
+    }
+}
+
+/// Flip a boolean field's current value (defaulting to `false` if unset) and
+/// write the result back. Returns the new value. Only valid for
+/// `FieldKind::Bool` fields — reject anything else so a typo'd array/scalar
+/// key fails loudly instead of silently doing nothing.
+pub fn toggle_field(doc: &mut DocumentMut, key: &str) -> Result<bool> {
+    if validate_key(key)? != FieldKind::Bool {
+        bail!("'ts toggle' only works on boolean fields, but '{key}' is not one");
+    }
+    let new_value = !get_bool(doc, key);
+    set_field(doc, key, &[new_value.to_string()], FieldKind::Bool)?;
+    Ok(new_value)
+}
+
+/// Resolve a possibly-negative `--index` against the current array length.
+/// Python-style: -1 means "before the last element", -len means "at the
+/// start". Out-of-range negative indices are reported with the position
+/// they resolved to, since the negative number alone doesn't say why it
+/// failed.
+fn resolve_insert_index(index: isize, key: &str, len: usize) -> Result<usize> {
+    let resolved = if index < 0 {
+        index + len as isize
+    } else {
+        index
+    };
+    if resolved < 0 || resolved as usize > len {
+        bail!(
+            "index {} (resolved to {}) out of bounds for array '{}' with {} elements",
+            index,
+            resolved,
+            key,
+            len
+        );
+    }
+    Ok(resolved as usize)
+}
+
+/// Add items to an array field. Appends by default, or inserts at `index`
+/// (negative indices count from the end, Python-style — see
+/// `resolve_insert_index`). Deduplicates on append; insert adds at position
+/// without dedup.
 pub fn add_items(
     doc: &mut DocumentMut,
     key: &str,
     values: &[String],
-    index: Option<usize>,
+    index: Option<isize>,
 ) -> Result<()> {
     let mut arr = get_existing_array(doc, key);
 
     match index {
         Some(idx) => {
-            if idx > arr.len() {
-                bail!(
-                    "index {} out of bounds for array '{}' with {} elements",
-                    idx,
-                    key,
-                    arr.len()
-                );
-            }
+            let resolved = resolve_insert_index(idx, key, arr.len())?;
             // Insert at position (no dedup — user explicitly chose position)
             for (offset, v) in values.iter().enumerate() {
-                arr.insert(idx + offset, v.as_str());
+                arr.insert(resolved + offset, v.as_str());
             }
         }
         None => {
@@ -283,6 +440,14 @@ pub fn remove_item_by_index(doc: &mut DocumentMut, key: &str, index: usize) -> R
     Ok(())
 }
 
+/// Replace an array field with an empty array.
+/// Keeps the field/table, matching the single-removal behavior — only
+/// `unset_field` removes the field entirely.
+pub fn clear_array(doc: &mut DocumentMut, key: &str) -> Result<()> {
+    set_array_in_doc(doc, key, Array::new());
+    Ok(())
+}
+
 /// Remove a field from a toml_edit document.
 /// Does not remove the containing table, even if it becomes empty.
 pub fn unset_field(doc: &mut DocumentMut, key: &str) -> Result<()> {
@@ -290,7 +455,7 @@ pub fn unset_field(doc: &mut DocumentMut, key: &str) -> Result<()> {
 
     match table_name {
         Some(table) => {
-            if let Some(Item::Table(tbl)) = doc.get_mut(table) {
+            if let Some(tbl) = lookup_table_mut(doc, table) {
                 tbl.remove(field);
             }
         }
@@ -302,6 +467,45 @@ pub fn unset_field(doc: &mut DocumentMut, key: &str) -> Result<()> {
     Ok(())
 }
 
+/// Move a value from one dotted key path to another, preserving the
+/// original `Item` (including any comments attached to it). Returns
+/// `false` without touching the document if `from` isn't present, so
+/// callers (e.g. `ts migrate`) can apply a whole rename table idempotently.
+/// Does not remove the source table even if it becomes empty — see
+/// `unset_field`.
+pub fn rename_key(doc: &mut DocumentMut, from: &str, to: &str) -> Result<bool> {
+    let (from_table, from_field) = parse_key(from);
+    let item = match from_table {
+        Some(table) => lookup_table_mut(doc, table).and_then(|t| t.remove(from_field)),
+        None => doc.remove(from_field),
+    };
+    let Some(item) = item else {
+        return Ok(false);
+    };
+
+    let (to_table, to_field) = parse_key(to);
+    match to_table {
+        Some(table) => {
+            ensure_table(doc, table);
+            let tbl = lookup_table_mut(doc, table).expect("just ensured");
+            tbl.insert(to_field, item);
+        }
+        None => {
+            doc.insert(to_field, item);
+        }
+    }
+    Ok(true)
+}
+
+/// Remove a table if it exists and is now empty. Used after `rename_key`
+/// to clean up legacy containers (e.g. `[rustc]`) that have no modern
+/// equivalent, once they've been drained of all their keys.
+pub fn remove_table_if_empty(doc: &mut DocumentMut, table: &str) {
+    if lookup_table(doc, table).is_some_and(|t| t.is_empty()) {
+        doc.remove(table);
+    }
+}
+
 /// Smart-parse a raw value string into a toml_edit Value for table entries.
 /// Booleans → bool, integers → i64, everything else → string.
 fn parse_smart_value(raw: &str) -> Value {
@@ -322,6 +526,11 @@ fn parse_smart_value(raw: &str) -> Value {
 /// `table_path` is the dotted path to the table (e.g., "cargo.config").
 /// `sub_key` is the key within that table (e.g., "profile.release.opt-level").
 /// `raw_value` is the string value to set (auto-parsed to bool/int/string).
+///
+/// If `table_name` already exists (as either a standard `[table]` or an
+/// inline `{ ... }` value), its style is left alone — `Item`'s string
+/// indexing operates on both `Table` and inline-table `Value`s, so a
+/// sub-key set never promotes an inline table to a standard one.
 pub fn set_table_value(
     doc: &mut DocumentMut,
     table_path: &str,
@@ -351,6 +560,26 @@ pub fn set_table_value(
     Ok(())
 }
 
+/// Whether `key` already has an explicit value in `doc`, used by `ts set
+/// --if-unset` to skip writes that would clobber something already
+/// present. For `Table` sub-keys (e.g.
+/// `cargo.config."profile.release.opt-level"`) checks the sub-key itself,
+/// not just the table's existence.
+pub fn field_is_set(doc: &DocumentMut, key: &str, kind: FieldKind) -> bool {
+    if kind == FieldKind::Table {
+        let Some((table_path, sub_key)) = parse_table_key(key) else {
+            return false;
+        };
+        return lookup_table(doc, table_path).is_some_and(|t| t.get(sub_key).is_some());
+    }
+
+    let (table_name, field) = parse_key(key);
+    match table_name {
+        Some(table) => lookup_table(doc, table).is_some_and(|t| t.get(field).is_some()),
+        None => doc.get(field).is_some(),
+    }
+}
+
 /// Remove a single key from a table field.
 pub fn unset_table_value(doc: &mut DocumentMut, table_path: &str, sub_key: &str) -> Result<()> {
     let (parent, table_name) = parse_key(table_path);
@@ -402,6 +631,30 @@ mod tests {
         assert!(err.to_string().contains("unknown key"));
     }
 
+    #[test]
+    fn validate_key_suggests_single_prefix_match() {
+        let err = validate_key("cargo.b").unwrap_err();
+        assert!(err.to_string().contains("did you mean `cargo.build_std`?"));
+    }
+
+    #[test]
+    fn validate_key_suggests_multiple_prefix_matches() {
+        let err = validate_key("cargo.target_").unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("cargo.target_triple"));
+        assert!(msg.contains("cargo.target_json"));
+        assert!(msg.contains("cargo.target_dir"));
+    }
+
+    #[test]
+    fn validate_key_total_miss_lists_all_keys() {
+        let err = validate_key("totally.unknown").unwrap_err();
+        let msg = err.to_string();
+        assert!(msg.contains("valid keys:"));
+        assert!(msg.contains("panic"));
+        assert!(msg.contains("linker.args"));
+    }
+
     #[test]
     fn validate_value_panic() {
         assert!(validate_value("panic", "abort").is_ok());
@@ -648,6 +901,44 @@ mod tests {
         assert!(err.unwrap_err().to_string().contains("out of bounds"));
     }
 
+    #[test]
+    fn add_insert_negative_one_before_last_element() {
+        let input = "[linker]\nargs = [\"-static\", \"-nostdlib\"]\n";
+        let mut doc = input.parse::<DocumentMut>().unwrap();
+        add_items(
+            &mut doc,
+            "linker.args",
+            &vs(&["-Wl,--gc-sections"]),
+            Some(-1),
+        )
+        .unwrap();
+        let arr = doc["linker"]["args"].as_array().unwrap();
+        assert_eq!(arr.len(), 3);
+        assert_eq!(arr.get(0).unwrap().as_str(), Some("-static"));
+        assert_eq!(arr.get(1).unwrap().as_str(), Some("-Wl,--gc-sections"));
+        assert_eq!(arr.get(2).unwrap().as_str(), Some("-nostdlib"));
+    }
+
+    #[test]
+    fn add_insert_negative_len_at_start() {
+        let input = "[linker]\nargs = [\"-static\", \"-nostdlib\"]\n";
+        let mut doc = input.parse::<DocumentMut>().unwrap();
+        add_items(&mut doc, "linker.args", &vs(&["-nostartfiles"]), Some(-2)).unwrap();
+        let arr = doc["linker"]["args"].as_array().unwrap();
+        assert_eq!(arr.get(0).unwrap().as_str(), Some("-nostartfiles"));
+    }
+
+    #[test]
+    fn add_insert_negative_out_of_range() {
+        let input = "[linker]\nargs = [\"-static\", \"-nostdlib\"]\n";
+        let mut doc = input.parse::<DocumentMut>().unwrap();
+        let err = add_items(&mut doc, "linker.args", &vs(&["-static"]), Some(-3));
+        assert!(err.is_err());
+        let msg = err.unwrap_err().to_string();
+        assert!(msg.contains("out of bounds"));
+        assert!(msg.contains("resolved to -1"));
+    }
+
     #[test]
     fn add_insert_does_not_dedup() {
         let input = "[linker]\nargs = [\"-static\"]\n";
@@ -749,6 +1040,25 @@ mod tests {
     #[test]
     fn validate_key_table() {
         assert_eq!(validate_key("cargo.config").unwrap(), FieldKind::Table);
+        assert_eq!(validate_key("profile_overrides").unwrap(), FieldKind::Table);
+    }
+
+    #[test]
+    fn validate_key_opt_level_deps_scalar() {
+        assert_eq!(
+            validate_key("cargo.opt_level_deps").unwrap(),
+            FieldKind::Scalar
+        );
+    }
+
+    #[test]
+    fn set_table_value_profile_overrides() {
+        let mut doc = "".parse::<DocumentMut>().unwrap();
+        set_table_value(&mut doc, "profile_overrides", "release.deps.opt-level", "2").unwrap();
+        assert_eq!(
+            doc["profile_overrides"]["release.deps.opt-level"].as_integer(),
+            Some(2)
+        );
     }
 
     #[test]
@@ -837,6 +1147,32 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_table_value_preserves_inline_table_style() {
+        let input = "[cargo]\nconfig = { \"profile.release.opt-level\" = \"s\" }\n";
+        let mut doc = input.parse::<DocumentMut>().unwrap();
+        set_table_value(&mut doc, "cargo.config", "profile.release.lto", "true").unwrap();
+
+        assert_eq!(
+            doc["cargo"]["config"]["profile.release.opt-level"].as_str(),
+            Some("s")
+        );
+        assert_eq!(
+            doc["cargo"]["config"]["profile.release.lto"].as_bool(),
+            Some(true)
+        );
+
+        let rendered = doc.to_string();
+        assert!(
+            rendered.contains("config = {"),
+            "sub-key set should keep `config` as an inline table, got:\n{rendered}"
+        );
+        assert!(
+            !rendered.contains("[cargo.config]"),
+            "sub-key set should not convert the inline table to a standard table, got:\n{rendered}"
+        );
+    }
+
     #[test]
     fn unset_table_value_removes_key() {
         let input = "[cargo.config]\n\"profile.release.opt-level\" = \"s\"\n\"profile.release.lto\" = true\n";
@@ -858,4 +1194,156 @@ mod tests {
         let mut doc = "".parse::<DocumentMut>().unwrap();
         unset_table_value(&mut doc, "cargo.config", "nonexistent").unwrap();
     }
+
+    // --- linker.version_script tests (doubly-nested table path) ---
+
+    #[test]
+    fn validate_key_version_script() {
+        assert_eq!(
+            validate_key("linker.version_script.global").unwrap(),
+            FieldKind::Array
+        );
+        assert_eq!(
+            validate_key("linker.version_script.local").unwrap(),
+            FieldKind::Scalar
+        );
+    }
+
+    #[test]
+    fn set_version_script_global_creates_nested_tables() {
+        let mut doc = "".parse::<DocumentMut>().unwrap();
+        set_field(
+            &mut doc,
+            "linker.version_script.global",
+            &vs(&["_start", "main"]),
+            FieldKind::Array,
+        )
+        .unwrap();
+        let arr = doc["linker"]["version_script"]["global"]
+            .as_array()
+            .unwrap();
+        assert_eq!(arr.len(), 2);
+        assert_eq!(arr.get(0).unwrap().as_str(), Some("_start"));
+    }
+
+    #[test]
+    fn set_version_script_local_scalar() {
+        let mut doc = "".parse::<DocumentMut>().unwrap();
+        set_field(
+            &mut doc,
+            "linker.version_script.local",
+            &vs(&["*"]),
+            FieldKind::Scalar,
+        )
+        .unwrap();
+        assert_eq!(doc["linker"]["version_script"]["local"].as_str(), Some("*"));
+    }
+
+    #[test]
+    fn set_version_script_preserves_linker_args() {
+        let input = "[linker]\nargs = [\"-static\"]\n";
+        let mut doc = input.parse::<DocumentMut>().unwrap();
+        set_field(
+            &mut doc,
+            "linker.version_script.global",
+            &vs(&["_start"]),
+            FieldKind::Array,
+        )
+        .unwrap();
+        assert_eq!(
+            doc["linker"]["args"]
+                .as_array()
+                .unwrap()
+                .get(0)
+                .unwrap()
+                .as_str(),
+            Some("-static")
+        );
+        assert_eq!(
+            doc["linker"]["version_script"]["global"]
+                .as_array()
+                .unwrap()
+                .get(0)
+                .unwrap()
+                .as_str(),
+            Some("_start")
+        );
+    }
+
+    #[test]
+    fn unset_version_script_field() {
+        let input = "[linker.version_script]\nglobal = [\"_start\"]\nlocal = \"*\"\n";
+        let mut doc = input.parse::<DocumentMut>().unwrap();
+        unset_field(&mut doc, "linker.version_script.global").unwrap();
+        assert!(doc["linker"]["version_script"].get("global").is_none());
+        assert_eq!(doc["linker"]["version_script"]["local"].as_str(), Some("*"));
+    }
+
+    #[test]
+    fn normalize_opt_level_strips_gcc_style_dash_o() {
+        assert_eq!(normalize_value("cargo.opt_level_deps", "-O3"), "3");
+        assert_eq!(normalize_value("cargo.opt_level_deps", "O3"), "3");
+        assert_eq!(normalize_value("cargo.opt_level_deps", "-Oz"), "z");
+        assert_eq!(normalize_value("cargo.opt_level_deps", "3"), "3");
+    }
+
+    #[test]
+    fn normalize_opt_level_leaves_other_fields_alone() {
+        assert_eq!(normalize_value("panic", "-O2"), "-O2");
+    }
+
+    #[test]
+    fn validate_value_opt_level_deps() {
+        for ok in ["0", "1", "2", "3", "s", "z"] {
+            assert!(validate_value("cargo.opt_level_deps", ok).is_ok());
+        }
+        assert!(validate_value("cargo.opt_level_deps", "9").is_err());
+    }
+
+    #[test]
+    fn rejects_out_of_range_dash_o_after_normalization() {
+        let normalized = normalize_value("cargo.opt_level_deps", "-O9");
+        assert_eq!(normalized, "9");
+        assert!(validate_value("cargo.opt_level_deps", &normalized).is_err());
+    }
+
+    #[test]
+    fn field_is_set_false_for_absent_scalar() {
+        let doc: DocumentMut = "".parse().unwrap();
+        assert!(!field_is_set(&doc, "panic", FieldKind::Scalar));
+    }
+
+    #[test]
+    fn field_is_set_true_for_present_scalar() {
+        let doc: DocumentMut = "panic = \"abort\"\n".parse().unwrap();
+        assert!(field_is_set(&doc, "panic", FieldKind::Scalar));
+    }
+
+    #[test]
+    fn field_is_set_true_for_present_nested_scalar() {
+        let doc: DocumentMut = "[cargo]\nprofile = \"release\"\n".parse().unwrap();
+        assert!(field_is_set(&doc, "cargo.profile", FieldKind::Scalar));
+    }
+
+    #[test]
+    fn field_is_set_false_for_absent_table_sub_key() {
+        let doc: DocumentMut = "".parse().unwrap();
+        assert!(!field_is_set(
+            &doc,
+            "cargo.config.\"profile.release.opt-level\"",
+            FieldKind::Table
+        ));
+    }
+
+    #[test]
+    fn field_is_set_true_for_present_table_sub_key() {
+        let doc: DocumentMut = "[cargo.config]\n\"profile.release.opt-level\" = \"s\"\n"
+            .parse()
+            .unwrap();
+        assert!(field_is_set(
+            &doc,
+            "cargo.config.\"profile.release.opt-level\"",
+            FieldKind::Table
+        ));
+    }
 }
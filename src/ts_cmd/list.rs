@@ -4,26 +4,24 @@ use anyhow::Result;
 use std::path::Path;
 
 use crate::TSPEC_SUFFIX;
-use crate::find_paths::{get_package_name, resolve_package_dir};
+use crate::find_paths::{current_package_name, resolve_package_dir};
 use crate::workspace::WorkspaceInfo;
 
 /// List all tspec files in workspace or for a specific package
 pub fn list_tspecs(project_root: &Path, package: Option<&str>, all: bool) -> Result<()> {
     let workspace = project_root;
 
-    // Check if we're in a package directory (has Cargo.toml with [package], not just workspace)
-    let cwd = std::env::current_dir()?;
-    let in_package_dir = get_package_name(&cwd).is_ok();
-
-    // Resolve: --all > -p PKG > cwd > all
-    let list_all = all || (package.is_none() && !in_package_dir);
+    // Resolve: --all > -p PKG > cwd (relative to project_root) > all.
+    // `current_package_name` treats cwd as "no package" when --mp points
+    // elsewhere, so an explicit --mp always wins over a stale cwd.
+    let cwd_package = current_package_name(project_root);
 
     if let Some(name) = package {
         // Explicit package specified
         let package_dir = resolve_package_dir(workspace, Some(name))?;
         let tspecs = find_tspec_files(&package_dir)?;
         print_package_tspecs(name, &package_dir, &tspecs);
-    } else if list_all {
+    } else if all || cwd_package.is_none() {
         // List all packages
         let info = WorkspaceInfo::discover(project_root)?;
         let mut found_any = false;
@@ -39,11 +37,11 @@ pub fn list_tspecs(project_root: &Path, package: Option<&str>, all: bool) -> Res
         if !found_any {
             println!("No *{} files found in workspace", TSPEC_SUFFIX);
         }
-    } else {
+    } else if let Some(pkg_name) = cwd_package {
         // In a package directory, list just this package
-        let pkg_name = get_package_name(&cwd)?;
-        let tspecs = find_tspec_files(&cwd)?;
-        print_package_tspecs(&pkg_name, &cwd, &tspecs);
+        let package_dir = resolve_package_dir(workspace, Some(&pkg_name))?;
+        let tspecs = find_tspec_files(&package_dir)?;
+        print_package_tspecs(&pkg_name, &package_dir, &tspecs);
     }
 
     Ok(())
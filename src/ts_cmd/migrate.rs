@@ -0,0 +1,150 @@
+//! `tspec ts migrate` - Rewrite legacy key paths to their modern equivalent
+
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use toml_edit::DocumentMut;
+
+use super::edit;
+use crate::find_paths::{find_tspec, resolve_ts_package_dir};
+use crate::tspec::LEGACY_KEY_MAP;
+
+/// Rewrite every known legacy key in a tspec to its modern equivalent
+/// in place, preserving comments/formatting. Idempotent: a tspec with no
+/// legacy keys left is written back unchanged (modulo whitespace already
+/// normalized by `toml_edit`), and running twice in a row produces no
+/// further renames the second time.
+pub fn migrate_tspec(
+    project_root: &Path,
+    package: Option<&str>,
+    tspec: Option<&str>,
+) -> Result<()> {
+    let workspace = project_root;
+    let package_dir = resolve_ts_package_dir(workspace, package)?;
+
+    let output_path = match find_tspec(&package_dir, tspec)? {
+        Some(path) => path,
+        None => bail!("no tspec found to migrate"),
+    };
+
+    let content = std::fs::read_to_string(&output_path)
+        .with_context(|| format!("failed to read: {}", output_path.display()))?;
+
+    let mut doc: DocumentMut = content
+        .parse()
+        .with_context(|| format!("failed to parse: {}", output_path.display()))?;
+
+    let renamed = apply_legacy_renames(&mut doc)?;
+
+    if renamed.is_empty() {
+        println!("No legacy keys found in {}", output_path.display());
+        return Ok(());
+    }
+
+    std::fs::write(&output_path, doc.to_string())
+        .with_context(|| format!("failed to write: {}", output_path.display()))?;
+
+    println!(
+        "Migrated {}",
+        output_path
+            .strip_prefix(workspace)
+            .unwrap_or(&output_path)
+            .display()
+    );
+    for (from, to) in &renamed {
+        println!("  {from} -> {to}");
+    }
+
+    Ok(())
+}
+
+/// Apply every rename in [`LEGACY_KEY_MAP`] to `doc`, dropping any legacy
+/// container table (e.g. `[rustc]`) left empty afterward. Returns the
+/// renames that actually happened.
+fn apply_legacy_renames(doc: &mut DocumentMut) -> Result<Vec<(&'static str, &'static str)>> {
+    let mut renamed = Vec::new();
+    for &(from, to) in LEGACY_KEY_MAP {
+        if edit::rename_key(doc, from, to)? {
+            renamed.push((from, to));
+        }
+    }
+    for &(from, _) in LEGACY_KEY_MAP {
+        if let Some((table, _)) = from.rsplit_once('.') {
+            edit::remove_table_if_empty(doc, table);
+        }
+    }
+    Ok(renamed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tspec::load_spec;
+    use tempfile::TempDir;
+
+    fn migrate_in_file(content: &str) -> (TempDir, std::path::PathBuf, String) {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(format!("tspec{}", crate::TSPEC_SUFFIX));
+        std::fs::write(&path, content).unwrap();
+
+        let mut doc: DocumentMut = content.parse().unwrap();
+        apply_legacy_renames(&mut doc).unwrap();
+        let output = doc.to_string();
+        std::fs::write(&path, &output).unwrap();
+
+        (dir, path, output)
+    }
+
+    #[test]
+    fn rustc_panic_migrates_to_top_level_panic() {
+        let input = "[rustc]\npanic = \"abort\"\n";
+        let (_dir, path, output) = migrate_in_file(input);
+        let spec = load_spec(&path).unwrap();
+        assert_eq!(spec.panic, Some(crate::options::PanicMode::Abort));
+        assert!(!output.contains("[rustc]"));
+    }
+
+    #[test]
+    fn cargo_target_migrates_to_cargo_target_triple() {
+        let input = "[cargo]\ntarget = \"x86_64-unknown-linux-musl\"\n";
+        let (_dir, path, output) = migrate_in_file(input);
+        let spec = load_spec(&path).unwrap();
+        assert_eq!(
+            spec.cargo.target_triple.as_deref(),
+            Some("x86_64-unknown-linux-musl")
+        );
+        // `[cargo]` itself has a real modern meaning, so it stays even
+        // though the legacy `target` key inside it is gone.
+        assert!(output.contains("[cargo]"));
+        assert!(!output.contains("target ="));
+    }
+
+    #[test]
+    fn leaves_other_cargo_keys_untouched() {
+        let input = "[cargo]\ntarget = \"x86_64-unknown-linux-musl\"\nprofile = \"release\"\n";
+        let (_dir, path, _) = migrate_in_file(input);
+        let spec = load_spec(&path).unwrap();
+        assert_eq!(spec.cargo.profile.as_deref(), Some("release"));
+    }
+
+    #[test]
+    fn no_legacy_keys_is_a_no_op() {
+        let input = "panic = \"abort\"\n";
+        let mut doc: DocumentMut = input.parse().unwrap();
+        let renamed = apply_legacy_renames(&mut doc).unwrap();
+        assert!(renamed.is_empty());
+        assert_eq!(doc.to_string(), input);
+    }
+
+    #[test]
+    fn migrating_twice_is_idempotent() {
+        let input = "[rustc]\npanic = \"abort\"\n";
+        let mut doc: DocumentMut = input.parse().unwrap();
+        let first = apply_legacy_renames(&mut doc).unwrap();
+        assert_eq!(first, vec![("rustc.panic", "panic")]);
+
+        let after_first = doc.to_string();
+        let second = apply_legacy_renames(&mut doc).unwrap();
+        assert!(second.is_empty());
+        assert_eq!(doc.to_string(), after_first);
+    }
+}
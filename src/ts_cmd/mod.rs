@@ -2,23 +2,41 @@
 
 mod add;
 mod backup;
+mod check_refs;
 mod edit;
 mod hash;
 mod list;
+mod migrate;
 mod new;
+mod normalize;
+mod pin;
+mod pin_target;
 mod remove;
 mod restore;
+mod rollback;
 mod set;
 mod show;
+mod toggle;
+mod tree;
 mod unset;
+mod validate;
 
 pub use add::add_value;
 pub use backup::backup_tspec;
+pub use check_refs::check_refs_tspec;
 pub use hash::hash_tspec;
 pub use list::list_tspecs;
+pub use migrate::migrate_tspec;
 pub use new::new_tspec;
+pub use normalize::normalize_tspec;
+pub use pin::pin_tspec;
+pub use pin_target::pin_target;
 pub use remove::remove_value;
 pub use restore::restore_tspec;
-pub use set::set_value;
+pub use rollback::rollback_tspec;
+pub use set::{set_from_file, set_value};
 pub use show::show_tspec;
+pub use toggle::toggle_value;
+pub use tree::tree_tspec;
 pub use unset::unset_value;
+pub use validate::validate_tspec;
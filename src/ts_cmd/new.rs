@@ -4,55 +4,100 @@ use anyhow::Result;
 use std::path::Path;
 
 use crate::TSPEC_SUFFIX;
-use crate::find_paths::{find_package_dir, find_tspec, resolve_package_dir};
-use crate::tspec::save_spec;
+use crate::find_paths::{find_package_dir, find_tspec, resolve_ts_package_dir};
+use crate::journal::Journal;
+use crate::tspec::serialize_spec;
 use crate::types::Spec;
+use crate::workspace::WorkspaceInfo;
+
+/// Starter content for a new tspec when neither `--from` nor `--empty` is
+/// given: a few of the most commonly-set fields, commented out, so `ts new`
+/// produces something worth reading instead of a blank file.
+const TEMPLATED_DEFAULT: &str = "\
+# panic = \"abort\"
+# strip = \"symbols\"
+
+[cargo]
+# profile = \"release\"
+# target_triple = \"x86_64-unknown-linux-musl\"
+
+[linker]
+# args = [\"-static\"]
+";
 
 /// Create a new tspec file (public entry point)
+///
+/// `all`: create it in every workspace member instead of a single
+/// package. All targets are checked for conflicts up front and written
+/// through a [`Journal`], so a failure partway through a multi-member
+/// create rolls back rather than leaving some members with the new spec
+/// and others without it.
 pub fn new_tspec(
     project_root: &Path,
     package: Option<&str>,
     name: &str,
     from: Option<&str>,
+    empty: bool,
+    all: bool,
 ) -> Result<()> {
     let workspace = project_root;
-    let package_dir = resolve_package_dir(workspace, package)?;
-    let output_path = package_dir.join(format!("{}{}", name, TSPEC_SUFFIX));
-
-    // Check if file already exists
-    if output_path.exists() {
-        anyhow::bail!(
-            "tspec '{}' already exists. Use a different name or delete the existing file.",
-            output_path.file_name().unwrap().to_string_lossy(),
-        );
-    }
 
-    match from {
+    let package_dirs: Vec<(String, std::path::PathBuf)> = if all {
+        let info = WorkspaceInfo::discover(workspace)?;
+        info.members
+            .iter()
+            .map(|m| (m.name.clone(), m.path.clone()))
+            .collect()
+    } else {
+        let package_dir = resolve_ts_package_dir(workspace, package)?;
+        let pkg_name = package.unwrap_or("current package").to_string();
+        vec![(pkg_name, package_dir)]
+    };
+
+    // Resolve --from once against the current package dir before
+    // fanning out, so "package/spec" and bare "spec" both still mean
+    // "relative to the invoking package", not each target in turn.
+    let source_path = match from {
         Some(source) => {
-            // --from: raw file copy to preserve comments/formatting
-            let source_path = resolve_source_spec(workspace, &package_dir, source)?;
-            std::fs::copy(&source_path, &output_path).map_err(|e| {
-                anyhow::anyhow!(
-                    "failed to copy {} to {}: {}",
-                    source_path.display(),
-                    output_path.display(),
-                    e
-                )
-            })?;
+            let current_dir = resolve_ts_package_dir(workspace, package)?;
+            Some(resolve_source_spec(workspace, &current_dir, source)?)
         }
-        None => {
-            // No source: create default empty spec via serde
-            save_spec(&Spec::default(), &output_path)?;
+        None => None,
+    };
+    let contents = match &source_path {
+        Some(path) => std::fs::read(path)
+            .map_err(|e| anyhow::anyhow!("failed to read {}: {}", path.display(), e))?,
+        None if empty => serialize_spec(&Spec::default())?.into_bytes(),
+        None => TEMPLATED_DEFAULT.as_bytes().to_vec(),
+    };
+
+    let mut targets = Vec::with_capacity(package_dirs.len());
+    for (_, package_dir) in &package_dirs {
+        let output_path = package_dir.join(format!("{}{}", name, TSPEC_SUFFIX));
+        if output_path.exists() {
+            anyhow::bail!(
+                "tspec '{}' already exists. Use a different name or delete the existing file.",
+                output_path.display(),
+            );
         }
+        targets.push(output_path);
     }
 
-    println!(
-        "Created {}",
-        output_path
-            .strip_prefix(workspace)
-            .unwrap_or(&output_path)
-            .display()
-    );
+    let mut journal = Journal::begin(workspace)?;
+    for output_path in &targets {
+        journal.write(output_path, &contents)?;
+    }
+    journal.commit()?;
+
+    for output_path in &targets {
+        println!(
+            "Created {}",
+            output_path
+                .strip_prefix(workspace)
+                .unwrap_or(output_path)
+                .display()
+        );
+    }
 
     Ok(())
 }
@@ -90,7 +135,7 @@ fn resolve_source_spec(
 mod tests {
     use super::*;
     use crate::test_constants::SUFFIX;
-    use crate::tspec::load_spec;
+    use crate::tspec::{load_spec, save_spec};
     use tempfile::TempDir;
 
     #[test]
@@ -107,6 +152,18 @@ mod tests {
         assert_eq!(spec, Spec::default());
     }
 
+    #[test]
+    fn templated_default_parses_as_empty_spec() {
+        let dir = TempDir::new().unwrap();
+        let output_path = dir.path().join(format!("test{}", SUFFIX));
+        std::fs::write(&output_path, TEMPLATED_DEFAULT).unwrap();
+
+        // Every field in the template is commented out, so it should load
+        // the same as a completely empty spec.
+        let spec = load_spec(&output_path).unwrap();
+        assert_eq!(spec, Spec::default());
+    }
+
     #[test]
     fn create_tspec_from_source_preserves_bytes() {
         let dir = TempDir::new().unwrap();
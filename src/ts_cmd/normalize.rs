@@ -0,0 +1,249 @@
+//! `tspec ts normalize` - Canonicalize a spec file's section/key ordering
+//! and whitespace, preserving comments (leveraging toml_edit's decor).
+
+use anyhow::{Context, Result};
+use toml_edit::DocumentMut;
+
+use crate::TSPEC_SUFFIX;
+use crate::find_paths::{current_package_name, find_tspec, resolve_package_dir};
+
+use super::list::find_tspec_files;
+
+/// Canonical top-level key order. Keys not in this list (shouldn't normally
+/// happen — `edit::FIELD_REGISTRY` is the source of truth for valid keys —
+/// but a hand-edited file could have stray ones) sort after all known keys,
+/// in their original relative order.
+const TOP_LEVEL_ORDER: &[&str] = &["panic", "strip", "rustflags", "cargo", "rustc", "linker"];
+
+/// Sections whose own keys get sorted alphabetically.
+const SORTED_SECTIONS: &[&str] = &["cargo", "rustc", "linker"];
+
+fn top_level_rank(key: &str) -> usize {
+    TOP_LEVEL_ORDER
+        .iter()
+        .position(|&k| k == key)
+        .unwrap_or(TOP_LEVEL_ORDER.len())
+}
+
+/// Rewrite a spec's TOML text into canonical layout. Comments stay attached
+/// to the key/value pair they precede (or follow, on the same line) because
+/// toml_edit's sort keeps each item's decor with it.
+fn normalize_text(content: &str) -> Result<String> {
+    let mut doc: DocumentMut = content.parse().context("failed to parse spec as TOML")?;
+
+    doc.as_table_mut()
+        .sort_values_by(|k1, _, k2, _| top_level_rank(k1.get()).cmp(&top_level_rank(k2.get())));
+
+    // `sort_values_by` only reorders the root table's own item map; it does
+    // not relocate `[section]` header blocks in the rendered output, since
+    // those are rendered in `doc_position` order. Re-stamp each top-level
+    // table's position to match the just-established item order so the
+    // headers actually move.
+    let top_level_keys: Vec<String> = doc.as_table().iter().map(|(k, _)| k.to_string()).collect();
+    let mut position = 1usize;
+    for key in top_level_keys {
+        if let Some(table) = doc.get_mut(&key).and_then(|i| i.as_table_mut()) {
+            table.set_position(position);
+            position += 1;
+        }
+    }
+
+    for section in SORTED_SECTIONS {
+        if let Some(table) = doc.get_mut(section).and_then(|i| i.as_table_mut()) {
+            table.sort_values();
+        }
+    }
+
+    let raw = doc.to_string();
+    let trimmed_lines = raw
+        .lines()
+        .map(str::trim_end)
+        .collect::<Vec<_>>()
+        .join("\n");
+    let mut normalized = trimmed_lines.trim_end().to_string();
+    normalized.push('\n');
+    Ok(normalized)
+}
+
+/// Normalize one tspec file in place, or just report whether it's already
+/// normalized when `check` is true (no write). Returns `true` when the file
+/// was (or already is) normalized.
+fn normalize_file(path: &std::path::Path, check: bool) -> Result<bool> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read: {}", path.display()))?;
+    let normalized = normalize_text(&content)?;
+
+    if normalized == content {
+        println!("{} already normalized", path.display());
+        return Ok(true);
+    }
+
+    if check {
+        println!("{} is not normalized", path.display());
+        return Ok(false);
+    }
+
+    std::fs::write(path, &normalized)
+        .with_context(|| format!("failed to write: {}", path.display()))?;
+    println!("Normalized {}", path.display());
+    Ok(true)
+}
+
+/// Normalize tspec(s) for a package (or all workspace packages with `-w`).
+///
+/// Returns `true` when every processed file is normalized (always true
+/// unless `check` is set and at least one file needed changes).
+pub fn normalize_tspec(
+    project_root: &std::path::Path,
+    package: Option<&str>,
+    all: bool,
+    tspec: Option<&str>,
+    check: bool,
+) -> Result<bool> {
+    let workspace = project_root;
+    let cwd_package = current_package_name(project_root);
+
+    let mut package_dirs = Vec::new();
+    if let Some(name) = package {
+        package_dirs.push(resolve_package_dir(workspace, Some(name))?);
+    } else if all || cwd_package.is_none() {
+        let info = crate::workspace::WorkspaceInfo::discover(project_root)?;
+        for member in &info.members {
+            package_dirs.push(member.path.clone());
+        }
+    } else if let Some(pkg_name) = cwd_package {
+        package_dirs.push(resolve_package_dir(workspace, Some(&pkg_name))?);
+    }
+
+    let mut all_normalized = true;
+    for package_dir in &package_dirs {
+        let paths = match tspec {
+            Some(name) => match find_tspec(package_dir, Some(name))? {
+                Some(p) => vec![p],
+                None => anyhow::bail!("tspec '{}' not found in {}", name, package_dir.display()),
+            },
+            None => find_tspec_files(package_dir)?
+                .into_iter()
+                .map(|name| package_dir.join(name))
+                .collect(),
+        };
+
+        if paths.is_empty() {
+            println!(
+                "No *{} files found in {}",
+                TSPEC_SUFFIX,
+                package_dir.display()
+            );
+            continue;
+        }
+
+        for path in &paths {
+            if !normalize_file(path, check)? {
+                all_normalized = false;
+            }
+        }
+    }
+
+    Ok(all_normalized)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    #[test]
+    fn reorders_top_level_sections() {
+        let input = "panic = \"abort\"\n\n[linker]\nargs = [\"-static\"]\n\n[cargo]\nprofile = \"release\"\n";
+        let output = normalize_text(input).unwrap();
+        let panic_pos = output.find("panic").unwrap();
+        let cargo_pos = output.find("[cargo]").unwrap();
+        let linker_pos = output.find("[linker]").unwrap();
+        assert!(panic_pos < cargo_pos);
+        assert!(cargo_pos < linker_pos);
+    }
+
+    #[test]
+    fn sorts_keys_within_a_section() {
+        let input = "[cargo]\ntarget_triple = \"x\"\nprofile = \"release\"\n";
+        let output = normalize_text(input).unwrap();
+        let profile_pos = output.find("profile").unwrap();
+        let triple_pos = output.find("target_triple").unwrap();
+        assert!(profile_pos < triple_pos);
+    }
+
+    #[test]
+    fn preserves_comment_before_key_across_reorder() {
+        let input = "# keep panics loud in debug\npanic = \"abort\"\n\n[linker]\nargs = [\"-static\"]\n\n[cargo]\nprofile = \"release\"\n";
+        let output = normalize_text(input).unwrap();
+        let comment_pos = output.find("# keep panics loud in debug").unwrap();
+        let panic_pos = output.find("panic = ").unwrap();
+        let cargo_pos = output.find("[cargo]").unwrap();
+        let linker_pos = output.find("[linker]").unwrap();
+        assert!(comment_pos < panic_pos);
+        assert!(panic_pos < cargo_pos);
+        assert!(
+            cargo_pos < linker_pos,
+            "cargo should now sort before linker, comment intact on panic"
+        );
+    }
+
+    #[test]
+    fn preserves_inline_comment_on_same_line() {
+        let input = "panic = \"abort\" # loud failures\n";
+        let output = normalize_text(input).unwrap();
+        assert!(output.contains("panic = \"abort\" # loud failures"));
+    }
+
+    #[test]
+    fn preserves_trailing_comment_at_eof() {
+        let input = "panic = \"abort\"\n# end of file note\n";
+        let output = normalize_text(input).unwrap();
+        assert!(output.ends_with("# end of file note\n"));
+    }
+
+    #[test]
+    fn trims_trailing_whitespace_and_ensures_single_final_newline() {
+        let input = "panic = \"abort\"   \n\n\n";
+        let output = normalize_text(input).unwrap();
+        assert!(!output.contains(' ') || !output.contains("   \n"));
+        assert!(output.ends_with('\n'));
+        assert!(!output.ends_with("\n\n"));
+    }
+
+    #[test]
+    fn idempotent_on_already_normalized_input() {
+        let input = "panic = \"abort\"\n\n[cargo]\nprofile = \"release\"\n";
+        let once = normalize_text(input).unwrap();
+        let twice = normalize_text(&once).unwrap();
+        assert_eq!(once, twice);
+    }
+
+    #[test]
+    fn normalize_file_check_mode_does_not_write() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("tspec.ts.toml");
+        let input = "[cargo]\ntarget_triple = \"x\"\nprofile = \"release\"\n";
+        std::fs::write(&path, input).unwrap();
+
+        let already_normalized = normalize_file(&path, true).unwrap();
+        assert!(!already_normalized);
+        assert_eq!(std::fs::read_to_string(&path).unwrap(), input);
+    }
+
+    #[test]
+    fn normalize_file_writes_when_not_checking() {
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join("tspec.ts.toml");
+        std::fs::write(
+            &path,
+            "[cargo]\ntarget_triple = \"x\"\nprofile = \"release\"\n",
+        )
+        .unwrap();
+
+        let ok = normalize_file(&path, false).unwrap();
+        assert!(ok);
+        let content = std::fs::read_to_string(&path).unwrap();
+        assert!(content.find("profile").unwrap() < content.find("target_triple").unwrap());
+    }
+}
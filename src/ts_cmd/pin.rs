@@ -0,0 +1,134 @@
+//! `tspec ts pin` - Write the resolved tspec's hash into Cargo.toml metadata
+
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use toml_edit::{DocumentMut, Item, Table};
+
+use crate::find_paths::{find_tspec, resolve_ts_package_dir};
+use crate::tspec::{hash_spec, load_spec};
+
+/// Pin the current resolved spec's hash into `[package.metadata.tspec]`.
+pub fn pin_tspec(project_root: &Path, package: Option<&str>, tspec: Option<&str>) -> Result<()> {
+    let workspace = project_root;
+    let package_dir = resolve_ts_package_dir(workspace, package)?;
+
+    let spec_path = match find_tspec(&package_dir, tspec)? {
+        Some(path) => path,
+        None => bail!("no tspec found to pin"),
+    };
+    let spec = load_spec(&spec_path)?;
+    let hash = hash_spec(&spec)?;
+
+    let manifest_path = package_dir.join("Cargo.toml");
+    let content = std::fs::read_to_string(&manifest_path)
+        .with_context(|| format!("failed to read {}", manifest_path.display()))?;
+    let mut doc: DocumentMut = content
+        .parse()
+        .with_context(|| format!("failed to parse {}", manifest_path.display()))?;
+
+    if doc.get("package").is_none() {
+        doc["package"] = Item::Table(Table::new());
+    }
+    if doc["package"].get("metadata").is_none() {
+        doc["package"]["metadata"] = Item::Table(Table::new());
+    }
+    if doc["package"]["metadata"].get("tspec").is_none() {
+        doc["package"]["metadata"]["tspec"] = Item::Table(Table::new());
+    }
+    doc["package"]["metadata"]["tspec"]["spec_hash"] = toml_edit::value(hash.clone());
+
+    std::fs::write(&manifest_path, doc.to_string())
+        .with_context(|| format!("failed to write {}", manifest_path.display()))?;
+
+    println!(
+        "Pinned {} to {} ({})",
+        manifest_path
+            .strip_prefix(workspace)
+            .unwrap_or(&manifest_path)
+            .display(),
+        hash,
+        spec_path.file_name().unwrap_or_default().to_string_lossy()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::metadata::read_tspec_metadata;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, name: &str, content: &str) {
+        std::fs::write(dir.join(name), content).unwrap();
+    }
+
+    #[test]
+    fn pin_tspec_writes_hash_into_fresh_metadata_table() {
+        let tmp = TempDir::new().unwrap();
+        write(
+            tmp.path(),
+            "Cargo.toml",
+            "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\n",
+        );
+        write(tmp.path(), "tspec.ts.toml", "panic = \"abort\"\n");
+
+        pin_tspec(tmp.path(), Some(tmp.path().to_str().unwrap()), None).unwrap();
+
+        let metadata = read_tspec_metadata(tmp.path()).unwrap();
+        assert!(metadata.spec_hash.is_some());
+
+        // Existing fields stay intact.
+        let content = std::fs::read_to_string(tmp.path().join("Cargo.toml")).unwrap();
+        assert!(content.contains("name = \"pkg\""));
+    }
+
+    #[test]
+    fn pin_tspec_overwrites_stale_hash() {
+        let tmp = TempDir::new().unwrap();
+        write(
+            tmp.path(),
+            "Cargo.toml",
+            "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\n\n\
+             [package.metadata.tspec]\nspec_hash = \"deadbeef\"\n",
+        );
+        write(tmp.path(), "tspec.ts.toml", "panic = \"abort\"\n");
+
+        pin_tspec(tmp.path(), Some(tmp.path().to_str().unwrap()), None).unwrap();
+
+        let metadata = read_tspec_metadata(tmp.path()).unwrap();
+        assert_ne!(metadata.spec_hash.as_deref(), Some("deadbeef"));
+    }
+
+    #[test]
+    fn pin_tspec_preserves_default_spec_field() {
+        let tmp = TempDir::new().unwrap();
+        write(
+            tmp.path(),
+            "Cargo.toml",
+            "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\n\n\
+             [package.metadata.tspec]\ndefault_spec = \"tspec\"\n",
+        );
+        write(tmp.path(), "tspec.ts.toml", "panic = \"abort\"\n");
+
+        pin_tspec(tmp.path(), Some(tmp.path().to_str().unwrap()), None).unwrap();
+
+        let metadata = read_tspec_metadata(tmp.path()).unwrap();
+        assert_eq!(metadata.default_spec.as_deref(), Some("tspec"));
+        assert!(metadata.spec_hash.is_some());
+    }
+
+    #[test]
+    fn pin_tspec_no_spec_errors() {
+        let tmp = TempDir::new().unwrap();
+        write(
+            tmp.path(),
+            "Cargo.toml",
+            "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\n",
+        );
+
+        let result = pin_tspec(tmp.path(), Some(tmp.path().to_str().unwrap()), None);
+        assert!(result.is_err());
+        assert!(result.unwrap_err().to_string().contains("no tspec found"));
+    }
+}
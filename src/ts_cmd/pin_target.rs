@@ -0,0 +1,178 @@
+//! `tspec ts pin-target` - Write the resolved target JSON's hash into the spec
+
+use anyhow::{Context, Result, bail};
+use std::path::Path;
+use toml_edit::DocumentMut;
+
+use super::edit::{self, FieldKind};
+use crate::cargo_build::resolve_target_json_path;
+use crate::find_paths::{find_tspec, resolve_ts_package_dir};
+use crate::tspec::{hash_file_sha256, load_spec};
+
+/// Compute the current hash of a spec's `cargo.target_json` file and write
+/// it into `cargo.target_json_hash`, the same way `tspec ts pin` refreshes
+/// `spec_hash` in Cargo.toml — except this pin lives in the spec file
+/// itself (via `toml_edit`, preserving comments/formatting) since it's a
+/// spec field, not package metadata.
+pub fn pin_target(project_root: &Path, package: Option<&str>, tspec: Option<&str>) -> Result<()> {
+    let workspace = project_root;
+    let package_dir = resolve_ts_package_dir(workspace, package)?;
+
+    let spec_path = match find_tspec(&package_dir, tspec)? {
+        Some(path) => path,
+        None => bail!("no tspec found to pin-target"),
+    };
+    let spec = load_spec(&spec_path)?;
+    let Some(target_json_path) = resolve_target_json_path(&spec, workspace) else {
+        bail!(
+            "{} has no cargo.target_json set — nothing to pin",
+            spec_path.display()
+        );
+    };
+    let hash = hash_file_sha256(&target_json_path)?;
+
+    let content = std::fs::read_to_string(&spec_path)
+        .with_context(|| format!("failed to read: {}", spec_path.display()))?;
+    let mut doc: DocumentMut = content
+        .parse()
+        .with_context(|| format!("failed to parse: {}", spec_path.display()))?;
+    edit::set_field(
+        &mut doc,
+        "cargo.target_json_hash",
+        std::slice::from_ref(&hash),
+        FieldKind::Scalar,
+    )?;
+    std::fs::write(&spec_path, doc.to_string())
+        .with_context(|| format!("failed to write: {}", spec_path.display()))?;
+
+    println!(
+        "Pinned {} to {} ({})",
+        spec_path
+            .strip_prefix(workspace)
+            .unwrap_or(&spec_path)
+            .display(),
+        hash,
+        target_json_path
+            .strip_prefix(workspace)
+            .unwrap_or(&target_json_path)
+            .display()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tspec::load_spec as load_spec_for_test;
+    use tempfile::TempDir;
+
+    fn write(dir: &Path, name: &str, content: &str) {
+        std::fs::write(dir.join(name), content).unwrap();
+    }
+
+    #[test]
+    fn pin_target_writes_hash_into_spec() {
+        let tmp = TempDir::new().unwrap();
+        write(
+            tmp.path(),
+            "Cargo.toml",
+            "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\n",
+        );
+        write(tmp.path(), "custom.json", "{\"arch\": \"x86_64\"}");
+        write(
+            tmp.path(),
+            "tspec.ts.toml",
+            "[cargo]\ntarget_json = \"custom.json\"\n",
+        );
+
+        pin_target(tmp.path(), Some(tmp.path().to_str().unwrap()), None).unwrap();
+
+        let spec = load_spec_for_test(&tmp.path().join("tspec.ts.toml")).unwrap();
+        let hash = spec.cargo.target_json_hash.unwrap();
+        assert!(hash.starts_with("sha256:"));
+    }
+
+    #[test]
+    fn pin_target_overwrites_stale_hash() {
+        let tmp = TempDir::new().unwrap();
+        write(
+            tmp.path(),
+            "Cargo.toml",
+            "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\n",
+        );
+        write(tmp.path(), "custom.json", "{\"arch\": \"x86_64\"}");
+        write(
+            tmp.path(),
+            "tspec.ts.toml",
+            "[cargo]\ntarget_json = \"custom.json\"\ntarget_json_hash = \"sha256:deadbeef\"\n",
+        );
+
+        pin_target(tmp.path(), Some(tmp.path().to_str().unwrap()), None).unwrap();
+
+        let spec = load_spec_for_test(&tmp.path().join("tspec.ts.toml")).unwrap();
+        assert_ne!(
+            spec.cargo.target_json_hash.as_deref(),
+            Some("sha256:deadbeef")
+        );
+    }
+
+    #[test]
+    fn pin_target_preserves_comments() {
+        let tmp = TempDir::new().unwrap();
+        write(
+            tmp.path(),
+            "Cargo.toml",
+            "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\n",
+        );
+        write(tmp.path(), "custom.json", "{}");
+        write(
+            tmp.path(),
+            "tspec.ts.toml",
+            "# my custom target\n[cargo]\ntarget_json = \"custom.json\"\n",
+        );
+
+        pin_target(tmp.path(), Some(tmp.path().to_str().unwrap()), None).unwrap();
+
+        let content = std::fs::read_to_string(tmp.path().join("tspec.ts.toml")).unwrap();
+        assert!(content.contains("# my custom target"));
+    }
+
+    #[test]
+    fn pin_target_no_target_json_errors() {
+        let tmp = TempDir::new().unwrap();
+        write(
+            tmp.path(),
+            "Cargo.toml",
+            "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\n",
+        );
+        write(tmp.path(), "tspec.ts.toml", "");
+
+        let result = pin_target(tmp.path(), Some(tmp.path().to_str().unwrap()), None);
+        assert!(result.is_err());
+        assert!(
+            result
+                .unwrap_err()
+                .to_string()
+                .contains("no cargo.target_json")
+        );
+    }
+
+    #[test]
+    fn pin_target_missing_file_errors() {
+        let tmp = TempDir::new().unwrap();
+        write(
+            tmp.path(),
+            "Cargo.toml",
+            "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\n",
+        );
+        write(
+            tmp.path(),
+            "tspec.ts.toml",
+            "[cargo]\ntarget_json = \"missing.json\"\n",
+        );
+
+        let result = pin_target(tmp.path(), Some(tmp.path().to_str().unwrap()), None);
+        assert!(result.is_err());
+    }
+}
@@ -5,19 +5,20 @@ use std::path::Path;
 use toml_edit::DocumentMut;
 
 use super::edit::{self, FieldKind};
-use crate::find_paths::{find_tspec, resolve_package_dir};
+use crate::find_paths::{find_tspec, resolve_ts_package_dir};
 
-/// Remove items from an array field in a tspec (by value or by index)
+/// Remove items from an array field in a tspec (by value, by index, or --all to empty it)
 pub fn remove_value(
     project_root: &Path,
     package: Option<&str>,
     key: &str,
     values: &[String],
     index: Option<usize>,
+    all: bool,
     tspec: Option<&str>,
 ) -> Result<()> {
     let workspace = project_root;
-    let package_dir = resolve_package_dir(workspace, package)?;
+    let package_dir = resolve_ts_package_dir(workspace, package)?;
 
     let output_path = match find_tspec(&package_dir, tspec)? {
         Some(path) => path,
@@ -33,14 +34,19 @@ pub fn remove_value(
         );
     }
 
-    // Validate: either index or values, not both, not neither
-    if index.is_some() && !values.is_empty() {
+    // Validate: exactly one of --all, --index, or values
+    if all && (index.is_some() || !values.is_empty()) {
+        bail!("cannot use --all together with --index or values");
+    }
+    if !all && index.is_some() && !values.is_empty() {
         bail!(
             "cannot use both --index and values; use --index to remove by position, or provide values to remove by value"
         );
     }
-    if index.is_none() && values.is_empty() {
-        bail!("provide values to remove, or use --index to remove by position");
+    if !all && index.is_none() && values.is_empty() {
+        bail!(
+            "provide values to remove, use --index to remove by position, or use --all to empty the array"
+        );
     }
 
     // Read, parse, edit, write
@@ -51,7 +57,9 @@ pub fn remove_value(
         .parse()
         .with_context(|| format!("failed to parse: {}", output_path.display()))?;
 
-    if let Some(idx) = index {
+    if all {
+        edit::clear_array(&mut doc, key)?;
+    } else if let Some(idx) = index {
         edit::remove_item_by_index(&mut doc, key, idx)?;
     } else {
         edit::remove_items_by_value(&mut doc, key, values)?;
@@ -59,6 +67,14 @@ pub fn remove_value(
 
     std::fs::write(&output_path, doc.to_string())
         .with_context(|| format!("failed to write: {}", output_path.display()))?;
+    let value_summary = if all {
+        "--all".to_string()
+    } else if let Some(idx) = index {
+        format!("index {idx}")
+    } else {
+        values.join(", ")
+    };
+    crate::audit::record("remove", key, &value_summary, &output_path);
 
     println!(
         "Saved {}",
@@ -158,4 +174,19 @@ mod tests {
         let spec = load_spec(&path).unwrap();
         assert_eq!(spec.linker.args, vec!["-static".to_string()]);
     }
+
+    #[test]
+    fn remove_all_empties_array() {
+        let input = "[linker]\nargs = [\"-static\", \"-nostdlib\"]\n";
+        let dir = TempDir::new().unwrap();
+        let path = dir.path().join(format!("tspec{}", SUFFIX));
+        std::fs::write(&path, input).unwrap();
+
+        let mut doc: DocumentMut = input.parse().unwrap();
+        edit::clear_array(&mut doc, "linker.args").unwrap();
+        std::fs::write(&path, doc.to_string()).unwrap();
+
+        let spec = load_spec(&path).unwrap();
+        assert!(spec.linker.args.is_empty());
+    }
 }
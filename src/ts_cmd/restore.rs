@@ -4,12 +4,12 @@ use anyhow::{Context, Result, bail};
 use std::path::Path;
 
 use crate::TSPEC_SUFFIX;
-use crate::find_paths::{find_tspec, resolve_package_dir};
+use crate::find_paths::{find_tspec, resolve_ts_package_dir};
 
 /// Restore a tspec from a versioned backup to its base name
 pub fn restore_tspec(project_root: &Path, package: Option<&str>, tspec: &str) -> Result<()> {
     let workspace = project_root;
-    let package_dir = resolve_package_dir(workspace, package)?;
+    let package_dir = resolve_ts_package_dir(workspace, package)?;
 
     let backup_path = match find_tspec(&package_dir, Some(tspec))? {
         Some(path) => path,
@@ -0,0 +1,27 @@
+//! `tspec ts rollback` - Undo an incomplete multi-file tspec operation
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::journal::rollback_pending;
+
+/// Roll back an incomplete journal left behind by a process that died
+/// mid-write (e.g. `ts new --from -w` killed partway through).
+pub fn rollback_tspec(project_root: &Path) -> Result<()> {
+    match rollback_pending(project_root)? {
+        None => println!("No incomplete tspec operation found."),
+        Some(report) => {
+            for path in &report.restored {
+                println!("Restored {}", path.display());
+            }
+            for path in &report.removed {
+                println!("Removed {}", path.display());
+            }
+            println!(
+                "Rolled back {} file(s).",
+                report.restored.len() + report.removed.len()
+            );
+        }
+    }
+    Ok(())
+}
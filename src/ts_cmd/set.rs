@@ -5,18 +5,224 @@ use std::path::Path;
 use toml_edit::DocumentMut;
 
 use super::edit::{self, FieldKind};
-use crate::find_paths::{find_tspec, resolve_package_dir};
+use crate::cargo_build::check_spec_misconfigurations;
+use crate::find_paths::{find_tspec, find_tspecs, get_package_name, resolve_ts_package_dir};
+use crate::tspec::load_spec;
+
+/// When `tspec` is `None` (no `-t` given) and more than one spec matches
+/// the default `tspec*{TSPEC_SUFFIX}` glob, `find_tspec`'s single default
+/// (`tspec{TSPEC_SUFFIX}`) is a silent guess about which one the user
+/// meant. Require `-t <name>` to disambiguate, or `--yes` to confirm the
+/// default is really the intended target.
+fn guard_ambiguous_default_spec(pkg_dir: &Path, tspec: Option<&str>, yes: bool) -> Result<()> {
+    if tspec.is_some() || yes {
+        return Ok(());
+    }
+    let matches = find_tspecs(pkg_dir, &[]).unwrap_or_default();
+    if matches.len() <= 1 {
+        return Ok(());
+    }
+    let names: Vec<String> = matches
+        .iter()
+        .filter_map(|p| p.file_name())
+        .map(|n| n.to_string_lossy().to_string())
+        .collect();
+    anyhow::bail!(
+        "multiple tspecs found ({}); pass -t <name> to pick one, or --yes to edit the default \
+         (tspec{}) anyway",
+        names.join(", "),
+        crate::TSPEC_SUFFIX
+    );
+}
+
+/// Reload the just-written spec and print any misconfiguration warnings
+/// (e.g. an uninstalled `target_triple`), same checks as at build time.
+fn warn_on_misconfiguration(output_path: &Path, package_dir: &Path, project_root: &Path) {
+    let Ok(pkg_name) = get_package_name(package_dir) else {
+        return;
+    };
+    let Ok(spec) = load_spec(output_path) else {
+        return;
+    };
+    for warning in check_spec_misconfigurations(&pkg_name, &spec, package_dir, project_root, false)
+    {
+        eprintln!("{warning}");
+    }
+}
+
+/// One parsed line from a `--from-file` batch: `key = value...` or `key += value...`.
+struct Assignment {
+    key: String,
+    append: bool,
+    values: Vec<String>,
+}
+
+/// Parse one non-blank, non-comment line of a `--from-file` batch.
+fn parse_assignment_line(line: &str) -> Result<Assignment> {
+    let (key_part, append, rest) = if let Some(idx) = line.find("+=") {
+        (&line[..idx], true, &line[idx + 2..])
+    } else if let Some(idx) = line.find('=') {
+        (&line[..idx], false, &line[idx + 1..])
+    } else {
+        anyhow::bail!("expected 'key = value' or 'key += value'");
+    };
+
+    let key = key_part.trim().to_string();
+    if key.is_empty() {
+        anyhow::bail!("missing key");
+    }
+
+    let values: Vec<String> = rest.split_whitespace().map(str::to_string).collect();
+    if values.is_empty() {
+        anyhow::bail!("missing value for key '{key}'");
+    }
+
+    Ok(Assignment {
+        key,
+        append,
+        values,
+    })
+}
+
+/// Apply one assignment to an already-open document, validating as `ts set`/`ts add` would.
+fn apply_assignment(doc: &mut DocumentMut, assignment: &Assignment) -> Result<()> {
+    let Assignment {
+        key,
+        append,
+        values,
+    } = assignment;
+
+    let kind = edit::validate_key(key)?;
+
+    if *append {
+        if kind != FieldKind::Array {
+            anyhow::bail!("'+=' only works on array fields, but '{key}' is not an array field");
+        }
+        return edit::add_items(doc, key, values, None);
+    }
+
+    if kind == FieldKind::Table {
+        let Some((table_path, sub_key)) = edit::parse_table_key(key) else {
+            anyhow::bail!("table field '{key}' requires a sub-key, e.g. {key}.\"key\"");
+        };
+        if values.len() != 1 {
+            anyhow::bail!(
+                "table sub-key '{key}' requires exactly one value, got {}",
+                values.len()
+            );
+        }
+        return edit::set_table_value(doc, table_path, sub_key, &values[0]);
+    }
+
+    if kind == FieldKind::Scalar {
+        if values.len() != 1 {
+            anyhow::bail!(
+                "scalar field '{key}' requires exactly one value, got {}",
+                values.len()
+            );
+        }
+        let normalized = edit::normalize_value(key, &values[0]);
+        edit::validate_value(key, &normalized)?;
+        return edit::set_field(doc, key, &[normalized], kind);
+    }
 
-/// Set a field in a tspec (scalar or replace entire array) and save in place
+    edit::set_field(doc, key, values, kind)
+}
+
+/// Apply a batch of `key = value` / `key += value` assignments from a file to one
+/// spec, in order, writing the result once at the end. Blank lines and lines
+/// starting with `#` are skipped. Errors report the offending file and line number.
+pub fn set_from_file(
+    project_root: &Path,
+    package: Option<&str>,
+    from_file: &Path,
+    tspec: Option<&str>,
+) -> Result<()> {
+    let workspace = project_root;
+    let package_dir = resolve_ts_package_dir(workspace, package)?;
+
+    let output_path = match find_tspec(&package_dir, tspec)? {
+        Some(path) => path,
+        None => {
+            let base_name = match tspec {
+                Some(t) => t
+                    .strip_suffix(crate::TSPEC_SUFFIX)
+                    .or_else(|| t.strip_suffix(".toml"))
+                    .unwrap_or(t),
+                None => "tspec",
+            };
+            package_dir.join(format!("{}{}", base_name, crate::TSPEC_SUFFIX))
+        }
+    };
+
+    let batch = std::fs::read_to_string(from_file)
+        .with_context(|| format!("failed to read: {}", from_file.display()))?;
+
+    let content = if output_path.exists() {
+        std::fs::read_to_string(&output_path)
+            .with_context(|| format!("failed to read: {}", output_path.display()))?
+    } else {
+        String::new()
+    };
+
+    let mut doc: DocumentMut = content
+        .parse()
+        .with_context(|| format!("failed to parse: {}", output_path.display()))?;
+
+    let mut applied = 0usize;
+    for (idx, line) in batch.lines().enumerate() {
+        let line_no = idx + 1;
+        let trimmed = line.trim();
+        if trimmed.is_empty() || trimmed.starts_with('#') {
+            continue;
+        }
+
+        let assignment = parse_assignment_line(trimmed)
+            .with_context(|| format!("{}:{line_no}: {trimmed}", from_file.display()))?;
+        apply_assignment(&mut doc, &assignment)
+            .with_context(|| format!("{}:{line_no}: {trimmed}", from_file.display()))?;
+        applied += 1;
+    }
+
+    std::fs::write(&output_path, doc.to_string())
+        .with_context(|| format!("failed to write: {}", output_path.display()))?;
+    crate::audit::record(
+        "set --from-file",
+        from_file.to_string_lossy().as_ref(),
+        &format!("{applied} assignment(s)"),
+        &output_path,
+    );
+    warn_on_misconfiguration(&output_path, &package_dir, project_root);
+
+    println!(
+        "Saved {} ({applied} assignment(s) applied)",
+        output_path
+            .strip_prefix(workspace)
+            .unwrap_or(&output_path)
+            .display()
+    );
+
+    Ok(())
+}
+
+/// Set a field in a tspec (scalar or replace entire array) and save in
+/// place. When `if_unset` is true, a field that already has an explicit
+/// value is left untouched and reported instead of overwritten. When no
+/// `-t` is given and multiple tspecs exist for the package, `yes` must be
+/// true to proceed against the default (see `guard_ambiguous_default_spec`).
 pub fn set_value(
     project_root: &Path,
     package: Option<&str>,
     key: &str,
     values: &[String],
     tspec: Option<&str>,
+    if_unset: bool,
+    yes: bool,
 ) -> Result<()> {
     let workspace = project_root;
-    let package_dir = resolve_package_dir(workspace, package)?;
+    let package_dir = resolve_ts_package_dir(workspace, package)?;
+
+    guard_ambiguous_default_spec(&package_dir, tspec, yes)?;
 
     // Resolve tspec path (existing or new)
     let output_path = match find_tspec(&package_dir, tspec)? {
@@ -36,6 +242,25 @@ pub fn set_value(
     // Validate key and value
     let kind = edit::validate_key(key)?;
 
+    // Read existing content or start empty
+    let content = if output_path.exists() {
+        std::fs::read_to_string(&output_path)
+            .with_context(|| format!("failed to read: {}", output_path.display()))?
+    } else {
+        String::new()
+    };
+
+    // Parse once; both the table and scalar/array branches below edit and
+    // write the same document.
+    let mut doc: DocumentMut = content
+        .parse()
+        .with_context(|| format!("failed to parse: {}", output_path.display()))?;
+
+    if if_unset && edit::field_is_set(&doc, key, kind) {
+        println!("Already set: {} (skipped, --if-unset given)", key);
+        return Ok(());
+    }
+
     // Handle Table fields with sub-keys
     if kind == FieldKind::Table {
         if let Some((table_path, sub_key)) = edit::parse_table_key(key) {
@@ -47,21 +272,11 @@ pub fn set_value(
                 );
             }
 
-            let content = if output_path.exists() {
-                std::fs::read_to_string(&output_path)
-                    .with_context(|| format!("failed to read: {}", output_path.display()))?
-            } else {
-                String::new()
-            };
-
-            let mut doc: DocumentMut = content
-                .parse()
-                .with_context(|| format!("failed to parse: {}", output_path.display()))?;
-
             edit::set_table_value(&mut doc, table_path, sub_key, &values[0])?;
 
             std::fs::write(&output_path, doc.to_string())
                 .with_context(|| format!("failed to write: {}", output_path.display()))?;
+            crate::audit::record("set", key, &values[0], &output_path);
 
             println!(
                 "Saved {}",
@@ -81,7 +296,7 @@ pub fn set_value(
     }
 
     // Validate enum constraints for scalar fields
-    if kind == FieldKind::Scalar {
+    let values: Vec<String> = if kind == FieldKind::Scalar {
         if values.len() != 1 {
             anyhow::bail!(
                 "scalar field '{}' requires exactly one value, got {}",
@@ -89,26 +304,20 @@ pub fn set_value(
                 values.len()
             );
         }
-        edit::validate_value(key, &values[0])?;
-    }
-
-    // Read existing content or start empty
-    let content = if output_path.exists() {
-        std::fs::read_to_string(&output_path)
-            .with_context(|| format!("failed to read: {}", output_path.display()))?
+        let normalized = edit::normalize_value(key, &values[0]);
+        edit::validate_value(key, &normalized)?;
+        vec![normalized]
     } else {
-        String::new()
+        values.to_vec()
     };
-
-    // Parse, edit, write
-    let mut doc: DocumentMut = content
-        .parse()
-        .with_context(|| format!("failed to parse: {}", output_path.display()))?;
+    let values = values.as_slice();
 
     edit::set_field(&mut doc, key, values, kind)?;
 
     std::fs::write(&output_path, doc.to_string())
         .with_context(|| format!("failed to write: {}", output_path.display()))?;
+    crate::audit::record("set", key, &values.join(", "), &output_path);
+    warn_on_misconfiguration(&output_path, &package_dir, project_root);
 
     println!(
         "Saved {}",
@@ -328,4 +537,243 @@ mod tests {
         let result = edit::parse_table_key("cargo.config");
         assert!(result.is_none());
     }
+
+    // --- --from-file batch tests ---
+
+    fn write_tspec_package(tspec_content: &str) -> (TempDir, std::path::PathBuf) {
+        let dir = TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\nedition = \"2024\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join(format!("tspec{}", SUFFIX)), tspec_content).unwrap();
+        let pkg_dir = dir.path().to_path_buf();
+        (dir, pkg_dir)
+    }
+
+    #[test]
+    fn parse_assignment_line_set() {
+        let a = super::parse_assignment_line("panic = abort").unwrap();
+        assert_eq!(a.key, "panic");
+        assert!(!a.append);
+        assert_eq!(a.values, vs(&["abort"]));
+    }
+
+    #[test]
+    fn parse_assignment_line_append() {
+        let a = super::parse_assignment_line("linker.args += -static").unwrap();
+        assert_eq!(a.key, "linker.args");
+        assert!(a.append);
+        assert_eq!(a.values, vs(&["-static"]));
+    }
+
+    #[test]
+    fn parse_assignment_line_missing_operator_errors() {
+        assert!(super::parse_assignment_line("panic abort").is_err());
+    }
+
+    #[test]
+    fn parse_assignment_line_missing_value_errors() {
+        assert!(super::parse_assignment_line("panic =").is_err());
+    }
+
+    #[test]
+    fn set_from_file_applies_multiple_assignments_in_order() {
+        let (dir, pkg_dir) = write_tspec_package("");
+        let batch_path = dir.path().join("edits.txt");
+        std::fs::write(
+            &batch_path,
+            "panic = abort\n\
+             cargo.profile = release\n\
+             linker.args = -static\n\
+             linker.args += -nostdlib\n",
+        )
+        .unwrap();
+
+        super::set_from_file(&pkg_dir, Some(pkg_dir.to_str().unwrap()), &batch_path, None).unwrap();
+
+        let spec = load_spec(&pkg_dir.join(format!("tspec{}", SUFFIX))).unwrap();
+        assert_eq!(spec.panic, Some(crate::options::PanicMode::Abort));
+        assert_eq!(spec.cargo.profile, Some("release".to_string()));
+        assert_eq!(
+            spec.linker.args,
+            vec!["-static".to_string(), "-nostdlib".to_string()]
+        );
+    }
+
+    #[test]
+    fn set_from_file_skips_blank_and_comment_lines() {
+        let (dir, pkg_dir) = write_tspec_package("");
+        let batch_path = dir.path().join("edits.txt");
+        std::fs::write(&batch_path, "\n# set panic mode\npanic = abort\n\n").unwrap();
+
+        super::set_from_file(&pkg_dir, Some(pkg_dir.to_str().unwrap()), &batch_path, None).unwrap();
+
+        let spec = load_spec(&pkg_dir.join(format!("tspec{}", SUFFIX))).unwrap();
+        assert_eq!(spec.panic, Some(crate::options::PanicMode::Abort));
+    }
+
+    #[test]
+    fn if_unset_sets_field_when_absent() {
+        let (_dir, pkg_dir) = write_tspec_package("");
+
+        super::set_value(
+            &pkg_dir,
+            Some(pkg_dir.to_str().unwrap()),
+            "panic",
+            &vs(&["abort"]),
+            None,
+            true,
+            false,
+        )
+        .unwrap();
+
+        let spec = load_spec(&pkg_dir.join(format!("tspec{}", SUFFIX))).unwrap();
+        assert_eq!(spec.panic, Some(crate::options::PanicMode::Abort));
+    }
+
+    #[test]
+    fn if_unset_skips_field_when_already_present() {
+        let (_dir, pkg_dir) = write_tspec_package("panic = \"unwind\"\n");
+
+        super::set_value(
+            &pkg_dir,
+            Some(pkg_dir.to_str().unwrap()),
+            "panic",
+            &vs(&["abort"]),
+            None,
+            true,
+            false,
+        )
+        .unwrap();
+
+        let spec = load_spec(&pkg_dir.join(format!("tspec{}", SUFFIX))).unwrap();
+        assert_eq!(spec.panic, Some(crate::options::PanicMode::Unwind));
+    }
+
+    #[test]
+    fn if_unset_skips_table_sub_key_when_already_present() {
+        let (_dir, pkg_dir) =
+            write_tspec_package("[cargo.config]\n\"profile.release.opt-level\" = \"s\"\n");
+
+        super::set_value(
+            &pkg_dir,
+            Some(pkg_dir.to_str().unwrap()),
+            "cargo.config.\"profile.release.opt-level\"",
+            &vs(&["z"]),
+            None,
+            true,
+            false,
+        )
+        .unwrap();
+
+        let spec = load_spec(&pkg_dir.join(format!("tspec{}", SUFFIX))).unwrap();
+        assert_eq!(
+            spec.cargo.config.get("profile.release.opt-level"),
+            Some(&crate::types::ConfigValue::String("s".to_string()))
+        );
+    }
+
+    #[test]
+    fn without_if_unset_overwrites_existing_field() {
+        let (_dir, pkg_dir) = write_tspec_package("panic = \"unwind\"\n");
+
+        super::set_value(
+            &pkg_dir,
+            Some(pkg_dir.to_str().unwrap()),
+            "panic",
+            &vs(&["abort"]),
+            None,
+            false,
+            false,
+        )
+        .unwrap();
+
+        let spec = load_spec(&pkg_dir.join(format!("tspec{}", SUFFIX))).unwrap();
+        assert_eq!(spec.panic, Some(crate::options::PanicMode::Abort));
+    }
+
+    #[test]
+    fn multiple_specs_without_tspec_arg_requires_yes() {
+        let (_dir, pkg_dir) = write_tspec_package("");
+        std::fs::write(
+            pkg_dir.join(format!("tspec-alt{}", SUFFIX)),
+            "panic = \"unwind\"\n",
+        )
+        .unwrap();
+
+        let err = super::set_value(
+            &pkg_dir,
+            Some(pkg_dir.to_str().unwrap()),
+            "panic",
+            &vs(&["abort"]),
+            None,
+            false,
+            false,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("multiple tspecs found"));
+    }
+
+    #[test]
+    fn multiple_specs_with_yes_edits_default() {
+        let (_dir, pkg_dir) = write_tspec_package("");
+        std::fs::write(
+            pkg_dir.join(format!("tspec-alt{}", SUFFIX)),
+            "panic = \"unwind\"\n",
+        )
+        .unwrap();
+
+        super::set_value(
+            &pkg_dir,
+            Some(pkg_dir.to_str().unwrap()),
+            "panic",
+            &vs(&["abort"]),
+            None,
+            false,
+            true,
+        )
+        .unwrap();
+
+        let spec = load_spec(&pkg_dir.join(format!("tspec{}", SUFFIX))).unwrap();
+        assert_eq!(spec.panic, Some(crate::options::PanicMode::Abort));
+    }
+
+    #[test]
+    fn multiple_specs_with_explicit_tspec_does_not_require_yes() {
+        let (_dir, pkg_dir) = write_tspec_package("");
+        std::fs::write(
+            pkg_dir.join(format!("tspec-alt{}", SUFFIX)),
+            "panic = \"unwind\"\n",
+        )
+        .unwrap();
+
+        super::set_value(
+            &pkg_dir,
+            Some(pkg_dir.to_str().unwrap()),
+            "panic",
+            &vs(&["abort"]),
+            Some("tspec-alt"),
+            false,
+            false,
+        )
+        .unwrap();
+
+        let spec = load_spec(&pkg_dir.join(format!("tspec-alt{}", SUFFIX))).unwrap();
+        assert_eq!(spec.panic, Some(crate::options::PanicMode::Abort));
+    }
+
+    #[test]
+    fn set_from_file_reports_line_number_on_error() {
+        let (dir, pkg_dir) = write_tspec_package("");
+        let batch_path = dir.path().join("edits.txt");
+        std::fs::write(&batch_path, "panic = abort\nnonexistent = value\n").unwrap();
+
+        let err =
+            super::set_from_file(&pkg_dir, Some(pkg_dir.to_str().unwrap()), &batch_path, None)
+                .unwrap_err();
+        let msg = format!("{err:#}");
+        assert!(msg.contains(":2:"), "expected line 2 in error: {msg}");
+    }
 }
@@ -4,7 +4,8 @@ use anyhow::Result;
 use std::path::Path;
 
 use crate::TSPEC_SUFFIX;
-use crate::find_paths::{find_tspec, get_package_name, resolve_package_dir};
+use crate::find_paths::{current_package_name, find_tspec, resolve_package_dir};
+use crate::tspec::load_spec_strict;
 
 use super::list::find_tspec_files;
 
@@ -17,18 +18,14 @@ pub fn show_tspec(
 ) -> Result<()> {
     let workspace = project_root;
 
-    // Check if we're in a package directory
-    let cwd = std::env::current_dir()?;
-    let in_package_dir = get_package_name(&cwd).is_ok();
-
-    // Resolve: --all > -p PKG > cwd > all
-    let show_all = all || (package.is_none() && !in_package_dir);
+    // Resolve: --all > -p PKG > cwd (relative to project_root) > all
+    let cwd_package = current_package_name(project_root);
 
     if let Some(name) = package {
         // Explicit package specified
         let package_dir = resolve_package_dir(workspace, Some(name))?;
         show_package_tspecs(&package_dir, name, tspec)?;
-    } else if show_all {
+    } else if all || cwd_package.is_none() {
         // Show all packages
         let info = crate::workspace::WorkspaceInfo::discover(project_root)?;
         for (i, member) in info.members.iter().enumerate() {
@@ -38,10 +35,10 @@ pub fn show_tspec(
             println!("=== {} ===", member.name);
             show_package_tspecs(&member.path, &member.name, tspec)?;
         }
-    } else {
+    } else if let Some(pkg_name) = cwd_package {
         // In a package directory
-        let pkg_name = get_package_name(&cwd)?;
-        show_package_tspecs(&cwd, &pkg_name, tspec)?;
+        let package_dir = resolve_package_dir(workspace, Some(&pkg_name))?;
+        show_package_tspecs(&package_dir, &pkg_name, tspec)?;
     }
 
     Ok(())
@@ -92,5 +89,8 @@ fn print_tspec_content(path: &Path) -> Result<()> {
     if !content.ends_with('\n') {
         println!();
     }
+    if let Err(e) = load_spec_strict(path) {
+        println!("warning: {e}");
+    }
     Ok(())
 }
@@ -0,0 +1,147 @@
+//! `tspec ts toggle` - Flip a boolean field's value without typing it out
+
+use anyhow::{Context, Result};
+use std::path::Path;
+use toml_edit::DocumentMut;
+
+use super::edit;
+use crate::find_paths::{find_tspec, resolve_ts_package_dir};
+
+/// Flip a boolean field (e.g. `cargo.hermetic_env`), defaulting to `true` if
+/// currently unset, and save in place. Errors on any field that isn't
+/// boolean-valued.
+pub fn toggle_value(
+    project_root: &Path,
+    package: Option<&str>,
+    key: &str,
+    tspec: Option<&str>,
+) -> Result<()> {
+    let workspace = project_root;
+    let package_dir = resolve_ts_package_dir(workspace, package)?;
+
+    let output_path = match find_tspec(&package_dir, tspec)? {
+        Some(path) => path,
+        None => {
+            let base_name = match tspec {
+                Some(t) => t
+                    .strip_suffix(crate::TSPEC_SUFFIX)
+                    .or_else(|| t.strip_suffix(".toml"))
+                    .unwrap_or(t),
+                None => "tspec",
+            };
+            package_dir.join(format!("{}{}", base_name, crate::TSPEC_SUFFIX))
+        }
+    };
+
+    // Validate up front so an unknown/non-boolean key errors before touching the file.
+    edit::validate_key(key)?;
+
+    let content = if output_path.exists() {
+        std::fs::read_to_string(&output_path)
+            .with_context(|| format!("failed to read: {}", output_path.display()))?
+    } else {
+        String::new()
+    };
+
+    let mut doc: DocumentMut = content
+        .parse()
+        .with_context(|| format!("failed to parse: {}", output_path.display()))?;
+
+    let new_value = edit::toggle_field(&mut doc, key)?;
+
+    std::fs::write(&output_path, doc.to_string())
+        .with_context(|| format!("failed to write: {}", output_path.display()))?;
+    crate::audit::record("toggle", key, &new_value.to_string(), &output_path);
+
+    println!(
+        "Saved {} ({key} = {new_value})",
+        output_path
+            .strip_prefix(workspace)
+            .unwrap_or(&output_path)
+            .display()
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::test_constants::SUFFIX;
+    use crate::tspec::load_spec;
+
+    fn write_tspec_package(tspec_content: &str) -> (tempfile::TempDir, std::path::PathBuf) {
+        let dir = tempfile::TempDir::new().unwrap();
+        std::fs::write(
+            dir.path().join("Cargo.toml"),
+            "[package]\nname = \"pkg\"\nversion = \"0.1.0\"\nedition = \"2024\"\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join(format!("tspec{}", SUFFIX)), tspec_content).unwrap();
+        let pkg_dir = dir.path().to_path_buf();
+        (dir, pkg_dir)
+    }
+
+    #[test]
+    fn toggle_unset_field_becomes_true() {
+        let (dir, pkg_dir) = write_tspec_package("");
+        super::toggle_value(
+            &pkg_dir,
+            Some(pkg_dir.to_str().unwrap()),
+            "cargo.hermetic_env",
+            None,
+        )
+        .unwrap();
+        let spec = load_spec(&pkg_dir.join(format!("tspec{}", SUFFIX))).unwrap();
+        assert!(spec.cargo.hermetic_env);
+        drop(dir);
+    }
+
+    #[test]
+    fn toggle_true_becomes_false() {
+        let (dir, pkg_dir) = write_tspec_package("[cargo]\nhermetic_env = true\n");
+        super::toggle_value(
+            &pkg_dir,
+            Some(pkg_dir.to_str().unwrap()),
+            "cargo.hermetic_env",
+            None,
+        )
+        .unwrap();
+        let spec = load_spec(&pkg_dir.join(format!("tspec{}", SUFFIX))).unwrap();
+        assert!(!spec.cargo.hermetic_env);
+        drop(dir);
+    }
+
+    #[test]
+    fn toggle_twice_round_trips() {
+        let (dir, pkg_dir) = write_tspec_package("");
+        let key = "cargo.hermetic_env";
+        super::toggle_value(&pkg_dir, Some(pkg_dir.to_str().unwrap()), key, None).unwrap();
+        super::toggle_value(&pkg_dir, Some(pkg_dir.to_str().unwrap()), key, None).unwrap();
+        let spec = load_spec(&pkg_dir.join(format!("tspec{}", SUFFIX))).unwrap();
+        assert!(!spec.cargo.hermetic_env);
+        drop(dir);
+    }
+
+    #[test]
+    fn toggle_rejects_non_boolean_field() {
+        let (dir, pkg_dir) = write_tspec_package("");
+        let err = super::toggle_value(&pkg_dir, Some(pkg_dir.to_str().unwrap()), "panic", None)
+            .unwrap_err();
+        assert!(err.to_string().contains("only works on boolean fields"));
+        drop(dir);
+    }
+
+    #[test]
+    fn toggle_rejects_unknown_field() {
+        let (dir, pkg_dir) = write_tspec_package("");
+        let err = super::toggle_value(
+            &pkg_dir,
+            Some(pkg_dir.to_str().unwrap()),
+            "nonexistent",
+            None,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("unknown key"));
+        drop(dir);
+    }
+}
@@ -0,0 +1,78 @@
+//! `tspec ts tree` - Visualize a tspec's resolution graph
+//!
+//! This tree has exactly one node today: specs in this codebase are flat
+//! TOML files with no `extends`/`include` mechanism, so there's no base or
+//! included fragment to walk and no cycle that could form. The command
+//! still exists now so there's a stable diagnostic to extend once
+//! inheritance lands — at that point this should walk the chain
+//! (child -> base -> grandbase, plus included fragments) and flag a cycle
+//! if a node reappears on its own ancestor path.
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::find_paths::{find_tspec, resolve_ts_package_dir};
+use crate::tspec::{hash_spec, load_spec};
+
+/// Print the resolution tree for one tspec (currently always a single node).
+pub fn tree_tspec(project_root: &Path, package: Option<&str>, tspec: Option<&str>) -> Result<()> {
+    let package_dir = resolve_ts_package_dir(project_root, package)?;
+
+    let path = match find_tspec(&package_dir, tspec)? {
+        Some(path) => path,
+        None => anyhow::bail!("no tspec found to show a tree for"),
+    };
+
+    let name = path
+        .file_name()
+        .map(|s| s.to_string_lossy().into_owned())
+        .unwrap_or_else(|| path.display().to_string());
+    let spec = load_spec(&path)?;
+    let hash = hash_spec(&spec)?;
+
+    println!("{name} ({hash})");
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_pkg(tmp: &TempDir, tspec_contents: &str) {
+        std::fs::write(
+            tmp.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            tmp.path().join(format!("tspec{}", crate::TSPEC_SUFFIX)),
+            tspec_contents,
+        )
+        .unwrap();
+    }
+
+    #[test]
+    fn prints_a_single_node_with_its_hash() {
+        let tmp = TempDir::new().unwrap();
+        write_pkg(&tmp, "panic = \"abort\"\n");
+
+        // No panic path to capture stdout here, so just confirm it resolves
+        // and hashes cleanly — the printed line is exercised by the CLI
+        // integration tests for `ts hash`, whose format this mirrors.
+        tree_tspec(tmp.path(), None, None).unwrap();
+    }
+
+    #[test]
+    fn errors_when_no_tspec_exists() {
+        let tmp = TempDir::new().unwrap();
+        std::fs::write(
+            tmp.path().join("Cargo.toml"),
+            "[package]\nname = \"app\"\nversion = \"0.1.0\"\n",
+        )
+        .unwrap();
+
+        let err = tree_tspec(tmp.path(), None, None).unwrap_err();
+        assert!(err.to_string().contains("no tspec found"));
+    }
+}
@@ -5,7 +5,7 @@ use std::path::Path;
 use toml_edit::DocumentMut;
 
 use super::edit;
-use crate::find_paths::{find_tspec, resolve_package_dir};
+use crate::find_paths::{find_tspec, resolve_ts_package_dir};
 
 /// Remove a field from a tspec
 pub fn unset_value(
@@ -15,7 +15,7 @@ pub fn unset_value(
     tspec: Option<&str>,
 ) -> Result<()> {
     let workspace = project_root;
-    let package_dir = resolve_package_dir(workspace, package)?;
+    let package_dir = resolve_ts_package_dir(workspace, package)?;
 
     let output_path = match find_tspec(&package_dir, tspec)? {
         Some(path) => path,
@@ -46,6 +46,7 @@ pub fn unset_value(
 
     std::fs::write(&output_path, doc.to_string())
         .with_context(|| format!("failed to write: {}", output_path.display()))?;
+    crate::audit::record("unset", key, "", &output_path);
 
     println!(
         "Saved {}",
@@ -139,7 +140,7 @@ mod tests {
         let input = "[cargo.config]\n\"profile.release.opt-level\" = \"s\"\n\"profile.release.lto\" = true\n";
         let (_dir, path, _) = unset_in_file(input, "cargo.config.\"profile.release.opt-level\"");
         let spec = load_spec(&path).unwrap();
-        assert!(spec.cargo.config.get("profile.release.opt-level").is_none());
+        assert!(!spec.cargo.config.contains_key("profile.release.opt-level"));
         assert_eq!(
             spec.cargo.config.get("profile.release.lto"),
             Some(&crate::types::ConfigValue::Bool(true))
@@ -0,0 +1,108 @@
+//! `tspec ts validate` - Strictly check tspec files for unknown keys
+
+use anyhow::Result;
+use std::path::Path;
+
+use crate::TSPEC_SUFFIX;
+use crate::cargo_build::resolve_target_json_path;
+use crate::find_paths::{current_package_name, find_tspec, resolve_package_dir};
+use crate::tspec::{load_spec_strict, require_target_json_pin};
+
+use super::list::find_tspec_files;
+
+/// Validate a tspec file's contents under strict loading, reporting unknown
+/// keys. Returns `Ok(true)` if every checked spec is clean, `Ok(false)` if
+/// any was flagged (for a non-zero exit in CI).
+pub fn validate_tspec(
+    project_root: &Path,
+    package: Option<&str>,
+    all: bool,
+    tspec: Option<&str>,
+) -> Result<bool> {
+    let workspace = project_root;
+    let cwd_package = current_package_name(project_root);
+    let mut all_ok = true;
+    let warn_unpinned_target = require_target_json_pin(workspace)?;
+
+    if let Some(name) = package {
+        let package_dir = resolve_package_dir(workspace, Some(name))?;
+        all_ok &=
+            validate_package_tspecs(workspace, &package_dir, name, tspec, warn_unpinned_target)?;
+    } else if all || cwd_package.is_none() {
+        let info = crate::workspace::WorkspaceInfo::discover(project_root)?;
+        for member in &info.members {
+            all_ok &= validate_package_tspecs(
+                workspace,
+                &member.path,
+                &member.name,
+                tspec,
+                warn_unpinned_target,
+            )?;
+        }
+    } else if let Some(pkg_name) = cwd_package {
+        let package_dir = resolve_package_dir(workspace, Some(&pkg_name))?;
+        all_ok &= validate_package_tspecs(
+            workspace,
+            &package_dir,
+            &pkg_name,
+            tspec,
+            warn_unpinned_target,
+        )?;
+    }
+
+    Ok(all_ok)
+}
+
+/// Validate every tspec for a single package, printing one OK/FAIL line each.
+fn validate_package_tspecs(
+    workspace: &Path,
+    package_dir: &Path,
+    pkg_name: &str,
+    tspec: Option<&str>,
+    warn_unpinned_target: bool,
+) -> Result<bool> {
+    match tspec {
+        Some(name) => match find_tspec(package_dir, Some(name))? {
+            Some(path) => Ok(validate_one(workspace, &path, warn_unpinned_target)),
+            None => anyhow::bail!("tspec '{}' not found for package '{}'", name, pkg_name),
+        },
+        None => {
+            let tspecs = find_tspec_files(package_dir)?;
+            if tspecs.is_empty() {
+                println!("{}: no *{} files found", pkg_name, TSPEC_SUFFIX);
+                return Ok(true);
+            }
+            let mut ok = true;
+            for name in &tspecs {
+                ok &= validate_one(workspace, &package_dir.join(name), warn_unpinned_target);
+            }
+            Ok(ok)
+        }
+    }
+}
+
+/// Validate a single tspec file, printing `OK <path>` or `FAIL <path>: <error>`.
+/// When `warn_unpinned_target` is set (via `workspace.ts.toml`'s
+/// `require_target_json_pin`), also prints a non-fatal warning for a spec
+/// that sets `cargo.target_json` without a `cargo.target_json_hash` pin.
+fn validate_one(workspace: &Path, path: &Path, warn_unpinned_target: bool) -> bool {
+    match load_spec_strict(path) {
+        Ok(spec) => {
+            println!("OK   {}", path.display());
+            if warn_unpinned_target
+                && resolve_target_json_path(&spec, workspace).is_some()
+                && spec.cargo.target_json_hash.is_none()
+            {
+                println!(
+                    "Warning: {}: cargo.target_json is set without a cargo.target_json_hash pin (run `tspec ts pin-target`)",
+                    path.display()
+                );
+            }
+            true
+        }
+        Err(e) => {
+            println!("FAIL {e}");
+            false
+        }
+    }
+}
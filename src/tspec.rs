@@ -3,13 +3,120 @@ use sha2::{Digest, Sha256};
 use std::path::{Path, PathBuf};
 
 use crate::TSPEC_SUFFIX;
-use crate::types::Spec;
+use crate::find_paths::{find_tspec, find_tspecs, resolve_package_dir};
+use crate::types::{ConfigValue, Spec, profile_dir_name, resolve_profile, resolve_target_triple};
 
-/// Load a spec from a TOML file
+/// Resolve a package's spec file and load it in one step: `resolve_package_dir`
+/// → `find_tspec` → `load_spec`, the dance most commands repeat by hand.
+/// `package` and `tspec` are the same optional `-p`/`-t` values commands
+/// already accept. Returns `None` for the spec when the package has no
+/// tspec at all (a plain-cargo build), matching `find_tspec`'s own
+/// Ok(None) convention — not an error.
+pub fn resolve_spec(
+    package: Option<&str>,
+    tspec: Option<&str>,
+    project_root: &Path,
+) -> Result<(PathBuf, Option<Spec>)> {
+    let package_dir = resolve_package_dir(project_root, package)?;
+    match find_tspec(&package_dir, tspec)? {
+        Some(path) => {
+            let spec = load_spec(&path)?;
+            Ok((path, Some(spec)))
+        }
+        None => Ok((package_dir, None)),
+    }
+}
+
+/// Like [`resolve_spec`], but for the glob/multi-file form (`-t 'tspec*'`):
+/// resolves every matching spec and loads each one, pairing path with spec.
+pub fn resolve_specs(
+    package: Option<&str>,
+    tspec_patterns: &[String],
+    project_root: &Path,
+) -> Result<Vec<(PathBuf, Spec)>> {
+    let package_dir = resolve_package_dir(project_root, package)?;
+    find_tspecs(&package_dir, tspec_patterns)?
+        .into_iter()
+        .map(|path| {
+            let spec = load_spec(&path)?;
+            Ok((path, spec))
+        })
+        .collect()
+}
+
+/// Filename for workspace-wide build defaults, read from the project root
+/// and applied to every binary package's spec (see
+/// `apply_workspace_linker_defaults`). Distinct from `TSPEC_SUFFIX` naming
+/// so it never collides with a package's own tspec, including at the root
+/// of a POPWS where the root package has its own spec.
+pub const WORKSPACE_DEFAULTS_FILE: &str = "workspace.ts.toml";
+
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+struct WorkspaceDefaults {
+    #[serde(default)]
+    linker: WorkspaceLinkerDefaults,
+    /// When true, `tspec ts validate` warns about any spec that sets
+    /// `cargo.target_json` without a matching `cargo.target_json_hash` pin.
+    #[serde(default)]
+    require_target_json_pin: bool,
+}
+
+#[derive(Debug, Clone, Default, serde::Deserialize)]
+struct WorkspaceLinkerDefaults {
+    #[serde(default)]
+    args: Vec<String>,
+}
+
+/// Prepend workspace-wide default linker args (from `workspace.ts.toml` at
+/// the project root, if present) ahead of `spec.linker.args`, so a
+/// package's own args still take effect last and can add to or shadow the
+/// defaults. Only meant to be called for binary-producing packages; a
+/// library has nothing to link.
+pub fn apply_workspace_linker_defaults(spec: &mut Spec, project_root: &Path) -> Result<()> {
+    let path = project_root.join(WORKSPACE_DEFAULTS_FILE);
+    if !path.exists() {
+        return Ok(());
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let defaults: WorkspaceDefaults =
+        toml::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))?;
+    if !defaults.linker.args.is_empty() {
+        let mut merged = defaults.linker.args;
+        merged.append(&mut spec.linker.args);
+        spec.linker.args = merged;
+    }
+    Ok(())
+}
+
+/// Read `require_target_json_pin` from `workspace.ts.toml` at the project
+/// root, if present. Defaults to `false` (no file, or the key absent).
+pub fn require_target_json_pin(project_root: &Path) -> Result<bool> {
+    let path = project_root.join(WORKSPACE_DEFAULTS_FILE);
+    if !path.exists() {
+        return Ok(false);
+    }
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("failed to read {}", path.display()))?;
+    let defaults: WorkspaceDefaults =
+        toml::from_str(&content).with_context(|| format!("failed to parse {}", path.display()))?;
+    Ok(defaults.require_target_json_pin)
+}
+
+/// Load a spec from a TOML file, interpolating `${VAR}` environment
+/// references in its string fields.
 pub fn load_spec(path: &Path) -> Result<Spec> {
     let content = std::fs::read_to_string(path)
         .with_context(|| format!("failed to read spec file: {}", path.display()))?;
-    let spec = parse_spec(&content)?;
+    for &(legacy, modern) in &find_legacy_keys(&content)? {
+        println!(
+            "Warning: {}: {}",
+            path.display(),
+            legacy_key_message(legacy, modern)
+        );
+    }
+    let mut spec = parse_spec(&content)?;
+    interpolate_env(&mut spec)?;
     Ok(spec)
 }
 
@@ -18,6 +125,191 @@ pub fn parse_spec(toml_str: &str) -> Result<Spec> {
     toml::from_str(toml_str).context("failed to parse spec TOML")
 }
 
+/// Known top-level and nested-table keys, used only by [`find_unknown_keys`]
+/// to flag typos. `cargo.config` and `profile_overrides` are intentionally
+/// freeform dotted-table maps (see [`crate::types::CargoConfig::config`]) and
+/// are not descended into.
+pub(crate) const TOP_LEVEL_KEYS: &[&str] = &[
+    "panic",
+    "strip",
+    "toolchain",
+    "cargo",
+    "rustflags",
+    "linker",
+    "profile_overrides",
+    "run",
+    "test",
+];
+const CARGO_KEYS: &[&str] = &[
+    "profile",
+    "target_triple",
+    "target_json",
+    "target_json_hash",
+    "unstable",
+    "target_dir",
+    "config",
+    "build_std",
+    "opt_level_deps",
+    "hermetic_env",
+    "env_allowlist",
+];
+const LINKER_KEYS: &[&str] = &["args", "version_script"];
+const RUN_KEYS: &[&str] = &["cwd", "args", "expect_exit"];
+const TEST_KEYS: &[&str] = &["args"];
+
+/// Collect dotted-path keys in `toml_str` that aren't recognized fields of
+/// [`Spec`] or its `cargo`/`linker`/`run`/`test` sub-tables. Returns paths
+/// like `"cargo.rusctc"` so the caller can report the location of a typo.
+fn find_unknown_keys(toml_str: &str) -> Result<Vec<String>> {
+    let value: toml::Value = toml::from_str(toml_str).context("failed to parse spec TOML")?;
+    let mut unknown = Vec::new();
+    let Some(table) = value.as_table() else {
+        return Ok(unknown);
+    };
+    for (key, val) in table {
+        if !TOP_LEVEL_KEYS.contains(&key.as_str()) {
+            unknown.push(key.clone());
+            continue;
+        }
+        let nested_keys = match key.as_str() {
+            "cargo" => Some(CARGO_KEYS),
+            "linker" => Some(LINKER_KEYS),
+            "run" => Some(RUN_KEYS),
+            "test" => Some(TEST_KEYS),
+            _ => None,
+        };
+        if let (Some(nested_keys), Some(nested_table)) = (nested_keys, val.as_table()) {
+            for nested_key in nested_table.keys() {
+                if !nested_keys.contains(&nested_key.as_str()) {
+                    unknown.push(format!("{key}.{nested_key}"));
+                }
+            }
+        }
+    }
+    Ok(unknown)
+}
+
+/// Like [`load_spec`], but first checks for unrecognized keys (typos like
+/// `rusctc` instead of `rustc`) that lenient `#[serde(default)]` loading
+/// would otherwise silently drop. Used by `ts show`/`ts validate`; the build
+/// path stays on [`load_spec`] for forward-compat with specs written by a
+/// newer tspec.
+pub fn load_spec_strict(path: &Path) -> Result<Spec> {
+    let content = std::fs::read_to_string(path)
+        .with_context(|| format!("failed to read spec file: {}", path.display()))?;
+    let legacy = find_legacy_keys(&content)?;
+    if !legacy.is_empty() {
+        anyhow::bail!(
+            "{}: {}",
+            path.display(),
+            legacy
+                .iter()
+                .map(|&(from, to)| legacy_key_message(from, to))
+                .collect::<Vec<_>>()
+                .join("; ")
+        );
+    }
+    let unknown = find_unknown_keys(&content)?;
+    if !unknown.is_empty() {
+        anyhow::bail!("{}: unknown key(s): {}", path.display(), unknown.join(", "));
+    }
+    let mut spec = parse_spec(&content)?;
+    interpolate_env(&mut spec)?;
+    Ok(spec)
+}
+
+/// Legacy key paths from the pre-tspec in-house format, mapped to their
+/// modern equivalent. Consulted by [`load_spec`]/[`load_spec_strict`] so a
+/// migration trap ("`rustc.panic` no longer does anything") gets a specific
+/// rename instead of a generic unknown-key warning, and by `tspec ts
+/// migrate`, which applies these rewrites in place.
+pub(crate) const LEGACY_KEY_MAP: &[(&str, &str)] = &[
+    ("rustc.panic", "panic"),
+    ("rustc.strip", "strip"),
+    ("cargo.target", "cargo.target_triple"),
+];
+
+/// Collect legacy key paths present in `toml_str`, paired with their modern
+/// replacement from [`LEGACY_KEY_MAP`].
+fn find_legacy_keys(toml_str: &str) -> Result<Vec<(&'static str, &'static str)>> {
+    let value: toml::Value = toml::from_str(toml_str).context("failed to parse spec TOML")?;
+    Ok(LEGACY_KEY_MAP
+        .iter()
+        .copied()
+        .filter(|&(legacy, _)| dotted_key_present(&value, legacy))
+        .collect())
+}
+
+/// Whether a dotted key path resolves to something in a parsed TOML value.
+fn dotted_key_present(value: &toml::Value, dotted: &str) -> bool {
+    let mut current = value;
+    for segment in dotted.split('.') {
+        match current.as_table().and_then(|t| t.get(segment)) {
+            Some(v) => current = v,
+            None => return false,
+        }
+    }
+    true
+}
+
+/// Migration instructions shown for a single legacy key.
+fn legacy_key_message(legacy: &str, modern: &str) -> String {
+    format!(
+        "legacy key '{legacy}' is no longer read; rename it to '{modern}' \
+         (run `tspec ts migrate` to rewrite it automatically, or by hand: \
+         `tspec ts unset {legacy}` then `tspec ts set {modern} <value>`)"
+    )
+}
+
+/// Substitute `${VAR}` and `${VAR:-default}` references in a spec's
+/// string-valued fields (`target_triple`, `target_dir`, linker args,
+/// rustflags) from the process environment, so a spec can reference
+/// machine- or CI-specific values (e.g. a toolchain path) while staying
+/// portable. Errors if a referenced variable is unset and no default is
+/// given. Runs once at load time, so everything downstream (including
+/// `hash_spec`) only ever sees the already-resolved values.
+fn interpolate_env(spec: &mut Spec) -> Result<()> {
+    if let Some(target_triple) = &spec.cargo.target_triple {
+        spec.cargo.target_triple = Some(interpolate_env_str(target_triple)?);
+    }
+    if let Some(target_dir) = &spec.cargo.target_dir {
+        spec.cargo.target_dir = Some(interpolate_env_str(target_dir)?);
+    }
+    for arg in &mut spec.linker.args {
+        *arg = interpolate_env_str(arg)?;
+    }
+    for flag in &mut spec.rustflags {
+        *flag = interpolate_env_str(flag)?;
+    }
+    Ok(())
+}
+
+/// Expand `${VAR}` and `${VAR:-default}` references in a single string.
+fn interpolate_env_str(input: &str) -> Result<String> {
+    let mut output = String::with_capacity(input.len());
+    let mut rest = input;
+    while let Some(start) = rest.find("${") {
+        output.push_str(&rest[..start]);
+        let after = &rest[start + 2..];
+        let end = after
+            .find('}')
+            .with_context(|| format!("unterminated \"${{\" in: {input}"))?;
+        let (name, default) = match after[..end].split_once(":-") {
+            Some((name, default)) => (name, Some(default)),
+            None => (&after[..end], None),
+        };
+        let value = std::env::var(name)
+            .ok()
+            .or_else(|| default.map(str::to_string));
+        output.push_str(&value.with_context(|| {
+            format!("environment variable '{name}' is not set and no default was given")
+        })?);
+        rest = &after[end + 1..];
+    }
+    output.push_str(rest);
+    Ok(output)
+}
+
 /// Serialize a spec to TOML string (canonical form for hashing)
 pub fn serialize_spec(spec: &Spec) -> Result<String> {
     toml::to_string(spec).context("failed to serialize spec")
@@ -32,6 +324,58 @@ pub fn hash_spec(spec: &Spec) -> Result<String> {
     Ok(hex::encode(&result[..4]))
 }
 
+/// Compute a `sha256:<hex>` content hash of a file, used to pin
+/// `cargo.target_json` against unreviewed edits (see
+/// [`verify_target_json_hash`], `tspec ts pin-target`). The `sha256:`
+/// prefix makes the field self-describing if the hash algorithm ever
+/// changes, the same way a pinned dependency lockfile would record it.
+pub fn hash_file_sha256(path: &Path) -> Result<String> {
+    let bytes =
+        std::fs::read(path).with_context(|| format!("failed to read {}", path.display()))?;
+    let mut hasher = Sha256::new();
+    hasher.update(&bytes);
+    let result = hasher.finalize();
+    Ok(format!("sha256:{}", hex::encode(result)))
+}
+
+/// Verify a spec's `cargo.target_json_hash` pin (if set) against the
+/// resolved target JSON file's current content, the same "drifted" failure
+/// [`crate::metadata::verify_spec_hash`] gives for a stale `spec_hash` pin.
+/// `workspace` is the project root `cargo.target_json` is resolved
+/// relative to (see [`crate::cargo_build::resolve_target_json_path`]).
+pub fn verify_target_json_hash(spec: &Spec, workspace: &Path) -> Result<()> {
+    let Some(pinned) = &spec.cargo.target_json_hash else {
+        return Ok(());
+    };
+    let Some(path) = crate::cargo_build::resolve_target_json_path(spec, workspace) else {
+        anyhow::bail!(
+            "cargo.target_json_hash is set but cargo.target_json is not — nothing to verify against"
+        );
+    };
+    let current = hash_file_sha256(&path)?;
+    if &current != pinned {
+        anyhow::bail!(
+            "{} drifted from the hash pinned in cargo.target_json_hash \
+             (pinned {pinned}, resolved {current}) — run `tspec ts pin-target` to update it",
+            path.display()
+        );
+    }
+    Ok(())
+}
+
+/// Verify a resolved spec's [`hash_spec`] against a caller-supplied expected
+/// value (`tspec build --expect-hash`), the same "drifted" failure
+/// [`crate::metadata::verify_spec_hash`] gives for a stale `spec_hash` pin,
+/// except the expected hash comes from the command line instead of a pin
+/// committed to Cargo.toml.
+pub fn verify_expected_hash(spec: &Spec, expected: &str) -> Result<()> {
+    let actual = hash_spec(spec)?;
+    if actual != expected {
+        anyhow::bail!("spec hash mismatch: expected {expected}, got {actual}");
+    }
+    Ok(())
+}
+
 /// Extract spec name from path by stripping the .ts.toml (or .toml) suffix
 pub fn spec_name_from_path(path: &Path) -> String {
     let filename = path
@@ -46,9 +390,23 @@ pub fn spec_name_from_path(path: &Path) -> String {
         .to_string()
 }
 
-/// Expand template placeholders in a spec's target_dir field.
-/// Returns None if target_dir is absent or empty.
-pub fn expand_target_dir(spec: &Spec, spec_name: &str) -> Result<Option<String>> {
+/// Expand template placeholders in a spec's target_dir field: `{name}` (spec
+/// filename sans suffix), `{hash}` (8-char content hash), `{profile}` (the
+/// resolved profile dir name, e.g. "release", "debug") and `{triple}` (the
+/// resolved target triple, or "host" when none is set). `cli_profile` and
+/// `force_profile` are the same inputs `resolve_profile` takes elsewhere, so
+/// `{profile}` reflects the actual build's profile rather than just the
+/// spec's own `cargo.profile`. Returns None if target_dir is absent or empty.
+///
+/// `get_binary_path` resolves profile dir and triple the same way, so a
+/// path built from `{profile}`/`{triple}` here always matches where the
+/// binary actually lands.
+pub fn expand_target_dir(
+    spec: &Spec,
+    spec_name: &str,
+    cli_profile: Option<&str>,
+    force_profile: bool,
+) -> Result<Option<String>> {
     let raw = match &spec.cargo.target_dir {
         Some(td) if !td.is_empty() => td,
         _ => return Ok(None),
@@ -65,6 +423,20 @@ pub fn expand_target_dir(spec: &Spec, spec_name: &str) -> Result<Option<String>>
         expanded = expanded.replace("{hash}", &hash);
     }
 
+    if expanded.contains("{profile}") {
+        let resolved = resolve_profile(spec.cargo.profile.as_deref(), cli_profile, force_profile);
+        let dir = resolved
+            .profile
+            .as_deref()
+            .map_or("debug", profile_dir_name);
+        expanded = expanded.replace("{profile}", dir);
+    }
+
+    if expanded.contains("{triple}") {
+        let triple = resolve_target_triple(&spec.cargo).unwrap_or_else(|| "host".to_string());
+        expanded = expanded.replace("{triple}", &triple);
+    }
+
     if expanded.is_empty() {
         Ok(None)
     } else {
@@ -72,6 +444,121 @@ pub fn expand_target_dir(spec: &Spec, spec_name: &str) -> Result<Option<String>>
     }
 }
 
+/// Expand template placeholders in a spec's `[run] cwd` field.
+/// Returns None if cwd is absent or empty, meaning "run from wherever
+/// the command was invoked" (the pre-existing default).
+pub fn expand_run_cwd(spec: &Spec, pkg_dir: &Path) -> Option<PathBuf> {
+    let raw = match &spec.run.cwd {
+        Some(cwd) if !cwd.is_empty() => cwd,
+        _ => return None,
+    };
+
+    let expanded = raw.replace("{package_dir}", &pkg_dir.display().to_string());
+    Some(PathBuf::from(expanded))
+}
+
+/// Resolve the effective target_dir for a build, folding in `--isolate`'s
+/// synthetic fallback. If the spec already sets `cargo.target_dir`, that
+/// (placeholder-expanded) value always wins. Otherwise, when `isolate` is
+/// set, synthesizes `{spec_name}-{hash}` so the build gets its own
+/// `target/` subdirectory instead of sharing `target/<profile>/<crate>`
+/// with every other spec for this package — e.g. two rustflags-only specs
+/// that would otherwise silently clobber each other's cached artifacts.
+pub fn resolve_isolated_target_dir(
+    spec: &Spec,
+    spec_name: &str,
+    isolate: bool,
+    cli_profile: Option<&str>,
+    force_profile: bool,
+) -> Result<Option<String>> {
+    let explicit = expand_target_dir(spec, spec_name, cli_profile, force_profile)?;
+    if explicit.is_some() || !isolate {
+        return Ok(explicit);
+    }
+    let hash = hash_spec(spec)?;
+    Ok(Some(format!("{spec_name}-{hash}")))
+}
+
+/// Suffix `apply_dev_overlay` appends to `cargo.target_dir` so a
+/// `--dev-overlay` build never shares (or is mistaken for) the artifacts of
+/// the spec it was relaxed from.
+pub const DEV_OVERLAY_TARGET_DIR_SUFFIX: &str = "-dev-overlay";
+
+/// Whether an (already expanded) target_dir was produced by [`apply_dev_overlay`].
+pub fn is_dev_overlay_target_dir(target_dir: &str) -> bool {
+    target_dir.ends_with(DEV_OVERLAY_TARGET_DIR_SUFFIX)
+}
+
+/// One relaxation `apply_dev_overlay` made, for printing to the user.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DevOverlayChange(pub String);
+
+const DEV_OVERLAY_RELAXED_KEYS: &[&str] = &["lto", "codegen-units", "opt-level"];
+
+/// Relax the expensive codegen knobs of `spec` for a fast edit-compile loop,
+/// for the given effective `profile`. Strips `lto`/`codegen-units`/
+/// `opt-level` overrides for that profile out of `cargo.config` (both the
+/// flat dotted-key and nested-table forms) and out of `profile_overrides`,
+/// so cargo falls back to the profile's own defaults, and clears
+/// `cargo.opt_level_deps`. Appends [`DEV_OVERLAY_TARGET_DIR_SUFFIX`] to
+/// `cargo.target_dir` so the relaxed build gets its own `target/`
+/// subdirectory. Target triple, panic mode, strip mode, and linker args are
+/// left untouched — the binary still links and runs the same as production.
+///
+/// This is a pure transformation over `Spec`, not a hidden special case in
+/// the build path — callers are expected to print the returned changes.
+pub fn apply_dev_overlay(spec: &Spec, profile: &str) -> (Spec, Vec<DevOverlayChange>) {
+    let mut overlay = spec.clone();
+    let mut changes = Vec::new();
+
+    for key in DEV_OVERLAY_RELAXED_KEYS {
+        let dotted = format!("profile.{profile}.{key}");
+        if overlay.cargo.config.remove(&dotted).is_some() {
+            changes.push(DevOverlayChange(format!(
+                "removed cargo.config.\"{dotted}\""
+            )));
+        }
+    }
+
+    if let Some(ConfigValue::Table(profiles)) = overlay.cargo.config.get_mut("profile")
+        && let Some(ConfigValue::Table(profile_table)) = profiles.get_mut(profile)
+    {
+        for key in DEV_OVERLAY_RELAXED_KEYS {
+            if profile_table.remove(*key).is_some() {
+                changes.push(DevOverlayChange(format!(
+                    "removed cargo.config.profile.{profile}.{key}"
+                )));
+            }
+        }
+    }
+
+    for key in DEV_OVERLAY_RELAXED_KEYS {
+        let deps_key = format!("{profile}.deps.{key}");
+        if overlay.profile_overrides.remove(&deps_key).is_some() {
+            changes.push(DevOverlayChange(format!(
+                "removed profile_overrides.\"{deps_key}\""
+            )));
+        }
+    }
+
+    if overlay.cargo.opt_level_deps.take().is_some() {
+        changes.push(DevOverlayChange("cleared cargo.opt_level_deps".to_string()));
+    }
+
+    let base_target_dir = overlay
+        .cargo
+        .target_dir
+        .clone()
+        .unwrap_or_else(|| "{name}".to_string());
+    let dev_target_dir = format!("{base_target_dir}{DEV_OVERLAY_TARGET_DIR_SUFFIX}");
+    changes.push(DevOverlayChange(format!(
+        "cargo.target_dir -> \"{dev_target_dir}\""
+    )));
+    overlay.cargo.target_dir = Some(dev_target_dir);
+
+    (overlay, changes)
+}
+
 /// Save a spec to a TOML file, creating parent directories if needed
 pub fn save_spec(spec: &Spec, path: &Path) -> Result<()> {
     if let Some(parent) = path.parent() {
@@ -145,8 +632,101 @@ pub fn copy_spec_snapshot(source: &Path, name: &str, dir: &Path) -> Result<PathB
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::options::PanicMode;
     use crate::test_constants::SUFFIX;
     use crate::types::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn apply_workspace_linker_defaults_no_file_is_noop() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut spec = Spec {
+            linker: LinkerConfig {
+                args: vec!["-nostdlib".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        apply_workspace_linker_defaults(&mut spec, dir.path()).unwrap();
+        assert_eq!(spec.linker.args, vec!["-nostdlib".to_string()]);
+    }
+
+    #[test]
+    fn apply_workspace_linker_defaults_prepends_defaults() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join(WORKSPACE_DEFAULTS_FILE),
+            "[linker]\nargs = [\"-static\"]\n",
+        )
+        .unwrap();
+        let mut spec = Spec {
+            linker: LinkerConfig {
+                args: vec!["-nostdlib".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        apply_workspace_linker_defaults(&mut spec, dir.path()).unwrap();
+        assert_eq!(
+            spec.linker.args,
+            vec!["-static".to_string(), "-nostdlib".to_string()]
+        );
+    }
+
+    #[test]
+    fn resolve_spec_returns_none_when_no_tspec() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+        let (path, spec) =
+            resolve_spec(Some(dir.path().to_str().unwrap()), None, dir.path()).unwrap();
+        assert_eq!(path, dir.path());
+        assert!(spec.is_none());
+    }
+
+    #[test]
+    fn resolve_spec_loads_default_tspec() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+        std::fs::write(
+            dir.path().join(format!("tspec{SUFFIX}")),
+            "[cargo]\nprofile = \"release\"\n",
+        )
+        .unwrap();
+        let (path, spec) =
+            resolve_spec(Some(dir.path().to_str().unwrap()), None, dir.path()).unwrap();
+        assert_eq!(path, dir.path().join(format!("tspec{SUFFIX}")));
+        assert_eq!(spec.unwrap().cargo.profile.as_deref(), Some("release"));
+    }
+
+    #[test]
+    fn resolve_spec_explicit_name_errors_when_missing() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+        let result = resolve_spec(
+            Some(dir.path().to_str().unwrap()),
+            Some("missing.ts.toml"),
+            dir.path(),
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn resolve_specs_loads_all_matching() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("Cargo.toml"), "[package]\nname = \"x\"\n").unwrap();
+        std::fs::write(
+            dir.path().join(format!("tspec{SUFFIX}")),
+            "[cargo]\nprofile = \"release\"\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join(format!("tspec.min{SUFFIX}")),
+            "[cargo]\nprofile = \"release-small\"\n",
+        )
+        .unwrap();
+        let resolved = resolve_specs(Some(dir.path().to_str().unwrap()), &[], dir.path()).unwrap();
+        assert_eq!(resolved.len(), 2);
+    }
 
     #[test]
     fn parse_empty_spec() {
@@ -181,6 +761,22 @@ mod tests {
         );
     }
 
+    #[test]
+    fn verify_expected_hash_matching_ok() {
+        let spec = Spec::default();
+        let hash = hash_spec(&spec).unwrap();
+        verify_expected_hash(&spec, &hash).unwrap();
+    }
+
+    #[test]
+    fn verify_expected_hash_mismatch_errors() {
+        let spec = Spec::default();
+        let err = verify_expected_hash(&spec, "deadbeef").unwrap_err();
+        let actual = hash_spec(&spec).unwrap();
+        assert!(err.to_string().contains("deadbeef"));
+        assert!(err.to_string().contains(&actual));
+    }
+
     #[test]
     fn save_and_load_roundtrip() {
         let spec = Spec {
@@ -196,6 +792,9 @@ mod tests {
                 args: vec!["-static".to_string()],
                 ..Default::default()
             },
+            profile_overrides: Default::default(),
+            run: Default::default(),
+            test: Default::default(),
         };
         let dir = tempfile::tempdir().unwrap();
         let path = dir.path().join("test.toml");
@@ -282,14 +881,14 @@ mod tests {
     #[test]
     fn expand_target_dir_none() {
         let spec = Spec::default();
-        assert_eq!(expand_target_dir(&spec, "foo").unwrap(), None);
+        assert_eq!(expand_target_dir(&spec, "foo", None, false).unwrap(), None);
     }
 
     #[test]
     fn expand_target_dir_empty() {
         let mut spec = Spec::default();
         spec.cargo.target_dir = Some("".to_string());
-        assert_eq!(expand_target_dir(&spec, "foo").unwrap(), None);
+        assert_eq!(expand_target_dir(&spec, "foo", None, false).unwrap(), None);
     }
 
     #[test]
@@ -297,7 +896,7 @@ mod tests {
         let mut spec = Spec::default();
         spec.cargo.target_dir = Some("my-subdir".to_string());
         assert_eq!(
-            expand_target_dir(&spec, "foo").unwrap(),
+            expand_target_dir(&spec, "foo", None, false).unwrap(),
             Some("my-subdir".to_string())
         );
     }
@@ -307,7 +906,7 @@ mod tests {
         let mut spec = Spec::default();
         spec.cargo.target_dir = Some("{name}".to_string());
         assert_eq!(
-            expand_target_dir(&spec, "static-opt").unwrap(),
+            expand_target_dir(&spec, "static-opt", None, false).unwrap(),
             Some("static-opt".to_string())
         );
     }
@@ -316,7 +915,9 @@ mod tests {
     fn expand_target_dir_hash_placeholder() {
         let mut spec = Spec::default();
         spec.cargo.target_dir = Some("{hash}".to_string());
-        let result = expand_target_dir(&spec, "foo").unwrap().unwrap();
+        let result = expand_target_dir(&spec, "foo", None, false)
+            .unwrap()
+            .unwrap();
         assert_eq!(result.len(), 8);
         assert!(result.chars().all(|c| c.is_ascii_hexdigit()));
     }
@@ -325,8 +926,334 @@ mod tests {
     fn expand_target_dir_name_and_hash() {
         let mut spec = Spec::default();
         spec.cargo.target_dir = Some("{name}-{hash}".to_string());
-        let result = expand_target_dir(&spec, "opt").unwrap().unwrap();
+        let result = expand_target_dir(&spec, "opt", None, false)
+            .unwrap()
+            .unwrap();
+        assert!(result.starts_with("opt-"));
+        assert_eq!(result.len(), 4 + 8); // "opt-" + 8-char hash
+    }
+
+    #[test]
+    fn expand_target_dir_profile_placeholder_from_spec() {
+        let mut spec = Spec::default();
+        spec.cargo.target_dir = Some("iso/{profile}".to_string());
+        spec.cargo.profile = Some("release".to_string());
+        assert_eq!(
+            expand_target_dir(&spec, "foo", None, false).unwrap(),
+            Some("iso/release".to_string())
+        );
+    }
+
+    #[test]
+    fn expand_target_dir_profile_placeholder_defaults_to_debug() {
+        let mut spec = Spec::default();
+        spec.cargo.target_dir = Some("iso/{profile}".to_string());
+        assert_eq!(
+            expand_target_dir(&spec, "foo", None, false).unwrap(),
+            Some("iso/debug".to_string())
+        );
+    }
+
+    #[test]
+    fn expand_target_dir_profile_placeholder_uses_cli_profile() {
+        let mut spec = Spec::default();
+        spec.cargo.target_dir = Some("iso/{profile}".to_string());
+        assert_eq!(
+            expand_target_dir(&spec, "foo", Some("release-small"), false).unwrap(),
+            Some("iso/release-small".to_string())
+        );
+    }
+
+    #[test]
+    fn expand_target_dir_triple_placeholder_from_spec() {
+        let mut spec = Spec::default();
+        spec.cargo.target_dir = Some("iso/{triple}".to_string());
+        spec.cargo.target_triple = Some("x86_64-unknown-linux-musl".to_string());
+        assert_eq!(
+            expand_target_dir(&spec, "foo", None, false).unwrap(),
+            Some("iso/x86_64-unknown-linux-musl".to_string())
+        );
+    }
+
+    #[test]
+    fn expand_target_dir_triple_placeholder_no_triple_is_host() {
+        let spec = Spec {
+            cargo: crate::types::CargoConfig {
+                target_dir: Some("iso/{triple}".to_string()),
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        assert_eq!(
+            expand_target_dir(&spec, "foo", None, false).unwrap(),
+            Some("iso/host".to_string())
+        );
+    }
+
+    #[test]
+    fn expand_target_dir_triple_and_profile_combined() {
+        let mut spec = Spec::default();
+        spec.cargo.target_dir = Some("iso/{triple}/{name}".to_string());
+        spec.cargo.profile = Some("release".to_string());
+        assert_eq!(
+            expand_target_dir(&spec, "foo", None, false).unwrap(),
+            Some("iso/host/foo".to_string())
+        );
+    }
+
+    // ==================== resolve_isolated_target_dir tests ====================
+
+    #[test]
+    fn resolve_isolated_target_dir_prefers_explicit_target_dir() {
+        let mut spec = Spec::default();
+        spec.cargo.target_dir = Some("custom".to_string());
+        assert_eq!(
+            resolve_isolated_target_dir(&spec, "foo", true, None, false).unwrap(),
+            Some("custom".to_string())
+        );
+    }
+
+    #[test]
+    fn resolve_isolated_target_dir_no_isolate_no_explicit_is_none() {
+        let spec = Spec::default();
+        assert_eq!(
+            resolve_isolated_target_dir(&spec, "foo", false, None, false).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn resolve_isolated_target_dir_isolate_synthesizes_name_hash() {
+        let spec = Spec::default();
+        let result = resolve_isolated_target_dir(&spec, "opt", true, None, false)
+            .unwrap()
+            .unwrap();
         assert!(result.starts_with("opt-"));
         assert_eq!(result.len(), 4 + 8); // "opt-" + 8-char hash
+        assert_eq!(result, format!("opt-{}", hash_spec(&spec).unwrap()));
+    }
+
+    // ==================== interpolate_env tests ====================
+
+    #[test]
+    fn interpolate_env_str_no_placeholder() {
+        assert_eq!(interpolate_env_str("plain-value").unwrap(), "plain-value");
+    }
+
+    #[test]
+    fn interpolate_env_str_var_present() {
+        unsafe {
+            std::env::set_var("TSPEC_TEST_VAR_PRESENT", "musl-target");
+        }
+        let result = interpolate_env_str("${TSPEC_TEST_VAR_PRESENT}").unwrap();
+        unsafe {
+            std::env::remove_var("TSPEC_TEST_VAR_PRESENT");
+        }
+        assert_eq!(result, "musl-target");
+    }
+
+    #[test]
+    fn interpolate_env_str_var_absent_errors() {
+        unsafe {
+            std::env::remove_var("TSPEC_TEST_VAR_ABSENT");
+        }
+        assert!(interpolate_env_str("${TSPEC_TEST_VAR_ABSENT}").is_err());
+    }
+
+    #[test]
+    fn interpolate_env_str_default_used_when_absent() {
+        unsafe {
+            std::env::remove_var("TSPEC_TEST_VAR_DEFAULT");
+        }
+        let result = interpolate_env_str("${TSPEC_TEST_VAR_DEFAULT:-fallback}").unwrap();
+        assert_eq!(result, "fallback");
+    }
+
+    #[test]
+    fn interpolate_env_str_prefers_set_value_over_default() {
+        unsafe {
+            std::env::set_var("TSPEC_TEST_VAR_OVERRIDE", "actual");
+        }
+        let result = interpolate_env_str("${TSPEC_TEST_VAR_OVERRIDE:-fallback}").unwrap();
+        unsafe {
+            std::env::remove_var("TSPEC_TEST_VAR_OVERRIDE");
+        }
+        assert_eq!(result, "actual");
+    }
+
+    #[test]
+    fn interpolate_env_applies_to_spec_fields() {
+        unsafe {
+            std::env::set_var("TSPEC_TEST_TRIPLE", "x86_64-unknown-linux-musl");
+        }
+        let mut spec = Spec {
+            cargo: CargoConfig {
+                target_triple: Some("${TSPEC_TEST_TRIPLE}".to_string()),
+                target_dir: Some("out-${TSPEC_TEST_TRIPLE:-default}".to_string()),
+                ..Default::default()
+            },
+            rustflags: vec!["-C${TSPEC_TEST_TRIPLE}".to_string()],
+            linker: LinkerConfig {
+                args: vec!["--target=${TSPEC_TEST_TRIPLE}".to_string()],
+                ..Default::default()
+            },
+            ..Default::default()
+        };
+        interpolate_env(&mut spec).unwrap();
+        unsafe {
+            std::env::remove_var("TSPEC_TEST_TRIPLE");
+        }
+        assert_eq!(
+            spec.cargo.target_triple.as_deref(),
+            Some("x86_64-unknown-linux-musl")
+        );
+        assert_eq!(
+            spec.cargo.target_dir.as_deref(),
+            Some("out-x86_64-unknown-linux-musl")
+        );
+        assert_eq!(spec.rustflags[0], "-Cx86_64-unknown-linux-musl");
+        assert_eq!(spec.linker.args[0], "--target=x86_64-unknown-linux-musl");
+    }
+
+    #[test]
+    fn find_unknown_keys_flags_top_level_typo() {
+        let unknown = find_unknown_keys("rusctc = \"nightly\"\n").unwrap();
+        assert_eq!(unknown, vec!["rusctc".to_string()]);
+    }
+
+    #[test]
+    fn find_unknown_keys_flags_nested_section_typo() {
+        let unknown = find_unknown_keys("[cargo]\nrusctc = \"stable\"\n").unwrap();
+        assert_eq!(unknown, vec!["cargo.rusctc".to_string()]);
+    }
+
+    #[test]
+    fn find_unknown_keys_ignores_freeform_cargo_config() {
+        let unknown = find_unknown_keys("[cargo.config]\nanything.goes = \"here\"\n").unwrap();
+        assert!(unknown.is_empty());
+    }
+
+    #[test]
+    fn find_unknown_keys_empty_for_well_formed_spec() {
+        let unknown =
+            find_unknown_keys("panic = \"abort\"\n[cargo]\nprofile = \"release\"\n").unwrap();
+        assert!(unknown.is_empty());
+    }
+
+    #[test]
+    fn load_spec_strict_rejects_unknown_key_that_lenient_load_accepts() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("bad.ts.toml");
+        std::fs::write(&path, "rusctc = \"nightly\"\n").unwrap();
+
+        assert!(load_spec_strict(&path).is_err());
+        assert!(load_spec(&path).is_ok());
+    }
+
+    // ==================== apply_dev_overlay tests ====================
+
+    #[test]
+    fn apply_dev_overlay_strips_flat_dotted_keys_for_profile() {
+        let mut spec = Spec::default();
+        spec.cargo
+            .config
+            .insert("profile.release.lto".to_string(), ConfigValue::Bool(true));
+        spec.cargo.config.insert(
+            "profile.release.codegen-units".to_string(),
+            ConfigValue::Integer(1),
+        );
+        spec.cargo.config.insert(
+            "profile.release.opt-level".to_string(),
+            ConfigValue::String("z".to_string()),
+        );
+        let (overlay, changes) = apply_dev_overlay(&spec, "release");
+        assert!(overlay.cargo.config.is_empty());
+        assert_eq!(changes.len(), 4); // 3 removed keys + target_dir change
+    }
+
+    #[test]
+    fn apply_dev_overlay_strips_nested_table_keys_for_profile() {
+        let mut spec = Spec::default();
+        spec.cargo.config.insert(
+            "profile".to_string(),
+            ConfigValue::Table(BTreeMap::from([(
+                "release".to_string(),
+                ConfigValue::Table(BTreeMap::from([
+                    ("lto".to_string(), ConfigValue::Bool(true)),
+                    ("codegen-units".to_string(), ConfigValue::Integer(1)),
+                ])),
+            )])),
+        );
+        let (overlay, _changes) = apply_dev_overlay(&spec, "release");
+        let ConfigValue::Table(profiles) = overlay.cargo.config.get("profile").unwrap() else {
+            panic!("expected table");
+        };
+        let ConfigValue::Table(profile_table) = profiles.get("release").unwrap() else {
+            panic!("expected table");
+        };
+        assert!(profile_table.is_empty());
+    }
+
+    #[test]
+    fn apply_dev_overlay_strips_profile_overrides_deps_key() {
+        let mut spec = Spec::default();
+        spec.profile_overrides.insert(
+            "release.deps.opt-level".to_string(),
+            ConfigValue::Integer(2),
+        );
+        let (overlay, _changes) = apply_dev_overlay(&spec, "release");
+        assert!(overlay.profile_overrides.is_empty());
+    }
+
+    #[test]
+    fn apply_dev_overlay_clears_opt_level_deps() {
+        let mut spec = Spec::default();
+        spec.cargo.opt_level_deps = Some(ConfigValue::String("s".to_string()));
+        let (overlay, _changes) = apply_dev_overlay(&spec, "release");
+        assert!(overlay.cargo.opt_level_deps.is_none());
+    }
+
+    #[test]
+    fn apply_dev_overlay_leaves_untouched_fields_alone() {
+        let mut spec = Spec::default();
+        spec.cargo.target_triple = Some("x86_64-unknown-linux-musl".to_string());
+        spec.panic = Some(PanicMode::Abort);
+        spec.linker.args = vec!["-static".to_string()];
+        let (overlay, _changes) = apply_dev_overlay(&spec, "release");
+        assert_eq!(overlay.cargo.target_triple, spec.cargo.target_triple);
+        assert_eq!(overlay.panic, spec.panic);
+        assert_eq!(overlay.linker.args, spec.linker.args);
+    }
+
+    #[test]
+    fn apply_dev_overlay_suffixes_default_target_dir() {
+        let spec = Spec::default();
+        let (overlay, changes) = apply_dev_overlay(&spec, "release");
+        assert_eq!(
+            overlay.cargo.target_dir.as_deref(),
+            Some("{name}-dev-overlay")
+        );
+        assert!(
+            changes
+                .iter()
+                .any(|c| c.0.contains("cargo.target_dir -> \"{name}-dev-overlay\""))
+        );
+    }
+
+    #[test]
+    fn apply_dev_overlay_suffixes_explicit_target_dir() {
+        let mut spec = Spec::default();
+        spec.cargo.target_dir = Some("custom".to_string());
+        let (overlay, _changes) = apply_dev_overlay(&spec, "release");
+        assert_eq!(
+            overlay.cargo.target_dir.as_deref(),
+            Some("custom-dev-overlay")
+        );
+    }
+
+    #[test]
+    fn is_dev_overlay_target_dir_matches_suffix() {
+        assert!(is_dev_overlay_target_dir("app-dev-overlay"));
+        assert!(!is_dev_overlay_target_dir("app"));
     }
 }
@@ -1,3 +1,4 @@
+use anyhow::{Result, bail};
 use serde::{Deserialize, Serialize};
 use std::collections::BTreeMap;
 use std::fmt;
@@ -37,6 +38,10 @@ pub struct CargoFlags {
     pub verbosity: Verbosity,
     /// Number of parallel jobs (-j N)
     pub jobs: Option<u16>,
+    /// Require Cargo.lock to stay unchanged (--locked)
+    pub locked: bool,
+    /// Run without accessing the network (--offline)
+    pub offline: bool,
     /// Extra args appended to the cargo command (e.g., `["--test", "name", "--", "--ignored"]`)
     pub extra_args: Vec<String>,
 }
@@ -56,6 +61,12 @@ impl CargoFlags {
         if let Some(j) = self.jobs {
             cmd.arg("-j").arg(j.to_string());
         }
+        if self.locked {
+            cmd.arg("--locked");
+        }
+        if self.offline {
+            cmd.arg("--offline");
+        }
         if !self.extra_args.is_empty() {
             cmd.args(&self.extra_args);
         }
@@ -71,6 +82,7 @@ pub enum ConfigValue {
     Bool(bool),
     Integer(i64),
     String(String),
+    Array(Vec<ConfigValue>),
     Table(BTreeMap<String, ConfigValue>),
 }
 
@@ -80,6 +92,16 @@ impl fmt::Display for ConfigValue {
             ConfigValue::Bool(b) => write!(f, "{}", b),
             ConfigValue::Integer(n) => write!(f, "{}", n),
             ConfigValue::String(s) => write!(f, "\"{}\"", s),
+            ConfigValue::Array(items) => {
+                write!(f, "[")?;
+                for (i, item) in items.iter().enumerate() {
+                    if i > 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{}", item)?;
+                }
+                write!(f, "]")
+            }
             ConfigValue::Table(map) => write!(f, "{:?}", map),
         }
     }
@@ -110,6 +132,52 @@ fn flatten_inner(
     }
 }
 
+/// Per-package profile override keys cargo allows to vary between packages.
+/// `lto` and similar build-wide settings are deliberately excluded — cargo
+/// requires them to be uniform across the whole dependency graph and
+/// rejects a per-package override for them.
+pub const ALLOWED_PROFILE_OVERRIDE_KEYS: &[&str] =
+    &["opt-level", "debug", "codegen-units", "strip"];
+
+/// Parse a `profile_overrides` dotted key into `(profile, package selector, override key)`.
+/// `<profile>.deps.<key>` targets every dependency (`package."*"`);
+/// `<profile>.package.<name>.<key>` targets one package by name.
+fn parse_profile_override_key(key: &str) -> Result<(&str, String, &str)> {
+    let parts: Vec<&str> = key.split('.').collect();
+    match parts.as_slice() {
+        [profile, "deps", override_key] => Ok((profile, "\"*\"".to_string(), override_key)),
+        [profile, "package", name, override_key] => Ok((profile, name.to_string(), override_key)),
+        _ => bail!(
+            "invalid profile_overrides key '{key}' (expected '<profile>.deps.<key>' or \
+             '<profile>.package.<name>.<key>')"
+        ),
+    }
+}
+
+/// Validate and translate `[profile_overrides]` into `--config` key/value pairs,
+/// e.g. `release.deps.opt-level = "z"` becomes
+/// `profile.release.package."*".opt-level=\"z\"`.
+pub fn profile_override_config_args(
+    overrides: &BTreeMap<String, ConfigValue>,
+) -> Result<Vec<(String, String)>> {
+    let mut result = Vec::new();
+    for (key, value) in overrides {
+        let (profile, selector, override_key) = parse_profile_override_key(key)?;
+        if !ALLOWED_PROFILE_OVERRIDE_KEYS.contains(&override_key) {
+            bail!(
+                "profile override key '{override_key}' is not allowed per-package by cargo \
+                 (allowed: {}); settings like lto must be uniform across the whole build",
+                ALLOWED_PROFILE_OVERRIDE_KEYS.join(", ")
+            );
+        }
+        result.push((
+            format!("profile.{profile}.package.{selector}.{override_key}"),
+            value.to_string(),
+        ));
+    }
+    Ok(result)
+}
+
 /// Map a profile name to the directory cargo uses in target/.
 /// `"dev"` → `"debug"`, `"release"` → `"release"`, custom → as-is.
 pub fn profile_dir_name(profile: &str) -> &str {
@@ -119,6 +187,94 @@ pub fn profile_dir_name(profile: &str) -> &str {
     }
 }
 
+/// Where the effective profile came from, for display in summaries and debug output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+pub enum ProfileSource {
+    /// Taken from the spec's `cargo.profile`.
+    Spec,
+    /// Taken from the CLI's `--profile`/`--release`.
+    Cli,
+    /// Neither set; cargo's own default (debug).
+    Default,
+}
+
+/// Result of resolving the effective profile from a spec and the CLI.
+pub struct ProfileResolution {
+    /// The effective profile name, or `None` for cargo's default (debug).
+    pub profile: Option<String>,
+    pub source: ProfileSource,
+    /// Set when the spec and CLI both named a profile and they differ
+    /// and `force_profile` did not make the CLI win: `(spec_profile, ignored_cli_profile)`.
+    pub conflict: Option<(String, String)>,
+}
+
+/// Resolve the effective build profile from a spec's `cargo.profile` and the CLI's
+/// `--profile`/`--release`.
+///
+/// The spec wins by default when both are set and differ. Passing `force_profile = true`
+/// (the CLI's `--force-profile` flag) makes the CLI win instead; no conflict is recorded
+/// in that case since the override was explicit.
+pub fn resolve_profile(
+    spec_profile: Option<&str>,
+    cli_profile: Option<&str>,
+    force_profile: bool,
+) -> ProfileResolution {
+    match (spec_profile, cli_profile) {
+        (Some(s), Some(c)) if s != c => {
+            if force_profile {
+                ProfileResolution {
+                    profile: Some(c.to_string()),
+                    source: ProfileSource::Cli,
+                    conflict: None,
+                }
+            } else {
+                ProfileResolution {
+                    profile: Some(s.to_string()),
+                    source: ProfileSource::Spec,
+                    conflict: Some((s.to_string(), c.to_string())),
+                }
+            }
+        }
+        (Some(s), _) => ProfileResolution {
+            profile: Some(s.to_string()),
+            source: ProfileSource::Spec,
+            conflict: None,
+        },
+        (None, Some(c)) => ProfileResolution {
+            profile: Some(c.to_string()),
+            source: ProfileSource::Cli,
+            conflict: None,
+        },
+        (None, None) => ProfileResolution {
+            profile: None,
+            source: ProfileSource::Default,
+            conflict: None,
+        },
+    }
+}
+
+/// Resolve the effective target triple for a spec: `cargo.target_triple` if
+/// set, else the file stem of `cargo.target_json` (cargo treats a custom
+/// target JSON's stem as its triple), else `None` for the host. Shared by
+/// `get_binary_path` and `expand_target_dir`'s `{triple}` placeholder so
+/// both agree on what "the triple" means for a given spec.
+pub fn resolve_target_triple(cargo: &CargoConfig) -> Option<String> {
+    cargo.target_triple.clone().or_else(|| {
+        cargo
+            .target_json
+            .as_ref()
+            .and_then(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+    })
+}
+
+/// Format the one-line notice printed when a spec's profile overrides `--profile`.
+pub fn profile_conflict_notice(spec_profile: &str, ignored_cli_profile: &str) -> String {
+    format!(
+        "Notice: spec pins profile '{spec_profile}' — ignoring --profile {ignored_cli_profile} \
+         (use --force-profile to override)"
+    )
+}
+
 /// Cargo-specific configuration (flat struct)
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct CargoConfig {
@@ -128,6 +284,11 @@ pub struct CargoConfig {
     pub target_triple: Option<String>,
     /// Custom target JSON file path
     pub target_json: Option<PathBuf>,
+    /// Pinned content hash of `target_json`, e.g. `"sha256:abcd...`. Set
+    /// with `tspec ts pin-target`; verified against the file on disk at
+    /// load time so an unreviewed edit to the target JSON fails the build
+    /// instead of silently changing it (see `tspec::tspec::verify_target_json_hash`).
+    pub target_json_hash: Option<String>,
     /// Nightly-only -Z flags (e.g., ["panic-immediate-abort"])
     #[serde(default)]
     pub unstable: Vec<String>,
@@ -142,6 +303,19 @@ pub struct CargoConfig {
     /// Crates to rebuild with -Z build-std (nightly only)
     #[serde(default)]
     pub build_std: Vec<String>,
+    /// Convenience shorthand for `profile_overrides.<effective profile>.deps.opt-level`.
+    /// No dedicated `rustc` section exists in `Spec` — this lives here alongside the
+    /// other cargo/rustc-affecting knobs. Ignored for a profile that already sets
+    /// that key explicitly under `[profile_overrides]`.
+    pub opt_level_deps: Option<ConfigValue>,
+    /// Build with a scrubbed environment (see `--hermetic-env`), dropping
+    /// everything not on the fixed allowlist or `env_allowlist` below.
+    #[serde(default)]
+    pub hermetic_env: bool,
+    /// Extra environment variable names to keep when `hermetic_env` is set,
+    /// on top of the built-in allowlist (PATH, HOME, CARGO_HOME, RUSTUP_HOME, TERM).
+    #[serde(default)]
+    pub env_allowlist: Vec<String>,
 }
 
 /// Version script configuration for symbol visibility
@@ -168,6 +342,37 @@ pub struct LinkerConfig {
     pub version_script: Option<VersionScript>,
 }
 
+/// `tspec run` configuration (flat struct)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RunConfig {
+    /// Working directory to run the binary from. Supports the `{package_dir}`
+    /// placeholder (absolute path to the package directory). Unset keeps the
+    /// existing default of running from wherever `tspec run` itself was
+    /// invoked.
+    pub cwd: Option<String>,
+    /// Default arguments passed to the binary, before any CLI trailing args.
+    /// CLI args append after these; pass `--replace-args` to use only the
+    /// CLI args instead.
+    #[serde(default)]
+    pub args: Vec<String>,
+    /// Exit code the binary is expected to return (default 0). A run whose
+    /// actual exit code differs is a failure in the RUN SUMMARY even though
+    /// the process spawned successfully; a run killed by a signal never
+    /// satisfies this, regardless of the value. Overridable with
+    /// `--expect-exit`.
+    #[serde(default)]
+    pub expect_exit: i32,
+}
+
+/// `tspec test` configuration (flat struct)
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TestConfig {
+    /// Default arguments passed after `--` to the test binary (e.g.
+    /// `["--test-threads=1"]`), ahead of any CLI trailing test args.
+    #[serde(default)]
+    pub args: Vec<String>,
+}
+
 /// A translation spec
 #[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
 pub struct Spec {
@@ -187,6 +392,22 @@ pub struct Spec {
     pub rustflags: Vec<String>,
     #[serde(default)]
     pub linker: LinkerConfig,
+
+    /// Per-package profile overrides (e.g. fast `opt-level` for deps, `z` for
+    /// the final crate), keyed by dotted `<profile>.deps.<key>` or
+    /// `<profile>.package.<name>.<key>`. Uses the same flat dotted-table shape
+    /// as `cargo.config` so it's editable the same way via `ts set`/`ts add`/
+    /// `ts remove`. Translated into `--config 'profile.<p>.package...'` args
+    /// by `profile_override_config_args()`.
+    #[serde(default)]
+    pub profile_overrides: BTreeMap<String, ConfigValue>,
+
+    /// `tspec run` defaults (working directory, default binary args).
+    #[serde(default)]
+    pub run: RunConfig,
+    /// `tspec test` defaults (default test-binary args).
+    #[serde(default)]
+    pub test: TestConfig,
 }
 
 #[cfg(test)]
@@ -216,6 +437,110 @@ mod tests {
         assert_eq!(profile_dir_name("release-small"), "release-small");
     }
 
+    // resolve_profile: the four (spec, cli) combinations, plus forced override
+
+    #[test]
+    fn resolve_profile_neither_set() {
+        let r = resolve_profile(None, None, false);
+        assert_eq!(r.profile, None);
+        assert_eq!(r.source, ProfileSource::Default);
+        assert!(r.conflict.is_none());
+    }
+
+    #[test]
+    fn resolve_profile_spec_only() {
+        let r = resolve_profile(Some("release"), None, false);
+        assert_eq!(r.profile.as_deref(), Some("release"));
+        assert_eq!(r.source, ProfileSource::Spec);
+        assert!(r.conflict.is_none());
+    }
+
+    #[test]
+    fn resolve_profile_cli_only() {
+        let r = resolve_profile(None, Some("release-small"), false);
+        assert_eq!(r.profile.as_deref(), Some("release-small"));
+        assert_eq!(r.source, ProfileSource::Cli);
+        assert!(r.conflict.is_none());
+    }
+
+    #[test]
+    fn resolve_profile_both_set_and_agree() {
+        let r = resolve_profile(Some("release"), Some("release"), false);
+        assert_eq!(r.profile.as_deref(), Some("release"));
+        assert_eq!(r.source, ProfileSource::Spec);
+        assert!(r.conflict.is_none());
+    }
+
+    #[test]
+    fn resolve_profile_both_set_and_differ_spec_wins() {
+        let r = resolve_profile(Some("release"), Some("release-small"), false);
+        assert_eq!(r.profile.as_deref(), Some("release"));
+        assert_eq!(r.source, ProfileSource::Spec);
+        assert_eq!(
+            r.conflict,
+            Some(("release".to_string(), "release-small".to_string()))
+        );
+    }
+
+    #[test]
+    fn resolve_profile_force_profile_makes_cli_win() {
+        let r = resolve_profile(Some("release"), Some("release-small"), true);
+        assert_eq!(r.profile.as_deref(), Some("release-small"));
+        assert_eq!(r.source, ProfileSource::Cli);
+        assert!(r.conflict.is_none());
+    }
+
+    #[test]
+    fn profile_conflict_notice_mentions_both_profiles_and_override_flag() {
+        let msg = profile_conflict_notice("release", "release-small");
+        assert!(msg.contains("release"));
+        assert!(msg.contains("release-small"));
+        assert!(msg.contains("--force-profile"));
+    }
+
+    #[test]
+    fn resolve_target_triple_none_without_target() {
+        let cargo = CargoConfig::default();
+        assert_eq!(resolve_target_triple(&cargo), None);
+    }
+
+    #[test]
+    fn resolve_target_triple_uses_target_triple() {
+        let cargo = CargoConfig {
+            target_triple: Some("x86_64-unknown-linux-musl".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_target_triple(&cargo),
+            Some("x86_64-unknown-linux-musl".to_string())
+        );
+    }
+
+    #[test]
+    fn resolve_target_triple_falls_back_to_target_json_stem() {
+        let cargo = CargoConfig {
+            target_json: Some(PathBuf::from("custom-target.json")),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_target_triple(&cargo),
+            Some("custom-target".to_string())
+        );
+    }
+
+    #[test]
+    fn resolve_target_triple_prefers_target_triple_over_target_json() {
+        let cargo = CargoConfig {
+            target_triple: Some("host-triple".to_string()),
+            target_json: Some(PathBuf::from("custom-target.json")),
+            ..Default::default()
+        };
+        assert_eq!(
+            resolve_target_triple(&cargo),
+            Some("host-triple".to_string())
+        );
+    }
+
     #[test]
     fn flatten_config_nested() {
         let config = BTreeMap::from([(
@@ -242,4 +567,80 @@ mod tests {
         let config = BTreeMap::new();
         assert!(flatten_config(&config).is_empty());
     }
+
+    #[test]
+    fn flatten_config_array_value() {
+        let config = BTreeMap::from([(
+            "build".to_string(),
+            ConfigValue::Table(BTreeMap::from([(
+                "rustflags".to_string(),
+                ConfigValue::Array(vec![
+                    ConfigValue::String("-C".to_string()),
+                    ConfigValue::String("x".to_string()),
+                ]),
+            )])),
+        )]);
+        let flat = flatten_config(&config);
+        assert_eq!(
+            flat,
+            vec![("build.rustflags".to_string(), "[\"-C\", \"x\"]".to_string())]
+        );
+    }
+
+    #[test]
+    fn profile_override_deps_wildcard() {
+        let overrides = BTreeMap::from([(
+            "release.deps.opt-level".to_string(),
+            ConfigValue::Integer(2),
+        )]);
+        let args = profile_override_config_args(&overrides).unwrap();
+        assert_eq!(
+            args,
+            vec![(
+                "profile.release.package.\"*\".opt-level".to_string(),
+                "2".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn profile_override_named_package() {
+        let overrides = BTreeMap::from([(
+            "release.package.mycrate.strip".to_string(),
+            ConfigValue::String("symbols".to_string()),
+        )]);
+        let args = profile_override_config_args(&overrides).unwrap();
+        assert_eq!(
+            args,
+            vec![(
+                "profile.release.package.mycrate.strip".to_string(),
+                "\"symbols\"".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn profile_override_rejects_disallowed_key() {
+        let overrides = BTreeMap::from([("release.deps.lto".to_string(), ConfigValue::Bool(true))]);
+        let err = profile_override_config_args(&overrides).unwrap_err();
+        assert!(err.to_string().contains("lto"));
+        assert!(err.to_string().contains("not allowed per-package"));
+    }
+
+    #[test]
+    fn profile_override_rejects_malformed_key() {
+        let overrides =
+            BTreeMap::from([("release.opt-level".to_string(), ConfigValue::Integer(2))]);
+        let err = profile_override_config_args(&overrides).unwrap_err();
+        assert!(err.to_string().contains("invalid profile_overrides key"));
+    }
+
+    #[test]
+    fn profile_override_empty_emits_nothing() {
+        assert!(
+            profile_override_config_args(&BTreeMap::new())
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
@@ -0,0 +1,215 @@
+//! Shared human-friendly parsing/formatting for size and duration values,
+//! so CLI flags and spec fields that accept "1.5M" or "5m" all agree on
+//! the same syntax instead of each growing its own ad-hoc parser.
+
+use anyhow::{Result, bail};
+use std::time::Duration;
+
+/// Accepted size suffixes, shown in clap help strings (`--help` / doc comments)
+/// so the syntax is documented in exactly one place.
+pub const SIZE_SYNTAX_HELP: &str =
+    "a byte count, optionally suffixed with K, M, or G (e.g. \"4096\", \"120K\", \"1.5M\")";
+
+/// Accepted duration syntax, shown in clap help strings.
+pub const DURATION_SYNTAX_HELP: &str =
+    "a duration like \"90s\", \"5m\", \"1h30m\", or a bare number of seconds";
+
+/// Parse a human-friendly byte size like "1.5M", "120K", or "4096" into bytes.
+/// Suffixes are decimal (K = 1_000, M = 1_000_000, G = 1_000_000_000),
+/// case-insensitive, and optional (a bare number is bytes).
+pub fn parse_size(raw: &str) -> Result<u64> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        bail!("empty size");
+    }
+    if trimmed.starts_with('-') {
+        bail!("size cannot be negative: {raw}");
+    }
+
+    let (number_part, multiplier) = match trimmed.chars().last() {
+        Some(c @ ('k' | 'K')) => (&trimmed[..trimmed.len() - c.len_utf8()], 1_000u64),
+        Some(c @ ('m' | 'M')) => (&trimmed[..trimmed.len() - c.len_utf8()], 1_000_000u64),
+        Some(c @ ('g' | 'G')) => (&trimmed[..trimmed.len() - c.len_utf8()], 1_000_000_000u64),
+        _ => (trimmed, 1u64),
+    };
+
+    let number: f64 = number_part
+        .parse()
+        .map_err(|_| anyhow::anyhow!("invalid size: {raw} (expected {SIZE_SYNTAX_HELP})"))?;
+    if number < 0.0 {
+        bail!("size cannot be negative: {raw}");
+    }
+
+    let bytes = number * multiplier as f64;
+    if bytes > u64::MAX as f64 {
+        bail!("size too large: {raw}");
+    }
+    Ok(bytes.round() as u64)
+}
+
+/// Format a byte count using the same decimal suffixes `parse_size` accepts.
+pub fn format_size(bytes: u64) -> String {
+    if bytes >= 1_000_000 {
+        format!("{:.1}M", bytes as f64 / 1_000_000.0)
+    } else if bytes >= 1_000 {
+        format!("{:.1}K", bytes as f64 / 1_000.0)
+    } else {
+        format!("{bytes}")
+    }
+}
+
+/// Parse a human-friendly duration like "90s", "5m", "1h30m", or a bare
+/// number of seconds, into a [`Duration`].
+pub fn parse_duration(raw: &str) -> Result<Duration> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() {
+        bail!("empty duration");
+    }
+    if trimmed.starts_with('-') {
+        bail!("duration cannot be negative: {raw}");
+    }
+    if let Ok(secs) = trimmed.parse::<u64>() {
+        return Ok(Duration::from_secs(secs));
+    }
+
+    let mut total_secs: u64 = 0;
+    let mut number = String::new();
+    let mut saw_unit = false;
+    for c in trimmed.chars() {
+        if c.is_ascii_digit() || c == '.' {
+            number.push(c);
+            continue;
+        }
+        if number.is_empty() {
+            bail!("invalid duration: {raw} (expected {DURATION_SYNTAX_HELP})");
+        }
+        let value: f64 = number.parse().map_err(|_| {
+            anyhow::anyhow!("invalid duration: {raw} (expected {DURATION_SYNTAX_HELP})")
+        })?;
+        let unit_secs = match c {
+            'h' => 3600.0,
+            'm' => 60.0,
+            's' => 1.0,
+            _ => bail!("unknown duration suffix '{c}' in {raw} (expected {DURATION_SYNTAX_HELP})"),
+        };
+        total_secs = total_secs.saturating_add((value * unit_secs).round() as u64);
+        number.clear();
+        saw_unit = true;
+    }
+    if !number.is_empty() {
+        bail!("invalid duration: {raw} (expected {DURATION_SYNTAX_HELP})");
+    }
+    if !saw_unit {
+        bail!("invalid duration: {raw} (expected {DURATION_SYNTAX_HELP})");
+    }
+    Ok(Duration::from_secs(total_secs))
+}
+
+/// Format a [`Duration`] using the same unit suffixes `parse_duration` accepts.
+pub fn format_duration(d: Duration) -> String {
+    let mut secs = d.as_secs();
+    if secs == 0 {
+        return "0s".to_string();
+    }
+    let hours = secs / 3600;
+    secs %= 3600;
+    let minutes = secs / 60;
+    secs %= 60;
+
+    let mut out = String::new();
+    if hours > 0 {
+        out.push_str(&format!("{hours}h"));
+    }
+    if minutes > 0 {
+        out.push_str(&format!("{minutes}m"));
+    }
+    if secs > 0 || out.is_empty() {
+        out.push_str(&format!("{secs}s"));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_size_bare_number() {
+        assert_eq!(parse_size("4096").unwrap(), 4096);
+        assert_eq!(parse_size("0").unwrap(), 0);
+    }
+
+    #[test]
+    fn parse_size_suffixes() {
+        assert_eq!(parse_size("120K").unwrap(), 120_000);
+        assert_eq!(parse_size("1.5M").unwrap(), 1_500_000);
+        assert_eq!(parse_size("2G").unwrap(), 2_000_000_000);
+        assert_eq!(parse_size("1k").unwrap(), 1_000);
+    }
+
+    #[test]
+    fn parse_size_negative_is_error() {
+        assert!(parse_size("-5").is_err());
+        assert!(parse_size("-5K").is_err());
+    }
+
+    #[test]
+    fn parse_size_unknown_suffix_is_error() {
+        let err = parse_size("5X").unwrap_err();
+        assert!(err.to_string().contains("invalid size"));
+    }
+
+    #[test]
+    fn parse_size_empty_is_error() {
+        assert!(parse_size("").is_err());
+        assert!(parse_size("   ").is_err());
+    }
+
+    #[test]
+    fn format_size_round_trip_thresholds() {
+        assert_eq!(format_size(999), "999");
+        assert_eq!(format_size(1_000), "1.0K");
+        assert_eq!(format_size(1_000_000), "1.0M");
+    }
+
+    #[test]
+    fn parse_duration_bare_seconds() {
+        assert_eq!(parse_duration("90").unwrap(), Duration::from_secs(90));
+    }
+
+    #[test]
+    fn parse_duration_suffixed() {
+        assert_eq!(parse_duration("90s").unwrap(), Duration::from_secs(90));
+        assert_eq!(parse_duration("5m").unwrap(), Duration::from_secs(300));
+        assert_eq!(parse_duration("1h30m").unwrap(), Duration::from_secs(5400));
+    }
+
+    #[test]
+    fn parse_duration_negative_is_error() {
+        assert!(parse_duration("-5s").is_err());
+    }
+
+    #[test]
+    fn parse_duration_unknown_suffix_is_error() {
+        let err = parse_duration("5x").unwrap_err();
+        assert!(err.to_string().contains("unknown duration suffix"));
+    }
+
+    #[test]
+    fn parse_duration_empty_is_error() {
+        assert!(parse_duration("").is_err());
+    }
+
+    #[test]
+    fn parse_duration_overflow_saturates_not_panics() {
+        assert!(parse_duration("99999999999999h").is_ok());
+    }
+
+    #[test]
+    fn format_duration_round_trip() {
+        assert_eq!(format_duration(Duration::from_secs(0)), "0s");
+        assert_eq!(format_duration(Duration::from_secs(90)), "1m30s");
+        assert_eq!(format_duration(Duration::from_secs(5400)), "1h30m");
+        assert_eq!(format_duration(Duration::from_secs(3600)), "1h");
+    }
+}
@@ -0,0 +1,434 @@
+//! Opt-in, telemetry-free local usage logging.
+//!
+//! Enabled by setting `usage_log = ".tspec/usage.jsonl"` under
+//! `[workspace.metadata.tspec]` in the workspace root `Cargo.toml`. When
+//! unset, [`record`] is a no-op and nothing is ever written. Records contain
+//! only what command ran, which packages/specs/profile it touched, how long
+//! it took, whether it succeeded, and the tspec version — never a username,
+//! hostname, or environment variable. See `tspec usage report`.
+
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::Write as _;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+/// A spec touched during a command, with its content hash when known.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageSpec {
+    pub name: String,
+    pub hash: Option<String>,
+}
+
+/// One append-only usage log entry.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct UsageRecord {
+    /// UTC calendar date the command ran, as `YYYY-MM-DD` (sorts lexically).
+    pub date: String,
+    pub command: String,
+    pub packages: Vec<String>,
+    pub specs: Vec<UsageSpec>,
+    pub profile: Option<String>,
+    pub duration_ms: u128,
+    pub success: bool,
+    pub tspec_version: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct TspecWorkspaceConfig {
+    usage_log: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct WorkspaceMetadata {
+    #[serde(default)]
+    tspec: TspecWorkspaceConfig,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct WorkspaceSection {
+    #[serde(default)]
+    metadata: WorkspaceMetadata,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CargoToml {
+    #[serde(default)]
+    workspace: WorkspaceSection,
+}
+
+/// Read the configured usage log path from `[workspace.metadata.tspec]`
+/// in `project_root/Cargo.toml`, if any.
+///
+/// Returns `None` (disabled) on a missing Cargo.toml, a missing table, or a
+/// parse error — config problems must never block the underlying command.
+fn usage_log_path(project_root: &Path) -> Option<PathBuf> {
+    let content = std::fs::read_to_string(project_root.join("Cargo.toml")).ok()?;
+    let parsed: CargoToml = toml::from_str(&content).ok()?;
+    parsed.workspace.metadata.tspec.usage_log.map(PathBuf::from)
+}
+
+thread_local! {
+    static PACKAGES: RefCell<Vec<String>> = const { RefCell::new(Vec::new()) };
+    static SPECS: RefCell<Vec<UsageSpec>> = const { RefCell::new(Vec::new()) };
+    static PROFILE: RefCell<Option<String>> = const { RefCell::new(None) };
+}
+
+/// Note a package touched by the running command, for the next [`record`] call.
+pub fn note_package(name: impl Into<String>) {
+    let name = name.into();
+    PACKAGES.with(|p| {
+        let mut p = p.borrow_mut();
+        if !p.contains(&name) {
+            p.push(name);
+        }
+    });
+}
+
+/// Note a spec touched by the running command, for the next [`record`] call.
+/// Deduplicated by name: an all-workspace command can touch the same spec
+/// name (e.g. "tspec") across many packages, and a single record only
+/// needs to say that it happened, not how many times.
+pub fn note_spec(name: impl Into<String>, hash: Option<String>) {
+    let name = name.into();
+    SPECS.with(|s| {
+        let mut s = s.borrow_mut();
+        if !s.iter().any(|existing| existing.name == name) {
+            s.push(UsageSpec { name, hash });
+        }
+    });
+}
+
+/// Note the build profile used by the running command, for the next [`record`] call.
+pub fn note_profile(profile: impl Into<String>) {
+    PROFILE.with(|p| *p.borrow_mut() = Some(profile.into()));
+}
+
+fn take_notes() -> (Vec<String>, Vec<UsageSpec>, Option<String>) {
+    (
+        PACKAGES.with(|p| std::mem::take(&mut *p.borrow_mut())),
+        SPECS.with(|s| std::mem::take(&mut *s.borrow_mut())),
+        PROFILE.with(|p| p.borrow_mut().take()),
+    )
+}
+
+/// Append a usage record for `command`, if usage logging is enabled.
+///
+/// Called once per `tspec` invocation (see `main.rs`), even when that
+/// invocation iterates every package in a workspace under `-j` — every
+/// `note_package`/`note_spec` call during the run is collected in memory
+/// and flushed as a single `write_all()` here, so a workspace-wide command
+/// never performs more than one filesystem write no matter how many
+/// packages or specs it touches. That single write is also append-only and
+/// under `PIPE_BUF`, so concurrent `tspec` processes can't interleave or
+/// corrupt each other's lines (see `append_record`).
+///
+/// Best-effort: swallows every failure (disabled config, unwritable path,
+/// serialization error) so usage logging can never fail the real command.
+pub fn record(project_root: &Path, command: &str, duration: Duration, success: bool) {
+    let (packages, specs, profile) = take_notes();
+    let Some(log_path) = usage_log_path(project_root) else {
+        return;
+    };
+    let record = UsageRecord {
+        date: today_utc(),
+        command: command.to_string(),
+        packages,
+        specs,
+        profile,
+        duration_ms: duration.as_millis(),
+        success,
+        tspec_version: env!("CARGO_PKG_VERSION").to_string(),
+    };
+    let _ = append_record(&project_root.join(log_path), &record);
+}
+
+fn append_record(full_path: &Path, record: &UsageRecord) -> Result<()> {
+    if let Some(parent) = full_path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    let line = serde_json::to_string(record)?;
+    // A single write_all() under PIPE_BUF with O_APPEND is atomic on POSIX,
+    // so concurrent commands can't interleave partial lines.
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(full_path)?;
+    writeln!(file, "{line}")?;
+    Ok(())
+}
+
+/// Read and parse all usage records at `project_root`'s configured log,
+/// optionally filtered to `date >= since` (a `YYYY-MM-DD` string).
+///
+/// Returns an empty list (never an error) when usage logging is disabled,
+/// the log doesn't exist yet, or a line fails to parse.
+pub fn read_log(project_root: &Path, since: Option<&str>) -> Vec<UsageRecord> {
+    let Some(log_path) = usage_log_path(project_root) else {
+        return Vec::new();
+    };
+    let Ok(content) = std::fs::read_to_string(project_root.join(log_path)) else {
+        return Vec::new();
+    };
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str::<UsageRecord>(line).ok())
+        .filter(|r| since.is_none_or(|s| r.date.as_str() >= s))
+        .collect()
+}
+
+/// Aggregated counts and the slowest recent operations from a set of records.
+pub struct UsageReport {
+    pub by_command: BTreeMap<String, usize>,
+    pub by_spec: BTreeMap<String, usize>,
+    pub by_profile: BTreeMap<String, usize>,
+    /// Slowest records, descending by duration, capped at `limit`.
+    pub slowest: Vec<UsageRecord>,
+}
+
+/// Aggregate `records` into per-command/spec/profile counts and a slowest-N table.
+pub fn aggregate(records: Vec<UsageRecord>, slowest_limit: usize) -> UsageReport {
+    let mut by_command = BTreeMap::new();
+    let mut by_spec = BTreeMap::new();
+    let mut by_profile = BTreeMap::new();
+
+    for record in &records {
+        *by_command.entry(record.command.clone()).or_insert(0) += 1;
+        for spec in &record.specs {
+            *by_spec.entry(spec.name.clone()).or_insert(0) += 1;
+        }
+        if let Some(profile) = &record.profile {
+            *by_profile.entry(profile.clone()).or_insert(0) += 1;
+        }
+    }
+
+    let mut slowest = records;
+    slowest.sort_by_key(|r| std::cmp::Reverse(r.duration_ms));
+    slowest.truncate(slowest_limit);
+
+    UsageReport {
+        by_command,
+        by_spec,
+        by_profile,
+        slowest,
+    }
+}
+
+fn today_utc() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (y, m, d) = civil_from_days((secs / 86_400) as i64);
+    format!("{y:04}-{m:02}-{d:02}")
+}
+
+/// Howard Hinnant's `civil_from_days`: days-since-epoch to a Gregorian
+/// (year, month, day), without pulling in a date/time dependency.
+/// Full UTC timestamp as `YYYY-MM-DDTHH:MM:SSZ`, for logs that need more
+/// than `today_utc`'s calendar date (see `audit.rs`).
+pub(crate) fn now_utc_iso() -> String {
+    let secs = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs();
+    let (y, m, d) = civil_from_days((secs / 86_400) as i64);
+    let rem = secs % 86_400;
+    let (hh, mm, ss) = (rem / 3600, (rem % 3600) / 60, rem % 60);
+    format!("{y:04}-{m:02}-{d:02}T{hh:02}:{mm:02}:{ss:02}Z")
+}
+
+fn civil_from_days(z: i64) -> (i64, u32, u32) {
+    let z = z + 719_468;
+    let era = if z >= 0 { z } else { z - 146_096 } / 146_097;
+    let doe = (z - era * 146_097) as u64;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let y = yoe as i64 + era * 400;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let d = (doy - (153 * mp + 2) / 5 + 1) as u32;
+    let m = if mp < 10 { mp + 3 } else { mp - 9 } as u32;
+    let y = if m <= 2 { y + 1 } else { y };
+    (y, m, d)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_cargo_toml(dir: &Path, content: &str) {
+        std::fs::write(dir.join("Cargo.toml"), content).unwrap();
+    }
+
+    fn rec(command: &str, date: &str, duration_ms: u128, spec: Option<&str>) -> UsageRecord {
+        UsageRecord {
+            date: date.to_string(),
+            command: command.to_string(),
+            packages: vec!["demo".to_string()],
+            specs: spec
+                .map(|s| {
+                    vec![UsageSpec {
+                        name: s.to_string(),
+                        hash: None,
+                    }]
+                })
+                .unwrap_or_default(),
+            profile: Some("release".to_string()),
+            duration_ms,
+            success: true,
+            tspec_version: "0.0.0".to_string(),
+        }
+    }
+
+    #[test]
+    fn usage_log_path_disabled_by_default() {
+        let dir = TempDir::new().unwrap();
+        write_cargo_toml(dir.path(), "[workspace]\nmembers = []\n");
+        assert_eq!(usage_log_path(dir.path()), None);
+    }
+
+    #[test]
+    fn usage_log_path_enabled_via_workspace_metadata() {
+        let dir = TempDir::new().unwrap();
+        write_cargo_toml(
+            dir.path(),
+            "[workspace]\nmembers = []\n\n\
+             [workspace.metadata.tspec]\nusage_log = \".tspec/usage.jsonl\"\n",
+        );
+        assert_eq!(
+            usage_log_path(dir.path()),
+            Some(PathBuf::from(".tspec/usage.jsonl"))
+        );
+    }
+
+    #[test]
+    fn record_disabled_writes_nothing() {
+        let dir = TempDir::new().unwrap();
+        write_cargo_toml(dir.path(), "[workspace]\nmembers = []\n");
+
+        record(dir.path(), "build", Duration::from_millis(5), true);
+
+        assert!(!dir.path().join(".tspec").exists());
+        assert!(read_log(dir.path(), None).is_empty());
+    }
+
+    #[test]
+    fn record_enabled_appends_one_line_per_call() {
+        let dir = TempDir::new().unwrap();
+        write_cargo_toml(
+            dir.path(),
+            "[workspace]\nmembers = []\n\n\
+             [workspace.metadata.tspec]\nusage_log = \".tspec/usage.jsonl\"\n",
+        );
+
+        note_package("demo");
+        note_spec("tspec.ts.toml", Some("abcd1234".to_string()));
+        note_profile("release");
+        record(dir.path(), "build", Duration::from_millis(42), true);
+
+        record(dir.path(), "test", Duration::from_millis(7), false);
+
+        let records = read_log(dir.path(), None);
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].command, "build");
+        assert_eq!(records[0].packages, vec!["demo".to_string()]);
+        assert_eq!(records[0].specs[0].name, "tspec.ts.toml");
+        assert_eq!(records[0].profile.as_deref(), Some("release"));
+        assert!(records[0].success);
+        assert_eq!(records[1].command, "test");
+        assert!(!records[1].success);
+    }
+
+    #[test]
+    fn note_spec_dedupes_by_name() {
+        let dir = TempDir::new().unwrap();
+        write_cargo_toml(
+            dir.path(),
+            "[workspace]\nmembers = []\n\n\
+             [workspace.metadata.tspec]\nusage_log = \".tspec/usage.jsonl\"\n",
+        );
+
+        note_spec("tspec.ts.toml", Some("aaaa1111".to_string()));
+        note_spec("tspec.ts.toml", Some("aaaa1111".to_string()));
+        note_spec("tspec.release.ts.toml", None);
+        record(dir.path(), "build", Duration::from_millis(1), true);
+
+        let records = read_log(dir.path(), None);
+        assert_eq!(records[0].specs.len(), 2);
+    }
+
+    #[test]
+    fn note_calls_do_not_leak_into_unrelated_records() {
+        let dir = TempDir::new().unwrap();
+        write_cargo_toml(
+            dir.path(),
+            "[workspace]\nmembers = []\n\n\
+             [workspace.metadata.tspec]\nusage_log = \".tspec/usage.jsonl\"\n",
+        );
+
+        note_package("demo");
+        record(dir.path(), "build", Duration::from_millis(1), true);
+        record(dir.path(), "clean", Duration::from_millis(1), true);
+
+        let records = read_log(dir.path(), None);
+        assert_eq!(records[0].packages, vec!["demo".to_string()]);
+        assert!(records[1].packages.is_empty());
+    }
+
+    #[test]
+    fn read_log_filters_by_since() {
+        let records = vec![
+            rec("build", "2026-01-01", 10, Some("a")),
+            rec("build", "2026-03-01", 20, Some("b")),
+        ];
+        let filtered: Vec<_> = records
+            .into_iter()
+            .filter(|r| r.date.as_str() >= "2026-02-01")
+            .collect();
+        assert_eq!(filtered.len(), 1);
+        assert_eq!(filtered[0].command, "build");
+    }
+
+    #[test]
+    fn aggregate_counts_per_command_spec_and_profile() {
+        let records = vec![
+            rec("build", "2026-01-01", 100, Some("tspec.ts.toml")),
+            rec("build", "2026-01-02", 300, Some("tspec.ts.toml")),
+            rec("test", "2026-01-03", 50, Some("tspec.release.ts.toml")),
+        ];
+        let report = aggregate(records, 10);
+        assert_eq!(report.by_command.get("build"), Some(&2));
+        assert_eq!(report.by_command.get("test"), Some(&1));
+        assert_eq!(report.by_spec.get("tspec.ts.toml"), Some(&2));
+        assert_eq!(report.by_profile.get("release"), Some(&3));
+    }
+
+    #[test]
+    fn aggregate_slowest_is_sorted_descending_and_capped() {
+        let records = vec![
+            rec("build", "2026-01-01", 10, None),
+            rec("build", "2026-01-02", 300, None),
+            rec("build", "2026-01-03", 50, None),
+        ];
+        let report = aggregate(records, 2);
+        assert_eq!(report.slowest.len(), 2);
+        assert_eq!(report.slowest[0].duration_ms, 300);
+        assert_eq!(report.slowest[1].duration_ms, 50);
+    }
+
+    #[test]
+    fn civil_from_days_epoch_is_1970_01_01() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+    }
+
+    #[test]
+    fn civil_from_days_known_date() {
+        // 2026-08-08 is 20,673 days after the epoch.
+        assert_eq!(civil_from_days(20_673), (2026, 8, 8));
+    }
+}
@@ -0,0 +1,167 @@
+//! Collect build/test warnings instead of printing them as they occur.
+//!
+//! `check_spec_misconfigurations` and friends used to be printed immediately
+//! via `eprintln!`/`println!`, which is fine for a single-package command but
+//! means a `-w` run interleaves one package's warnings with the next
+//! package's build output. `Warnings` instead accumulates typed warnings
+//! across the whole run so they can be deduplicated and printed together at
+//! the end.
+
+use std::fmt;
+
+/// A warning raised while building/testing one or more packages.
+///
+/// Kept as a typed enum (rather than a bag of strings) so callers can match
+/// on `kind` if they ever need to, and so two warnings about the same
+/// underlying problem for the same package dedup by equality instead of by
+/// incidentally-identical formatted text.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Warning {
+    /// A `check_spec_misconfigurations` finding, already formatted (it
+    /// already folds in the package name and varies by misconfiguration
+    /// kind).
+    Misconfiguration(String),
+    /// Stripping a package's binary after a successful build failed.
+    StripFailed { package: String, error: String },
+    /// `-t`/`--tspec` patterns that don't look like tspec files, almost
+    /// always because the shell expanded an unquoted glob before tspec saw it.
+    ShellGlobExpansion { patterns: Vec<String> },
+}
+
+impl fmt::Display for Warning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Warning::Misconfiguration(message) => write!(f, "{message}"),
+            Warning::StripFailed { package, error } => {
+                write!(f, "Warning: strip failed for {package}: {error}")
+            }
+            Warning::ShellGlobExpansion { patterns } => {
+                writeln!(
+                    f,
+                    "Warning: -t arguments ({}) don't look like tspec files.",
+                    patterns.join(", ")
+                )?;
+                writeln!(
+                    f,
+                    "  The shell likely expanded your glob before tspec could see it."
+                )?;
+                write!(
+                    f,
+                    "  Quote the pattern to prevent shell expansion: -t 'pattern*'"
+                )
+            }
+        }
+    }
+}
+
+/// Accumulates warnings across a batch run, deduplicating identical ones
+/// (e.g. the same misconfiguration hit by more than one package/spec).
+#[derive(Default)]
+pub struct Warnings {
+    items: Vec<Warning>,
+}
+
+impl Warnings {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, warning: Warning) {
+        self.items.push(warning);
+    }
+
+    /// Convenience for the common case of pushing already-formatted
+    /// misconfiguration strings (e.g. from `check_spec_misconfigurations`).
+    pub fn extend_misconfigurations(&mut self, messages: impl IntoIterator<Item = String>) {
+        self.items
+            .extend(messages.into_iter().map(Warning::Misconfiguration));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+
+    /// Every distinct warning, in first-seen order.
+    fn deduped(&self) -> Vec<&Warning> {
+        let mut seen = std::collections::HashSet::new();
+        self.items.iter().filter(|w| seen.insert(*w)).collect()
+    }
+
+    /// Print every distinct warning once, in first-seen order, under a
+    /// "Warnings" header. No-op if nothing was collected.
+    pub fn print_grouped(&self) {
+        if self.items.is_empty() {
+            return;
+        }
+        println!();
+        println!("=== Warnings ===");
+        for warning in self.deduped() {
+            println!("{}", warning);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_collector_is_empty() {
+        let warnings = Warnings::new();
+        assert!(warnings.is_empty());
+    }
+
+    #[test]
+    fn push_and_extend_accumulate() {
+        let mut warnings = Warnings::new();
+        warnings.push(Warning::StripFailed {
+            package: "foo".to_string(),
+            error: "not an ELF file".to_string(),
+        });
+        warnings.extend_misconfigurations(["b".to_string(), "c".to_string()]);
+        assert!(!warnings.is_empty());
+        assert_eq!(warnings.items.len(), 3);
+    }
+
+    #[test]
+    fn duplicate_warnings_across_packages_are_collapsed() {
+        let mut warnings = Warnings::new();
+        warnings.extend_misconfigurations([
+            "Warning: -static linker arg without musl target for foo.".to_string(),
+            "Warning: -static linker arg without musl target for foo.".to_string(),
+        ]);
+        warnings.push(Warning::Misconfiguration(
+            "Warning: -static linker arg without musl target for foo.".to_string(),
+        ));
+        warnings.push(Warning::Misconfiguration(
+            "Warning: linker.args ignored for bar (no binary target)".to_string(),
+        ));
+
+        assert_eq!(warnings.deduped().len(), 2);
+    }
+
+    #[test]
+    fn distinct_strip_failures_for_different_packages_are_not_collapsed() {
+        let mut warnings = Warnings::new();
+        warnings.push(Warning::StripFailed {
+            package: "foo".to_string(),
+            error: "not an ELF file".to_string(),
+        });
+        warnings.push(Warning::StripFailed {
+            package: "bar".to_string(),
+            error: "not an ELF file".to_string(),
+        });
+
+        assert_eq!(warnings.deduped().len(), 2);
+    }
+
+    #[test]
+    fn shell_glob_expansion_message_mentions_patterns() {
+        let warning = Warning::ShellGlobExpansion {
+            patterns: vec!["main.rs".to_string(), "lib.rs".to_string()],
+        };
+        let rendered = warning.to_string();
+        assert!(rendered.contains("main.rs, lib.rs"));
+        assert!(rendered.contains("Quote the pattern"));
+    }
+}
@@ -3,24 +3,138 @@
 //! Uses `cargo metadata` to discover workspace members.
 
 use anyhow::{Context, Result};
-use cargo_metadata::MetadataCommand;
+use cargo_metadata::{MetadataCommand, Package};
+use std::fmt;
 use std::path::{Path, PathBuf};
 
+/// Coarse classification of what a workspace member produces, derived from
+/// its Cargo target kinds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub enum PackageKind {
+    /// Ships a binary and no library — a standalone application.
+    App,
+    /// Ships a library and no binary.
+    Lib,
+    /// Ships both a library and a binary, e.g. a CLI built on its own lib.
+    Tool,
+    /// No lib/bin targets, only test or bench harnesses.
+    Test,
+    /// No lib/bin targets, only a build script.
+    BuildTool,
+}
+
+impl fmt::Display for PackageKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            PackageKind::App => "App",
+            PackageKind::Lib => "Lib",
+            PackageKind::Tool => "Tool",
+            PackageKind::Test => "Test",
+            PackageKind::BuildTool => "BuildTool",
+        };
+        f.write_str(s)
+    }
+}
+
+/// Classify a package's kind from its Cargo target kinds. Lib+bin together
+/// is `Tool` (a CLI built on its own library); no lib/bin at all falls back
+/// to `Test` when it ships test/bench harnesses, else `BuildTool`.
+fn classify_kind(pkg: &Package) -> PackageKind {
+    let has_lib = pkg.targets.iter().any(|t| t.is_lib());
+    let has_bin = pkg.targets.iter().any(|t| t.is_bin());
+    if has_bin && has_lib {
+        PackageKind::Tool
+    } else if has_bin {
+        PackageKind::App
+    } else if has_lib {
+        PackageKind::Lib
+    } else if pkg.targets.iter().any(|t| t.is_test() || t.is_bench()) {
+        PackageKind::Test
+    } else {
+        PackageKind::BuildTool
+    }
+}
+
+/// A permanent reclassification from `[package.metadata.tspec] kind = "..."`,
+/// for packages whose targets alone don't tell the whole story (e.g. an
+/// xtask crate that also happens to ship a library and would otherwise
+/// auto-classify as `Tool`).
+fn metadata_kind_override(pkg: &Package) -> Option<PackageKind> {
+    let kind_str = pkg.metadata.get("tspec")?.get("kind")?.as_str()?;
+    parse_kind_str(kind_str)
+}
+
+/// Map a `[package.metadata.tspec] kind` string to a [`PackageKind`].
+/// Unrecognized strings return `None` so a typo falls back to auto-classification
+/// rather than silently misclassifying the package.
+fn parse_kind_str(s: &str) -> Option<PackageKind> {
+    match s {
+        "app" => Some(PackageKind::App),
+        "lib" => Some(PackageKind::Lib),
+        "tool" => Some(PackageKind::Tool),
+        "test" => Some(PackageKind::Test),
+        "build-tool" => Some(PackageKind::BuildTool),
+        _ => None,
+    }
+}
+
+/// Classify a package's kind: `[package.metadata.tspec] kind` wins when
+/// present and valid, otherwise fall back to target-based classification.
+fn resolve_kind(pkg: &Package) -> PackageKind {
+    metadata_kind_override(pkg).unwrap_or_else(|| classify_kind(pkg))
+}
+
+/// Which base set of members a workspace-mode command considers before
+/// [`MemberFilter`] is applied.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MemberScope {
+    /// Every workspace member (`-w`/`--workspace`).
+    All,
+    /// `[workspace] default-members`, or every member when unset.
+    Default,
+    /// Members with a binary target (`tspec run -w`).
+    Runnable,
+}
+
+/// Options controlling which members a workspace-mode command operates on,
+/// beyond the base [`MemberScope`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct MemberFilter {
+    /// Include `BuildTool`-kind members, which are excluded by default so
+    /// `-w` runs don't build internal tooling like xtask crates.
+    pub include_build_tools: bool,
+}
+
+/// Result of [`WorkspaceInfo::filtered_members`]: the members selected, plus
+/// how many `BuildTool` members were excluded (0 when `include_build_tools`
+/// was set) so callers can surface the exclusion instead of hiding it.
+pub struct FilteredMembers<'a> {
+    pub members: Vec<&'a PackageMember>,
+    pub excluded_build_tools: usize,
+}
+
 /// Information about a workspace package
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct PackageMember {
     pub name: String,
     pub version: String,
     pub path: PathBuf,
     pub has_binary: bool,
+    pub kind: PackageKind,
 }
 
 /// Workspace information from cargo metadata
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct WorkspaceInfo {
     pub root: PathBuf,
     pub members: Vec<PackageMember>,
     /// Version of the root package (if the workspace root has a [package] section)
     pub version: Option<String>,
+    /// Names of packages cargo would build by default (i.e. `cargo build` with
+    /// no `-p`/`--workspace`), per the root Cargo.toml's `[workspace]
+    /// default-members`. Empty when the workspace doesn't set default-members,
+    /// in which case cargo (and tspec) default to all members.
+    pub default_members: Vec<String>,
 }
 
 impl WorkspaceInfo {
@@ -41,8 +155,20 @@ impl WorkspaceInfo {
         }
     }
 
-    /// Discover workspace using cargo metadata
+    /// Discover workspace using cargo metadata, or a still-valid on-disk
+    /// cache of a previous `cargo metadata` run (see
+    /// [`crate::metadata_cache`]) when one exists.
     pub fn discover(project_root: &Path) -> Result<Self> {
+        if let Some(cached) = crate::metadata_cache::load(project_root) {
+            return Ok(cached);
+        }
+        let info = Self::discover_uncached(project_root)?;
+        crate::metadata_cache::store(&info);
+        Ok(info)
+    }
+
+    /// The real `cargo metadata` invocation, bypassing the cache entirely.
+    fn discover_uncached(project_root: &Path) -> Result<Self> {
         let metadata = MetadataCommand::new()
             .manifest_path(project_root.join("Cargo.toml"))
             .no_deps()
@@ -65,6 +191,7 @@ impl WorkspaceInfo {
                     .as_std_path()
                     .to_path_buf();
                 let has_binary = pkg.targets.iter().any(|t| t.is_bin());
+                let kind = resolve_kind(pkg);
 
                 // If this package's manifest is at the workspace root, capture its version
                 if pkg.manifest_path.as_std_path() == root_manifest {
@@ -76,14 +203,22 @@ impl WorkspaceInfo {
                     version: pkg.version.to_string(),
                     path,
                     has_binary,
+                    kind,
                 }
             })
             .collect();
 
+        let default_members = metadata
+            .workspace_default_packages()
+            .iter()
+            .map(|pkg| pkg.name.clone())
+            .collect();
+
         Ok(WorkspaceInfo {
             root,
             members,
             version: root_version,
+            default_members,
         })
     }
 
@@ -96,6 +231,53 @@ impl WorkspaceInfo {
     pub fn runnable_members(&self) -> Vec<&PackageMember> {
         self.members.iter().filter(|m| m.has_binary).collect()
     }
+
+    /// Members cargo would operate on by default (no `-p`/`--workspace`):
+    /// the `[workspace] default-members` set, or all members when the
+    /// workspace doesn't restrict it.
+    pub fn default_scoped_members(&self) -> Vec<&PackageMember> {
+        if self.default_members.is_empty() {
+            return self.buildable_members();
+        }
+        self.members
+            .iter()
+            .filter(|m| self.default_members.contains(&m.name))
+            .collect()
+    }
+
+    /// The single entry point workspace-mode commands (build/test/run/compare
+    /// `-w`) use to pick their members, so the `BuildTool` exclusion policy
+    /// can't diverge between them. Resolves `scope` to a base member list,
+    /// then drops `BuildTool`-kind members unless `filter.include_build_tools`.
+    pub fn filtered_members(
+        &self,
+        scope: MemberScope,
+        filter: MemberFilter,
+    ) -> FilteredMembers<'_> {
+        let base = match scope {
+            MemberScope::All => self.buildable_members(),
+            MemberScope::Default => self.default_scoped_members(),
+            MemberScope::Runnable => self.runnable_members(),
+        };
+        if filter.include_build_tools {
+            return FilteredMembers {
+                members: base,
+                excluded_build_tools: 0,
+            };
+        }
+        let excluded_build_tools = base
+            .iter()
+            .filter(|m| m.kind == PackageKind::BuildTool)
+            .count();
+        let members = base
+            .into_iter()
+            .filter(|m| m.kind != PackageKind::BuildTool)
+            .collect();
+        FilteredMembers {
+            members,
+            excluded_build_tools,
+        }
+    }
 }
 
 #[cfg(test)]
@@ -139,6 +321,7 @@ mod tests {
             root: PathBuf::from("/tmp/myproject"),
             members: Vec::new(),
             version: Some("1.2.3".to_string()),
+            default_members: Vec::new(),
         };
         assert_eq!(info.name_versioned(), "myproject v1.2.3");
     }
@@ -149,10 +332,65 @@ mod tests {
             root: PathBuf::from("/tmp/myproject"),
             members: Vec::new(),
             version: None,
+            default_members: Vec::new(),
         };
         assert_eq!(info.name_versioned(), "myproject");
     }
 
+    #[test]
+    fn default_scoped_members_falls_back_to_all_when_unset() {
+        let info = WorkspaceInfo {
+            root: PathBuf::from("/tmp/ws"),
+            members: vec![
+                PackageMember {
+                    name: "a".to_string(),
+                    version: "0.1.0".to_string(),
+                    path: PathBuf::from("/tmp/ws/a"),
+                    has_binary: true,
+                    kind: PackageKind::App,
+                },
+                PackageMember {
+                    name: "b".to_string(),
+                    version: "0.1.0".to_string(),
+                    path: PathBuf::from("/tmp/ws/b"),
+                    has_binary: true,
+                    kind: PackageKind::App,
+                },
+            ],
+            version: None,
+            default_members: Vec::new(),
+        };
+        assert_eq!(info.default_scoped_members().len(), 2);
+    }
+
+    #[test]
+    fn default_scoped_members_filters_to_default_members() {
+        let info = WorkspaceInfo {
+            root: PathBuf::from("/tmp/ws"),
+            members: vec![
+                PackageMember {
+                    name: "a".to_string(),
+                    version: "0.1.0".to_string(),
+                    path: PathBuf::from("/tmp/ws/a"),
+                    has_binary: true,
+                    kind: PackageKind::App,
+                },
+                PackageMember {
+                    name: "b".to_string(),
+                    version: "0.1.0".to_string(),
+                    path: PathBuf::from("/tmp/ws/b"),
+                    has_binary: true,
+                    kind: PackageKind::App,
+                },
+            ],
+            version: None,
+            default_members: vec!["a".to_string()],
+        };
+        let scoped = info.default_scoped_members();
+        assert_eq!(scoped.len(), 1);
+        assert_eq!(scoped[0].name, "a");
+    }
+
     #[test]
     fn discover_root_version_set_for_popws() {
         // tspec itself is a POPWS — root has [package], so version should be Some
@@ -164,4 +402,87 @@ mod tests {
             );
         }
     }
+
+    fn member_with_kind(name: &str, kind: PackageKind) -> PackageMember {
+        PackageMember {
+            name: name.to_string(),
+            version: "0.1.0".to_string(),
+            path: PathBuf::from(format!("/tmp/ws/{name}")),
+            has_binary: matches!(kind, PackageKind::App | PackageKind::Tool),
+            kind,
+        }
+    }
+
+    fn workspace_with_build_tool() -> WorkspaceInfo {
+        WorkspaceInfo {
+            root: PathBuf::from("/tmp/ws"),
+            members: vec![
+                member_with_kind("app", PackageKind::App),
+                member_with_kind("xtask", PackageKind::BuildTool),
+            ],
+            version: None,
+            default_members: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn filtered_members_excludes_build_tools_by_default() {
+        let ws = workspace_with_build_tool();
+        let filtered = ws.filtered_members(MemberScope::All, MemberFilter::default());
+        assert_eq!(filtered.members.len(), 1);
+        assert_eq!(filtered.members[0].name, "app");
+        assert_eq!(filtered.excluded_build_tools, 1);
+    }
+
+    #[test]
+    fn filtered_members_include_build_tools_keeps_them() {
+        let ws = workspace_with_build_tool();
+        let filtered = ws.filtered_members(
+            MemberScope::All,
+            MemberFilter {
+                include_build_tools: true,
+            },
+        );
+        assert_eq!(filtered.members.len(), 2);
+        assert_eq!(filtered.excluded_build_tools, 0);
+    }
+
+    #[test]
+    fn filtered_members_no_build_tools_reports_zero_excluded() {
+        let ws = WorkspaceInfo {
+            root: PathBuf::from("/tmp/ws"),
+            members: vec![member_with_kind("app", PackageKind::App)],
+            version: None,
+            default_members: Vec::new(),
+        };
+        let filtered = ws.filtered_members(MemberScope::All, MemberFilter::default());
+        assert_eq!(filtered.members.len(), 1);
+        assert_eq!(filtered.excluded_build_tools, 0);
+    }
+
+    #[test]
+    fn filtered_members_runnable_scope_excludes_build_tools_too() {
+        let mut ws = workspace_with_build_tool();
+        // A build tool with a binary target should still be excluded from
+        // Runnable scope by default — has_binary alone isn't an opt-in.
+        ws.members[1].has_binary = true;
+        let filtered = ws.filtered_members(MemberScope::Runnable, MemberFilter::default());
+        assert_eq!(filtered.members.len(), 1);
+        assert_eq!(filtered.members[0].name, "app");
+        assert_eq!(filtered.excluded_build_tools, 1);
+    }
+
+    #[test]
+    fn parse_kind_str_maps_all_known_values() {
+        assert_eq!(parse_kind_str("app"), Some(PackageKind::App));
+        assert_eq!(parse_kind_str("lib"), Some(PackageKind::Lib));
+        assert_eq!(parse_kind_str("tool"), Some(PackageKind::Tool));
+        assert_eq!(parse_kind_str("test"), Some(PackageKind::Test));
+        assert_eq!(parse_kind_str("build-tool"), Some(PackageKind::BuildTool));
+    }
+
+    #[test]
+    fn parse_kind_str_rejects_unknown_value() {
+        assert_eq!(parse_kind_str("bogus"), None);
+    }
 }
@@ -0,0 +1,26 @@
+//! Runs the `tspec examples --run-check` registry against the real
+//! fixtures, so the examples shown in `--help` output can't drift out of
+//! sync with the CLI they document.
+
+use std::path::PathBuf;
+use std::process::Command;
+
+fn fixtures_dir() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures")
+}
+
+#[test]
+fn registered_examples_all_pass_run_check() {
+    let output = Command::new("tspec")
+        .args(["examples", "--run-check", "--fixtures-dir"])
+        .arg(fixtures_dir())
+        .output()
+        .expect("failed to run tspec examples --run-check");
+
+    assert!(
+        output.status.success(),
+        "tspec examples --run-check failed:\nstdout:\n{}\nstderr:\n{}",
+        String::from_utf8_lossy(&output.stdout),
+        String::from_utf8_lossy(&output.stderr)
+    );
+}
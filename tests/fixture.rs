@@ -11,11 +11,18 @@ fn fixtures_dir() -> PathBuf {
 /// Returns `(TempDir, PathBuf)` — the temp dir guard (drop removes it) and the
 /// path to the copied project root inside the temp dir.
 pub fn copy_fixture(name: &str) -> (TempDir, PathBuf) {
+    copy_fixture_as(name, name)
+}
+
+/// Like [`copy_fixture`], but the copy lands in a directory named `dir_name`
+/// instead of `name` — e.g. to exercise a project root whose path contains
+/// spaces without needing a dedicated fixture directory for it.
+pub fn copy_fixture_as(name: &str, dir_name: &str) -> (TempDir, PathBuf) {
     let src = fixtures_dir().join(name);
     assert!(src.is_dir(), "fixture not found: {}", src.display());
 
     let tmp = TempDir::new().expect("failed to create temp dir");
-    let dst = tmp.path().join(name);
+    let dst = tmp.path().join(dir_name);
     copy_dir_recursive(&src, &dst).expect("failed to copy fixture");
     (tmp, dst)
 }
@@ -0,0 +1,8 @@
+/// Doubles a number.
+///
+/// ```
+/// assert_eq!(pop_doctest_fixture::double(2), 4);
+/// ```
+pub fn double(n: i32) -> i32 {
+    n * 2
+}
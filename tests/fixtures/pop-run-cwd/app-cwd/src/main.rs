@@ -0,0 +1,11 @@
+fn main() {
+    let args: Vec<String> = std::env::args().skip(1).collect();
+    match std::fs::read_to_string("data.txt") {
+        Ok(content) => println!("data: {}", content.trim()),
+        Err(e) => {
+            eprintln!("failed to read data.txt: {e}");
+            std::process::exit(1);
+        }
+    }
+    println!("args: {}", args.join(","));
+}